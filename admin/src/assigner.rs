@@ -1,9 +1,9 @@
 //! RustSec Advisory DB tool to assign ids
 
-use crate::{error::ErrorKind, prelude::*, Map};
+use crate::{error::ErrorKind, prelude::*};
 use rustsec::{
-    advisory::{IdKind, Parts},
-    Advisory, Collection,
+    advisory::{self, Parts},
+    Advisory, Collection, Database,
 };
 use std::{
     fs::{self, File},
@@ -23,7 +23,7 @@ pub enum OutputMode {
 
 /// assign ids to advisories in a particular repo_path
 pub fn assign_ids(repo_path: &Path, output_mode: OutputMode) {
-    let db = rustsec::Database::open(repo_path).unwrap_or_else(|e| {
+    let db = Database::open(repo_path).unwrap_or_else(|e| {
         status_err!(
             "couldn't open advisory DB repo from {}: {}",
             repo_path.display(),
@@ -49,39 +49,20 @@ pub fn assign_ids(repo_path: &Path, output_mode: OutputMode) {
         );
     }
 
-    let mut highest_id = Map::new();
-
-    for advisory in advisories {
-        let advisory_clone = advisory.clone();
-        let metadata = advisory_clone.metadata;
-        let id = metadata.id;
-        let year = metadata.date.year();
-
-        if let IdKind::RustSec = id.kind() {
-            let id_num = id.numerical_part().unwrap();
-
-            if let Some(&number) = highest_id.get(&year) {
-                if number < id_num {
-                    highest_id.insert(year, id_num);
-                }
-            } else {
-                highest_id.insert(year, id_num);
-            }
-        }
-    }
-
     let mut collection_strs = vec![];
     let crates_str = Collection::Crates.to_string();
     let rust_str = Collection::Rust.to_string();
     collection_strs.push(crates_str);
     collection_strs.push(rust_str);
 
+    let mut assigned_ids = vec![];
     let mut assignments = vec![];
     for collection_str in collection_strs {
         assign_ids_across_directory(
             collection_str,
             repo_path,
-            &mut highest_id,
+            &db,
+            &mut assigned_ids,
             output_mode,
             &mut assignments,
         );
@@ -96,7 +77,8 @@ pub fn assign_ids(repo_path: &Path, output_mode: OutputMode) {
 fn assign_ids_across_directory(
     collection_str: String,
     repo_path: &Path,
-    highest_ids: &mut Map<u32, u32>,
+    db: &Database,
+    assigned_ids: &mut Vec<advisory::Id>,
     output_mode: OutputMode,
     assignments: &mut Vec<String>,
 ) {
@@ -133,9 +115,10 @@ fn assign_ids_across_directory(
                     let advisory: Advisory = toml::from_str(advisory_parts.front_matter).unwrap();
                     let date = advisory.metadata.date;
                     let year = date.year();
-                    let new_id = highest_ids.get(&year).cloned().unwrap_or_default() + 1;
-                    let year_str = year.to_string();
-                    let string_id = format!("RUSTSEC-{}-{:04}", year_str, new_id);
+                    let new_id = db
+                        .next_available_id_with_pending(year, assigned_ids)
+                        .unwrap();
+                    let string_id = new_id.to_string();
                     let new_filename = format!("{}.md", string_id);
                     let new_path = dir_path_clone.join(new_filename);
                     let original_file = File::open(advisory_path_for_reading).unwrap();
@@ -155,7 +138,7 @@ fn assign_ids_across_directory(
                                 .unwrap();
                         }
                     }
-                    highest_ids.insert(year, new_id);
+                    assigned_ids.push(new_id);
                     fs::remove_file(advisory_path_for_deleting).unwrap();
                     if output_mode == OutputMode::HumanReadable {
                         status_ok!("Assignment", "Assigned {} to {}", string_id, dir_name);
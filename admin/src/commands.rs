@@ -3,6 +3,7 @@
 mod assign_id;
 mod lint;
 mod list_affected_versions;
+mod new;
 mod osv;
 mod sync;
 mod version;
@@ -10,7 +11,7 @@ mod web;
 
 use self::{
     assign_id::AssignIdCmd, lint::LintCmd, list_affected_versions::ListAffectedVersionsCmd,
-    osv::OsvCmd, sync::SyncCmd, version::VersionCmd, web::WebCmd,
+    new::NewCmd, osv::OsvCmd, sync::SyncCmd, version::VersionCmd, web::WebCmd,
 };
 use crate::config::AppConfig;
 use abscissa_core::{Command, Configurable, Runnable};
@@ -36,6 +37,10 @@ pub enum AdminSubCmd {
     #[command(about = "display version information")]
     Version(VersionCmd),
 
+    /// The `new` subcommand
+    #[command(about = "create the skeleton of a new advisory")]
+    New(NewCmd),
+
     /// The `assign-id` subcommand
     #[command(about = "assigning RUSTSEC ids to new vulnerabilities")]
     AssignId(AssignIdCmd),
@@ -0,0 +1,122 @@
+//! `rustsec-admin new` subcommand
+//!
+//! Creates the skeleton of a new advisory in the advisory DB, to be filled in
+//! by hand (or by a bot) before an ID is assigned.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use abscissa_core::{Command, Runnable};
+use clap::Parser;
+use rustsec::{
+    advisory::{Category, Id, Informational, Keyword, Template},
+    package, VersionReq,
+};
+
+use crate::prelude::*;
+
+/// `rustsec-admin new` subcommand
+#[derive(Command, Debug, Parser)]
+pub struct NewCmd {
+    /// Name of the affected package
+    #[arg(help = "name of the affected crate")]
+    package: package::Name,
+
+    /// Title of the advisory
+    #[arg(long, help = "one-line title of the advisory")]
+    title: String,
+
+    /// Kind of informational advisory
+    #[arg(
+        long,
+        help = "kind of informational advisory, e.g. `unmaintained` (default: vulnerability)"
+    )]
+    informational: Option<Informational>,
+
+    /// URL with more details
+    #[arg(long, help = "URL with more details about the issue")]
+    url: Option<String>,
+
+    /// Categories of the vulnerability
+    #[arg(long = "category", help = "category of the vulnerability (repeatable)")]
+    categories: Vec<Category>,
+
+    /// Freeform keywords
+    #[arg(long = "keyword", help = "keyword describing the issue (repeatable)")]
+    keywords: Vec<Keyword>,
+
+    /// Patched versions
+    #[arg(long, help = "range of patched versions, e.g. `>= 1.2.3` (repeatable)")]
+    patched: Vec<VersionReq>,
+
+    /// Unaffected versions
+    #[arg(
+        long,
+        help = "range of versions never affected, e.g. `< 1.0.0` (repeatable)"
+    )]
+    unaffected: Vec<VersionReq>,
+
+    /// Path to the advisory database
+    #[arg(
+        num_args = 1..,
+        help = "filesystem path to the RustSec advisory DB git repo"
+    )]
+    path: Vec<PathBuf>,
+}
+
+impl Runnable for NewCmd {
+    fn run(&self) {
+        let repo_path = match self.path.len() {
+            0 => Path::new("."),
+            1 => self.path[0].as_path(),
+            _ => unreachable!(),
+        };
+
+        let mut template = Template::new(self.package.clone(), &self.title)
+            .categories(self.categories.clone())
+            .keywords(self.keywords.clone())
+            .patched(self.patched.clone())
+            .unaffected(self.unaffected.clone());
+
+        if let Some(informational) = &self.informational {
+            template = template.informational(informational.clone());
+        }
+
+        if let Some(url) = &self.url {
+            let url = url.parse().unwrap_or_else(|e| {
+                status_err!("invalid URL {}: {}", url, e);
+                exit(1);
+            });
+            template = template.url(url);
+        }
+
+        let advisory = template.render().unwrap_or_else(|e| {
+            status_err!("couldn't generate advisory: {}", e);
+            exit(1);
+        });
+
+        let package_dir = repo_path
+            .join(rustsec::Collection::Crates.as_str())
+            .join(self.package.as_str());
+        let advisory_path = package_dir.join(format!("{}.md", Id::PLACEHOLDER));
+        if advisory_path.exists() {
+            status_err!(
+                "{} already exists; assign it an ID first",
+                advisory_path.display()
+            );
+            exit(1);
+        }
+
+        fs::create_dir_all(&package_dir)
+            .and_then(|()| fs::write(&advisory_path, advisory))
+            .unwrap_or_else(|e| {
+                status_err!("couldn't write {}: {}", advisory_path.display(), e);
+                exit(1);
+            });
+
+        status_ok!("Created", "{}", advisory_path.display());
+    }
+}
@@ -17,5 +17,3 @@ pub mod osv_export;
 pub mod prelude;
 pub mod synchronizer;
 pub mod web;
-
-use std::collections::BTreeMap as Map;
@@ -409,6 +409,7 @@ fn title_type(advisory: &rustsec::Advisory) -> String {
         Some(Informational::Notice) => format!("{}: Security notice about {}", id, package),
         Some(Informational::Unmaintained) => format!("{}: {} is unmaintained", id, package),
         Some(Informational::Unsound) => format!("{}: Unsoundness in {}", id, package),
+        Some(Informational::Deprecated) => format!("{}: {} is deprecated", id, package),
         Some(Informational::Other(s)) => format!("{}: {} is {}", id, package, s),
         Some(_) => format!("{}: Advisory for {}", id, package),
         // Not informational => vulnerability
@@ -0,0 +1,67 @@
+//! Tests for assigning IDs to new advisories
+
+#![deny(warnings, missing_docs, trivial_casts, unused_qualifications)]
+#![forbid(unsafe_code)]
+
+use abscissa_core::testing::prelude::*;
+use std::{fs, path::Path};
+use tempfile::TempDir;
+
+/// Write an advisory against the given package with the given ID and date
+fn write_advisory(db: &Path, package: &str, id: &str, date: &str) {
+    let dir = db.join("crates").join(package);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join(format!("{}.md", id)),
+        format!(
+            "```toml\n[advisory]\nid = \"{}\"\npackage = \"{}\"\ndate = \"{}\"\n\n\
+            [versions]\npatched = [\">= 1.2.3\"]\n```\n\n# Vulnerability in {}\n\nDescription\n",
+            id, package, date, package
+        ),
+    )
+    .unwrap();
+}
+
+/// Get the IDs of the advisories against the given package
+fn advisory_ids(db: &Path, package: &str) -> Vec<String> {
+    fs::read_dir(db.join("crates").join(package))
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let id = path.file_stem().unwrap().to_str().unwrap().to_owned();
+            let advisory = fs::read_to_string(&path).unwrap();
+            assert!(
+                advisory.contains(&format!("id = \"{}\"", id)),
+                "{}",
+                advisory
+            );
+            id
+        })
+        .collect()
+}
+
+/// IDs are assigned per year, following the highest ID of that year in the
+/// database or assigned before
+#[test]
+fn assigns_ids_by_year() {
+    let dir = TempDir::new().unwrap();
+    let db = dir.path();
+    write_advisory(db, "base", "RUSTSEC-2001-2101", "2001-02-03");
+    write_advisory(db, "first", "RUSTSEC-0000-0000", "2001-05-06");
+    write_advisory(db, "second", "RUSTSEC-0000-0000", "2002-07-08");
+    write_advisory(db, "third", "RUSTSEC-0000-0000", "2001-09-10");
+
+    CmdRunner::default()
+        .args(["assign-id", "--github-actions-output"])
+        .arg(db)
+        .capture_stdout()
+        .status()
+        .expect_success();
+
+    assert_eq!(advisory_ids(db, "base"), ["RUSTSEC-2001-2101"]);
+    assert_eq!(advisory_ids(db, "second"), ["RUSTSEC-2002-0001"]);
+
+    let mut ids = [advisory_ids(db, "first"), advisory_ids(db, "third")].concat();
+    ids.sort();
+    assert_eq!(ids, ["RUSTSEC-2001-2102", "RUSTSEC-2001-2103"]);
+}
@@ -1,16 +1,28 @@
 //! Core auditing functionality
 
+#[cfg(feature = "notify")]
+use crate::notifier::Notifier;
 use crate::{
-    binary_format::BinaryFormat, config::AuditConfig, error::display_err_with_source, prelude::*,
-    presenter::Presenter,
+    binary_format::BinaryFormat,
+    config::AuditConfig,
+    diagnostics::Workspace,
+    error::display_err_with_source,
+    policy::Policy,
+    prelude::*,
+    presenter::{DefaultPresenter, Presenter},
+    state::StateFile,
+};
+use rustsec::{
+    package::Package, registry, report, Error, ErrorKind, Lockfile, Version, Warning, WarningKind,
 };
-use rustsec::{registry, report, Error, ErrorKind, Lockfile, Warning, WarningKind};
 use std::{
     io::{self, Read},
-    path::Path,
+    path::{Path, PathBuf},
     process::exit,
     time::Duration,
 };
+#[cfg(feature = "release-dates")]
+use {crate::release_dates::ReleaseDates, rustsec::advisory::Date};
 
 // TODO: make configurable
 const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(5 * 60);
@@ -23,111 +35,121 @@ pub struct Auditor {
     /// Crates.io registry index
     registry_index: Option<registry::CachedIndex>,
 
+    /// Should the registry index be checked for yanked crates?
+    check_yanked: bool,
+
+    /// Should lockfile checksums be verified against the registry index?
+    check_checksums: bool,
+
     /// Presenter for displaying the report
-    presenter: Presenter,
+    presenter: Box<dyn Presenter>,
+
+    /// Policy deciding whether reports fail the audit
+    policy: Policy,
+
+    /// Webhook to deliver reports to, if configured
+    #[cfg(feature = "notify")]
+    notifier: Option<Notifier>,
+
+    /// Reports which are yet to be delivered to the webhook
+    #[cfg(feature = "notify")]
+    undelivered: Vec<rustsec::Report>,
+
+    /// Lookup of the release dates of patched versions, if enabled
+    #[cfg(feature = "release-dates")]
+    release_dates: Option<ReleaseDates>,
+
+    /// Findings which have already been reported, if a state file is configured
+    state_file: Option<StateFile>,
 
     /// Audit report settings
     report_settings: report::Settings,
-}
 
-impl Auditor {
-    /// Initialize the auditor
-    pub fn new(config: &AuditConfig) -> Self {
-        let advisory_db_url = config
-            .database
-            .url
-            .as_ref()
-            .map(AsRef::as_ref)
-            .unwrap_or(rustsec::repository::git::DEFAULT_URL);
+    /// Should the CVSS vectors of findings be included in the structured
+    /// representation?
+    cvss_details: bool,
 
-        let advisory_db_path = config
-            .database
-            .path
-            .as_ref()
-            .cloned()
-            .unwrap_or_else(rustsec::repository::git::Repository::default_path);
+    /// Should findings in packages only used by dev-dependencies be detected?
+    check_dev_dependencies: bool,
 
-        let database = if config.database.fetch {
-            if !config.output.is_quiet() {
-                status_ok!("Fetching", "advisory database from `{}`", advisory_db_url);
-            }
+    /// Should findings in packages only used at build time be detected?
+    check_build_dependencies: bool,
 
-            let mut result = rustsec::repository::git::Repository::fetch(
-                advisory_db_url,
-                &advisory_db_path,
-                !config.database.stale,
-                Duration::from_secs(0),
-            );
-            // If the directory is locked, print a message and wait for it to become unlocked.
-            // If we don't print the message, `cargo audit` would just hang with no explanation.
-            if let Err(e) = &result {
-                if e.kind() == ErrorKind::LockTimeout {
-                    status_warn!("directory {} is locked, waiting for up to {} seconds for it to become available", advisory_db_path.display(), DEFAULT_LOCK_TIMEOUT.as_secs());
-                    result = rustsec::repository::git::Repository::fetch(
-                        advisory_db_url,
-                        &advisory_db_path,
-                        !config.database.stale,
-                        DEFAULT_LOCK_TIMEOUT,
-                    );
-                }
-            }
+    /// Workspace the lockfile being audited belongs to, if it's needed to
+    /// detect dev-only or build-only packages and could be inspected
+    workspace: Option<Workspace>,
+}
 
-            let advisory_db_repo = result.unwrap_or_else(|e| {
-                status_err!(
-                    "couldn't fetch advisory database: {}",
-                    display_err_with_source(&e)
-                );
-                exit(1);
-            });
+impl Auditor {
+    /// Initialize the auditor, exiting the process if the advisory database or
+    /// webhook notifications can't be set up
+    pub fn new(config: &AuditConfig) -> Self {
+        Self::with_presenter(config, DefaultPresenter::new(&config.output)).unwrap_or_else(|e| {
+            status_err!("{}", display_err_with_source(&e));
+            exit(1);
+        })
+    }
 
-            rustsec::Database::load_from_repo(&advisory_db_repo).unwrap_or_else(|e| {
-                status_err!(
-                    "error loading advisory database: {}",
-                    display_err_with_source(&e)
-                );
-                exit(1);
-            })
-        } else {
-            rustsec::Database::open(&advisory_db_path).unwrap_or_else(|e| {
-                status_err!(
-                    "error loading advisory database: {}",
-                    display_err_with_source(&e)
-                );
-                exit(1);
-            })
-        };
+    /// Initialize an auditor which displays its progress and results with the
+    /// given [`Presenter`]
+    pub fn with_presenter(
+        config: &AuditConfig,
+        presenter: impl Presenter + 'static,
+    ) -> rustsec::Result<Self> {
+        let mut presenter: Box<dyn Presenter> = Box::new(presenter);
+        let advisory_db_path = advisory_db_path(config);
+        let database = Self::load_database(config, presenter.as_mut())?;
 
-        if !config.output.is_quiet() {
-            status_ok!(
+        if config.output.show_status() {
+            presenter.status(
                 "Loaded",
-                "{} security advisories (from {})",
-                database.iter().count(),
-                advisory_db_path.display()
+                &format!(
+                    "{} security advisories (from {})",
+                    database.iter().count(),
+                    advisory_db_path.display()
+                ),
             );
         }
 
-        let registry_index = if config.yanked.enabled {
+        let registry_index = if config.yanked.enabled || config.checksums.enabled {
             if config.yanked.update_index && config.database.fetch {
-                if !config.output.is_quiet() {
-                    status_ok!("Updating", "crates.io index");
+                if config.output.show_status() {
+                    presenter.status("Updating", "crates.io index");
                 }
 
-                let mut result = registry::CachedIndex::fetch(None, Duration::from_secs(0));
+                let retry = config.database.retry_policy();
+                let mut result =
+                    registry::CachedIndex::fetch(None, Duration::from_secs(0), retry.clone());
 
                 // If the directory is locked, print a message and wait for it to become unlocked.
                 // If we don't print the message, `cargo audit` would just hang with no explanation.
                 if let Err(e) = &result {
                     if e.kind() == ErrorKind::LockTimeout {
-                        status_warn!("directory {} is locked, waiting for up to {} seconds for it to become available", advisory_db_path.display(), DEFAULT_LOCK_TIMEOUT.as_secs());
-                        result = registry::CachedIndex::fetch(None, DEFAULT_LOCK_TIMEOUT);
+                        presenter.warning(&lock_message(&advisory_db_path));
+                        result = registry::CachedIndex::fetch(None, DEFAULT_LOCK_TIMEOUT, retry);
                     }
                 }
 
                 match result {
                     Ok(index) => Some(index),
+                    Err(err)
+                        if config.database.offline_fallback && err.kind() == ErrorKind::Network =>
+                    {
+                        if config.output.show_status() {
+                            presenter.warning(&format!(
+                                "couldn't update crates.io index, using the local copy: {}",
+                                display_err_with_source(&err)
+                            ));
+                        }
+
+                        registry::CachedIndex::open(DEFAULT_LOCK_TIMEOUT).ok()
+                    }
                     Err(err) => {
-                        if !config.output.is_quiet() {
-                            status_warn!("couldn't update crates.io index: {}", err);
+                        if config.output.show_status() {
+                            presenter.warning(&format!(
+                                "couldn't update crates.io index: {}",
+                                display_err_with_source(&err)
+                            ));
                         }
 
                         None
@@ -140,7 +162,7 @@ impl Auditor {
                 // If we don't print the message, `cargo audit` would just hang with no explanation.
                 if let Err(e) = &result {
                     if e.kind() == ErrorKind::LockTimeout {
-                        status_warn!("directory {} is locked, waiting for up to {} seconds for it to become available", advisory_db_path.display(), DEFAULT_LOCK_TIMEOUT.as_secs());
+                        presenter.warning(&lock_message(&advisory_db_path));
                         result = registry::CachedIndex::open(DEFAULT_LOCK_TIMEOUT)
                     }
                 }
@@ -148,8 +170,11 @@ impl Auditor {
                 match result {
                     Ok(index) => Some(index),
                     Err(err) => {
-                        if !config.output.is_quiet() {
-                            status_warn!("couldn't open crates.io index: {}", err);
+                        if config.output.show_status() {
+                            presenter.warning(&format!(
+                                "couldn't open crates.io index: {}",
+                                display_err_with_source(&err)
+                            ));
                         }
 
                         None
@@ -160,14 +185,176 @@ impl Auditor {
             None
         };
 
-        Self {
+        #[cfg(feature = "notify")]
+        let notifier = Notifier::new(&config.notify).map_err(|e| {
+            Error::with_source(
+                ErrorKind::BadParam,
+                "couldn't set up webhook notifications".to_owned(),
+                e,
+            )
+        })?;
+        #[cfg(not(feature = "notify"))]
+        if config.notify.url.is_some() {
+            return Err(Error::new(
+                ErrorKind::BadParam,
+                &"webhook notifications require cargo-audit to be built with the `notify` feature",
+            ));
+        }
+
+        #[cfg(feature = "release-dates")]
+        let release_dates = ReleaseDates::new(&config.release_dates).map_err(|e| {
+            Error::with_source(
+                ErrorKind::BadParam,
+                "couldn't set up release date lookups".to_owned(),
+                e,
+            )
+        })?;
+        #[cfg(not(feature = "release-dates"))]
+        if config.release_dates.enabled {
+            return Err(Error::new(
+                ErrorKind::BadParam,
+                &"release date lookups require cargo-audit to be built with the \
+                `release-dates` feature",
+            ));
+        }
+
+        let state_file = config
+            .output
+            .state_file
+            .as_deref()
+            .map(StateFile::load)
+            .transpose()
+            .map_err(|e| {
+                Error::with_source(
+                    ErrorKind::BadParam,
+                    "couldn't load state file".to_owned(),
+                    e,
+                )
+            })?;
+
+        Ok(Self {
             database,
             registry_index,
-            presenter: Presenter::new(&config.output),
+            check_yanked: config.yanked.enabled,
+            check_checksums: config.checksums.enabled,
+            presenter,
+            policy: Policy::new(&config.output),
+            #[cfg(feature = "notify")]
+            notifier,
+            #[cfg(feature = "notify")]
+            undelivered: Vec::new(),
+            #[cfg(feature = "release-dates")]
+            release_dates,
+            state_file,
             report_settings: config.report_settings(),
+            cvss_details: config.output.cvss_details,
+            check_dev_dependencies: config.advisories.dev_dependencies.is_some(),
+            check_build_dependencies: config.advisories.build_dependencies.is_some(),
+            workspace: None,
+        })
+    }
+
+    /// Load the advisory database as configured, fetching it first if enabled
+    pub fn load_database(
+        config: &AuditConfig,
+        presenter: &mut dyn Presenter,
+    ) -> rustsec::Result<rustsec::Database> {
+        let advisory_db_url = config
+            .database
+            .url
+            .as_ref()
+            .map(AsRef::as_ref)
+            .unwrap_or(rustsec::repository::git::DEFAULT_URL);
+
+        let advisory_db_path = advisory_db_path(config);
+
+        if config.database.fetch {
+            if config.output.show_status() {
+                presenter.status(
+                    "Fetching",
+                    &format!("advisory database from `{}`", advisory_db_url),
+                );
+            }
+
+            let backend = if config.database.system_git {
+                rustsec::repository::git::Backend::SystemGit
+            } else {
+                rustsec::repository::git::Backend::Gix
+            };
+            let mut fetch_options = rustsec::repository::git::FetchOptions::new()
+                .ensure_fresh(false)
+                .backend(backend)
+                .retry(config.database.retry_policy())
+                .offline_fallback(config.database.offline_fallback);
+
+            if let Some(revision) = &config.database.revision {
+                fetch_options = fetch_options.revision(revision);
+            }
+
+            let mut result = rustsec::repository::git::Repository::fetch_with_options(
+                advisory_db_url,
+                &advisory_db_path,
+                &fetch_options.clone().lock_timeout(Duration::from_secs(0)),
+            );
+            // If the directory is locked, print a message and wait for it to become unlocked.
+            // If we don't print the message, `cargo audit` would just hang with no explanation.
+            if let Err(e) = &result {
+                if e.kind() == ErrorKind::LockTimeout {
+                    presenter.warning(&lock_message(&advisory_db_path));
+                    result = rustsec::repository::git::Repository::fetch_with_options(
+                        advisory_db_url,
+                        &advisory_db_path,
+                        &fetch_options.lock_timeout(DEFAULT_LOCK_TIMEOUT),
+                    );
+                }
+            }
+
+            let advisory_db_repo = result.map_err(|e| {
+                Error::with_source(e.kind(), "couldn't fetch advisory database".to_owned(), e)
+            })?;
+
+            if let Some(e) = advisory_db_repo.fetch_error() {
+                presenter.warning(&format!(
+                    "couldn't fetch advisory database, using the local copy at {}: {}",
+                    advisory_db_path.display(),
+                    display_err_with_source(e)
+                ));
+            }
+
+            rustsec::Database::load_from_repo(&advisory_db_repo).map_err(|e| {
+                Error::with_source(e.kind(), "error loading advisory database".to_owned(), e)
+            })
+        } else if let Some(revision) = &config.database.revision {
+            // Read the advisories from the git history rather than checking
+            // the revision out, as the checkout may be shared with other users
+            rustsec::repository::git::Repository::open(&advisory_db_path)
+                .and_then(|repo| {
+                    rustsec::Database::open_at(
+                        &repo,
+                        &rustsec::database::PointInTime::Revision(revision.clone()),
+                    )
+                })
+                .map_err(|e| {
+                    Error::with_source(
+                        e.kind(),
+                        format!("error loading advisory database at revision {}", revision),
+                        e,
+                    )
+                })
+        } else {
+            rustsec::Database::open(&advisory_db_path).map_err(|e| {
+                Error::with_source(e.kind(), "error loading advisory database".to_owned(), e)
+            })
         }
     }
 
+    /// Replace the advisory database with a freshly loaded one, returning
+    /// the number of advisories it contains
+    pub fn reload_database(&mut self, config: &AuditConfig) -> rustsec::Result<usize> {
+        self.database = Self::load_database(config, self.presenter.as_mut())?;
+        Ok(self.database.iter().count())
+    }
+
     /// Perform an audit of a textual `Cargo.lock` file
     pub fn audit_lockfile(&mut self, lockfile_path: &Path) -> rustsec::Result<rustsec::Report> {
         let lockfile = match self.load_lockfile(lockfile_path) {
@@ -183,44 +370,207 @@ impl Auditor {
 
         self.presenter.before_report(lockfile_path, &lockfile);
 
-        let report = self.audit(&lockfile, None, None);
+        if self.check_dev_dependencies || self.check_build_dependencies {
+            let dir = match lockfile_path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+
+            // Resolving the dependencies with `cargo metadata` requires the
+            // Cargo package lock held by the registry index, so release it
+            // in the meantime
+            let reopen_index =
+                self.check_build_dependencies && self.registry_index.take().is_some();
+
+            match Workspace::load(dir, self.check_build_dependencies) {
+                Ok(workspace) => self.workspace = Some(workspace),
+                Err(e) => self.presenter.warning(&format!(
+                    "couldn't inspect workspace to detect dependency kinds: {}",
+                    display_err_with_source(&e)
+                )),
+            }
+
+            if reopen_index {
+                match registry::CachedIndex::open(DEFAULT_LOCK_TIMEOUT) {
+                    Ok(index) => self.registry_index = Some(index),
+                    Err(e) => self.presenter.warning(&format!(
+                        "couldn't reopen crates.io index: {}",
+                        display_err_with_source(&e)
+                    )),
+                }
+            }
+        }
+
+        let report = self.audit(&lockfile, None, None, None);
 
         let self_advisories = self.self_advisories();
 
-        self.presenter.print_self_report(self_advisories.as_slice());
+        self.presenter
+            .print_self_report(self_advisories.as_slice())
+            .map_err(display_error)?;
 
         report
     }
 
     #[cfg(feature = "binary-scanning")]
-    /// Perform an audit of multiple binary files
+    /// Perform an audit of multiple binary files.
+    ///
+    /// A path of `-` reads dependency JSON extracted from a binary from
+    /// standard input, see [`Auditor::audit_auditable_json`].
+    ///
+    /// Directories are searched recursively for binaries to audit. Unlike
+    /// binaries passed explicitly, files found this way which contain no
+    /// dependency information (e.g. executables not written in Rust) are
+    /// skipped rather than reported as errors.
+    ///
+    /// Binaries are scanned concurrently, using one worker thread per CPU.
     pub fn audit_binaries<P>(&mut self, binaries: &[P]) -> MultiFileReportSummmary
+    where
+        P: AsRef<Path>,
+    {
+        self.audit_binaries_with_jobs(binaries, crate::scan_pool::default_jobs())
+    }
+
+    #[cfg(feature = "binary-scanning")]
+    /// Perform an audit of multiple binary files like
+    /// [`Auditor::audit_binaries`], scanning up to `jobs` binaries
+    /// concurrently.
+    ///
+    /// Each binary is audited as soon as it has been scanned, so the reports
+    /// are displayed in the order scans complete.
+    pub fn audit_binaries_with_jobs<P>(
+        &mut self,
+        binaries: &[P],
+        jobs: std::num::NonZeroUsize,
+    ) -> MultiFileReportSummmary
     where
         P: AsRef<Path>,
     {
         let mut summary = MultiFileReportSummmary::default();
+
+        // Binaries to scan, and whether to skip them if they contain no
+        // dependency information
+        let mut pending = vec![];
         for path in binaries {
-            let result = self.audit_binary(path.as_ref());
-            match result {
-                Ok(report) => {
-                    if self.presenter.should_exit_with_failure(&report) {
-                        summary.vulnerabilities_found = true;
+            let path = path.as_ref();
+            if path.is_dir() {
+                match crate::binary_discovery::find_binaries(path) {
+                    Ok(found) => {
+                        self.presenter.binaries_found(path, found.len());
+                        pending.extend(found.into_iter().map(|binary_path| (binary_path, true)));
+                    }
+                    Err(e) => {
+                        self.presenter.error(&format!(
+                            "couldn't read directory {}: {}",
+                            path.display(),
+                            e
+                        ));
+                        summary.errors_encountered = true;
                     }
                 }
-                Err(e) => {
-                    status_err!("{}", display_err_with_source(&e));
-                    summary.errors_encountered = true;
+            } else {
+                pending.push((path.to_owned(), false));
+            }
+        }
+
+        crate::scan_pool::run(
+            pending,
+            jobs,
+            |(binary_path, _)| load_deps(binary_path),
+            |(binary_path, skip_unrecognized), info| {
+                let report = info
+                    .and_then(|info| self.audit_binary_info(info, &binary_path, skip_unrecognized));
+                self.add_to_summary(report, &mut summary);
+            },
+        );
+
+        self.finish_summary(summary)
+    }
+
+    #[cfg(feature = "binary-scanning")]
+    /// Perform an audit of dependency lists embedded in binaries by
+    /// `cargo auditable`, which have already been extracted from the binaries
+    /// as JSON, e.g. by pipelines scanning container images.
+    ///
+    /// A path of `-` reads the JSON from standard input, which is also
+    /// supported by [`Auditor::audit_binaries`].
+    pub fn audit_auditable_json<P>(&mut self, json_paths: &[P]) -> MultiFileReportSummmary
+    where
+        P: AsRef<Path>,
+    {
+        let mut summary = MultiFileReportSummmary::default();
+        for path in json_paths {
+            let path = path.as_ref();
+            let report = crate::binary_deps::load_deps_from_auditable_json(path)
+                .and_then(|info| self.audit_binary_info(info, path, false));
+            self.add_to_summary(report, &mut summary);
+        }
+
+        self.finish_summary(summary)
+    }
+
+    #[cfg(feature = "binary-scanning")]
+    /// Perform an audit of the programs installed with `cargo install` in the
+    /// given install root (e.g. `~/.cargo`).
+    ///
+    /// Binaries which contain no dependency information are audited using the
+    /// package they were installed from, as recorded by Cargo.
+    pub fn audit_installed(&mut self, root: &Path) -> MultiFileReportSummmary {
+        let mut summary = MultiFileReportSummmary::default();
+        let installed = match crate::installed::find_installed(root) {
+            Ok(installed) => installed,
+            Err(e) => {
+                self.presenter.error(&display_err_with_source(&e));
+                summary.errors_encountered = true;
+                return summary;
+            }
+        };
+
+        let mut vulnerable = vec![];
+        for package in &installed {
+            for binary_path in &package.binaries {
+                if !binary_path.exists() {
+                    self.presenter.warning(&format!(
+                        "{} was installed by {} {} but is missing, skipping",
+                        binary_path.display(),
+                        package.package.name,
+                        package.package.version
+                    ));
+                    continue;
+                }
+
+                match self.audit_installed_binary(package, binary_path) {
+                    Ok(report) => {
+                        if report.vulnerabilities.found {
+                            vulnerable.push(binary_path.as_path());
+                        }
+                        if self.policy.should_exit_with_failure(&report) {
+                            summary.vulnerabilities_found = true;
+                        }
+                        if summary.exit_code == 0 {
+                            summary.exit_code = self.policy.exit_code(&report);
+                        }
+                    }
+                    Err(e) => {
+                        self.presenter.error(&display_err_with_source(&e));
+                        summary.errors_encountered = true;
+                    }
                 }
             }
         }
 
+        self.presenter.installed_report(&vulnerable);
+
         let self_advisories = self.self_advisories();
 
-        self.presenter.print_self_report(self_advisories.as_slice());
+        if let Err(e) = self.presenter.print_self_report(self_advisories.as_slice()) {
+            self.presenter.error(&display_err_with_source(&*e));
+            summary.errors_encountered = true;
+        }
 
         if self
-            .presenter
-            .should_exit_with_failure_due_to_self(&self.self_advisories())
+            .policy
+            .should_exit_with_failure_due_to_self(&self_advisories)
         {
             summary.errors_encountered = true;
         }
@@ -228,20 +578,184 @@ impl Auditor {
     }
 
     #[cfg(feature = "binary-scanning")]
-    /// Perform an audit of a binary file with dependency data embedded by `cargo auditable`
-    fn audit_binary(&mut self, binary_path: &Path) -> rustsec::Result<rustsec::Report> {
-        use crate::binary_deps::BinaryReport::*;
-        let (binary_type, report) = crate::binary_deps::load_deps_from_binary(binary_path)?;
-        self.presenter.binary_scan_report(&report, binary_path);
+    /// Audit a binary installed with `cargo install`
+    fn audit_installed_binary(
+        &mut self,
+        package: &crate::installed::InstalledPackage,
+        binary_path: &Path,
+    ) -> rustsec::Result<rustsec::Report> {
+        use crate::binary_deps::BinaryReport;
+        let info = crate::binary_deps::load_deps_from_binary(binary_path)?;
+        if !matches!(info.report, BinaryReport::None) {
+            self.presenter.binary_scan_report(&info.report, binary_path);
+        }
+        let recovered = match info.report {
+            BinaryReport::Complete(lockfile) | BinaryReport::Incomplete(lockfile) => Some(lockfile),
+            BinaryReport::None => {
+                self.presenter
+                    .installed_package_only(&package.package, binary_path);
+                None
+            }
+        };
+
+        let mut lockfile = package.lockfile(recovered);
+        let mut vendored = self.vendored_packages(&info.vendored_libraries, &lockfile, binary_path);
+        lockfile.packages.append(&mut vendored);
+
+        self.audit(
+            &lockfile,
+            Some(binary_path),
+            Some(info.format),
+            info.rustc_version.as_ref(),
+        )
+    }
+
+    #[cfg(feature = "binary-scanning")]
+    /// Record the outcome of auditing a single binary in `summary`
+    fn add_to_summary(
+        &mut self,
+        report: rustsec::Result<Option<rustsec::Report>>,
+        summary: &mut MultiFileReportSummmary,
+    ) {
         match report {
-            Complete(lockfile) | Incomplete(lockfile) => {
-                self.audit(&lockfile, Some(binary_path), Some(binary_type))
+            Ok(Some(report)) => {
+                if self.policy.should_exit_with_failure(&report) {
+                    summary.vulnerabilities_found = true;
+                }
+                if summary.exit_code == 0 {
+                    summary.exit_code = self.policy.exit_code(&report);
+                }
+            }
+            Ok(None) => (),
+            Err(e) => {
+                self.presenter.error(&display_err_with_source(&e));
+                summary.errors_encountered = true;
+            }
+        }
+    }
+
+    #[cfg(feature = "binary-scanning")]
+    /// Report advisories against `cargo-audit` itself at the end of an audit
+    /// of binaries, and return the final `summary`
+    fn finish_summary(&mut self, mut summary: MultiFileReportSummmary) -> MultiFileReportSummmary {
+        let self_advisories = self.self_advisories();
+
+        if let Err(e) = self.presenter.print_self_report(self_advisories.as_slice()) {
+            self.presenter.error(&display_err_with_source(&*e));
+            summary.errors_encountered = true;
+        }
+
+        if self
+            .policy
+            .should_exit_with_failure_due_to_self(&self_advisories)
+        {
+            summary.errors_encountered = true;
+        }
+        summary
+    }
+
+    #[cfg(feature = "binary-scanning")]
+    /// Perform an audit of the dependency information recovered from a binary.
+    ///
+    /// Returns `Ok(None)` if `skip_unrecognized` is set and the binary contains
+    /// no dependency information.
+    fn audit_binary_info(
+        &mut self,
+        info: crate::binary_deps::BinaryInfo,
+        binary_path: &Path,
+        skip_unrecognized: bool,
+    ) -> rustsec::Result<Option<rustsec::Report>> {
+        use crate::binary_deps::BinaryReport;
+        let recognized = !matches!(info.report, BinaryReport::None);
+        if recognized {
+            self.presenter.binary_scan_report(&info.report, binary_path);
+        }
+
+        let mut lockfile = match info.report {
+            BinaryReport::Complete(lockfile) | BinaryReport::Incomplete(lockfile) => lockfile,
+            BinaryReport::None => crate::binary_deps::lockfile_from_packages(vec![]),
+        };
+        let mut vendored = self.vendored_packages(&info.vendored_libraries, &lockfile, binary_path);
+
+        // Binaries without dependency information are still audited when
+        // vendored C libraries are found in them
+        if !recognized && vendored.is_empty() {
+            if skip_unrecognized {
+                return Ok(None);
             }
-            None => Err(Error::new(
+            self.presenter
+                .binary_scan_report(&BinaryReport::None, binary_path);
+            return Err(Error::new(
                 ErrorKind::Parse,
                 &"No dependency information found! Is this a Rust executable built with cargo?",
-            )),
+            ));
         }
+
+        lockfile.packages.append(&mut vendored);
+        self.audit(
+            &lockfile,
+            Some(binary_path),
+            Some(info.format),
+            info.rustc_version.as_ref(),
+        )
+        .map(Some)
+    }
+
+    #[cfg(feature = "binary-scanning")]
+    /// Find the packages of the crates vendoring the given C libraries found
+    /// in a binary, skipping crates already listed in its `lockfile`.
+    ///
+    /// Looking up the packages requires the crates.io index.
+    fn vendored_packages(
+        &mut self,
+        libraries: &[crate::vendored_libs::VendoredLibrary],
+        lockfile: &Lockfile,
+        binary_path: &Path,
+    ) -> Vec<Package> {
+        let mut packages = vec![];
+
+        for library in libraries {
+            let listed = lockfile
+                .packages
+                .iter()
+                .any(|package| package.name.as_str() == library.source_crate);
+            if listed {
+                continue;
+            }
+
+            let index = match &mut self.registry_index {
+                Some(index) => index,
+                None => {
+                    self.presenter.warning(&format!(
+                        "{} {} is statically linked in {}, but the crates.io index is \
+                        needed to audit it",
+                        library.name,
+                        library.version,
+                        binary_path.display()
+                    ));
+                    continue;
+                }
+            };
+
+            let name = library.source_crate.parse().unwrap();
+            let package = match index.published_versions(&name) {
+                Ok(published) => library.source_package(&published),
+                Err(e) => {
+                    self.presenter.error(&format!(
+                        "couldn't look up the versions of {}: {}",
+                        library.source_crate,
+                        display_err_with_source(&e)
+                    ));
+                    continue;
+                }
+            };
+
+            self.presenter
+                .vendored_library(library, package.as_ref(), binary_path);
+            packages.extend(package);
+        }
+
+        packages
     }
 
     /// The part of the auditing process that is shared between auditing lockfiles and binary files
@@ -251,8 +765,79 @@ impl Auditor {
         path: Option<&Path>,
         #[allow(unused_variables)] // May be unused when the "binary-scanning" feature is disabled
         binary_format: Option<BinaryFormat>,
+        rustc_version: Option<&Version>,
     ) -> rustsec::Result<rustsec::Report> {
-        let mut report = rustsec::Report::generate(&self.database, lockfile, &self.report_settings);
+        let mut report = self.generate_report(lockfile, binary_format, rustc_version);
+
+        // Only report findings which weren't reported by previous audits
+        if let Some(state_file) = &self.state_file {
+            state_file.filter_reported(&mut report);
+            report.update_summary(lockfile);
+        }
+
+        self.presenter
+            .print_report(&report, lockfile, path)
+            .map_err(display_error)?;
+
+        // Findings are only recorded once they've actually been reported
+        if let Some(state_file) = &mut self.state_file {
+            state_file.record(&report);
+
+            if let Err(e) = state_file.save() {
+                self.presenter.warning(&format!(
+                    "couldn't save state file: {}",
+                    display_err_with_source(&e)
+                ));
+            }
+        }
+
+        #[cfg(feature = "notify")]
+        if self.notifier.is_some() {
+            self.undelivered.push(report.clone());
+        }
+
+        Ok(report)
+    }
+
+    /// Deliver the reports of the audits performed since the last call to
+    /// the webhook, if one is configured, in a single notification.
+    ///
+    /// Commands auditing several lockfiles or binaries call this once they're
+    /// done with all of them.
+    pub fn notify(&mut self) {
+        #[cfg(feature = "notify")]
+        if let Some(notifier) = &self.notifier {
+            let reports = std::mem::take(&mut self.undelivered);
+            if let Err(e) = notifier.notify(&reports) {
+                self.presenter.warning(&format!(
+                    "couldn't deliver report to webhook: {}",
+                    display_err_with_source(&e)
+                ));
+            }
+        }
+    }
+
+    /// Generate the report for the given audit source (e.g. a [`Lockfile`])
+    /// without displaying or delivering it
+    pub fn report<S>(&mut self, source: &S) -> rustsec::Report
+    where
+        S: rustsec::AuditSource + ?Sized,
+    {
+        self.generate_report(source, None, None)
+    }
+
+    fn generate_report<S>(
+        &mut self,
+        source: &S,
+        #[allow(unused_variables)] // May be unused when the "binary-scanning" feature is disabled
+        binary_format: Option<BinaryFormat>,
+        rustc_version: Option<&Version>,
+    ) -> rustsec::Report
+    where
+        S: rustsec::AuditSource + ?Sized,
+    {
+        let packages = source.packages();
+        let mut report = rustsec::Report::generate(&self.database, packages, &self.report_settings);
 
         #[cfg(feature = "binary-scanning")]
         if let Some(format) = binary_format {
@@ -260,8 +845,13 @@ impl Auditor {
             filter_report_by_binary_type(&format, &mut report);
         }
 
+        // Warn for advisories against the toolchain a binary was built with
+        if let Some(rustc_version) = rustc_version {
+            report.add_toolchain_warnings(&self.database, rustc_version);
+        }
+
         // Warn for yanked crates
-        let mut yanked = self.check_for_yanked_crates(lockfile);
+        let mut yanked = self.check_for_yanked_crates(packages);
         if !yanked.is_empty() {
             report
                 .warnings
@@ -270,23 +860,73 @@ impl Auditor {
                 .append(&mut yanked);
         }
 
-        self.presenter.print_report(&report, lockfile, path);
+        // Warn for packages whose checksum doesn't match the registry index
+        let mut mismatched = self.check_for_checksum_mismatches(packages);
+        if !mismatched.is_empty() {
+            report
+                .warnings
+                .entry(WarningKind::Checksum)
+                .or_default()
+                .append(&mut mismatched);
+        }
 
-        Ok(report)
+        if self.cvss_details {
+            report.add_cvss_details();
+        }
+
+        // Record how long vulnerabilities have been known, and when their
+        // fixes were released
+        #[cfg(feature = "release-dates")]
+        if let Some(release_dates) = &mut self.release_dates {
+            report.add_advisory_ages(&Date::today());
+
+            let presenter = &mut self.presenter;
+            report.add_patched_release_dates(|package| {
+                release_dates.releases(package).unwrap_or_else(|e| {
+                    presenter.warning(&format!(
+                        "couldn't look up releases of {}: {}",
+                        package.name,
+                        display_err_with_source(&e)
+                    ));
+                    vec![]
+                })
+            });
+        }
+
+        // Mark (and as configured, downgrade or skip) findings in packages
+        // only used by dev-dependencies or at build time
+        if let Some(workspace) = &self.workspace {
+            if self.check_dev_dependencies {
+                let dev_only = workspace.dev_only_packages(packages);
+                report.mark_dev_only(|package| dev_only.contains(&package));
+            }
+
+            if self.check_build_dependencies {
+                match workspace.build_only_packages(packages) {
+                    Some(build_only) => {
+                        report.mark_build_only(|package| build_only.contains(&package))
+                    }
+                    None => self.presenter.warning(
+                        "couldn't resolve the workspace's dependencies offline to detect \
+                        build-only packages",
+                    ),
+                }
+            }
+        }
+
+        report.sort();
+        report.update_summary(packages);
+        report
     }
 
-    fn check_for_yanked_crates(&mut self, lockfile: &Lockfile) -> Vec<Warning> {
+    fn check_for_yanked_crates(&mut self, packages: &[Package]) -> Vec<Warning> {
         let mut result = Vec::new();
-        if let Some(index) = &mut self.registry_index {
-            let pkgs_to_check: Vec<_> = lockfile
-                .packages
-                .iter()
-                .filter(|pkg| match &pkg.source {
-                    Some(source) => source.is_default_registry(),
-                    None => false,
-                })
-                .collect();
+        if !self.check_yanked {
+            return result;
+        }
 
+        if let Some(index) = &mut self.registry_index {
+            let pkgs_to_check = registry_packages(packages, &self.report_settings);
             let yanked = index.find_yanked(pkgs_to_check);
 
             for pkg in yanked {
@@ -295,26 +935,64 @@ impl Auditor {
                         let warning = Warning::new(WarningKind::Yanked, pkg, None, None, None);
                         result.push(warning);
                     }
-                    Err(e) => status_err!(
+                    Err(e) => self.presenter.error(&format!(
                         "couldn't check if the package is yanked: {}",
                         display_err_with_source(&e)
-                    ),
+                    )),
                 }
             }
         }
         result
     }
 
-    /// Load the lockfile to be audited
-    fn load_lockfile(&self, lockfile_path: &Path) -> rustsec::Result<Lockfile> {
-        if lockfile_path == Path::new("-") {
+    /// Compare the checksums of the given packages against the crates.io index
+    fn check_for_checksum_mismatches(&mut self, packages: &[Package]) -> Vec<Warning> {
+        let mut result = Vec::new();
+        if !self.check_checksums {
+            return result;
+        }
+
+        if let Some(index) = &mut self.registry_index {
+            let pkgs_to_check = registry_packages(packages, &self.report_settings);
+            let mismatched = index.find_checksum_mismatches(pkgs_to_check);
+
+            for pkg in mismatched {
+                match pkg {
+                    Ok((pkg, checksum)) => {
+                        result.push(Warning::checksum_mismatch(pkg, checksum));
+                    }
+                    // Packages missing from the index were already reported
+                    // by the check for yanked crates
+                    Err(e) if self.check_yanked && e.kind() == ErrorKind::NotFound => {}
+                    Err(e) => self.presenter.error(&format!(
+                        "couldn't verify the package checksum: {}",
+                        display_err_with_source(&e)
+                    )),
+                }
+            }
+        }
+        result
+    }
+
+    /// Load the lockfile to be audited.
+    ///
+    /// Lockfiles are parsed leniently, so that lockfiles written by newer
+    /// versions of Cargo can still be audited, with a warning.
+    fn load_lockfile(&mut self, lockfile_path: &Path) -> rustsec::Result<Lockfile> {
+        let (lockfile, warnings) = if lockfile_path == Path::new("-") {
             // Read Cargo.lock from STDIN
             let mut lockfile_toml = String::new();
             io::stdin().read_to_string(&mut lockfile_toml)?;
-            Ok(lockfile_toml.parse()?)
+            Lockfile::from_str_lenient(&lockfile_toml)?
         } else {
-            Ok(Lockfile::load(lockfile_path)?)
+            Lockfile::load_lenient(lockfile_path)?
+        };
+
+        for warning in warnings {
+            self.presenter.warning(&warning);
         }
+
+        Ok(lockfile)
     }
 
     /// Query the database for advisories about `cargo-audit` or `rustsec` itself
@@ -337,18 +1015,57 @@ impl Auditor {
         results
     }
 
+    /// Exit code for the given report according to the configured exit codes
+    /// for each kind of failure, or `0` if the audit passed.
+    /// Advisories against `cargo-audit` itself count as denied warnings.
+    /// **Performance:** calls `Auditor.self_advisories()`, which is costly.
+    /// Do not call this in a hot loop.
+    pub fn exit_code(&self, report: &rustsec::Report) -> i32 {
+        match self.policy.exit_code(report) {
+            0 if self
+                .policy
+                .should_exit_with_failure_due_to_self(&self.self_advisories()) =>
+            {
+                self.policy.exit_codes().denied_warnings
+            }
+            code => code,
+        }
+    }
+
     /// Determines whether the process should exit with failure based on configuration
     /// such as `--deny=warnings`.
     /// **Performance:** calls `Auditor.self_advisories()`, which is costly.
     /// Do not call this in a hot loop.
     pub fn should_exit_with_failure(&self, report: &rustsec::Report) -> bool {
-        self.presenter.should_exit_with_failure(report)
+        self.policy.should_exit_with_failure(report)
             || self
-                .presenter
+                .policy
                 .should_exit_with_failure_due_to_self(&self.self_advisories())
     }
 }
 
+#[cfg(feature = "binary-scanning")]
+/// Recover the dependency information embedded in a binary by
+/// `cargo auditable`, or read it as JSON from STDIN if `binary_path` is `-`
+fn load_deps(binary_path: &Path) -> rustsec::Result<crate::binary_deps::BinaryInfo> {
+    if binary_path == Path::new("-") {
+        crate::binary_deps::load_deps_from_auditable_json(binary_path)
+    } else {
+        crate::binary_deps::load_deps_from_binary(binary_path)
+    }
+}
+
+/// Packages among the given ones which come from crates.io
+fn registry_packages<'a>(packages: &'a [Package], settings: &report::Settings) -> Vec<&'a Package> {
+    packages
+        .iter()
+        .filter(|pkg| match &pkg.source {
+            Some(source) => source.is_default_registry() && settings.is_source_audited(pkg),
+            None => false,
+        })
+        .collect()
+}
+
 /// Summary of the report over multiple scanned files
 #[derive(Clone, Copy, Debug, Default)]
 pub struct MultiFileReportSummmary {
@@ -356,4 +1073,44 @@ pub struct MultiFileReportSummmary {
     pub vulnerabilities_found: bool,
     /// Whether any errors were encountered during scanning
     pub errors_encountered: bool,
+    /// Exit code for the first failing report (see [`Auditor::exit_code`])
+    pub exit_code: i32,
+}
+
+/// Message displayed while waiting for the given directory to be unlocked
+fn lock_message(path: &Path) -> String {
+    format!(
+        "directory {} is locked, waiting for up to {} seconds for it to become available",
+        path.display(),
+        DEFAULT_LOCK_TIMEOUT.as_secs()
+    )
+}
+
+/// Path to the local copy of the advisory database, which defaults to a
+/// separate directory for each database URL.
+///
+/// Databases from other URLs used to be kept in the default directory as
+/// well, so an existing checkout there is moved to the URL's own directory.
+fn advisory_db_path(config: &AuditConfig) -> PathBuf {
+    use rustsec::repository::git::{Repository, DEFAULT_URL};
+
+    config.database.path.as_ref().cloned().unwrap_or_else(|| {
+        let url = config.database.url.as_deref().unwrap_or(DEFAULT_URL);
+        Repository::migrate_default_path(url).unwrap_or_else(|e| {
+            status_warn!(
+                "couldn't move the advisory database for {} to its own directory: {}",
+                url,
+                display_err_with_source(&e)
+            );
+            Repository::default_path_for(url)
+        })
+    })
+}
+
+/// Convert an error displaying a report into an error of the audit
+fn display_error(e: crate::error::Error) -> Error {
+    Error::new(
+        ErrorKind::Io,
+        &format!("couldn't display report: {}", display_err_with_source(&*e)),
+    )
 }
@@ -1,14 +1,28 @@
 //! Extracts dependencies from binary files, using one of two ways:
 //! 1. Recovers the dependency list embedded by `cargo auditable` (using `auditable-info`)
 //! 2. Failing that, recovers as many crates as possible from panic messages (using `quitters`)
+//!
+//! Dependency JSON already extracted from a binary can also be loaded directly.
+//!
+//! The version of `rustc` the binary was built with is also recovered when it's recorded in the binary,
+//! as are the versions of C libraries statically linked into it (see [`crate::vendored_libs`]).
 
-use std::{path::Path, str::FromStr};
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+    str::FromStr,
+};
 
 use cargo_lock::{Lockfile, Package};
-use rustsec::{Error, ErrorKind};
+use rustsec::{Error, ErrorKind, Version};
 
-use crate::binary_format::BinaryFormat;
+use crate::{
+    binary_format::BinaryFormat,
+    vendored_libs::{self, VendoredLibrary},
+};
 
+/// Dependency information recovered from a binary file
 pub enum BinaryReport {
     /// Full dependency list embedded by `cargo auditable`
     Complete(Lockfile),
@@ -18,25 +32,56 @@ pub enum BinaryReport {
     None,
 }
 
-/// Load the dependency tree from a binary file
-pub fn load_deps_from_binary(binary_path: &Path) -> rustsec::Result<(BinaryFormat, BinaryReport)> {
+impl rustsec::AuditSource for BinaryReport {
+    fn packages(&self) -> &[Package] {
+        match self {
+            BinaryReport::Complete(lockfile) | BinaryReport::Incomplete(lockfile) => {
+                &lockfile.packages
+            }
+            BinaryReport::None => &[],
+        }
+    }
+}
+
+/// Information recovered from a binary file
+pub struct BinaryInfo {
+    /// Format of the binary
+    pub format: BinaryFormat,
+
+    /// Dependencies of the binary
+    pub report: BinaryReport,
+
+    /// Version of `rustc` the binary was built with, if it's recorded in it
+    pub rustc_version: Option<Version>,
+
+    /// C libraries statically linked into the binary
+    pub vendored_libraries: Vec<VendoredLibrary>,
+}
+
+/// Load the dependency tree, the `rustc` version (if found), and the
+/// statically linked C libraries from a binary file
+pub fn load_deps_from_binary(binary_path: &Path) -> rustsec::Result<BinaryInfo> {
     // TODO: input size limit
-    let file_contents = std::fs::read(binary_path)?;
-    let format = detect_format(&file_contents);
+    let file_contents = fs::read(binary_path)?;
+    let info = |report| BinaryInfo {
+        format: detect_format(&file_contents),
+        report,
+        rustc_version: rustc_version(&file_contents),
+        vendored_libraries: vendored_libs::detect(&file_contents),
+    };
     let stuff = auditable_info::audit_info_from_slice(&file_contents, 8 * 1024 * 1024);
 
     use auditable_info::Error::*; // otherwise rustfmt makes the matches multiline and unreadable
     match stuff {
-        Ok(json_struct) => Ok((
-            format,
-            BinaryReport::Complete(Lockfile::try_from(&json_struct)?),
-        )),
+        Ok(json_struct) => Ok(info(BinaryReport::Complete(Lockfile::try_from(
+            &json_struct,
+        )?))),
         Err(e) => match e {
             NoAuditData => {
                 if let Some(deps) = deps_from_panic_messages(&file_contents) {
-                    Ok((format, BinaryReport::Incomplete(deps)))
+                    Ok(info(BinaryReport::Incomplete(deps)))
                 } else {
-                    Ok((format, BinaryReport::None))
+                    Ok(info(BinaryReport::None))
                 }
             }
             // The error handling boilerplate is in here instead of the `rustsec` crate because as of this writing
@@ -64,7 +109,38 @@ pub fn load_deps_from_binary(binary_path: &Path) -> rustsec::Result<(BinaryForma
     }
 }
 
-fn detect_format(data: &[u8]) -> BinaryFormat {
+/// Load the dependency tree from the JSON embedded in binaries by
+/// `cargo auditable`, which has already been extracted from a binary, read
+/// from the file at the given path or from standard input if it is `-`
+pub fn load_deps_from_auditable_json(json_path: &Path) -> rustsec::Result<BinaryInfo> {
+    let json = if json_path == Path::new("-") {
+        let mut json = String::new();
+        io::stdin().read_to_string(&mut json)?;
+        json
+    } else {
+        fs::read_to_string(json_path)?
+    };
+
+    let version_info: auditable_serde::VersionInfo = serde_json::from_str(&json).map_err(|e| {
+        Error::with_source(
+            ErrorKind::Parse,
+            format!(
+                "could not parse auditable dependency JSON {}",
+                json_path.display()
+            ),
+            e,
+        )
+    })?;
+
+    Ok(BinaryInfo {
+        format: BinaryFormat::Unknown,
+        report: BinaryReport::Complete(Lockfile::try_from(&version_info)?),
+        rustc_version: None,
+        vendored_libraries: vec![],
+    })
+}
+
+pub(crate) fn detect_format(data: &[u8]) -> BinaryFormat {
     match binfarce::detect_format(data) {
         binfarce::Format::Unknown => {
             // binfarce doesn't detect WASM
@@ -78,27 +154,52 @@ fn detect_format(data: &[u8]) -> BinaryFormat {
     }
 }
 
+/// Marker preceding the compiler version in the `.comment` section of ELF binaries,
+/// e.g. `rustc version 1.77.2 (25ef9e3d8 2024-04-09)`
+const RUSTC_VERSION_MARKER: &[u8] = b"rustc version ";
+
+/// Find the version of `rustc` which built the binary, if it's recorded in it
+fn rustc_version(data: &[u8]) -> Option<Version> {
+    data.windows(RUSTC_VERSION_MARKER.len())
+        .enumerate()
+        .filter(|(_, window)| *window == RUSTC_VERSION_MARKER)
+        .find_map(|(offset, _)| {
+            let rest = &data[offset + RUSTC_VERSION_MARKER.len()..];
+            let end = rest
+                .iter()
+                .position(|byte| !(byte.is_ascii_alphanumeric() || b".-+".contains(byte)))
+                .unwrap_or(rest.len());
+            std::str::from_utf8(&rest[..end]).ok()?.parse().ok()
+        })
+}
+
 fn deps_from_panic_messages(data: &[u8]) -> Option<Lockfile> {
     let deps = quitters::versions(data);
     if !deps.is_empty() {
-        let packages: Vec<Package> = deps.into_iter().map(to_package).collect();
-        Some(Lockfile {
-            version: cargo_lock::ResolveVersion::V2,
-            packages,
-            root: None,
-            metadata: Default::default(),
-            patch: Default::default(),
-        })
+        Some(lockfile_from_packages(
+            deps.into_iter().map(to_package).collect(),
+        ))
     } else {
         None
     }
 }
 
+/// Create a lockfile listing the given packages, which were recovered from a binary
+pub(crate) fn lockfile_from_packages(packages: Vec<Package>) -> Lockfile {
+    Lockfile {
+        version: cargo_lock::ResolveVersion::V2,
+        packages,
+        root: None,
+        metadata: Default::default(),
+        patch: Default::default(),
+    }
+}
+
 // matches https://docs.rs/cargo-lock/8.0.2/src/cargo_lock/package/source.rs.html#19
 // to signal crates.io to the `cargo-lock` crate
 const CRATES_IO_INDEX: &str = "registry+https://github.com/rust-lang/crates.io-index";
 
-fn to_package(quitter: (&str, cargo_lock::Version)) -> Package {
+fn to_package(quitter: (&str, Version)) -> Package {
     Package {
         // The `quitters` crate already ensures the name is valid, so we can just `.unwrap()` here
         name: cargo_lock::Name::from_str(quitter.0).unwrap(),
@@ -0,0 +1,66 @@
+//! Recursively discovers binary files (ELF, Mach-O, PE, WASM) in a directory,
+//! e.g. an application's install prefix or an unpacked container image rootfs.
+
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::{binary_deps::detect_format, binary_format::BinaryFormat};
+
+/// Number of bytes needed to identify the binary format
+const HEADER_LEN: usize = 8;
+
+/// Find all binary files in the given directory and its subdirectories.
+///
+/// Symbolic links are not followed, so each file is only reported once even
+/// in filesystem images containing link loops. Subdirectories and files which
+/// can't be read (e.g. due to permissions) are skipped.
+///
+/// The returned paths are sorted, so that repeated scans of the same
+/// directory produce reports in the same order.
+pub fn find_binaries(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut binaries = vec![];
+    let mut pending = vec![fs::read_dir(dir)?];
+
+    while let Some(entries) = pending.pop() {
+        for entry in entries.flatten() {
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(_) => continue,
+            };
+
+            if file_type.is_dir() {
+                if let Ok(subdir) = fs::read_dir(entry.path()) {
+                    pending.push(subdir);
+                }
+            } else if file_type.is_file() && is_binary(&entry.path()) {
+                binaries.push(entry.path());
+            }
+        }
+    }
+
+    binaries.sort();
+    Ok(binaries)
+}
+
+/// Check the header of the given file for a known binary format
+fn is_binary(path: &Path) -> bool {
+    let mut header = Vec::with_capacity(HEADER_LEN);
+
+    match File::open(path) {
+        Ok(file) => {
+            if file
+                .take(HEADER_LEN as u64)
+                .read_to_end(&mut header)
+                .is_err()
+            {
+                return false;
+            }
+        }
+        Err(_) => return false,
+    }
+
+    detect_format(&header) != BinaryFormat::Unknown
+}
@@ -6,9 +6,17 @@ mod fix;
 #[cfg(feature = "binary-scanning")]
 mod binary_scanning;
 
+#[cfg(feature = "binary-scanning")]
+mod installed;
+
+#[cfg(feature = "enrich")]
+mod enrich;
+
+mod server;
+
 use crate::{
     auditor::Auditor,
-    config::{AuditConfig, DenyOption, FilterList, OutputFormat},
+    config::{AuditConfig, DenyOption, FilterList, OutputFile, OutputFormat},
     error::display_err_with_source,
     lockfile,
     prelude::*,
@@ -17,14 +25,28 @@ use abscissa_core::{
     config::Override, error::Context, terminal::ColorChoice, FrameworkError, FrameworkErrorKind,
 };
 use clap::{Parser, ValueEnum};
-use rustsec::platforms::target::{Arch, OS};
-use std::{fmt, path::PathBuf, process::exit};
+use rustsec::{
+    platforms::{
+        target::{Arch, OS},
+        CustomPlatform, Platform,
+    },
+    report::SourceRule,
+};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    process::exit,
+};
 
 #[cfg(feature = "binary-scanning")]
 use self::binary_scanning::BinCommand;
+#[cfg(feature = "enrich")]
+use self::enrich::EnrichCommand;
 #[cfg(feature = "fix")]
 use self::fix::FixCommand;
-#[cfg(any(feature = "fix", feature = "binary-scanning"))]
+#[cfg(feature = "binary-scanning")]
+use self::installed::InstalledCommand;
+use self::server::ServerCommand;
 use clap::Subcommand;
 
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -63,8 +85,8 @@ impl fmt::Display for Color {
 #[derive(Command, Clone, Default, Debug, Parser)]
 #[command(version)]
 pub struct AuditCommand {
-    /// Optional subcommand (used for `cargo audit fix` and `cargo audit bin`)
-    #[cfg(any(feature = "fix", feature = "binary-scanning"))]
+    /// Optional subcommand (used for `cargo audit fix`, `cargo audit bin`,
+    /// `cargo audit installed`, and `cargo audit server`)
     #[command(subcommand)]
     subcommand: Option<AuditSubcommand>,
 
@@ -81,7 +103,7 @@ pub struct AuditCommand {
     #[arg(
         short,
         long = "db",
-        help = "advisory database git repo path (default: ~/.cargo/advisory-db)"
+        help = "advisory database git repo path (default: ~/.cargo/advisory-db, or one per URL)"
     )]
     db: Option<PathBuf>,
 
@@ -116,6 +138,74 @@ pub struct AuditCommand {
     )]
     ignore_source: bool,
 
+    /// Only report vulnerabilities which can be fixed by upgrading
+    #[arg(
+        long = "fixable-only",
+        help = "Only report vulnerabilities for which patched versions have been released"
+    )]
+    fixable_only: bool,
+
+    /// Look up when the patched versions of vulnerable packages were released
+    #[arg(
+        long = "release-dates",
+        help = "Look up the release dates of patched versions using the crates.io API"
+    )]
+    release_dates: bool,
+
+    /// Warn about packages from sources the advisory database doesn't cover
+    #[arg(
+        long = "check-sources",
+        help = "Warn for git dependencies without a pinned rev/tag and for registries other than crates.io"
+    )]
+    check_sources: bool,
+
+    /// Only audit packages from the given sources
+    #[arg(
+        long = "include-source",
+        value_name = "SOURCE",
+        help = "only audit packages from this source: path, git, crates-io, or a registry's index URL (can be specified multiple times)"
+    )]
+    include_sources: Vec<SourceRule>,
+
+    /// Skip packages from the given sources
+    #[arg(
+        long = "exclude-source",
+        value_name = "SOURCE",
+        help = "don't audit packages from this source, e.g. `path` for workspace members (can be specified multiple times)"
+    )]
+    exclude_sources: Vec<SourceRule>,
+
+    /// How to treat findings in packages only used by dev-dependencies
+    #[arg(
+        long = "dev-dependencies",
+        value_name = "report|downgrade|skip",
+        help = "Detect findings in crates only used by dev-dependencies, and report, downgrade (don't fail on), or skip them"
+    )]
+    dev_dependencies: Option<rustsec::report::DependencyScopePolicy>,
+
+    /// How to treat findings in packages only used at build time
+    #[arg(
+        long = "build-dependencies",
+        value_name = "report|downgrade|skip",
+        help = "Detect findings in crates only used at build time (by build scripts and proc macros), and report, downgrade (don't fail on), or skip them"
+    )]
+    build_dependencies: Option<rustsec::report::DependencyScopePolicy>,
+
+    /// Verify lockfile checksums against the crates.io index
+    #[arg(
+        long = "verify-checksums",
+        help = "fail if a Cargo.lock checksum differs from the one in the crates.io index"
+    )]
+    verify_checksums: bool,
+
+    /// Don't verify lockfile checksums, even if enabled in `audit.toml`
+    #[arg(
+        long = "no-verify-checksums",
+        conflicts_with = "verify_checksums",
+        help = "don't verify Cargo.lock checksums against the crates.io index"
+    )]
+    no_verify_checksums: bool,
+
     /// Skip fetching the advisory database git repository
     #[arg(
         short = 'n',
@@ -128,6 +218,36 @@ pub struct AuditCommand {
     #[arg(long = "stale", help = "allow stale database")]
     stale: bool,
 
+    /// Fetch the advisory database using the system `git` binary
+    #[arg(
+        long = "system-git",
+        help = "fetch the advisory DB with the `git` binary on PATH (e.g. to use its credential helpers and proxy settings)"
+    )]
+    system_git: bool,
+
+    /// Advisory database revision to use
+    #[arg(
+        long = "db-revision",
+        value_name = "REVISION",
+        help = "branch, tag, or commit of the advisory DB to use (default: latest commit)"
+    )]
+    db_revision: Option<String>,
+
+    /// Number of times to retry failed fetches
+    #[arg(
+        long = "retries",
+        value_name = "N",
+        help = "number of times to retry fetching the advisory DB and crates.io index after network errors (default: 3)"
+    )]
+    retries: Option<u32>,
+
+    /// Use the local copy of the advisory database if it can't be fetched
+    #[arg(
+        long = "offline-fallback",
+        help = "use the local copies of the advisory DB and crates.io index if they can't be fetched, reporting the DB as stale"
+    )]
+    offline_fallback: bool,
+
     /// Target CPU architecture to find vulnerabilities for
     #[arg(
         long = "target-arch",
@@ -142,10 +262,26 @@ pub struct AuditCommand {
     )]
     target_os: Vec<OS>,
 
+    /// Target platforms to find vulnerabilities for
+    #[arg(
+        long = "target",
+        value_name = "TRIPLE|FILE",
+        help = "filter vulnerabilities by the CPU and OS of a target triple or custom target JSON file (default: no filter). Can be specified multiple times"
+    )]
+    target: Vec<String>,
+
     /// URL to the advisory database git repository
     #[arg(short = 'u', long = "url", help = "URL for advisory database git repo")]
     url: Option<String>,
 
+    /// Configuration profile to use
+    #[arg(
+        long = "profile",
+        value_name = "NAME",
+        help = "Apply the settings of the given [profile.NAME] section of the config file"
+    )]
+    profile: Option<String>,
+
     /// Quiet mode - avoids printing extraneous information
     #[arg(
         short = 'q',
@@ -157,10 +293,92 @@ pub struct AuditCommand {
     /// Output reports as JSON
     #[arg(long = "json", help = "Output report in JSON format")]
     output_json: bool,
+
+    /// Output format to use
+    #[arg(
+        long = "format",
+        value_name = "terminal|json|diagnostics|toml|yaml|dot|graphml",
+        help = "Output report in the given format"
+    )]
+    output_format: Option<OutputFormat>,
+
+    /// Output findings as diagnostics located in `Cargo.toml`
+    #[arg(
+        long = "diagnostics",
+        help = "Output a JSON diagnostic per finding, located at the dependency in Cargo.toml"
+    )]
+    output_diagnostics: bool,
+
+    /// Files to also write the report to
+    #[arg(
+        long = "output",
+        value_name = "FORMAT=PATH",
+        help = "Also write the report to PATH in the given format, e.g. json=audit.json (can be specified multiple times)"
+    )]
+    output_files: Vec<OutputFile>,
+
+    /// Don't print anything unless the audit fails
+    #[arg(
+        long = "quiet-success",
+        help = "Avoid printing anything unless the audit fails"
+    )]
+    quiet_success: bool,
+
+    /// Exit codes for each kind of failure
+    #[arg(
+        long = "exit-code",
+        value_name = "KIND=CODE",
+        help = "exit code to use for: vulnerabilities, denied-warnings, stale-database (can be specified multiple times)"
+    )]
+    exit_code: Vec<String>,
+
+    /// File recording the findings which have already been reported
+    #[arg(
+        long = "state-file",
+        value_name = "PATH",
+        help = "Only report findings not recorded in this file, and record them in it"
+    )]
+    state_file: Option<PathBuf>,
+
+    /// Show the descriptions of advisories
+    #[arg(
+        long = "show-description",
+        help = "Show the description of each advisory"
+    )]
+    show_description: bool,
+
+    /// Include structured CVSS information in reports
+    #[arg(
+        long = "cvss-details",
+        help = "Include the metrics, score, and severity of CVSS vectors in reports"
+    )]
+    cvss_details: bool,
+
+    /// Webhook to deliver reports to
+    #[arg(
+        long = "notify-url",
+        value_name = "URL",
+        help = "POST the JSON report to this URL after auditing"
+    )]
+    notify_url: Option<String>,
+
+    /// Only deliver reports with findings to the webhook
+    #[arg(
+        long = "notify-only-on-findings",
+        help = "only POST the report if vulnerabilities or warnings were found"
+    )]
+    notify_only_on_findings: bool,
+
+    /// Additional HTTP headers to send to the webhook
+    #[arg(
+        long = "notify-header",
+        value_name = "NAME: VALUE",
+        help = "HTTP header to send with the report (can be specified multiple times)"
+    )]
+    notify_header: Vec<String>,
 }
 
 /// Subcommands of `cargo audit`
-#[cfg(any(feature = "fix", feature = "binary-scanning"))]
 #[derive(Subcommand, Clone, Debug, Runnable)]
 pub enum AuditSubcommand {
     /// `cargo audit fix` subcommand
@@ -175,9 +393,53 @@ pub enum AuditSubcommand {
         long_about = "Scan compiled binaries for known vulnerabilities.
 
 Performs a complete scan if the binary is built with 'cargo auditable'.
-If not, recovers a part of the dependency list from panic messages."
+If not, recovers a part of the dependency list from panic messages.
+
+Directories (e.g. an unpacked container image) are searched recursively
+for ELF, Mach-O, PE and WASM files, each of which is reported separately."
     )]
     Bin(BinCommand),
+
+    /// `cargo audit installed` subcommand
+    #[cfg(feature = "binary-scanning")]
+    #[command(
+        about = "scan programs installed with 'cargo install'",
+        long_about = "Scan the programs installed with 'cargo install' for known vulnerabilities.
+
+Reads the list of installed packages from '.crates2.json' in the install root
+and scans each of their binaries like 'cargo audit bin' does. Binaries which
+contain no dependency information are audited using the package they were
+installed from."
+    )]
+    Installed(InstalledCommand),
+
+    /// `cargo audit server` subcommand
+    #[command(
+        about = "answer audit requests over JSON-RPC",
+        long_about = "Keep the advisory database loaded and answer audit requests over JSON-RPC.
+
+Reads JSON-RPC 2.0 requests from stdin (or from the clients of a Unix socket
+given with --socket), one per line, and writes a response line for each.
+The 'audit' method takes the contents of a Cargo.lock file in its 'lockfile'
+parameter and returns the report as printed by 'cargo audit --json'. The
+'refresh' method reloads the advisory database and 'shutdown' stops the server."
+    )]
+    Server(ServerCommand),
+
+    /// `cargo audit enrich` subcommand
+    #[cfg(feature = "enrich")]
+    #[command(
+        about = "add data from external sources to a JSON report",
+        long_about = "Add data about the advisories of a JSON report from external sources.
+
+Reads a report written by 'cargo audit --json' and adds the aliases of each
+advisory from OSV, the EPSS scores of its CVEs, whether they're in CISA's
+catalog of Known Exploited Vulnerabilities, and the severity of its GitHub
+Security Advisory (authenticating with GITHUB_TOKEN if set). Each advisory is
+looked up once and requests are rate limited (see 'requests_per_second' in the
+'[enrich]' section of the configuration)."
+    )]
+    Enrich(EnrichCommand),
 }
 
 impl AuditCommand {
@@ -189,7 +451,14 @@ impl AuditCommand {
 
 impl Override<AuditConfig> for AuditCommand {
     fn override_config(&self, config: AuditConfig) -> Result<AuditConfig, FrameworkError> {
-        let mut config = config;
+        // Profiles are applied first, so explicit arguments take precedence
+        let mut config = match &self.profile {
+            Some(profile) => config
+                .with_profile(profile)
+                .map_err(|e| Context::new(FrameworkErrorKind::ConfigError, Some(Box::new(e))))?,
+            None => config,
+        };
+
         if let Some(db) = &self.db {
             config.database.path = Some(db.into());
         }
@@ -197,21 +466,67 @@ impl Override<AuditConfig> for AuditCommand {
         for advisory_id in &self.ignore {
             config.advisories.ignore.push(
                 advisory_id
-                    .parse()
-                    .map_err(|e| Context::new(FrameworkErrorKind::ParseError, Some(Box::new(e))))?,
+                    .parse::<rustsec::advisory::Id>()
+                    .map_err(|e| Context::new(FrameworkErrorKind::ParseError, Some(Box::new(e))))?
+                    .into(),
             );
         }
 
         config.advisories.ignore_source |= self.ignore_source;
+        config.advisories.fixable_only |= self.fixable_only;
+
+        if let Some(dev_dependencies) = self.dev_dependencies {
+            config.advisories.dev_dependencies = Some(dev_dependencies);
+        }
+
+        if let Some(build_dependencies) = self.build_dependencies {
+            config.advisories.build_dependencies = Some(build_dependencies);
+        }
+
+        config.release_dates.enabled |= self.release_dates;
+        config.sources.enabled |= self.check_sources;
+        config
+            .sources
+            .include
+            .extend(self.include_sources.iter().cloned());
+        config
+            .sources
+            .exclude
+            .extend(self.exclude_sources.iter().cloned());
+        config.checksums.enabled =
+            (config.checksums.enabled || self.verify_checksums) && !self.no_verify_checksums;
         config.database.fetch |= !self.no_fetch;
         config.database.stale |= self.stale;
+        config.database.system_git |= self.system_git;
+        config.database.offline_fallback |= self.offline_fallback;
+
+        if let Some(retries) = self.retries {
+            config.database.retries = Some(retries);
+        }
+
+        if let Some(revision) = &self.db_revision {
+            config.database.revision = Some(revision.clone());
+        }
 
-        if !self.target_arch.is_empty() {
-            config.target.arch = Some(FilterList::Many(self.target_arch.clone()));
+        let mut target_arch = self.target_arch.clone();
+        let mut target_os = self.target_os.clone();
+
+        for target in &self.target {
+            let platform = find_platform(target)?;
+            if !target_arch.contains(&platform.target_arch) {
+                target_arch.push(platform.target_arch);
+            }
+            if !target_os.contains(&platform.target_os) {
+                target_os.push(platform.target_os);
+            }
+        }
+
+        if !target_arch.is_empty() {
+            config.target.arch = Some(FilterList::Many(target_arch));
         }
 
-        if !self.target_os.is_empty() {
-            config.target.os = Some(FilterList::Many(self.target_os.clone()));
+        if !target_os.is_empty() {
+            config.target.os = Some(FilterList::Many(target_os));
         }
 
         if let Some(url) = &self.url {
@@ -227,11 +542,70 @@ impl Override<AuditConfig> for AuditCommand {
         }
 
         config.output.quiet |= self.quiet;
+        config.output.show_description |= self.show_description;
+        config.output.cvss_details |= self.cvss_details;
+        config.output.quiet_success |= self.quiet_success;
+
+        if let Some(state_file) = &self.state_file {
+            config.output.state_file = Some(state_file.clone());
+        }
+
+        for exit_code in &self.exit_code {
+            let (kind, code) = exit_code
+                .split_once('=')
+                .and_then(|(kind, code)| Some((kind, code.parse().ok()?)))
+                .ok_or_else(|| {
+                    Context::new(
+                        FrameworkErrorKind::ParseError,
+                        Some(
+                            format!("invalid exit code (expected `KIND=CODE`): {}", exit_code)
+                                .into(),
+                        ),
+                    )
+                })?;
+            config
+                .output
+                .exit_codes
+                .set(kind, code)
+                .map_err(|e| Context::new(FrameworkErrorKind::ParseError, Some(Box::new(e))))?;
+        }
+
+        if let Some(url) = &self.notify_url {
+            config.notify.url = Some(url.clone());
+        }
+
+        config.notify.only_on_findings |= self.notify_only_on_findings;
+
+        for header in &self.notify_header {
+            let (name, value) = header.split_once(':').ok_or_else(|| {
+                Context::new(
+                    FrameworkErrorKind::ParseError,
+                    Some(format!("invalid header (expected `NAME: VALUE`): {}", header).into()),
+                )
+            })?;
+            config
+                .notify
+                .headers
+                .insert(name.trim().to_owned(), value.trim().to_owned());
+        }
+
+        if let Some(format) = self.output_format {
+            config.output.format = format;
+        }
 
         if self.output_json {
             config.output.format = OutputFormat::Json;
         }
 
+        if self.output_diagnostics {
+            config.output.format = OutputFormat::Diagnostics;
+        }
+
+        config
+            .output
+            .files
+            .extend(self.output_files.iter().cloned());
+
         Ok(config)
     }
 }
@@ -250,6 +624,23 @@ impl Runnable for AuditCommand {
             exit(0)
         }
 
+        #[cfg(feature = "binary-scanning")]
+        if let Some(AuditSubcommand::Installed(installed)) = &self.subcommand {
+            installed.run();
+            exit(0)
+        }
+
+        if let Some(AuditSubcommand::Server(server)) = &self.subcommand {
+            server.run();
+            exit(0)
+        }
+
+        #[cfg(feature = "enrich")]
+        if let Some(AuditSubcommand::Enrich(enrich)) = &self.subcommand {
+            enrich.run();
+            exit(0)
+        }
+
         let maybe_path = self.file.as_deref();
         // It is important to generate the lockfile before initializing the auditor,
         // otherwise we might deadlock because both need the Cargo package lock
@@ -259,13 +650,9 @@ impl Runnable for AuditCommand {
         });
         let mut auditor = self.auditor();
         let report = auditor.audit_lockfile(&path);
+        auditor.notify();
         match report {
-            Ok(report) => {
-                if auditor.should_exit_with_failure(&report) {
-                    exit(1);
-                }
-                exit(0);
-            }
+            Ok(report) => exit(auditor.exit_code(&report)),
             Err(e) => {
                 status_err!("{}", display_err_with_source(&e));
                 exit(2);
@@ -280,3 +667,40 @@ impl AuditCommand {
         Auditor::new(&APP.config())
     }
 }
+
+/// Find the platform described by the given target triple, or by the custom
+/// target specification JSON file at the given path
+fn find_platform(target: &str) -> Result<CustomPlatform, FrameworkError> {
+    let parse_error =
+        |e: rustsec::Error| Context::new(FrameworkErrorKind::ParseError, Some(Box::new(e)));
+
+    if let Some(platform) = Platform::find(target) {
+        return Ok(platform.into());
+    }
+
+    let path = Path::new(target);
+    if path.extension().map_or(true, |ext| ext != "json") {
+        return Err(parse_error(rustsec::Error::new(
+            rustsec::ErrorKind::Parse,
+            &format!("unknown target triple: {}", target),
+        ))
+        .into());
+    }
+
+    let json = std::fs::read_to_string(path).map_err(|e| {
+        parse_error(rustsec::Error::with_source(
+            rustsec::ErrorKind::Io,
+            format!("couldn't read {}", path.display()),
+            e,
+        ))
+    })?;
+    let platform = serde_json::from_str(&json).map_err(|e| {
+        parse_error(rustsec::Error::with_source(
+            rustsec::ErrorKind::Parse,
+            format!("couldn't parse target specification {}", path.display()),
+            e,
+        ))
+    })?;
+
+    Ok(platform)
+}
@@ -2,7 +2,7 @@
 
 use crate::{auditor::Auditor, prelude::*};
 use clap::Parser;
-use std::{path::PathBuf, process::exit};
+use std::{num::NonZeroUsize, path::PathBuf, process::exit};
 
 #[cfg(feature = "binary-scanning")]
 /// The `cargo audit` subcommand
@@ -13,16 +13,41 @@ pub struct BinCommand {
     #[arg(
         value_parser,
         required = true,
-        help = "Paths to the binaries to be scanned"
+        help = "Paths to the binaries to be scanned, or directories to search for binaries \
+        (`-` reads dependency JSON extracted by `cargo auditable` from STDIN)"
     )]
     binary_paths: Vec<PathBuf>,
+
+    /// Treat the paths as dependency JSON already extracted from binaries
+    #[arg(
+        long = "auditable-json",
+        help = "Treat the paths as `cargo auditable` dependency JSON already extracted from binaries"
+    )]
+    auditable_json: bool,
+
+    /// Number of binaries to scan concurrently
+    #[arg(
+        short,
+        long,
+        help = "Number of binaries to scan concurrently (default: number of CPUs)"
+    )]
+    jobs: Option<NonZeroUsize>,
 }
 
 impl Runnable for BinCommand {
     fn run(&self) {
-        let report = self.auditor().audit_binaries(&self.binary_paths);
+        let mut auditor = self.auditor();
+        let report = if self.auditable_json {
+            auditor.audit_auditable_json(&self.binary_paths)
+        } else {
+            match self.jobs {
+                Some(jobs) => auditor.audit_binaries_with_jobs(&self.binary_paths, jobs),
+                None => auditor.audit_binaries(&self.binary_paths),
+            }
+        };
+        auditor.notify();
         if report.vulnerabilities_found {
-            exit(1)
+            exit(report.exit_code)
         } else if report.errors_encountered {
             exit(2)
         } else {
@@ -0,0 +1,106 @@
+//! The `cargo audit enrich` subcommand
+
+use crate::{
+    config::EnrichSource,
+    enrich::Enricher,
+    error::{display_err_with_source, Error, ErrorKind},
+    prelude::*,
+};
+use clap::Parser;
+use rustsec::Report;
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::PathBuf,
+    process::exit,
+};
+
+/// The `cargo audit enrich` subcommand
+#[derive(Command, Clone, Default, Debug, Parser)]
+#[command()]
+pub struct EnrichCommand {
+    /// Report to enrich
+    #[arg(
+        value_name = "REPORT",
+        help = "JSON report to enrich, as written by 'cargo audit --json' (default: stdin)"
+    )]
+    report: Option<PathBuf>,
+
+    /// File to write the enriched report to
+    #[arg(
+        short = 'o',
+        long = "output",
+        value_name = "PATH",
+        help = "write the enriched report to a file instead of stdout"
+    )]
+    output: Option<PathBuf>,
+
+    /// Sources to fetch data from
+    #[arg(
+        long = "source",
+        value_name = "SOURCE",
+        help = "source to fetch data from: osv, epss, kev or ghsa (can be repeated; default: all)"
+    )]
+    sources: Vec<EnrichSource>,
+}
+
+impl Runnable for EnrichCommand {
+    fn run(&self) {
+        if let Err(e) = self.enrich() {
+            status_err!("{}", display_err_with_source(&e));
+            exit(2);
+        }
+        exit(0);
+    }
+}
+
+impl EnrichCommand {
+    /// Read the report, enrich it and write it out
+    fn enrich(&self) -> Result<(), Error> {
+        let config = APP.config();
+        let mut enrich_config = config.enrich.clone();
+        if !self.sources.is_empty() {
+            enrich_config.sources = self.sources.clone();
+        }
+
+        let mut report = self.read_report()?;
+        Enricher::new(&enrich_config, config.database.retry_policy())?.enrich(&mut report)?;
+
+        let json = serde_json::to_string(&report).map_err(|e| ErrorKind::Parse.context(e))?;
+        match &self.output {
+            Some(path) => fs::write(path, json).map_err(|e| {
+                ErrorKind::Io.context(format!("couldn't write {}: {}", path.display(), e))
+            })?,
+            None => {
+                let mut stdout = io::stdout().lock();
+                writeln!(stdout, "{}", json)
+                    .and_then(|()| stdout.flush())
+                    .map_err(|e| ErrorKind::Io.context(e))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the report from the given file, or stdin
+    fn read_report(&self) -> Result<Report, Error> {
+        let json = match self.report.as_deref() {
+            Some(path) if path.as_os_str() != "-" => fs::read_to_string(path).map_err(|e| {
+                ErrorKind::Io.context(format!("couldn't read {}: {}", path.display(), e))
+            })?,
+            _ => {
+                let mut json = String::new();
+                io::stdin()
+                    .read_to_string(&mut json)
+                    .map_err(|e| ErrorKind::Io.context(e))?;
+                json
+            }
+        };
+
+        serde_json::from_str(&json).map_err(|e| {
+            ErrorKind::Parse
+                .context(format!("invalid report: {}", e))
+                .into()
+        })
+    }
+}
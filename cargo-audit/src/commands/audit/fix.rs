@@ -1,12 +1,13 @@
 //! The `cargo audit fix` subcommand
 
-use crate::{auditor::Auditor, lockfile, prelude::*};
+use crate::{auditor::Auditor, commit::Committer, lockfile, prelude::*};
 use abscissa_core::{Command, Runnable};
 use cargo_lock::Lockfile;
 use clap::Parser;
-use rustsec::{advisory::Id, Fixer};
+use rustsec::{advisory::Id, Fixer, Vulnerability};
 use std::{
     collections::BTreeSet,
+    io::{self, BufRead, Write},
     path::{Path, PathBuf},
     process::exit,
 };
@@ -21,6 +22,43 @@ pub struct FixCommand {
     /// Perform a dry run
     #[arg(long = "dry-run", help = "perform a dry run for the fix")]
     dry_run: bool,
+
+    /// Review each planned upgrade before applying it
+    #[arg(
+        short = 'i',
+        long = "interactive",
+        help = "review each planned upgrade and choose whether to apply it"
+    )]
+    interactive: bool,
+
+    /// Suggest `[patch.crates-io]` entries for vulnerabilities which couldn't be fixed
+    #[arg(
+        long = "suggest-patches",
+        help = "suggest [patch.crates-io] entries for vulnerabilities which could not be fixed"
+    )]
+    suggest_patches: bool,
+
+    /// Git repositories to point `[patch.crates-io]` suggestions at
+    #[arg(
+        long = "patch-fork",
+        value_name = "CRATE=URL",
+        help = "git repository with a patched version of the given crate (can be specified multiple times)"
+    )]
+    patch_fork: Vec<String>,
+
+    /// Append suggested patches to `Cargo.toml`
+    #[arg(
+        long = "write-patches",
+        help = "append [patch.crates-io] suggestions which have a --patch-fork to Cargo.toml"
+    )]
+    write_patches: bool,
+
+    /// Commit each applied fix with git
+    #[arg(
+        long = "commit",
+        help = "create a git commit for each applied fix, naming the advisory and the upgrade"
+    )]
+    commit: bool,
 }
 
 impl FixCommand {
@@ -33,6 +71,170 @@ impl FixCommand {
     pub fn cargo_lock_path(&self) -> Option<&Path> {
         self.file.as_deref()
     }
+
+    /// Look up the `--patch-fork` URL given for a crate (if any)
+    fn patch_fork(&self, crate_name: &str) -> Option<&str> {
+        self.patch_fork.iter().find_map(|fork| {
+            let (name, url) = fork.split_once('=')?;
+            (name == crate_name).then_some(url)
+        })
+    }
+
+    /// Walk through the upgrades planned for the given vulnerabilities, asking
+    /// whether to accept, skip, or defer each one.
+    ///
+    /// Returns the vulnerabilities to fix, along with the IDs of the advisories
+    /// which were not accepted. Vulnerabilities without a patched version are
+    /// always returned so they are reported as usual.
+    fn review<'a>(
+        &self,
+        fixer: &Fixer,
+        vulnerabilities: &'a [Vulnerability],
+    ) -> (Vec<&'a Vulnerability>, BTreeSet<Id>) {
+        let mut accepted = vec![];
+        let mut skipped = BTreeSet::new();
+        let mut deferred = BTreeSet::new();
+        let mut stdin = io::stdin().lock();
+        let mut quit = false;
+
+        for planned in fixer.plan(vulnerabilities) {
+            let vulnerability = planned.vulnerability;
+            let target = match &planned.target {
+                Some(target) if !quit => target,
+                Some(_) => {
+                    skipped.insert(vulnerability.advisory.id.clone());
+                    continue;
+                }
+                None => {
+                    accepted.push(vulnerability);
+                    continue;
+                }
+            };
+
+            println!();
+            println!(
+                "{}: {}",
+                vulnerability.advisory.id, vulnerability.advisory.title
+            );
+            println!(
+                "    {} {} \u{2192} {} ({})",
+                vulnerability.package.name,
+                vulnerability.package.version,
+                target,
+                if planned.semver_compatible {
+                    "semver-compatible"
+                } else {
+                    "semver-incompatible, Cargo.toml may need to be updated"
+                }
+            );
+
+            loop {
+                print!("Apply this upgrade? [a]ccept, [s]kip, [d]efer, [q]uit: ");
+                io::stdout().flush().unwrap();
+
+                let mut answer = String::new();
+                if stdin.read_line(&mut answer).unwrap_or(0) == 0 {
+                    // Treat the end of input like quitting
+                    println!();
+                    answer = "q".to_owned();
+                }
+
+                match answer.trim() {
+                    "a" | "accept" => accepted.push(vulnerability),
+                    "s" | "skip" => {
+                        skipped.insert(vulnerability.advisory.id.clone());
+                    }
+                    "d" | "defer" => {
+                        deferred.insert(vulnerability.advisory.id.clone());
+                    }
+                    "q" | "quit" => {
+                        skipped.insert(vulnerability.advisory.id.clone());
+                        quit = true;
+                    }
+                    _ => continue,
+                }
+                break;
+            }
+        }
+
+        println!();
+
+        if !skipped.is_empty() {
+            status_warn!(
+                "Skipped {}; add them to `ignore` in the `[advisories]` section of \
+                audit.toml to stop them from being reported",
+                join_ids(&skipped)
+            );
+        }
+
+        if !deferred.is_empty() {
+            status_warn!(
+                "Deferred {}; run `cargo audit fix --interactive` again to review them",
+                join_ids(&deferred)
+            );
+        }
+
+        skipped.append(&mut deferred);
+        (accepted, skipped)
+    }
+
+    /// Print (and optionally write) `[patch.crates-io]` suggestions for the
+    /// given vulnerabilities
+    fn suggest_patches(&self, fixer: &Fixer, vulnerabilities: &[&Vulnerability], lockfile: &Path) {
+        let mut suggestions = vec![];
+
+        for vulnerability in vulnerabilities {
+            let fork = match self.patch_fork(vulnerability.package.name.as_str()) {
+                Some(url) => match url.parse() {
+                    Ok(url) => Some(url),
+                    Err(e) => {
+                        status_err!("invalid --patch-fork URL {}: {}", url, e);
+                        exit(2);
+                    }
+                },
+                None => None,
+            };
+
+            if let Some(suggestion) = fixer.patch_suggestion(vulnerability, fork) {
+                suggestions.push(suggestion);
+            }
+        }
+
+        if suggestions.is_empty() {
+            return;
+        }
+
+        status_warn!(
+            "The following [patch.crates-io] entries may be used to override the vulnerable \
+            versions.\nCargo requires patches to use a different source than crates.io, \
+            so a `git` repository with the fix (e.g. a fork) is needed (see --patch-fork):"
+        );
+
+        for suggestion in &suggestions {
+            println!("{}", suggestion);
+        }
+
+        if self.write_patches {
+            let manifest_path = lockfile.with_file_name("Cargo.toml");
+
+            match fixer.append_patches(&manifest_path, &suggestions) {
+                Ok(written) => {
+                    for suggestion in written {
+                        status_ok!(
+                            "Patched",
+                            "{} in {}",
+                            suggestion.package,
+                            manifest_path.display()
+                        );
+                    }
+                }
+                Err(e) => {
+                    status_err!("couldn't write patches: {}", e);
+                    exit(2);
+                }
+            }
+        }
+    }
 }
 
 impl Runnable for FixCommand {
@@ -42,7 +244,9 @@ impl Runnable for FixCommand {
             exit(2);
         });
 
-        let report = self.auditor().audit_lockfile(&path);
+        let mut auditor = self.auditor();
+        let report = auditor.audit_lockfile(&path);
+        auditor.notify();
         let report = match report {
             Ok(report) => {
                 // TODO: also handle warnings
@@ -69,10 +273,38 @@ impl Runnable for FixCommand {
             status_warn!("Performing a dry run, the fixes will not be applied");
         }
 
+        if self.commit && dry_run {
+            status_warn!("Nothing will be committed during a dry run");
+        }
+        let committer = if self.commit && !dry_run {
+            match Committer::new(&path).and_then(|committer| {
+                committer.ensure_clean()?;
+                Ok(committer)
+            }) {
+                Ok(committer) => Some(committer),
+                Err(e) => {
+                    status_err!("can't commit fixes: {}", e);
+                    exit(2);
+                }
+            }
+        } else {
+            None
+        };
+
+        // Let the user choose which upgrades to apply before changing anything
+        let (vulnerabilities, declined) = if self.interactive {
+            self.review(&fixer, &report.vulnerabilities.list)
+        } else {
+            (
+                report.vulnerabilities.list.iter().collect(),
+                BTreeSet::new(),
+            )
+        };
+
         let mut unpatchable_vulns: BTreeSet<Id> = BTreeSet::new();
         let mut failed_patches = 0;
 
-        for vulnerability in &report.vulnerabilities.list {
+        for vulnerability in vulnerabilities {
             if vulnerability.versions.patched().is_empty() {
                 unpatchable_vulns.insert(vulnerability.advisory.id.clone());
                 status_warn!(
@@ -81,6 +313,25 @@ impl Runnable for FixCommand {
                     vulnerability.package.name
                 );
             } else {
+                // Remember what the lockfile looked like before the fix, to
+                // tell which version the package was upgraded to
+                let before = match &committer {
+                    Some(committer) => {
+                        match committer
+                            .ensure_clean()
+                            .and_then(|()| Ok(Lockfile::load(&path)?))
+                        {
+                            Ok(lockfile) => Some(lockfile),
+                            Err(e) => {
+                                failed_patches += 1;
+                                status_warn!("Not fixing {}: {}", vulnerability.advisory.id, e);
+                                continue;
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
                 let mut command = fixer.get_fix_command(vulnerability, dry_run);
                 // If the path to Cargo.lock has been specified explicitly,
                 // run the `cargo update` command in that directory
@@ -94,13 +345,39 @@ impl Runnable for FixCommand {
                 // so any status or error messages from `cargo update` will automatically be forwarded
                 // to the user of `cargo audit fix`.
                 let status = command.status();
-                if let Err(e) = status {
-                    failed_patches += 1;
-                    status_warn!(
-                        "Failed to run `cargo update` for package {}: {}",
-                        vulnerability.package.name,
-                        e
-                    );
+                match status {
+                    Err(e) => {
+                        failed_patches += 1;
+                        status_warn!(
+                            "Failed to run `cargo update` for package {}: {}",
+                            vulnerability.package.name,
+                            e
+                        );
+                    }
+                    Ok(status) if status.success() => {
+                        if let (Some(committer), Some(before)) = (&committer, &before) {
+                            match committer.commit(before, vulnerability) {
+                                Ok(Some(upgraded)) => status_ok!(
+                                    "Committed",
+                                    "{} {} \u{2192} {} ({})",
+                                    vulnerability.package.name,
+                                    vulnerability.package.version,
+                                    upgraded,
+                                    vulnerability.advisory.id
+                                ),
+                                Ok(None) => (),
+                                Err(e) => {
+                                    failed_patches += 1;
+                                    status_warn!(
+                                        "Failed to commit the fix for {}: {}",
+                                        vulnerability.advisory.id,
+                                        e
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    Ok(_) => (),
                 }
             }
         }
@@ -129,10 +406,12 @@ impl Runnable for FixCommand {
 
             let report_after_fix = auditor.audit_lockfile(&path).unwrap();
             let vulns_after_fix = &report_after_fix.vulnerabilities.list;
-            let fixable_but_unfixed: Vec<String> = vulns_after_fix
+            let fixable_but_unfixed: Vec<&Vulnerability> = vulns_after_fix
                 .iter()
-                .filter(|vuln| !unpatchable_vulns.contains(&vuln.advisory.id))
-                .map(|vuln| vuln.advisory.id.to_string())
+                .filter(|vuln| {
+                    !unpatchable_vulns.contains(&vuln.advisory.id)
+                        && !declined.contains(&vuln.advisory.id)
+                })
                 .collect();
             if !fixable_but_unfixed.is_empty() {
                 status_warn!(
@@ -140,8 +419,16 @@ impl Runnable for FixCommand {
                     This usually occurs when the fixed version is not semver-compatible,\n\
                     or the version range specified in your `Cargo.toml` is too restrictive\n\
                     (e.g. uses `=` or `=<` operators) so the fixed version would not match it.",
-                    fixable_but_unfixed.join(", ")
+                    fixable_but_unfixed
+                        .iter()
+                        .map(|vuln| vuln.advisory.id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
                 );
+
+                if self.suggest_patches || self.write_patches || !self.patch_fork.is_empty() {
+                    self.suggest_patches(&fixer, &fixable_but_unfixed, &path);
+                }
             }
 
             let remaining_vulns_count = report_after_fix.vulnerabilities.list.len();
@@ -164,3 +451,11 @@ impl Runnable for FixCommand {
         }
     }
 }
+
+/// Join the given advisory IDs into a comma-separated list
+fn join_ids(ids: &BTreeSet<Id>) -> String {
+    ids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
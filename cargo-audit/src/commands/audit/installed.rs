@@ -0,0 +1,54 @@
+//! The `cargo audit installed` subcommand
+
+use crate::{auditor::Auditor, prelude::*};
+use clap::Parser;
+use std::{env, path::PathBuf, process::exit};
+
+#[cfg(feature = "binary-scanning")]
+/// The `cargo audit installed` subcommand
+#[derive(Command, Clone, Default, Debug, Parser)]
+#[command()]
+pub struct InstalledCommand {
+    /// Cargo install root to audit
+    #[arg(
+        long = "root",
+        value_name = "DIR",
+        help = "install root to audit (default: $CARGO_INSTALL_ROOT or $CARGO_HOME)"
+    )]
+    root: Option<PathBuf>,
+}
+
+impl Runnable for InstalledCommand {
+    fn run(&self) {
+        let root = self.root().unwrap_or_else(|| {
+            status_err!("couldn't determine the Cargo install root, use --root to set it");
+            exit(2);
+        });
+
+        let mut auditor = self.auditor();
+        let report = auditor.audit_installed(&root);
+        auditor.notify();
+        if report.vulnerabilities_found {
+            exit(report.exit_code)
+        } else if report.errors_encountered {
+            exit(2)
+        } else {
+            exit(0)
+        }
+    }
+}
+
+impl InstalledCommand {
+    /// Initialize `Auditor`
+    pub fn auditor(&self) -> Auditor {
+        Auditor::new(&APP.config())
+    }
+
+    /// Install root to audit, using the same precedence as `cargo install`
+    fn root(&self) -> Option<PathBuf> {
+        self.root
+            .clone()
+            .or_else(|| env::var_os("CARGO_INSTALL_ROOT").map(PathBuf::from))
+            .or_else(|| home::cargo_home().ok())
+    }
+}
@@ -0,0 +1,95 @@
+//! The `cargo audit server` subcommand
+
+use crate::{prelude::*, server::Server};
+use clap::Parser;
+use std::{io, path::PathBuf, process::exit};
+
+/// The `cargo audit server` subcommand
+#[derive(Command, Clone, Default, Debug, Parser)]
+#[command()]
+pub struct ServerCommand {
+    /// Unix socket to listen on instead of stdio
+    #[cfg(unix)]
+    #[arg(
+        long = "socket",
+        value_name = "PATH",
+        help = "listen for connections on a Unix socket instead of using stdio"
+    )]
+    socket: Option<PathBuf>,
+}
+
+impl Runnable for ServerCommand {
+    fn run(&self) {
+        let mut config = APP.config().as_ref().clone();
+
+        #[cfg(unix)]
+        if let Some(socket) = &self.socket {
+            let mut server = Server::new(config);
+            if let Err(e) = serve_socket(&mut server, socket) {
+                status_err!("couldn't serve on {}: {}", socket.display(), e);
+                exit(2);
+            }
+            exit(0);
+        }
+
+        // Responses are written to stdout, so status messages must not be
+        config.output.quiet = true;
+        let mut server = Server::new(config);
+        if let Err(e) = server.serve(io::stdin().lock(), io::stdout().lock()) {
+            status_err!("{}", e);
+            exit(2);
+        }
+        exit(0);
+    }
+}
+
+/// Answer requests from each client connecting to the given Unix socket in
+/// turn, until one of them sends a `shutdown` request
+#[cfg(unix)]
+fn serve_socket(server: &mut Server, path: &std::path::Path) -> io::Result<()> {
+    use std::{
+        fs,
+        io::BufReader,
+        os::unix::{
+            fs::{FileTypeExt, MetadataExt},
+            net::UnixListener,
+        },
+    };
+
+    // Remove the socket left behind by a previous server, but nothing else
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => fs::remove_file(path)?,
+        Ok(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "path exists and is not a socket",
+            ))
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+        Err(e) => return Err(e),
+    }
+
+    let listener = UnixListener::bind(path)?;
+    let bound = fs::symlink_metadata(path)?;
+    status_ok!("Listening", "on {}", path.display());
+
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let reader = BufReader::new(stream.try_clone()?);
+
+        // A client going away shouldn't take the server down with it
+        match server.serve(reader, stream) {
+            Ok(true) => break,
+            Ok(false) => (),
+            Err(e) => status_warn!("client connection failed: {}", e),
+        }
+    }
+
+    // Only unlink the socket if it's still the one we bound
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.dev() == bound.dev() && metadata.ino() == bound.ino() => {
+            fs::remove_file(path)
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,147 @@
+//! Committing the fixes applied by `cargo audit fix` with git, one commit per
+//! fixed advisory
+
+use crate::error::{Error, ErrorKind};
+use rustsec::{Lockfile, Version, Vulnerability};
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// Commits the changes made to a lockfile with git
+#[derive(Debug)]
+pub struct Committer {
+    /// Directory containing the lockfile, which git is run in
+    dir: PathBuf,
+
+    /// File name of the lockfile, which is the only file committed
+    file_name: OsString,
+}
+
+impl Committer {
+    /// Create a committer for the lockfile at the given path
+    pub fn new(lockfile_path: &Path) -> Result<Self, Error> {
+        let canonical_path = lockfile_path.canonicalize()?;
+
+        Ok(Self {
+            dir: canonical_path.parent().unwrap().to_owned(),
+            file_name: canonical_path.file_name().unwrap().to_owned(),
+        })
+    }
+
+    /// Ensure the lockfile has no uncommitted changes, which would otherwise
+    /// be swept into the commit of the next fix
+    pub fn ensure_clean(&self) -> Result<(), Error> {
+        if self.is_modified()? {
+            return Err(ErrorKind::Repo
+                .context(format!(
+                    "{} has uncommitted changes; commit or stash them first",
+                    self.file_name.to_string_lossy()
+                ))
+                .into());
+        }
+
+        Ok(())
+    }
+
+    /// Commit the changes made to the lockfile to fix the given vulnerability,
+    /// given the lockfile as it was before the fix was applied.
+    ///
+    /// Returns the version the vulnerable package was upgraded to, or `None`
+    /// if there was nothing to commit (e.g. because the vulnerable version is
+    /// still locked, or the same upgrade already fixed another advisory).
+    pub fn commit(
+        &self,
+        before: &Lockfile,
+        vulnerability: &Vulnerability,
+    ) -> Result<Option<Version>, Error> {
+        let package = &vulnerability.package;
+        let after = Lockfile::load(self.dir.join(&self.file_name))?;
+
+        // Nothing to commit if the vulnerable version is still locked
+        if after
+            .packages
+            .iter()
+            .any(|p| p.name == package.name && p.version == package.version)
+        {
+            return Ok(None);
+        }
+
+        let upgraded = match upgraded_version(before, &after, vulnerability) {
+            Some(version) => version,
+            None => return Ok(None),
+        };
+
+        if !self.is_modified()? {
+            return Ok(None);
+        }
+
+        let advisory = &vulnerability.advisory;
+        let mut message = format!(
+            "Update {} {} \u{2192} {} to fix {}\n\n{}: {}",
+            package.name, package.version, upgraded, advisory.id, advisory.id, advisory.title
+        );
+        if let Some(url) = advisory.id.url() {
+            message.push('\n');
+            message.push_str(&url);
+        }
+
+        self.git(&["add", "--"])?;
+        // Only commit the lockfile, even if other changes are staged
+        self.git(&["commit", "--quiet", "--only", "-m", &message, "--"])?;
+
+        Ok(Some(upgraded))
+    }
+
+    /// Does the lockfile differ from the committed one?
+    fn is_modified(&self) -> Result<bool, Error> {
+        let output = self.git(&["status", "--porcelain", "--"])?;
+        Ok(!output.stdout.is_empty())
+    }
+
+    /// Run `git` with the given arguments followed by the lockfile's name
+    fn git(&self, args: &[&str]) -> Result<Output, Error> {
+        let output = Command::new("git")
+            .args(args)
+            .arg(&self.file_name)
+            .current_dir(&self.dir)
+            .output()
+            .map_err(|e| ErrorKind::Repo.context(format!("couldn't run `git`: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(ErrorKind::Repo
+                .context(format!(
+                    "`git {}` failed: {}",
+                    args[0],
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ))
+                .into());
+        }
+
+        Ok(output)
+    }
+}
+
+/// Find the version the vulnerable package was upgraded to, i.e. the lowest
+/// unaffected version of it which is locked now but wasn't before
+fn upgraded_version(
+    before: &Lockfile,
+    after: &Lockfile,
+    vulnerability: &Vulnerability,
+) -> Option<Version> {
+    let name = &vulnerability.package.name;
+
+    after
+        .packages
+        .iter()
+        .filter(|p| &p.name == name && !vulnerability.versions.is_vulnerable(&p.version))
+        .filter(|p| {
+            !before
+                .packages
+                .iter()
+                .any(|old| &old.name == name && old.version == p.version)
+        })
+        .map(|p| p.version.clone())
+        .min()
+}
@@ -1,12 +1,17 @@
 //! The configuration file
 
 use rustsec::{
-    advisory,
+    advisory, package,
     platforms::target::{Arch, OS},
-    report, Error, ErrorKind, WarningKind,
+    report,
+    retry::RetryPolicy,
+    Error, ErrorKind, VersionReq, WarningKind,
 };
 use serde::{Deserialize, Serialize};
-use std::{path::PathBuf, str::FromStr};
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+
+/// Number of days without commits after which the advisory database is stale
+const STALE_DATABASE_DAYS: u64 = 90;
 
 /// `cargo audit` configuration:
 ///
@@ -19,14 +24,38 @@ pub struct AuditConfig {
     #[serde(default)]
     pub advisories: AdvisoryConfig,
 
+    /// Configuration for verifying package checksums against the registry index
+    #[serde(default)]
+    pub checksums: ChecksumsConfig,
+
     /// Advisory Database configuration
     #[serde(default)]
     pub database: DatabaseConfig,
 
+    /// Configuration for enriching reports with data from external sources
+    #[serde(default)]
+    pub enrich: EnrichConfig,
+
+    /// Webhook notification configuration
+    #[serde(default)]
+    pub notify: NotifyConfig,
+
     /// Output configuration
     #[serde(default)]
     pub output: OutputConfig,
 
+    /// Configuration for banning packages regardless of advisories
+    #[serde(default)]
+    pub packages: PackagesConfig,
+
+    /// Configuration for looking up the release dates of patched versions
+    #[serde(default)]
+    pub release_dates: ReleaseDatesConfig,
+
+    /// Configuration for auditing the sources of packages
+    #[serde(default)]
+    pub sources: SourcesConfig,
+
     /// Target-related configuration
     #[serde(default)]
     pub target: TargetConfig,
@@ -34,16 +63,102 @@ pub struct AuditConfig {
     /// Configuration for auditing for yanked crates
     #[serde(default)]
     pub yanked: YankedConfig,
+
+    /// Named profiles (e.g. `[profile.ci]`) whose settings override the ones
+    /// above when selected (see [`AuditConfig::with_profile`])
+    #[serde(default)]
+    pub profile: BTreeMap<String, toml::Table>,
 }
 
 impl AuditConfig {
+    /// Resolve the profile with the given name, returning this configuration
+    /// with the profile's settings applied on top.
+    ///
+    /// Tables in the profile are merged into the corresponding ones of the
+    /// configuration, while any other value (including arrays, such as
+    /// `ignore` lists) replaces the configured one.
+    pub fn with_profile(&self, name: &str) -> Result<Self, Error> {
+        let profile = self.profile.get(name).ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                &format!("unknown configuration profile: {}", name),
+            )
+        })?;
+
+        let mut config = toml::Value::try_from(Self {
+            profile: BTreeMap::new(),
+            ..self.clone()
+        })
+        .map_err(|e| {
+            Error::with_source(
+                ErrorKind::Parse,
+                "couldn't serialize configuration".to_owned(),
+                e,
+            )
+        })?;
+        merge_toml(&mut config, toml::Value::Table(profile.clone()));
+
+        let mut resolved: Self = config.try_into().map_err(|e| {
+            Error::with_source(
+                ErrorKind::Parse,
+                format!("invalid configuration profile: {}", name),
+                e,
+            )
+        })?;
+        resolved.profile.clone_from(&self.profile);
+        Ok(resolved)
+    }
+
     /// Get audit report settings from the configuration
     pub fn report_settings(&self) -> report::Settings {
         let mut settings = report::Settings {
-            ignore: self.advisories.ignore.clone(),
+            ignore: self
+                .advisories
+                .ignore
+                .iter()
+                .filter(|i| !i.is_scoped())
+                .map(|i| i.id().clone())
+                .collect(),
+            ignore_reasons: self
+                .advisories
+                .ignore
+                .iter()
+                .filter(|i| !i.is_scoped())
+                .filter_map(|i| Some((i.id().clone(), i.reason()?.to_owned())))
+                .collect(),
+            scoped_ignores: self
+                .advisories
+                .ignore
+                .iter()
+                .filter_map(IgnoreEntry::scoped)
+                .collect(),
             severity: self.advisories.severity_threshold,
+            warning_severity: self.advisories.warning_severity_threshold,
             target_arch: self.target.arch(),
             target_os: self.target.os(),
+            max_database_age_days: Some(STALE_DATABASE_DAYS),
+            deny_stale_database: !self.database.stale,
+            fixable_only: self.advisories.fixable_only,
+            source_warnings: self.sources.enabled,
+            include_sources: self.sources.include.clone(),
+            exclude_sources: self.sources.exclude.clone(),
+            dev_dependencies: self.advisories.dev_dependencies.unwrap_or_default(),
+            build_dependencies: self.advisories.build_dependencies.unwrap_or_default(),
+            banned_packages: self
+                .packages
+                .banned
+                .iter()
+                .map(PackageEntry::rule)
+                .collect(),
+            allowed_packages: self
+                .packages
+                .allowed
+                .iter()
+                .map(PackageEntry::rule)
+                .collect(),
+            promote_informational: self.advisories.promote_informational.clone(),
+            promote: self.advisories.promote.clone(),
+            demote: self.advisories.demote.clone(),
             ..Default::default()
         };
 
@@ -56,6 +171,7 @@ impl AuditConfig {
             settings.informational_warnings = vec![
                 advisory::Informational::Unmaintained,
                 advisory::Informational::Unsound,
+                advisory::Informational::Deprecated,
                 advisory::Informational::Notice,
             ];
         }
@@ -78,6 +194,7 @@ impl AuditConfig {
                     insert_if_not_present(advisory::Informational::Notice);
                     insert_if_not_present(advisory::Informational::Unmaintained);
                     insert_if_not_present(advisory::Informational::Unsound);
+                    insert_if_not_present(advisory::Informational::Deprecated);
                     break;
                 }
                 DenyOption::Unmaintained => {
@@ -98,20 +215,142 @@ impl AuditConfig {
 pub struct AdvisoryConfig {
     /// Ignore advisories for the given IDs
     #[serde(default)]
-    pub ignore: Vec<advisory::Id>,
+    pub ignore: Vec<IgnoreEntry>,
 
     /// Ignore the source of this advisory, matching any package of the same name.
     #[serde(default)]
     pub ignore_source: bool,
 
+    /// Only report vulnerabilities for which patched versions are available
+    #[serde(default)]
+    pub fixable_only: bool,
+
+    /// Detect findings in packages which are only pulled in by
+    /// dev-dependencies (using `cargo metadata`), and report, downgrade, or
+    /// skip them
+    #[serde(default)]
+    pub dev_dependencies: Option<report::DependencyScopePolicy>,
+
+    /// Detect findings in packages which are only used at build time, by
+    /// build scripts or proc macros (using `cargo metadata`), and report,
+    /// downgrade, or skip them
+    #[serde(default)]
+    pub build_dependencies: Option<report::DependencyScopePolicy>,
+
     /// Warn for the given types of informational advisories
     pub informational_warnings: Option<Vec<advisory::Informational>>,
 
+    /// Report the given types of informational advisories as vulnerabilities
+    /// rather than warnings
+    #[serde(default)]
+    pub promote_informational: Vec<advisory::Informational>,
+
+    /// Report the informational advisories with the given IDs as
+    /// vulnerabilities rather than warnings
+    #[serde(default)]
+    pub promote: Vec<advisory::Id>,
+
+    /// Report the advisories with the given IDs as `notice` warnings rather
+    /// than vulnerabilities
+    #[serde(default)]
+    pub demote: Vec<advisory::Id>,
+
     /// CVSS Qualitative Severity Rating Scale threshold to alert at.
     ///
     /// Vulnerabilities with explicit CVSS info which have a severity below
-    /// this threshold will be ignored.
+    /// this threshold will be ignored. This also applies to warnings, unless
+    /// `warning_severity_threshold` is set.
     pub severity_threshold: Option<advisory::Severity>,
+
+    /// CVSS Qualitative Severity Rating Scale threshold to warn at for
+    /// informational advisories, overriding `severity_threshold` for warnings
+    #[serde(default)]
+    pub warning_severity_threshold: Option<advisory::Severity>,
+}
+
+/// Advisory to ignore, given either by its ID alone or along with the reason
+/// it's being ignored and the package (versions) to ignore it for:
+///
+/// ```toml
+/// ignore = [
+///     "RUSTSEC-2019-0001",
+///     { id = "RUSTSEC-2020-0002", reason = "..." },
+///     { id = "RUSTSEC-2020-0003", package = "foo", version = "1.2.*" },
+/// ]
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum IgnoreEntry {
+    /// Advisory ID
+    Id(advisory::Id),
+    /// Advisory ID along with additional details
+    Detailed(IgnoreDetails),
+}
+
+impl IgnoreEntry {
+    /// Get the ID of the ignored advisory
+    pub fn id(&self) -> &advisory::Id {
+        match self {
+            IgnoreEntry::Id(id) => id,
+            IgnoreEntry::Detailed(details) => &details.id,
+        }
+    }
+
+    /// Get the reason the advisory is ignored (if given)
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            IgnoreEntry::Id(_) => None,
+            IgnoreEntry::Detailed(details) => details.reason.as_deref(),
+        }
+    }
+
+    /// Is the advisory only ignored for a particular package and/or versions?
+    pub fn is_scoped(&self) -> bool {
+        match self {
+            IgnoreEntry::Id(_) => false,
+            IgnoreEntry::Detailed(details) => {
+                details.package.is_some() || details.version.is_some()
+            }
+        }
+    }
+
+    /// Get the scoped ignore to use in the report settings, if the advisory
+    /// is only ignored for a particular package and/or versions
+    pub fn scoped(&self) -> Option<report::ScopedIgnore> {
+        match self {
+            IgnoreEntry::Detailed(details) if self.is_scoped() => Some(report::ScopedIgnore {
+                id: details.id.clone(),
+                package: details.package.clone(),
+                versions: details.version.clone(),
+                reason: details.reason.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl From<advisory::Id> for IgnoreEntry {
+    fn from(id: advisory::Id) -> Self {
+        IgnoreEntry::Id(id)
+    }
+}
+
+/// Details of an ignored advisory
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct IgnoreDetails {
+    /// ID of the advisory to ignore
+    pub id: advisory::Id,
+
+    /// Why the advisory is being ignored
+    pub reason: Option<String>,
+
+    /// Only ignore the advisory for the package with this name
+    pub package: Option<package::Name>,
+
+    /// Only ignore the advisory while the package's version matches this
+    /// requirement (e.g. `1.2.*`)
+    pub version: Option<VersionReq>,
 }
 
 /// Advisory Database configuration.
@@ -121,7 +360,8 @@ pub struct AdvisoryConfig {
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct DatabaseConfig {
-    /// Path to the local copy of advisory database's git repo (default: ~/.cargo/advisory-db)
+    /// Path to the local copy of advisory database's git repo (default:
+    /// ~/.cargo/advisory-db, or ~/.cargo/advisory-db-<source> for other URLs)
     pub path: Option<PathBuf>,
 
     /// URL to the advisory database's git repo (default: <https://github.com/RustSec/advisory-db>)
@@ -132,6 +372,53 @@ pub struct DatabaseConfig {
 
     /// Allow a stale advisory database? (i.e. one which hasn't been updated in 90 days)
     pub stale: bool,
+
+    /// Fetch the advisory database using the `git` binary instead of the
+    /// built-in git implementation (default: false)
+    #[serde(default)]
+    pub system_git: bool,
+
+    /// Branch, tag, or commit of the advisory database to use instead of the
+    /// latest commit on its default branch
+    #[serde(default)]
+    pub revision: Option<String>,
+
+    /// Number of times to retry fetching the advisory database and the
+    /// crates.io index when it fails due to network errors (default: 3)
+    #[serde(default)]
+    pub retries: Option<u32>,
+
+    /// Use the existing local copies of the advisory database and the
+    /// crates.io index if they still can't be fetched after retrying. The
+    /// database is then reported as stale (default: false)
+    #[serde(default)]
+    pub offline_fallback: bool,
+}
+
+impl DatabaseConfig {
+    /// Policy for retrying fetches which fail due to network errors
+    pub fn retry_policy(&self) -> RetryPolicy {
+        match self.retries {
+            Some(retries) => RetryPolicy::new().retries(retries),
+            None => RetryPolicy::new(),
+        }
+    }
+}
+
+/// Webhook notification configuration
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NotifyConfig {
+    /// URL to POST the JSON report to after each audit
+    pub url: Option<String>,
+
+    /// Only deliver reports containing vulnerabilities or warnings
+    #[serde(default)]
+    pub only_on_findings: bool,
+
+    /// Additional HTTP headers to send with the report (e.g. authorization)
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
 }
 
 /// Output configuration
@@ -146,17 +433,143 @@ pub struct OutputConfig {
     #[serde(default)]
     pub format: OutputFormat,
 
+    /// Files to also write the report to, each in its own format, so that
+    /// several formats can be emitted by a single audit
+    #[serde(default)]
+    pub files: Vec<OutputFile>,
+
     /// Enable quiet mode
     pub quiet: bool,
 
     /// Show inverse dependency trees along with advisories (default: true)
     pub show_tree: Option<bool>,
+
+    /// Show the descriptions of advisories (default: false)
+    #[serde(default)]
+    pub show_description: bool,
+
+    /// Include the metrics, score, and severity of CVSS vectors in
+    /// machine-readable reports, rather than only the vector strings
+    /// (default: false)
+    #[serde(default)]
+    pub cvss_details: bool,
+
+    /// Don't print anything unless the audit fails (default: false)
+    #[serde(default)]
+    pub quiet_success: bool,
+
+    /// Exit codes to use for each kind of failure
+    #[serde(default)]
+    pub exit_codes: ExitCodes,
+
+    /// File recording the findings which have already been reported, so that
+    /// later audits only print and fail for new findings (default: disabled)
+    #[serde(default)]
+    pub state_file: Option<PathBuf>,
 }
 
 impl OutputConfig {
     /// Is quiet mode enabled?
     pub fn is_quiet(&self) -> bool {
-        self.quiet || self.format == OutputFormat::Json
+        self.quiet || self.quiet_success || self.format == OutputFormat::Json
+    }
+
+    /// Is the report printed in a machine-readable format, so nothing else
+    /// may be printed alongside it?
+    pub fn is_machine_readable(&self) -> bool {
+        self.format != OutputFormat::Terminal
+    }
+
+    /// Should status messages be printed? They aren't in quiet mode, nor when
+    /// they'd be mixed into a machine-readable report.
+    pub fn show_status(&self) -> bool {
+        !self.is_quiet() && !self.is_machine_readable()
+    }
+}
+
+/// File to write the report to in a machine-readable format, given on the
+/// command line as `FORMAT=PATH` (e.g. `json=audit.json`)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputFile {
+    /// Format of the report
+    pub format: OutputFormat,
+
+    /// Path of the file
+    pub path: PathBuf,
+}
+
+impl FromStr for OutputFile {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let (format, path) = s.split_once('=').ok_or_else(|| {
+            Error::new(
+                ErrorKind::Parse,
+                &format!("invalid output (expected `FORMAT=PATH`): {}", s),
+            )
+        })?;
+
+        let format = format.parse()?;
+        if format == OutputFormat::Terminal {
+            return Err(Error::new(
+                ErrorKind::Parse,
+                &format!("terminal output can't be written to a file: {}", s),
+            ));
+        }
+
+        Ok(Self {
+            format,
+            path: path.into(),
+        })
+    }
+}
+
+/// Exit codes to use when an audit fails.
+///
+/// When an audit fails for several reasons, the first applicable non-zero code
+/// in the order below is used. Each kind of failure has the same name in
+/// `audit.toml` as on the command line, e.g. `denied-warnings`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ExitCodes {
+    /// Exit code when vulnerabilities are found (default: 1)
+    pub vulnerabilities: i32,
+
+    /// Exit code when denied warnings are found (default: 1)
+    pub denied_warnings: i32,
+
+    /// Exit code when the advisory database is stale (default: 1)
+    pub stale_database: i32,
+}
+
+impl ExitCodes {
+    /// Set the exit code for the given kind of failure, which is one of
+    /// `vulnerabilities`, `denied-warnings`, or `stale-database`
+    pub fn set(&mut self, kind: &str, code: i32) -> Result<(), Error> {
+        match kind {
+            "vulnerabilities" => self.vulnerabilities = code,
+            "denied-warnings" => self.denied_warnings = code,
+            "stale-database" => self.stale_database = code,
+            other => {
+                return Err(Error::new(
+                    ErrorKind::Parse,
+                    &format!("invalid exit code kind: {}", other),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ExitCodes {
+    fn default() -> Self {
+        Self {
+            vulnerabilities: 1,
+            denied_warnings: 1,
+            stale_database: 1,
+        }
     }
 }
 
@@ -196,7 +609,12 @@ impl DenyOption {
             DenyOption::Warnings => &[
                 WarningKind::Unmaintained,
                 WarningKind::Unsound,
+                WarningKind::Deprecated,
                 WarningKind::Yanked,
+                WarningKind::Toolchain,
+                WarningKind::Checksum,
+                WarningKind::Source,
+                WarningKind::Banned,
             ],
             DenyOption::Unmaintained => &[WarningKind::Unmaintained],
             DenyOption::Unsound => &[WarningKind::Unsound],
@@ -233,6 +651,48 @@ pub enum OutputFormat {
     #[serde(rename = "terminal")]
     #[default]
     Terminal,
+
+    /// Display a JSON diagnostic for each finding, one per line, locating it
+    /// in the workspace's `Cargo.toml` files
+    #[serde(rename = "diagnostics")]
+    Diagnostics,
+
+    /// Display TOML
+    #[serde(rename = "toml")]
+    Toml,
+
+    /// Display YAML
+    #[serde(rename = "yaml")]
+    Yaml,
+
+    /// Display the graph of dependencies affected by advisories in the DOT
+    /// language of Graphviz
+    #[serde(rename = "dot")]
+    Dot,
+
+    /// Display the graph of dependencies affected by advisories as GraphML
+    #[serde(rename = "graphml")]
+    GraphMl,
+}
+
+impl FromStr for OutputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "json" => Ok(OutputFormat::Json),
+            "terminal" => Ok(OutputFormat::Terminal),
+            "diagnostics" => Ok(OutputFormat::Diagnostics),
+            "toml" => Ok(OutputFormat::Toml),
+            "yaml" => Ok(OutputFormat::Yaml),
+            "dot" => Ok(OutputFormat::Dot),
+            "graphml" => Ok(OutputFormat::GraphMl),
+            other => Err(Error::new(
+                ErrorKind::Parse,
+                &format!("invalid output format: {}", other),
+            )),
+        }
+    }
 }
 
 /// Helper enum for configuring filter values
@@ -305,6 +765,212 @@ impl Default for YankedConfig {
     }
 }
 
+/// Configuration for verifying package checksums against the registry index
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChecksumsConfig {
+    /// Should the checksums in `Cargo.lock` be compared against the ones
+    /// published in the crates.io index? (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// Configuration for auditing the sources of packages
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SourcesConfig {
+    /// Should packages from git repositories without a pinned revision or tag,
+    /// or from registries other than crates.io, be warned about?
+    /// (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Only audit packages from these sources: `path`, `git`, `crates-io`,
+    /// or the index URL of a registry (default: all sources)
+    #[serde(default)]
+    pub include: Vec<report::SourceRule>,
+
+    /// Don't audit packages from these sources, e.g. `path` to skip
+    /// workspace members
+    #[serde(default)]
+    pub exclude: Vec<report::SourceRule>,
+}
+
+/// Configuration for banning packages regardless of advisories, e.g. to
+/// enforce an organization's policy
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackagesConfig {
+    /// Packages which must not be depended on, reported as `banned` warnings
+    /// which always fail the audit
+    #[serde(default)]
+    pub banned: Vec<PackageEntry>,
+
+    /// Exceptions to the banned packages, e.g. for a project which has been
+    /// granted an exemption from an organization-wide ban
+    #[serde(default)]
+    pub allowed: Vec<PackageEntry>,
+}
+
+/// Package banned or allowed in the `[packages]` configuration, given by name
+/// alone or along with a range of versions and a reason:
+///
+/// ```toml
+/// banned = [
+///     "openssl",
+///     { name = "time", version = "< 0.2", reason = "unsound localtime_r" },
+/// ]
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum PackageEntry {
+    /// Package name, applying to all of its versions
+    Name(package::Name),
+    /// Package name along with additional details
+    Detailed(PackageDetails),
+}
+
+impl PackageEntry {
+    /// Get the corresponding rule to use in the report settings
+    pub fn rule(&self) -> report::PackageRule {
+        match self {
+            PackageEntry::Name(name) => report::PackageRule {
+                name: name.clone(),
+                versions: None,
+                reason: None,
+            },
+            PackageEntry::Detailed(details) => report::PackageRule {
+                name: details.name.clone(),
+                versions: details.version.clone(),
+                reason: details.reason.clone(),
+            },
+        }
+    }
+}
+
+/// Details of a banned or allowed package
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackageDetails {
+    /// Name of the package
+    pub name: package::Name,
+
+    /// Only apply to versions of the package matching this requirement
+    #[serde(default)]
+    pub version: Option<VersionReq>,
+
+    /// Reason the package is banned or allowed
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Configuration for looking up when the first patched version of each
+/// vulnerable package was released
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReleaseDatesConfig {
+    /// Should the release dates of patched versions be looked up using the
+    /// crates.io API? (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Base URL of the crates.io-compatible API to query
+    /// (default: `https://crates.io/api/v1`)
+    #[serde(default)]
+    pub api_url: Option<String>,
+}
+
+/// Configuration for enriching reports with data about their advisories
+/// fetched from external sources (see `cargo audit enrich`)
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnrichConfig {
+    /// Sources to fetch data from (default: all of them)
+    #[serde(default)]
+    pub sources: Vec<EnrichSource>,
+
+    /// Maximum number of requests to send per second, across all sources
+    /// (default: 5)
+    #[serde(default)]
+    pub requests_per_second: Option<u32>,
+
+    /// Base URL of the OSV API (default: `https://api.osv.dev/v1`)
+    #[serde(default)]
+    pub osv_url: Option<String>,
+
+    /// URL of the EPSS API (default: `https://api.first.org/data/v1/epss`)
+    #[serde(default)]
+    pub epss_url: Option<String>,
+
+    /// URL of CISA's Known Exploited Vulnerabilities catalog in JSON
+    /// (default: the catalog's feed on `www.cisa.gov`)
+    #[serde(default)]
+    pub kev_url: Option<String>,
+
+    /// Base URL of the GitHub API (default: `https://api.github.com`)
+    #[serde(default)]
+    pub github_api_url: Option<String>,
+}
+
+impl EnrichConfig {
+    /// Is data to be fetched from the given source?
+    pub fn is_enabled(&self, source: EnrichSource) -> bool {
+        self.sources.is_empty() || self.sources.contains(&source)
+    }
+}
+
+/// External source of data about advisories
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnrichSource {
+    /// Aliases of advisories from the OSV database
+    Osv,
+
+    /// EPSS scores of CVEs from FIRST
+    Epss,
+
+    /// CISA's catalog of Known Exploited Vulnerabilities
+    Kev,
+
+    /// Severities of GitHub Security Advisories
+    Ghsa,
+}
+
+impl FromStr for EnrichSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "osv" => Ok(EnrichSource::Osv),
+            "epss" => Ok(EnrichSource::Epss),
+            "kev" => Ok(EnrichSource::Kev),
+            "ghsa" => Ok(EnrichSource::Ghsa),
+            other => Err(Error::new(
+                ErrorKind::Parse,
+                &format!("invalid enrichment source: {}", other),
+            )),
+        }
+    }
+}
+
+/// Merge the `overrides` into `base`: tables are merged recursively, and any
+/// other values are replaced
+fn merge_toml(base: &mut toml::Value, overrides: toml::Value) {
+    match (base, overrides) {
+        (toml::Value::Table(base), toml::Value::Table(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
 /// Helper function for returning a default of `true`
 fn default_true() -> bool {
     true
@@ -0,0 +1,643 @@
+//! Diagnostics which locate findings at the dependency declarations in the
+//! workspace's `Cargo.toml` files which pull the affected crates in, so that
+//! IDEs and `cargo` wrappers can highlight the offending lines.
+
+use rustsec::{
+    advisory,
+    cargo_lock::{Dependency, Lockfile, Package},
+    Error, ErrorKind, Report, Warning, WarningKind,
+};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    process::Command,
+};
+use toml::Spanned;
+
+/// Finding located in the workspace's manifests
+#[derive(Clone, Debug, Serialize)]
+pub struct Diagnostic {
+    /// `error` for vulnerabilities and denied warnings, `warning` otherwise
+    pub level: &'static str,
+
+    /// `vulnerability`, or the kind of warning
+    pub kind: String,
+
+    /// ID of the advisory the finding is about (if any)
+    pub advisory: Option<advisory::Id>,
+
+    /// Affected crate
+    pub package: PackageId,
+
+    /// Human-readable description of the finding
+    pub message: String,
+
+    /// Dependency declarations which pull the affected crate into the workspace
+    pub locations: Vec<Location>,
+}
+
+/// Name and version of a crate
+#[derive(Clone, Debug, Serialize)]
+pub struct PackageId {
+    /// Crate name
+    pub name: String,
+
+    /// Crate version
+    pub version: String,
+}
+
+/// Location of a dependency declaration in a `Cargo.toml` file
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Location {
+    /// Path to the `Cargo.toml` file
+    pub manifest_path: PathBuf,
+
+    /// Workspace member declaring the dependency
+    pub member: String,
+
+    /// Name the dependency is declared under
+    pub dependency: String,
+
+    /// Line of the declaration (starting at 1)
+    pub line: usize,
+
+    /// Column of the declaration (starting at 1)
+    pub column: usize,
+
+    /// Line the declaration ends on
+    pub end_line: usize,
+
+    /// Column after the end of the declaration
+    pub end_column: usize,
+}
+
+/// Members of a Cargo workspace, along with their manifests
+#[derive(Debug, Default)]
+pub struct Workspace {
+    members: Vec<Member>,
+
+    /// Names and versions of the packages which are only used at build time,
+    /// if the workspace's dependencies could be resolved
+    build_only: Option<BTreeSet<(String, String)>>,
+}
+
+/// Workspace member
+#[derive(Debug)]
+struct Member {
+    /// Package name
+    name: String,
+
+    /// Package version
+    version: String,
+
+    /// Path to the member's `Cargo.toml`
+    manifest_path: PathBuf,
+
+    /// Dependencies of the member, as reported by `cargo metadata`
+    dependencies: Vec<MetadataDependency>,
+
+    /// Dependency declarations in the member's `Cargo.toml`
+    manifest: Manifest,
+
+    /// Contents of the member's `Cargo.toml`
+    source: String,
+}
+
+/// Output of `cargo metadata`
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<MetadataPackage>,
+
+    /// IDs of the workspace members among the packages
+    workspace_members: Vec<String>,
+
+    /// Resolved dependency graph, unless run with `--no-deps`
+    resolve: Option<Resolve>,
+}
+
+/// Package in the output of `cargo metadata`
+#[derive(Deserialize)]
+struct MetadataPackage {
+    id: String,
+    name: String,
+    version: String,
+    manifest_path: PathBuf,
+    dependencies: Vec<MetadataDependency>,
+
+    #[serde(default)]
+    targets: Vec<MetadataTarget>,
+}
+
+impl MetadataPackage {
+    /// Is this package a proc macro?
+    fn is_proc_macro(&self) -> bool {
+        self.targets
+            .iter()
+            .any(|target| target.kind.iter().any(|kind| kind == "proc-macro"))
+    }
+}
+
+/// Target (e.g. library or binary) in the output of `cargo metadata`
+#[derive(Deserialize)]
+struct MetadataTarget {
+    kind: Vec<String>,
+}
+
+/// Resolved dependency graph in the output of `cargo metadata`
+#[derive(Deserialize)]
+struct Resolve {
+    nodes: Vec<ResolveNode>,
+}
+
+/// Package in the resolved dependency graph
+#[derive(Deserialize)]
+struct ResolveNode {
+    id: String,
+    deps: Vec<ResolveDependency>,
+}
+
+/// Dependency edge in the resolved dependency graph
+#[derive(Deserialize)]
+struct ResolveDependency {
+    pkg: String,
+
+    #[serde(default)]
+    dep_kinds: Vec<ResolveDependencyKind>,
+}
+
+/// Kind of a dependency edge (a package may be depended on in several ways)
+#[derive(Deserialize)]
+struct ResolveDependencyKind {
+    /// `dev` or `build` for those kinds of dependencies
+    kind: Option<String>,
+}
+
+/// Dependency in the output of `cargo metadata`
+#[derive(Debug, Deserialize)]
+struct MetadataDependency {
+    /// Name of the depended-on package
+    name: String,
+
+    /// Name the dependency was renamed to (if any)
+    rename: Option<String>,
+
+    /// `dev` or `build` for those kinds of dependencies
+    kind: Option<String>,
+
+    /// Platform the dependency is specific to (if any)
+    target: Option<String>,
+}
+
+/// Dependency tables of a `Cargo.toml` file
+#[derive(Debug, Default, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    dependencies: DependencyTable,
+
+    #[serde(default, rename = "dev-dependencies")]
+    dev_dependencies: DependencyTable,
+
+    #[serde(default, rename = "build-dependencies")]
+    build_dependencies: DependencyTable,
+
+    #[serde(default)]
+    target: BTreeMap<String, Manifest>,
+}
+
+/// Dependencies in a `Cargo.toml` file, keyed by the name they're declared under
+type DependencyTable = BTreeMap<Spanned<String>, IgnoredAny>;
+
+impl Workspace {
+    /// Find the members of the workspace whose `Cargo.toml` is in the given
+    /// directory using `cargo metadata`.
+    ///
+    /// With `resolve`, the workspace's dependencies are also resolved to tell
+    /// which packages are only used at build time. That requires all of them
+    /// to be available offline, so only the members are loaded otherwise.
+    pub fn load(dir: &Path, resolve: bool) -> rustsec::Result<Self> {
+        let metadata = if resolve {
+            Self::metadata(dir, false).or_else(|_| Self::metadata(dir, true))?
+        } else {
+            Self::metadata(dir, true)?
+        };
+
+        let build_only = metadata.resolve.as_ref().map(|graph| {
+            build_only_packages(&metadata.packages, &metadata.workspace_members, graph)
+        });
+
+        let mut members = vec![];
+        for package in metadata.packages {
+            if !metadata.workspace_members.contains(&package.id) {
+                continue;
+            }
+
+            let source = std::fs::read_to_string(&package.manifest_path).map_err(|e| {
+                Error::with_source(
+                    ErrorKind::Io,
+                    format!("couldn't read {}", package.manifest_path.display()),
+                    e,
+                )
+            })?;
+            let manifest = toml::from_str(&source).map_err(|e| {
+                Error::with_source(
+                    ErrorKind::Parse,
+                    format!("couldn't parse {}", package.manifest_path.display()),
+                    e,
+                )
+            })?;
+
+            members.push(Member {
+                name: package.name,
+                version: package.version,
+                manifest_path: package.manifest_path,
+                dependencies: package.dependencies,
+                manifest,
+                source,
+            });
+        }
+
+        Ok(Self {
+            members,
+            build_only,
+        })
+    }
+
+    /// Run `cargo metadata` in the given directory
+    fn metadata(dir: &Path, no_deps: bool) -> rustsec::Result<Metadata> {
+        let mut command = Command::new("cargo");
+        command.args(["metadata", "--format-version", "1", "--offline"]);
+        if no_deps {
+            command.arg("--no-deps");
+        }
+
+        let output = command.current_dir(dir).output().map_err(|e| {
+            Error::with_source(ErrorKind::Io, "couldn't run `cargo metadata`".to_owned(), e)
+        })?;
+
+        if !output.status.success() {
+            return Err(Error::new(
+                ErrorKind::Io,
+                &format!(
+                    "`cargo metadata` failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+            ));
+        }
+
+        let metadata: Metadata = serde_json::from_slice(&output.stdout).map_err(|e| {
+            Error::with_source(
+                ErrorKind::Parse,
+                "couldn't parse `cargo metadata` output".to_owned(),
+                e,
+            )
+        })?;
+
+        Ok(metadata)
+    }
+
+    /// Find the declarations of the workspace members' direct dependencies
+    /// which (transitively) depend on the given package
+    fn locate(
+        &self,
+        packages: &[Package],
+        graph: &DependencyGraph<'_>,
+        package: &Package,
+    ) -> Vec<Location> {
+        let mut locations = vec![];
+
+        for member in &self.members {
+            let member_package = match member.find_package(packages) {
+                Some(package) => package,
+                None => continue,
+            };
+
+            for dependency in &member_package.dependencies {
+                if !graph.reaches(dependency, package) {
+                    continue;
+                }
+
+                for declared in member.declarations(dependency.name.as_str()) {
+                    if !locations.contains(&declared) {
+                        locations.push(declared);
+                    }
+                }
+            }
+        }
+
+        locations
+    }
+
+    /// Find the given packages which are only used at build time, by build
+    /// scripts or proc macros.
+    ///
+    /// Returns `None` if the workspace's dependencies couldn't be resolved
+    /// (see [`Workspace::load`]).
+    pub fn build_only_packages<'a>(&self, packages: &'a [Package]) -> Option<Vec<&'a Package>> {
+        let build_only = self.build_only.as_ref()?;
+
+        Some(
+            packages
+                .iter()
+                .filter(|package| {
+                    build_only.contains(&(package.name.to_string(), package.version.to_string()))
+                })
+                .collect(),
+        )
+    }
+
+    /// Find the given packages which are only pulled in by the
+    /// dev-dependencies of the workspace members, i.e. which aren't reachable
+    /// from any member's normal or build dependencies.
+    ///
+    /// Dependencies of dependencies are always normal ones as far as the
+    /// lockfile is concerned (their own dev-dependencies aren't locked), so
+    /// only the members' dependency kinds need to be looked at.
+    pub fn dev_only_packages<'a>(&self, packages: &'a [Package]) -> Vec<&'a Package> {
+        let graph = DependencyGraph::new(packages);
+        let mut production = BTreeSet::new();
+        let mut pending = vec![];
+
+        for member in &self.members {
+            let member_package = match member.find_package(packages) {
+                Some(package) => package,
+                None => continue,
+            };
+
+            production.insert((
+                member_package.name.as_str(),
+                member_package.version.to_string(),
+            ));
+            pending.extend(
+                member_package
+                    .dependencies
+                    .iter()
+                    .filter(|dependency| member.is_production_dependency(dependency)),
+            );
+        }
+
+        // Without any members to start from, nothing can be told apart
+        if production.is_empty() {
+            return vec![];
+        }
+
+        while let Some(dependency) = pending.pop() {
+            let key = (dependency.name.as_str(), dependency.version.to_string());
+            if !production.insert(key.clone()) {
+                continue;
+            }
+
+            if let Some(package) = graph.packages.get(&key) {
+                pending.extend(&package.dependencies);
+            }
+        }
+
+        packages
+            .iter()
+            .filter(|package| {
+                !production.contains(&(package.name.as_str(), package.version.to_string()))
+            })
+            .collect()
+    }
+}
+
+/// Find the packages which are only used at build time, i.e. which are only
+/// reachable from the workspace members through build-dependencies or proc
+/// macros (ignoring dev-dependencies), keyed by name and version
+fn build_only_packages(
+    packages: &[MetadataPackage],
+    members: &[String],
+    graph: &Resolve,
+) -> BTreeSet<(String, String)> {
+    let packages: HashMap<&str, &MetadataPackage> = packages
+        .iter()
+        .map(|package| (package.id.as_str(), package))
+        .collect();
+    let nodes: HashMap<&str, &ResolveNode> = graph
+        .nodes
+        .iter()
+        .map(|node| (node.id.as_str(), node))
+        .collect();
+
+    // Packages used at runtime, and all packages used to build the members
+    let mut runtime = BTreeSet::new();
+    let mut built = BTreeSet::new();
+    let mut pending: Vec<(&str, bool)> = members.iter().map(|id| (id.as_str(), true)).collect();
+
+    while let Some((id, is_runtime)) = pending.pop() {
+        // Proc macros only run in the compiler, like build scripts
+        let is_proc_macro = packages.get(id).is_some_and(|p| p.is_proc_macro());
+        let is_runtime = is_runtime && !is_proc_macro;
+
+        // Revisit packages found to be used at runtime after all
+        let first_visit = built.insert(id);
+        let newly_runtime = is_runtime && runtime.insert(id);
+        if !first_visit && !newly_runtime {
+            continue;
+        }
+
+        for dependency in nodes.get(id).map_or(&[][..], |node| &node.deps) {
+            let kinds = &dependency.dep_kinds;
+            let normal = kinds.is_empty() || kinds.iter().any(|k| k.kind.is_none());
+            let build = kinds.iter().any(|k| k.kind.as_deref() == Some("build"));
+
+            if normal || build {
+                pending.push((&dependency.pkg, is_runtime && normal));
+            }
+        }
+    }
+
+    built
+        .difference(&runtime)
+        .filter(|id| !members.iter().any(|member| member == *id))
+        .filter_map(|id| packages.get(id))
+        .map(|package| (package.name.clone(), package.version.clone()))
+        .collect()
+}
+
+impl Member {
+    /// Find this member's entry among the given packages
+    fn find_package<'a>(&self, packages: &'a [Package]) -> Option<&'a Package> {
+        packages.iter().find(|p| {
+            p.source.is_none()
+                && p.name.as_str() == self.name
+                && p.version.to_string() == self.version
+        })
+    }
+
+    /// Is the given dependency of this member declared as a normal or build
+    /// dependency (as opposed to only a dev-dependency)?
+    fn is_production_dependency(&self, dependency: &Dependency) -> bool {
+        self.dependencies.iter().any(|declared| {
+            declared.name == dependency.name.as_str() && declared.kind.as_deref() != Some("dev")
+        })
+    }
+
+    /// Find the declarations of the dependency on the given package
+    fn declarations(&self, package_name: &str) -> Vec<Location> {
+        self.dependencies
+            .iter()
+            .filter(|dependency| dependency.name == package_name)
+            .filter_map(|dependency| {
+                let declared_name = dependency.rename.as_ref().unwrap_or(&dependency.name);
+                let manifest = match &dependency.target {
+                    Some(target) => self.manifest.target.get(target)?,
+                    None => &self.manifest,
+                };
+                let table = match dependency.kind.as_deref() {
+                    Some("dev") => &manifest.dev_dependencies,
+                    Some("build") => &manifest.build_dependencies,
+                    _ => &manifest.dependencies,
+                };
+                let key = table.keys().find(|key| key.get_ref() == declared_name)?;
+                Some(self.location(declared_name, key.span()))
+            })
+            .collect()
+    }
+
+    /// Get the location of the given byte range of the manifest
+    fn location(&self, dependency: &str, span: std::ops::Range<usize>) -> Location {
+        let (line, column) = line_and_column(&self.source, span.start);
+        let (end_line, end_column) = line_and_column(&self.source, span.end);
+
+        Location {
+            manifest_path: self.manifest_path.clone(),
+            member: self.name.clone(),
+            dependency: dependency.to_owned(),
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+}
+
+/// Dependency relationships between the packages in a lockfile
+struct DependencyGraph<'a> {
+    packages: HashMap<(&'a str, String), &'a Package>,
+}
+
+impl<'a> DependencyGraph<'a> {
+    fn new(packages: &'a [Package]) -> Self {
+        Self {
+            packages: packages
+                .iter()
+                .map(|package| {
+                    (
+                        (package.name.as_str(), package.version.to_string()),
+                        package,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Is `target` the given dependency, or one of its transitive dependencies?
+    fn reaches(&self, dependency: &Dependency, target: &Package) -> bool {
+        let mut visited = BTreeSet::new();
+        let mut pending = vec![dependency];
+
+        while let Some(dependency) = pending.pop() {
+            if dependency.name == target.name && dependency.version == target.version {
+                return true;
+            }
+
+            let key = (dependency.name.as_str(), dependency.version.to_string());
+            if !visited.insert(key.clone()) {
+                continue;
+            }
+
+            if let Some(package) = self.packages.get(&key) {
+                pending.extend(&package.dependencies);
+            }
+        }
+
+        false
+    }
+}
+
+/// Build the diagnostics for the findings in the given report, locating them
+/// in the given workspace (if known). Warnings of the `denied` kinds are
+/// reported as errors.
+pub fn diagnostics(
+    report: &Report,
+    lockfile: &Lockfile,
+    workspace: Option<&Workspace>,
+    denied: &BTreeSet<WarningKind>,
+) -> Vec<Diagnostic> {
+    let graph = DependencyGraph::new(&lockfile.packages);
+    let locate = |package: &Package| {
+        workspace
+            .map(|workspace| workspace.locate(&lockfile.packages, &graph, package))
+            .unwrap_or_default()
+    };
+
+    let vulnerabilities = report.vulnerabilities.list.iter().map(|vulnerability| {
+        let advisory = &vulnerability.advisory;
+        Diagnostic {
+            level: "error",
+            kind: "vulnerability".to_owned(),
+            advisory: Some(advisory.id.clone()),
+            package: PackageId::new(&vulnerability.package),
+            message: format!("{} ({})", advisory.title, advisory.id),
+            locations: locate(&vulnerability.package),
+        }
+    });
+
+    let warnings = report.warnings.iter().flat_map(|(kind, warnings)| {
+        warnings.iter().map(move |warning| Diagnostic {
+            level: if denied.contains(kind) {
+                "error"
+            } else {
+                "warning"
+            },
+            kind: kind.as_str().to_owned(),
+            advisory: warning
+                .advisory
+                .as_ref()
+                .map(|advisory| advisory.id.clone()),
+            package: PackageId::new(&warning.package),
+            message: warning_message(warning),
+            locations: locate(&warning.package),
+        })
+    });
+
+    vulnerabilities.chain(warnings).collect()
+}
+
+impl PackageId {
+    fn new(package: &Package) -> Self {
+        Self {
+            name: package.name.to_string(),
+            version: package.version.to_string(),
+        }
+    }
+}
+
+/// Describe the given warning
+fn warning_message(warning: &Warning) -> String {
+    let package = &warning.package;
+    match &warning.advisory {
+        Some(advisory) => format!("{} ({})", advisory.title, advisory.id),
+        None if warning.is_checksum_mismatch() => format!(
+            "checksum of {} {} doesn't match the crates.io index",
+            package.name, package.version
+        ),
+        None => match &warning.ban_reason {
+            Some(reason) => format!(
+                "{} {} is {}: {}",
+                package.name, package.version, warning.kind, reason
+            ),
+            None => format!("{} {} is {}", package.name, package.version, warning.kind),
+        },
+    }
+}
+
+/// Convert a byte offset into the given text to a line and column number
+fn line_and_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
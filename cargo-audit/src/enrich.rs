@@ -0,0 +1,515 @@
+//! Enrichment of reports with data about their advisories fetched from
+//! external sources (OSV aliases, EPSS scores, CISA's KEV catalog and GitHub
+//! Security Advisory severities), which keeps audits themselves offline
+
+use crate::{
+    config::{EnrichConfig, EnrichSource},
+    error::{Error, ErrorKind},
+};
+use reqwest::StatusCode;
+use rustsec::{
+    advisory::{self, Date, Id},
+    report::{Enrichment, Epss, KnownExploited, Probability},
+    retry::RetryPolicy,
+    Report,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, thread,
+    time::{Duration, Instant},
+};
+
+/// Default base URL of the OSV API
+const DEFAULT_OSV_URL: &str = "https://api.osv.dev/v1";
+
+/// Default URL of the EPSS API
+const DEFAULT_EPSS_URL: &str = "https://api.first.org/data/v1/epss";
+
+/// Default URL of the Known Exploited Vulnerabilities catalog
+const DEFAULT_KEV_URL: &str =
+    "https://www.cisa.gov/sites/default/files/feeds/known_exploited_vulnerabilities.json";
+
+/// Default base URL of the GitHub API
+const DEFAULT_GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Host of the GitHub API, the only one `GITHUB_TOKEN` is sent to
+const GITHUB_API_HOST: &str = "api.github.com";
+
+/// Default maximum number of requests per second
+const DEFAULT_REQUESTS_PER_SECOND: u32 = 5;
+
+/// Maximum number of CVEs to look up in a single EPSS request
+const EPSS_BATCH_SIZE: usize = 100;
+
+/// Maximum number of package versions to look up in a single OSV request
+const OSV_BATCH_SIZE: usize = 1000;
+
+/// Name of the crates.io ecosystem in OSV
+const OSV_ECOSYSTEM: &str = "crates.io";
+
+/// How long to wait for the APIs to respond
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Fetches data about the advisories of reports from external sources, sending
+/// at most the configured number of requests per second
+pub struct Enricher {
+    /// HTTP client used to query the sources
+    client: reqwest::blocking::Client,
+
+    /// Enrichment configuration
+    config: EnrichConfig,
+
+    /// Policy for retrying requests which fail due to network errors
+    retry: RetryPolicy,
+
+    /// Minimum time between two requests
+    interval: Duration,
+
+    /// When the last request was sent
+    last_request: Option<Instant>,
+
+    /// Token to authenticate to the GitHub API with, from `GITHUB_TOKEN`,
+    /// unless the GitHub API URL points elsewhere
+    github_token: Option<String>,
+}
+
+/// Request to the OSV `querybatch` endpoint
+#[derive(Serialize)]
+struct OsvBatchQuery<'a> {
+    queries: Vec<OsvQuery<'a>>,
+}
+
+/// Query for the OSV records affecting a version of a package
+#[derive(Serialize)]
+struct OsvQuery<'a> {
+    package: OsvPackage<'a>,
+    version: &'a str,
+}
+
+/// Package in an [`OsvQuery`]
+#[derive(Serialize)]
+struct OsvPackage<'a> {
+    name: &'a str,
+    ecosystem: &'static str,
+}
+
+/// Response of the OSV `querybatch` endpoint, with one result per query
+#[derive(Deserialize)]
+struct OsvBatchResponse {
+    results: Vec<OsvBatchResult>,
+}
+
+/// Records affecting the package version of an [`OsvQuery`]
+#[derive(Deserialize)]
+struct OsvBatchResult {
+    #[serde(default)]
+    vulns: Vec<OsvRecord>,
+}
+
+/// Record in an [`OsvBatchResult`], which only has its ID
+#[derive(Deserialize)]
+struct OsvRecord {
+    id: String,
+}
+
+/// Response of the EPSS API
+#[derive(Deserialize)]
+struct EpssResponse {
+    data: Vec<EpssScore>,
+}
+
+/// Score of a CVE in [`EpssResponse`], whose numbers are given as strings
+#[derive(Deserialize)]
+struct EpssScore {
+    cve: String,
+    epss: String,
+    percentile: String,
+}
+
+/// Known Exploited Vulnerabilities catalog
+#[derive(Deserialize)]
+struct KevCatalog {
+    vulnerabilities: Vec<KevEntry>,
+}
+
+/// Entry of [`KevCatalog`]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KevEntry {
+    #[serde(rename = "cveID")]
+    cve_id: String,
+    date_added: Option<String>,
+}
+
+/// Response of the GitHub `advisories/{ghsa_id}` endpoint
+#[derive(Deserialize)]
+struct GithubAdvisory {
+    severity: Option<String>,
+}
+
+impl Enricher {
+    /// Create an enricher from the given configuration, retrying requests
+    /// according to the given policy
+    pub fn new(config: &EnrichConfig, retry: RetryPolicy) -> Result<Self, Error> {
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(concat!("cargo-audit/", env!("CARGO_PKG_VERSION")))
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| ErrorKind::Enrich.context(e))?;
+
+        let requests_per_second = config
+            .requests_per_second
+            .unwrap_or(DEFAULT_REQUESTS_PER_SECOND)
+            .max(1);
+
+        let github_api_url = base_url(&config.github_api_url, DEFAULT_GITHUB_API_URL);
+        let github_token = env::var("GITHUB_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty() && is_github_api(github_api_url));
+
+        Ok(Self {
+            client,
+            config: config.clone(),
+            retry,
+            interval: Duration::from_secs(1) / requests_per_second,
+            last_request: None,
+            github_token,
+        })
+    }
+
+    /// Fetch data about the advisories of the findings in the report from the
+    /// configured sources, and attach it to the findings.
+    ///
+    /// OSV aliases, EPSS scores and the KEV catalog are fetched with as few
+    /// requests as possible, and each GitHub Security Advisory is looked up
+    /// once.
+    pub fn enrich(&mut self, report: &mut Report) -> Result<(), Error> {
+        let advisories: Vec<advisory::Metadata> =
+            report.advisories().into_iter().cloned().collect();
+
+        let mut aliases = if self.config.is_enabled(EnrichSource::Osv) {
+            self.osv_aliases(report)?
+        } else {
+            BTreeMap::new()
+        };
+
+        let mut enrichments = BTreeMap::new();
+        for advisory in &advisories {
+            let enrichment = Enrichment {
+                aliases: aliases.remove(&advisory.id).unwrap_or_default(),
+                ..Default::default()
+            };
+            enrichments.insert(advisory.id.clone(), enrichment);
+        }
+
+        // IDs of each advisory in other databases, including the ones from OSV
+        let known_ids = |advisory: &advisory::Metadata, enrichment: &Enrichment| {
+            let mut ids = BTreeSet::new();
+            ids.extend(advisory.aliases.iter().cloned());
+            ids.extend(enrichment.aliases.iter().cloned());
+            ids
+        };
+
+        let cves: BTreeSet<Id> = advisories
+            .iter()
+            .flat_map(|advisory| known_ids(advisory, &enrichments[&advisory.id]))
+            .filter(Id::is_cve)
+            .collect();
+
+        let epss = if self.config.is_enabled(EnrichSource::Epss) && !cves.is_empty() {
+            self.epss_scores(&cves)?
+        } else {
+            BTreeMap::new()
+        };
+
+        let kev = if self.config.is_enabled(EnrichSource::Kev) && !cves.is_empty() {
+            self.kev_entries(&cves)?
+        } else {
+            BTreeMap::new()
+        };
+
+        for advisory in &advisories {
+            let ids = known_ids(advisory, &enrichments[&advisory.id]);
+            let ghsa_severity = match ids.iter().find(|id| id.is_ghsa()) {
+                Some(ghsa) if self.config.is_enabled(EnrichSource::Ghsa) => {
+                    self.ghsa_severity(ghsa)?
+                }
+                _ => None,
+            };
+
+            let enrichment = enrichments.get_mut(&advisory.id).expect("advisory");
+            enrichment.epss = ids.iter().find_map(|id| epss.get(id)).cloned();
+            enrichment.known_exploited = ids.iter().find_map(|id| kev.get(id)).cloned();
+            enrichment.ghsa_severity = ghsa_severity;
+        }
+
+        report.enrich(|advisory| {
+            enrichments
+                .get(&advisory.id)
+                .filter(|enrichment| !enrichment.is_empty())
+                .cloned()
+        });
+
+        Ok(())
+    }
+
+    /// Look up the aliases of the advisories of the report's findings in OSV,
+    /// querying the records affecting their package versions in batches.
+    ///
+    /// OSV only returns the IDs of these records, so the aliases of an
+    /// advisory are the records about its package version from databases
+    /// other than RustSec, except for the ones listed as aliases by the
+    /// package version's other advisories.
+    fn osv_aliases(&mut self, report: &Report) -> Result<BTreeMap<Id, Vec<Id>>, Error> {
+        // Advisories of the findings about each package version
+        let mut findings: BTreeMap<(String, String), Vec<&advisory::Metadata>> = BTreeMap::new();
+        let advisories = report
+            .vulnerabilities
+            .list
+            .iter()
+            .map(|vulnerability| (&vulnerability.package, Some(&vulnerability.advisory)))
+            .chain(
+                report
+                    .warnings
+                    .values()
+                    .flatten()
+                    .map(|warning| (&warning.package, warning.advisory.as_ref())),
+            );
+        for (package, advisory) in advisories {
+            if let Some(advisory) = advisory {
+                let key = (package.name.to_string(), package.version.to_string());
+                let advisories = findings.entry(key).or_default();
+                if advisories.iter().all(|other| other.id != advisory.id) {
+                    advisories.push(advisory);
+                }
+            }
+        }
+
+        let url = format!(
+            "{}/querybatch",
+            base_url(&self.config.osv_url, DEFAULT_OSV_URL)
+        );
+        let package_versions: Vec<_> = findings.keys().collect();
+        let mut aliases: BTreeMap<Id, BTreeSet<Id>> = BTreeMap::new();
+
+        for batch in package_versions.chunks(OSV_BATCH_SIZE) {
+            let query = OsvBatchQuery {
+                queries: batch
+                    .iter()
+                    .map(|(name, version)| OsvQuery {
+                        package: OsvPackage {
+                            name,
+                            ecosystem: OSV_ECOSYSTEM,
+                        },
+                        version,
+                    })
+                    .collect(),
+            };
+
+            let response: Option<OsvBatchResponse> = self.post(&url, &query)?;
+            let results = response.map(|response| response.results);
+            for (key, result) in batch.iter().zip(results.unwrap_or_default()) {
+                let advisories = &findings[*key];
+                for advisory in advisories {
+                    let claimed: BTreeSet<&Id> = advisories
+                        .iter()
+                        .filter(|other| other.id != advisory.id)
+                        .flat_map(|other| &other.aliases)
+                        .collect();
+
+                    aliases.entry(advisory.id.clone()).or_default().extend(
+                        result
+                            .vulns
+                            .iter()
+                            .filter_map(|record| record.id.parse::<Id>().ok())
+                            .filter(|id| !id.is_rustsec() && !claimed.contains(id)),
+                    );
+                }
+            }
+        }
+
+        Ok(aliases
+            .into_iter()
+            .map(|(id, aliases)| (id, aliases.into_iter().collect()))
+            .collect())
+    }
+
+    /// Look up the EPSS scores of the given CVEs, in batches
+    fn epss_scores(&mut self, cves: &BTreeSet<Id>) -> Result<BTreeMap<Id, Epss>, Error> {
+        let cves: Vec<&str> = cves.iter().map(Id::as_str).collect();
+        let mut scores = BTreeMap::new();
+
+        for batch in cves.chunks(EPSS_BATCH_SIZE) {
+            let url = format!(
+                "{}?cve={}",
+                base_url(&self.config.epss_url, DEFAULT_EPSS_URL),
+                batch.join(",")
+            );
+
+            let response: Option<EpssResponse> = self.get(&url, None)?;
+            for score in response.map(|response| response.data).unwrap_or_default() {
+                let epss = (|| {
+                    Some(Epss {
+                        cve: score.cve.parse().ok()?,
+                        score: Probability::new(score.epss.parse().ok()?)?,
+                        percentile: Probability::new(score.percentile.parse().ok()?)?,
+                    })
+                })();
+
+                if let Some(epss) = epss {
+                    scores.insert(epss.cve.clone(), epss);
+                }
+            }
+        }
+
+        Ok(scores)
+    }
+
+    /// Fetch the KEV catalog, returning the entries of the given CVEs
+    fn kev_entries(&mut self, cves: &BTreeSet<Id>) -> Result<BTreeMap<Id, KnownExploited>, Error> {
+        let url = base_url(&self.config.kev_url, DEFAULT_KEV_URL).to_owned();
+        let catalog: Option<KevCatalog> = self.get(&url, None)?;
+
+        Ok(catalog
+            .map(|catalog| catalog.vulnerabilities)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|entry| {
+                let cve: Id = entry.cve_id.parse().ok()?;
+                if !cves.contains(&cve) {
+                    return None;
+                }
+
+                let date_added = entry.date_added.and_then(|date| date.parse::<Date>().ok());
+                Some((cve.clone(), KnownExploited { cve, date_added }))
+            })
+            .collect())
+    }
+
+    /// Look up the severity of the given GitHub Security Advisory
+    fn ghsa_severity(&mut self, ghsa: &Id) -> Result<Option<String>, Error> {
+        let url = format!(
+            "{}/advisories/{}",
+            base_url(&self.config.github_api_url, DEFAULT_GITHUB_API_URL),
+            ghsa
+        );
+
+        let token = self.github_token.clone();
+        let advisory: Option<GithubAdvisory> = self.get(&url, token.as_deref())?;
+        Ok(advisory.and_then(|advisory| advisory.severity))
+    }
+
+    /// Wait until another request can be sent without exceeding the
+    /// configured rate
+    fn wait_turn(&mut self) {
+        if let Some(last_request) = self.last_request {
+            let elapsed = last_request.elapsed();
+            if elapsed < self.interval {
+                thread::sleep(self.interval - elapsed);
+            }
+        }
+
+        self.last_request = Some(Instant::now());
+    }
+
+    /// Get the JSON document at the given URL, which is `None` if it doesn't
+    /// exist
+    fn get<T: DeserializeOwned>(
+        &mut self,
+        url: &str,
+        token: Option<&str>,
+    ) -> Result<Option<T>, Error> {
+        self.request(url, |client| {
+            let request = client.get(url);
+            match token {
+                Some(token) => request.bearer_auth(token),
+                None => request,
+            }
+        })
+    }
+
+    /// Post the given JSON document to the URL, returning the JSON document
+    /// it responds with, which is `None` if the URL doesn't exist
+    fn post<T: DeserializeOwned, B: Serialize>(
+        &mut self,
+        url: &str,
+        body: &B,
+    ) -> Result<Option<T>, Error> {
+        let body = serde_json::to_string(body).map_err(|e| ErrorKind::Enrich.context(e))?;
+        self.request(url, |client| {
+            client
+                .post(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone())
+        })
+    }
+
+    /// Send the request built by `request` to the given URL, returning the
+    /// JSON document it responds with, which is `None` if the URL doesn't
+    /// exist.
+    ///
+    /// Requests failing due to network errors, rate limiting or server errors
+    /// are retried according to the retry policy.
+    fn request<T: DeserializeOwned>(
+        &mut self,
+        url: &str,
+        request: impl Fn(&reqwest::blocking::Client) -> reqwest::blocking::RequestBuilder,
+    ) -> Result<Option<T>, Error> {
+        let retry = self.retry.clone();
+        let response = retry
+            .run(|| {
+                self.wait_turn();
+
+                let response = request(&self.client)
+                    .header(reqwest::header::ACCEPT, "application/json")
+                    .send()
+                    .map_err(|e| network_error(url, e))?;
+                let status = response.status();
+                if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                    return Err(rustsec::Error::new(
+                        rustsec::ErrorKind::Network,
+                        &format!("{} responded with {}", url, status),
+                    ));
+                }
+
+                let body = response.text().map_err(|e| network_error(url, e))?;
+                Ok((status, body))
+            })
+            .map_err(|e| ErrorKind::Enrich.context(e))?;
+
+        match response {
+            (StatusCode::NOT_FOUND, _) => Ok(None),
+            (status, _) if !status.is_success() => Err(ErrorKind::Enrich
+                .context(format!("{} responded with {}", url, status))
+                .into()),
+            (_, body) => serde_json::from_str(&body).map(Some).map_err(|e| {
+                ErrorKind::Enrich
+                    .context(format!("invalid response from {}: {}", url, e))
+                    .into()
+            }),
+        }
+    }
+}
+
+/// Configured URL of a source, or its default one
+fn base_url<'a>(configured: &'a Option<String>, default: &'a str) -> &'a str {
+    configured
+        .as_deref()
+        .unwrap_or(default)
+        .trim_end_matches('/')
+}
+
+/// Is the given URL the one of the GitHub API?
+fn is_github_api(url: &str) -> bool {
+    reqwest::Url::parse(url)
+        .is_ok_and(|url| url.scheme() == "https" && url.host_str() == Some(GITHUB_API_HOST))
+}
+
+fn network_error(url: &str, e: reqwest::Error) -> rustsec::Error {
+    rustsec::Error::with_source(
+        rustsec::ErrorKind::Network,
+        format!("couldn't fetch {}: {}", url, e),
+        e,
+    )
+}
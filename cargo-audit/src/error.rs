@@ -32,6 +32,18 @@ pub enum ErrorKind {
     #[error("version error")]
     Version,
 
+    /// Errors delivering reports to a webhook
+    #[error("notification error")]
+    Notify,
+
+    /// Errors querying the registry's web API
+    #[error("registry API error")]
+    Registry,
+
+    /// Errors fetching data to enrich reports with
+    #[error("enrichment error")]
+    Enrich,
+
     /// Other kinds of errors
     #[error("other error")]
     Other,
@@ -86,8 +98,8 @@ impl From<rustsec::Error> for Error {
         match err.kind() {
             rustsec::ErrorKind::Io => ErrorKind::Io,
             rustsec::ErrorKind::Parse => ErrorKind::Parse,
-            rustsec::ErrorKind::Repo => ErrorKind::Repo,
-            rustsec::ErrorKind::Version => ErrorKind::Version,
+            rustsec::ErrorKind::Network | rustsec::ErrorKind::Repo => ErrorKind::Repo,
+            rustsec::ErrorKind::Version | rustsec::ErrorKind::VersionSpec => ErrorKind::Version,
             _ => ErrorKind::Other,
         }
         .context(err)
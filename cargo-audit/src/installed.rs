@@ -0,0 +1,128 @@
+//! Discovers programs installed with `cargo install`, using the list of
+//! installed packages Cargo keeps in `.crates2.json` in its install root.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use cargo_lock::{package::SourceId, Lockfile, Package};
+use rustsec::{Error, ErrorKind};
+use serde::Deserialize;
+
+/// Name of the file Cargo records installed packages in
+const CRATES2_JSON: &str = ".crates2.json";
+
+/// A package installed with `cargo install`
+#[derive(Clone, Debug)]
+pub struct InstalledPackage {
+    /// The installed package, as recorded by Cargo
+    pub package: Package,
+
+    /// Paths to the binaries installed from the package
+    pub binaries: Vec<PathBuf>,
+}
+
+impl InstalledPackage {
+    /// Build the lockfile to audit for this package given the dependencies
+    /// recovered from one of its binaries (if any), making sure the installed
+    /// package itself is always included
+    pub fn lockfile(&self, recovered: Option<Lockfile>) -> Lockfile {
+        let mut lockfile = recovered.unwrap_or_else(|| Lockfile {
+            version: cargo_lock::ResolveVersion::V2,
+            packages: vec![],
+            root: None,
+            metadata: Default::default(),
+            patch: Default::default(),
+        });
+
+        let listed = lockfile.packages.iter().any(|package| {
+            package.name == self.package.name && package.version == self.package.version
+        });
+        if !listed {
+            lockfile.packages.push(self.package.clone());
+        }
+
+        lockfile
+    }
+}
+
+/// Contents of `.crates2.json`
+#[derive(Deserialize)]
+struct CrateListing {
+    /// Installed packages, keyed by package ID
+    /// (e.g. `ripgrep 14.1.0 (registry+https://github.com/rust-lang/crates.io-index)`)
+    installs: BTreeMap<String, InstallInfo>,
+}
+
+/// Information Cargo records about an installed package
+#[derive(Deserialize)]
+struct InstallInfo {
+    /// File names of the installed binaries
+    #[serde(default)]
+    bins: Vec<String>,
+}
+
+/// Find the packages installed in the given Cargo install root (e.g. `~/.cargo`)
+pub fn find_installed(root: &Path) -> rustsec::Result<Vec<InstalledPackage>> {
+    let path = root.join(CRATES2_JSON);
+    let json = std::fs::read_to_string(&path).map_err(|e| {
+        Error::with_source(
+            ErrorKind::Io,
+            format!("couldn't read {}", path.display()),
+            e,
+        )
+    })?;
+    let listing: CrateListing = serde_json::from_str(&json).map_err(|e| {
+        Error::with_source(
+            ErrorKind::Parse,
+            format!("couldn't parse {}", path.display()),
+            e,
+        )
+    })?;
+
+    listing
+        .installs
+        .into_iter()
+        .map(|(id, info)| {
+            Ok(InstalledPackage {
+                package: parse_package_id(&id)?,
+                binaries: info
+                    .bins
+                    .iter()
+                    .map(|bin| root.join("bin").join(bin))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Parse a package ID of the form `name version (source)`
+fn parse_package_id(id: &str) -> rustsec::Result<Package> {
+    let invalid = || {
+        Error::new(
+            ErrorKind::Parse,
+            &format!("invalid package ID in {}: {}", CRATES2_JSON, id),
+        )
+    };
+
+    let mut parts = id.splitn(3, ' ');
+    let name = parts.next().ok_or_else(invalid)?;
+    let version = parts.next().ok_or_else(invalid)?;
+    let source = parts
+        .next()
+        .and_then(|source| source.strip_prefix('(')?.strip_suffix(')'))
+        .ok_or_else(invalid)?;
+
+    Ok(Package {
+        name: name.parse().map_err(|_| invalid())?,
+        version: version.parse().map_err(|_| invalid())?,
+        // Packages installed from a local path have no meaningful source
+        source: SourceId::from_url(source)
+            .ok()
+            .filter(|source| !source.is_path()),
+        checksum: None,
+        dependencies: vec![],
+        replace: None,
+    })
+}
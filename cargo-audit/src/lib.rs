@@ -8,6 +8,11 @@
 //!
 //! Then run `cargo audit` in the toplevel directory of any crate or workspace.
 //!
+//! To embed audits in other tools (e.g. xtask-style tools or other Cargo
+//! subcommands) without spawning `cargo audit`, use [`auditor::Auditor`],
+//! passing your own [`presenter::Presenter`] implementation to
+//! [`auditor::Auditor::with_presenter`] to control the output.
+//!
 //! If you wish to consume its core functionality as a library, see the
 //! documentation for the `rustsec` crate:
 //!
@@ -24,15 +29,37 @@ pub mod application;
 pub mod auditor;
 #[cfg(feature = "binary-scanning")]
 mod binary_deps;
+#[cfg(feature = "binary-scanning")]
+mod binary_discovery;
 mod binary_format;
 #[cfg(feature = "binary-scanning")]
 mod binary_type_filter;
 pub mod commands;
+#[cfg(feature = "fix")]
+pub mod commit;
 pub mod config;
+pub mod diagnostics;
+#[cfg(feature = "enrich")]
+pub mod enrich;
 pub mod error;
+#[cfg(feature = "binary-scanning")]
+mod installed;
 pub mod lockfile;
+mod markdown;
+#[cfg(feature = "notify")]
+pub mod notifier;
+pub mod policy;
 mod prelude;
 pub mod presenter;
+#[cfg(feature = "release-dates")]
+pub mod release_dates;
+#[cfg(feature = "binary-scanning")]
+mod scan_pool;
+pub mod server;
+pub mod sink;
+pub mod state;
+#[cfg(feature = "binary-scanning")]
+pub mod vendored_libs;
 
 /// Current version of the `cargo-audit` crate
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
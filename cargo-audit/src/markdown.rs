@@ -0,0 +1,37 @@
+//! Rendering of Markdown advisory descriptions to the terminal
+
+use rustsec::advisory::markdown::{self, Output, Style};
+use std::io;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+/// Render the given Markdown text to the terminal, styling headings,
+/// emphasis, code, and links
+pub fn render(out: &mut impl WriteColor, markdown: &str) -> io::Result<()> {
+    markdown::render(markdown, &mut Terminal(out))
+}
+
+/// Adapter writing rendered Markdown to a terminal
+struct Terminal<'w, W: WriteColor>(&'w mut W);
+
+impl<W: WriteColor> Output for Terminal<'_, W> {
+    type Error = io::Error;
+
+    fn write_str(&mut self, text: &str) -> io::Result<()> {
+        self.0.write_all(text.as_bytes())
+    }
+
+    fn set_style(&mut self, style: Style) -> io::Result<()> {
+        if style == Style::default() {
+            return self.0.reset();
+        }
+
+        let mut spec = ColorSpec::new();
+        spec.set_bold(style.bold)
+            .set_italic(style.italic)
+            .set_underline(style.underline);
+        if style.code {
+            spec.set_fg(Some(Color::Cyan));
+        }
+        self.0.set_color(&spec)
+    }
+}
@@ -0,0 +1,83 @@
+//! Delivery of audit reports to webhooks
+
+use crate::{
+    config::NotifyConfig,
+    error::{Error, ErrorKind},
+};
+use rustsec::Report;
+use std::time::Duration;
+
+/// How long to wait for the webhook to respond
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Posts JSON reports to a webhook URL
+pub struct Notifier {
+    /// HTTP client used to deliver reports
+    client: reqwest::blocking::Client,
+
+    /// Webhook configuration
+    config: NotifyConfig,
+}
+
+impl Notifier {
+    /// Create a notifier from the given configuration, if a webhook URL
+    /// has been configured
+    pub fn new(config: &NotifyConfig) -> Result<Option<Self>, Error> {
+        if config.url.is_none() {
+            return Ok(None);
+        }
+
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(concat!("cargo-audit/", env!("CARGO_PKG_VERSION")))
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| ErrorKind::Notify.context(e))?;
+
+        Ok(Some(Self {
+            client,
+            config: config.clone(),
+        }))
+    }
+
+    /// Post the given reports to the webhook as JSON in a single request:
+    /// a single report as is, and several (e.g. one per binary) merged with
+    /// [`Report::merge`].
+    ///
+    /// Nothing is posted if there are no reports, or if the webhook is
+    /// configured to only be notified of reports with findings and none of
+    /// them have any.
+    pub fn notify(&self, reports: &[Report]) -> Result<(), Error> {
+        let url = match &self.config.url {
+            Some(url) => url,
+            None => return Ok(()),
+        };
+
+        let has_findings =
+            |report: &Report| report.vulnerabilities.found || !report.warnings.is_empty();
+        if reports.is_empty() || self.config.only_on_findings && !reports.iter().any(has_findings) {
+            return Ok(());
+        }
+
+        let body = match reports {
+            [report] => serde_json::to_vec(report),
+            reports => serde_json::to_vec(&Report::merge(reports)),
+        }
+        .map_err(|e| ErrorKind::Notify.context(e))?;
+        let mut request = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+
+        request
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| ErrorKind::Notify.context(e))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+//! Policy deciding whether an audit fails, and with which exit code
+
+use crate::config::{DenyOption, ExitCodes, OutputConfig};
+use rustsec::WarningKind;
+use std::collections::BTreeSet as Set;
+
+/// Decides whether audit reports fail the audit according to the output
+/// configuration (e.g. `--deny warnings`), independently of how the reports
+/// are displayed
+#[derive(Clone, Debug)]
+pub struct Policy {
+    /// Warning kinds that correspond to deny-warnings options
+    deny_warning_kinds: Set<WarningKind>,
+
+    /// Are all warnings denied, including advisories against `cargo-audit` itself?
+    deny_warnings: bool,
+
+    /// Exit codes to use for each kind of failure
+    exit_codes: ExitCodes,
+}
+
+impl Policy {
+    /// Create the policy for the given output configuration
+    pub fn new(config: &OutputConfig) -> Self {
+        Self {
+            // Checksum mismatches may indicate a tampered lockfile, and
+            // banned packages are never allowed, so they are always denied
+            deny_warning_kinds: config
+                .deny
+                .iter()
+                .flat_map(|k| k.get_warning_kind())
+                .copied()
+                .chain([WarningKind::Checksum, WarningKind::Banned])
+                .collect(),
+            deny_warnings: config.deny.contains(&DenyOption::Warnings),
+            exit_codes: config.exit_codes.clone(),
+        }
+    }
+
+    /// Warning kinds which fail the audit
+    pub fn denied_warning_kinds(&self) -> &Set<WarningKind> {
+        &self.deny_warning_kinds
+    }
+
+    /// Is the given kind of warning denied?
+    pub fn is_denied(&self, kind: WarningKind) -> bool {
+        self.deny_warning_kinds.contains(&kind)
+    }
+
+    /// Determines whether the process should exit with failure based on configuration
+    /// such as --deny=warnings
+    #[must_use]
+    pub fn should_exit_with_failure(&self, report: &rustsec::Report) -> bool {
+        if report.vulnerabilities.found || report.is_stale_database_denied() {
+            return true;
+        }
+        let (denied, _allowed) = self.count_warnings(report);
+        if denied != 0 {
+            return true;
+        }
+        false
+    }
+
+    /// Exit codes to use for each kind of failure
+    pub fn exit_codes(&self) -> &ExitCodes {
+        &self.exit_codes
+    }
+
+    /// Exit code for the given report according to the configured
+    /// [`ExitCodes`], or `0` if the audit passed
+    #[must_use]
+    pub fn exit_code(&self, report: &rustsec::Report) -> i32 {
+        let codes = &self.exit_codes;
+        let (denied, _allowed) = self.count_warnings(report);
+
+        [
+            (report.vulnerabilities.found, codes.vulnerabilities),
+            (denied != 0, codes.denied_warnings),
+            (report.is_stale_database_denied(), codes.stale_database),
+        ]
+        .into_iter()
+        .find(|&(failed, code)| failed && code != 0)
+        .map_or(0, |(_, code)| code)
+    }
+
+    /// Determines whether the process should exit with failure based on configuration
+    /// such as --deny=warnings
+    #[must_use]
+    pub fn should_exit_with_failure_due_to_self(
+        &self,
+        self_advisories: &[rustsec::Advisory],
+    ) -> bool {
+        !self_advisories.is_empty() && self.deny_warnings
+    }
+
+    /// Count up the warnings, sorting into denied and allowed.
+    /// Returns `(denied, allowed)`
+    pub fn count_warnings(&self, report: &rustsec::Report) -> (u64, u64) {
+        let mut num_denied: u64 = 0;
+        let mut num_not_denied: u64 = 0;
+
+        // Downgraded warnings about dev-only or build-only packages are never
+        // denied
+        let settings = &report.settings;
+
+        for (kind, warnings) in report.warnings.iter() {
+            for warning in warnings {
+                let downgraded = settings.is_downgraded(warning.dev_only, warning.build_only);
+                if self.is_denied(*kind) && !downgraded {
+                    num_denied += 1;
+                } else {
+                    num_not_denied += 1;
+                }
+            }
+        }
+        (num_denied, num_not_denied)
+    }
+}
@@ -2,7 +2,11 @@
 
 use crate::{
     config::{DenyOption, OutputConfig, OutputFormat},
+    error::{Error, ErrorKind},
+    markdown,
+    policy::Policy,
     prelude::*,
+    sink::Sink,
 };
 use abscissa_core::terminal::{
     self,
@@ -14,46 +18,137 @@ use rustsec::{
         dependency::{self, graph::EdgeDirection, Dependency},
         Lockfile, Package,
     },
-    WarningKind,
 };
-use std::{collections::BTreeSet as Set, io, path::Path};
-use std::{io::Write as _, string::ToString as _};
+use std::string::ToString as _;
+use std::{
+    collections::BTreeSet as Set,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
 
 #[cfg(feature = "binary-scanning")]
-use crate::binary_deps::BinaryReport;
+pub use crate::binary_deps::BinaryReport;
+#[cfg(feature = "binary-scanning")]
+use crate::vendored_libs::VendoredLibrary;
+
+/// Displays the progress and results of audits performed by an [`Auditor`].
+///
+/// Implement this to embed audits in other tools with their own output, and
+/// pass the implementation to [`Auditor::with_presenter`]. All methods except
+/// [`Presenter::print_report`] have default implementations: progress
+/// information is discarded, while warnings and errors are printed to stderr.
+///
+/// [`Auditor`]: crate::auditor::Auditor
+/// [`Auditor::with_presenter`]: crate::auditor::Auditor::with_presenter
+pub trait Presenter {
+    /// Display the progress of an audit (e.g. `Fetching` the advisory database)
+    fn status(&mut self, _status: &str, _message: &str) {}
+
+    /// Display a problem encountered during an audit which doesn't stop it
+    fn warning(&mut self, message: &str) {
+        eprintln!("warning: {}", message);
+    }
+
+    /// Display an error encountered during an audit
+    fn error(&mut self, message: &str) {
+        eprintln!("error: {}", message);
+    }
+
+    /// Information to display before a report is generated
+    fn before_report(&mut self, _path: &Path, _lockfile: &Lockfile) {}
+
+    #[cfg(feature = "binary-scanning")]
+    /// Information to display after searching a directory for binaries
+    fn binaries_found(&mut self, _dir: &Path, _count: usize) {}
+
+    #[cfg(feature = "binary-scanning")]
+    /// Information to display before a binary file is scanned
+    fn binary_scan_report(&mut self, _report: &BinaryReport, _path: &Path) {}
+
+    #[cfg(feature = "binary-scanning")]
+    /// Information to display when a C library is found statically linked into
+    /// a binary, along with the package of the crate vendoring it (which is
+    /// audited along with the binary's dependencies) if it could be determined
+    fn vendored_library(
+        &mut self,
+        _library: &VendoredLibrary,
+        _package: Option<&Package>,
+        _path: &Path,
+    ) {
+    }
+
+    #[cfg(feature = "binary-scanning")]
+    /// Information to display when an installed binary contains no dependency
+    /// information, so only the package it was installed from is audited
+    fn installed_package_only(&mut self, _package: &Package, _path: &Path) {}
+
+    #[cfg(feature = "binary-scanning")]
+    /// Information to display after auditing installed programs
+    fn installed_report(&mut self, _vulnerable: &[&Path]) {}
 
-/// Vulnerability information presenter
+    /// Display the vulnerability report generated by an audit of the given
+    /// lockfile, or of the binary at `path` if one was audited
+    fn print_report(
+        &mut self,
+        report: &rustsec::Report,
+        lockfile: &Lockfile,
+        path: Option<&Path>,
+    ) -> Result<(), Error>;
+
+    /// Display the advisories against `cargo-audit` itself, if there are any
+    fn print_self_report(&mut self, _self_advisories: &[rustsec::Advisory]) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Presenter used by `cargo audit`, which displays reports in the configured
+/// output format
 #[derive(Clone, Debug)]
-pub struct Presenter {
+pub struct DefaultPresenter {
     /// Keep track packages we've displayed once so we don't show the same dep tree
     // TODO(tarcieri): group advisories about the same package?
     displayed_packages: Set<Dependency>,
 
-    /// Keep track of the warning kinds that correspond to deny-warnings options
-    deny_warning_kinds: Set<WarningKind>,
+    /// Policy deciding which warnings are denied
+    policy: Policy,
 
     /// Output configuration
     config: OutputConfig,
+
+    /// Path of the lockfile being audited, if auditing one
+    lockfile_path: Option<PathBuf>,
+
+    /// Files the report is also written to
+    sinks: Vec<Sink>,
 }
 
-impl Presenter {
-    /// Create a new vulnerability information presenter
-    pub fn new(config: &OutputConfig) -> Self {
-        Self {
-            displayed_packages: Set::new(),
-            deny_warning_kinds: config
-                .deny
-                .iter()
-                .flat_map(|k| k.get_warning_kind())
-                .copied()
-                .collect(),
-            config: config.clone(),
+impl Presenter for DefaultPresenter {
+    fn status(&mut self, status: &str, message: &str) {
+        status_ok!(status, message);
+    }
+
+    fn warning(&mut self, message: &str) {
+        // Keep stdout parseable when a machine-readable report is written to it
+        if self.config.is_machine_readable() {
+            eprintln!("warning: {}", message);
+        } else {
+            status_warn!(message);
+        }
+    }
+
+    fn error(&mut self, message: &str) {
+        if self.config.is_machine_readable() {
+            eprintln!("error: {}", message);
+        } else {
+            status_err!(message);
         }
     }
 
     /// Information to display before a report is generated
-    pub fn before_report(&mut self, path: &Path, lockfile: &Lockfile) {
-        if !self.config.is_quiet() {
+    fn before_report(&mut self, path: &Path, lockfile: &Lockfile) {
+        self.lockfile_path = Some(path.to_owned());
+
+        if self.config.show_status() {
             status_ok!(
                 "Scanning",
                 "{} for vulnerabilities ({} crate dependencies)",
@@ -63,11 +158,19 @@ impl Presenter {
         }
     }
 
+    #[cfg(feature = "binary-scanning")]
+    /// Information to display after searching a directory for binaries
+    fn binaries_found(&mut self, dir: &Path, count: usize) {
+        if self.config.show_status() {
+            status_ok!("Found", "{} binaries in {}", count, dir.display());
+        }
+    }
+
     #[cfg(feature = "binary-scanning")]
     /// Information to display before a binary file is scanned
-    pub fn binary_scan_report(&mut self, report: &BinaryReport, path: &Path) {
+    fn binary_scan_report(&mut self, report: &BinaryReport, path: &Path) {
         use crate::binary_deps::BinaryReport::*;
-        if !self.config.is_quiet() {
+        if self.config.show_status() {
             match report {
                 Complete(lockfile) => status_ok!(
                     "Found",
@@ -88,41 +191,126 @@ impl Presenter {
         }
     }
 
-    fn warning_word(&self, count: u64) -> &str {
-        if count != 1 {
-            "warnings"
+    #[cfg(feature = "binary-scanning")]
+    /// Information to display when a C library is found statically linked into
+    /// a binary
+    fn vendored_library(
+        &mut self,
+        library: &VendoredLibrary,
+        package: Option<&Package>,
+        path: &Path,
+    ) {
+        if !self.config.show_status() {
+            return;
+        }
+
+        match package {
+            Some(package) => status_ok!(
+                "Found",
+                "{} {} statically linked in {} (vendored by {} {})",
+                library.name,
+                library.version,
+                path.display(),
+                package.name,
+                package.version
+            ),
+            None => status_warn!(
+                "{} {} is statically linked in {}, but no published version of {} vendors it",
+                library.name,
+                library.version,
+                path.display(),
+                library.source_crate
+            ),
+        }
+    }
+
+    #[cfg(feature = "binary-scanning")]
+    /// Information to display when an installed binary contains no dependency
+    /// information, so only the package it was installed from is audited
+    fn installed_package_only(&mut self, package: &Package, path: &Path) {
+        if self.config.show_status() {
+            status_warn!(
+                "No dependency information found in {}, only auditing {} {} itself",
+                path.display(),
+                package.name,
+                package.version
+            );
+        }
+    }
+
+    #[cfg(feature = "binary-scanning")]
+    /// Information to display after auditing installed programs
+    fn installed_report(&mut self, vulnerable: &[&Path]) {
+        if vulnerable.is_empty() {
+            if self.config.show_status() {
+                status_ok!(
+                    "Success",
+                    "no installed programs are built against vulnerable crates"
+                );
+            }
         } else {
-            "warning"
+            status_err!(
+                "{} installed {} built against vulnerable crates: {}",
+                vulnerable.len(),
+                if vulnerable.len() == 1 {
+                    "program is"
+                } else {
+                    "programs are"
+                },
+                vulnerable
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
         }
     }
 
     /// Print the vulnerability report generated by an audit
-    pub fn print_report(
+    fn print_report(
         &mut self,
         report: &rustsec::Report,
         lockfile: &Lockfile,
         path: Option<&Path>,
-    ) {
-        if self.config.format == OutputFormat::Json {
-            serde_json::to_writer(io::stdout(), &report).unwrap();
-            io::stdout().flush().unwrap();
-            return;
+    ) -> Result<(), Error> {
+        // Files are written regardless of `quiet_success`, which only
+        // concerns what's printed
+        for sink in &mut self.sinks {
+            sink.write_report(
+                report,
+                lockfile,
+                self.lockfile_path.as_deref(),
+                path,
+                self.policy.denied_warning_kinds(),
+            )?;
+        }
+
+        if self.config.quiet_success && self.policy.exit_code(report) == 0 {
+            return Ok(());
         }
 
-        let tree = lockfile
-            .dependency_tree()
-            .expect("invalid Cargo.lock dependency tree");
+        if self.config.format != OutputFormat::Terminal {
+            return Sink::Stdout(self.config.format).write_report(
+                report,
+                lockfile,
+                self.lockfile_path.as_deref(),
+                path,
+                self.policy.denied_warning_kinds(),
+            );
+        }
+
+        let tree = lockfile.dependency_tree()?;
 
         // NOTE: when modifying the following logic, be sure to also update should_exit_with_failure()
 
         // Print out vulnerabilities and warnings
         for vulnerability in &report.vulnerabilities.list {
-            self.print_vulnerability(vulnerability, &tree);
+            self.print_vulnerability(vulnerability, &tree)?;
         }
 
         for warnings in report.warnings.values() {
             for warning in warnings.iter() {
-                self.print_warning(warning, &tree)
+                self.print_warning(warning, &tree)?;
             }
         }
 
@@ -142,9 +330,30 @@ impl Presenter {
                     None => status_err!("{} vulnerabilities found!", report.vulnerabilities.count),
                 }
             }
+        } else if !report.vulnerabilities.list.is_empty() {
+            // Vulnerabilities only in dev-dependencies or build-time
+            // dependencies, which were downgraded
+            let list = &report.vulnerabilities.list;
+            let scope = if list.iter().all(|vulnerability| vulnerability.dev_only) {
+                "dev-dependencies"
+            } else if list.iter().all(|vulnerability| vulnerability.build_only) {
+                "build-time dependencies"
+            } else {
+                "dev-dependencies and build-time dependencies"
+            };
+            status_warn!(
+                "{} {} found only in {}",
+                list.len(),
+                if list.len() == 1 {
+                    "vulnerability"
+                } else {
+                    "vulnerabilities"
+                },
+                scope
+            );
         }
 
-        let (num_denied, num_not_denied) = self.count_warnings(report);
+        let (num_denied, num_not_denied) = self.policy.count_warnings(report);
 
         if num_denied > 0 || num_not_denied > 0 {
             if num_denied > 0 {
@@ -178,12 +387,56 @@ impl Presenter {
                 }
             }
         }
+
+        if self.config.show_status() {
+            for ignored in &report.ignored {
+                let (id, package) = (&ignored.advisory.id, &ignored.package);
+                match &ignored.reason {
+                    Some(reason) => status_ok!(
+                        "Ignored",
+                        "{} for {} {}: {}",
+                        id,
+                        package.name,
+                        package.version,
+                        reason
+                    ),
+                    None => {
+                        status_ok!("Ignored", "{} for {} {}", id, package.name, package.version)
+                    }
+                }
+            }
+        }
+
+        if report.database.stale_cache {
+            if report.settings.deny_stale_database {
+                status_err!(
+                    "advisory database couldn't be fetched, so the local copy was used (use --stale to allow)"
+                );
+            } else {
+                status_warn!("advisory database couldn't be fetched, so the local copy was used");
+            }
+        } else if report.is_database_stale() {
+            let max_age_days = report.settings.max_database_age_days.unwrap_or_default();
+            if report.settings.deny_stale_database {
+                status_err!(
+                    "advisory database hasn't been updated in over {} days (use --stale to allow)",
+                    max_age_days
+                );
+            } else {
+                status_warn!(
+                    "advisory database hasn't been updated in over {} days",
+                    max_age_days
+                );
+            }
+        }
+
+        Ok(())
     }
 
     /// Print the vulnerability report for cargo-audit
-    pub fn print_self_report(&mut self, self_advisories: &[rustsec::Advisory]) {
+    fn print_self_report(&mut self, self_advisories: &[rustsec::Advisory]) -> Result<(), Error> {
         if self_advisories.is_empty() {
-            return;
+            return Ok(());
         }
         // Print out any self-advisories
         let msg = "This copy of cargo-audit has known advisories! Upgrade cargo-audit to the \
@@ -199,49 +452,31 @@ impl Presenter {
             self.print_metadata(
                 &advisory.metadata,
                 self.warning_color(self.config.deny.contains(&DenyOption::Warnings)),
-            );
+            )?;
         }
-        println!();
+        writeln!(io::stdout())?;
+        Ok(())
     }
+}
 
-    /// Determines whether the process should exit with failure based on configuration
-    /// such as --deny=warnings
-    #[must_use]
-    pub fn should_exit_with_failure(&self, report: &rustsec::Report) -> bool {
-        if report.vulnerabilities.found {
-            return true;
-        }
-        let (denied, _allowed) = self.count_warnings(report);
-        if denied != 0 {
-            return true;
+impl DefaultPresenter {
+    /// Create a new vulnerability information presenter
+    pub fn new(config: &OutputConfig) -> Self {
+        Self {
+            displayed_packages: Set::new(),
+            policy: Policy::new(config),
+            config: config.clone(),
+            lockfile_path: None,
+            sinks: config.files.iter().cloned().map(Sink::file).collect(),
         }
-        false
     }
 
-    /// Determines whether the process should exit with failure based on configuration
-    /// such as --deny=warnings
-    #[must_use]
-    pub fn should_exit_with_failure_due_to_self(
-        &self,
-        self_advisories: &[rustsec::Advisory],
-    ) -> bool {
-        !self_advisories.is_empty() && self.config.deny.contains(&DenyOption::Warnings)
-    }
-
-    /// Count up the warnings, sorting into denied and allowed.
-    /// Returns `(denied, allowed)`
-    fn count_warnings(&self, report: &rustsec::Report) -> (u64, u64) {
-        let mut num_denied: u64 = 0;
-        let mut num_not_denied: u64 = 0;
-
-        for (kind, warnings) in report.warnings.iter() {
-            if self.deny_warning_kinds.contains(kind) {
-                num_denied += warnings.len() as u64;
-            } else {
-                num_not_denied += warnings.len() as u64;
-            }
+    fn warning_word(&self, count: u64) -> &str {
+        if count != 1 {
+            "warnings"
+        } else {
+            "warning"
         }
-        (num_denied, num_not_denied)
     }
 
     /// Print information about the given vulnerability
@@ -249,13 +484,22 @@ impl Presenter {
         &mut self,
         vulnerability: &rustsec::Vulnerability,
         tree: &dependency::Tree,
-    ) {
-        self.print_attr(Red, "Crate:    ", &vulnerability.package.name);
-        self.print_attr(Red, "Version:  ", vulnerability.package.version.to_string());
-        self.print_metadata(&vulnerability.advisory, Red);
+    ) -> Result<(), Error> {
+        self.print_attr(Red, "Crate:    ", &vulnerability.package.name)?;
+        self.print_attr(Red, "Version:  ", vulnerability.package.version.to_string())?;
+        if vulnerability.dev_only {
+            self.print_attr(Red, "Scope:    ", "dev-dependencies only")?;
+        }
+        if vulnerability.build_only {
+            self.print_attr(Red, "Scope:    ", "build time only")?;
+        }
+        if let Some(informational) = &vulnerability.promoted_from {
+            self.print_attr(Red, "Promoted: ", format!("from {} warning", informational))?;
+        }
+        self.print_metadata(&vulnerability.advisory, Red)?;
 
         if vulnerability.versions.patched().is_empty() {
-            self.print_attr(Red, "Solution: ", "No fixed upgrade is available!");
+            self.print_attr(Red, "Solution: ", "No fixed upgrade is available!")?;
         } else {
             self.print_attr(
                 Red,
@@ -271,27 +515,89 @@ impl Presenter {
                         .as_slice()
                         .join(" OR ")
                 ),
-            );
+            )?;
         }
 
-        self.print_tree(Red, &vulnerability.package, tree);
-        println!();
+        if let Some(date) = &vulnerability.patched_release_date {
+            self.print_attr(Red, "Patched:  ", format!("released on {}", date))?;
+        }
+
+        if let Some(fixed_by) = &vulnerability.fixed_by {
+            for url in fixed_by.commit.iter().chain(&fixed_by.pr) {
+                self.print_attr(Red, "Fix:      ", url)?;
+            }
+        }
+
+        self.print_tree(Red, &vulnerability.package, tree)?;
+        writeln!(io::stdout())?;
+        Ok(())
     }
 
     /// Print information about a given warning
-    fn print_warning(&mut self, warning: &rustsec::Warning, tree: &dependency::Tree) {
-        let color = self.warning_color(self.deny_warning_kinds.contains(&warning.kind));
+    fn print_warning(
+        &mut self,
+        warning: &rustsec::Warning,
+        tree: &dependency::Tree,
+    ) -> Result<(), Error> {
+        let color = self.warning_color(self.policy.is_denied(warning.kind));
+
+        self.print_attr(color, "Crate:    ", &warning.package.name)?;
+        self.print_attr(color, "Version:  ", warning.package.version.to_string())?;
+        self.print_attr(color, "Warning:  ", warning.kind.as_str())?;
+        if warning.dev_only {
+            self.print_attr(color, "Scope:    ", "dev-dependencies only")?;
+        }
+        if warning.build_only {
+            self.print_attr(color, "Scope:    ", "build time only")?;
+        }
+        if warning.demoted {
+            self.print_attr(color, "Demoted:  ", "from vulnerability")?;
+        }
+
+        if let (Some(expected), Some(actual)) =
+            (&warning.registry_checksum, &warning.package.checksum)
+        {
+            self.print_attr(
+                color,
+                "Expected: ",
+                format!("{:x} (crates.io index)", expected),
+            )?;
+            self.print_attr(color, "Found:    ", format!("{:x} (Cargo.lock)", actual))?;
+        }
+
+        if let Some(reason) = &warning.ban_reason {
+            self.print_attr(color, "Reason:   ", reason)?;
+        }
 
-        self.print_attr(color, "Crate:    ", &warning.package.name);
-        self.print_attr(color, "Version:  ", warning.package.version.to_string());
-        self.print_attr(color, "Warning:  ", warning.kind.as_str());
+        if warning.is_untracked_source() {
+            if let Some(source) = &warning.package.source {
+                self.print_attr(color, "Source:   ", source.to_string())?;
+            }
+        }
 
         if let Some(metadata) = &warning.advisory {
-            self.print_metadata(metadata, color)
+            self.print_metadata(metadata, color)?;
+        }
+
+        let alternatives = warning.alternatives();
+        if !alternatives.is_empty() {
+            self.print_attr(
+                color,
+                "Solution: ",
+                format!(
+                    "Consider switching to {}",
+                    alternatives
+                        .iter()
+                        .map(|name| name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" OR ")
+                ),
+            )?;
         }
 
-        self.print_tree(color, &warning.package, tree);
-        println!();
+        self.print_tree(color, &warning.package, tree)?;
+        writeln!(io::stdout())?;
+        Ok(())
     }
 
     /// Get the color to use when displaying warnings
@@ -304,72 +610,94 @@ impl Presenter {
     }
 
     /// Print a warning about a particular advisory
-    fn print_metadata(&self, metadata: &rustsec::advisory::Metadata, color: Color) {
-        self.print_attr(color, "Title:    ", &metadata.title);
-        self.print_attr(color, "Date:     ", &metadata.date);
-        self.print_attr(color, "ID:       ", &metadata.id);
+    fn print_metadata(
+        &self,
+        metadata: &rustsec::advisory::Metadata,
+        color: Color,
+    ) -> Result<(), Error> {
+        self.print_attr(color, "Title:    ", &metadata.title)?;
+        self.print_attr(color, "Date:     ", &metadata.date)?;
+        self.print_attr(color, "ID:       ", &metadata.id)?;
 
         if metadata.license == License::CcBy40 {
             // We must preserve the original URL from the `url` field
             if let Some(url) = &metadata.url {
-                self.print_attr(color, "URL:      ", url);
+                self.print_attr(color, "URL:      ", url)?;
             } else if let Some(url) = &metadata.id.url() {
-                self.print_attr(color, "URL:      ", url);
+                self.print_attr(color, "URL:      ", url)?;
             }
         } else {
             // Prefer ID URL because the `url` field usually points to a bug tracker
             // or any other non-canonical source rather than an actual security advisory
             if let Some(url) = &metadata.id.url() {
-                self.print_attr(color, "URL:      ", url);
+                self.print_attr(color, "URL:      ", url)?;
             } else if let Some(url) = &metadata.url {
-                self.print_attr(color, "URL:      ", url);
+                self.print_attr(color, "URL:      ", url)?;
             }
         }
 
-        if let Some(cvss) = &metadata.cvss {
-            self.print_attr(
-                color,
-                "Severity: ",
-                format!("{} ({})", cvss.score().value(), cvss.score().severity()),
-            );
+        if let Some((label, cvss)) = metadata.selected_cvss(&Default::default()) {
+            let mut severity = format!("{} ({})", cvss.score().value(), cvss.score().severity());
+            if let Some(label) = label {
+                severity.push_str(&format!(" [{}]", label));
+            }
+            self.print_attr(color, "Severity: ", severity)?;
         }
+
+        if self.config.show_description && !metadata.description.is_empty() {
+            print_status(color, "Description:\n", "")?;
+            markdown::render(&mut terminal::stdout().lock(), &metadata.description)?;
+        }
+
+        Ok(())
     }
 
     /// Display an attribute of a particular vulnerability
-    fn print_attr(&self, color: Color, attr: &str, content: impl AsRef<str>) {
-        terminal::status::Status::new()
-            .bold()
-            .color(color)
-            .status(attr)
-            .print_stdout(content.as_ref())
-            .unwrap();
+    fn print_attr(&self, color: Color, attr: &str, content: impl AsRef<str>) -> Result<(), Error> {
+        print_status(color, attr, content.as_ref())
     }
 
     /// Print the inverse dependency tree to standard output
-    fn print_tree(&mut self, color: Color, package: &Package, tree: &dependency::Tree) {
+    fn print_tree(
+        &mut self,
+        color: Color,
+        package: &Package,
+        tree: &dependency::Tree,
+    ) -> Result<(), Error> {
         // Only show the tree once per package
         if !self.displayed_packages.insert(Dependency::from(package)) {
-            return;
+            return Ok(());
         }
 
         if !self.config.show_tree.unwrap_or(true) {
-            return;
+            return Ok(());
         }
 
-        terminal::status::Status::new()
-            .bold()
-            .color(color)
-            .status("Dependency tree:\n")
-            .print_stdout("")
-            .unwrap();
+        // Packages which aren't part of the dependency graph (e.g. the Rust
+        // standard library of an audited binary) have no tree to display
+        let package_node = match tree.nodes().get(&Dependency::from(package)) {
+            Some(node) => *node,
+            None => return Ok(()),
+        };
 
-        let package_node = tree.nodes()[&Dependency::from(package)];
+        print_status(color, "Dependency tree:\n", "")?;
         tree.render(
             &mut io::stdout(),
             package_node,
             EdgeDirection::Incoming,
             false,
-        )
-        .unwrap();
+        )?;
+
+        Ok(())
     }
 }
+
+/// Print a bold, colored status followed by the given message to stdout
+fn print_status(color: Color, status: &str, msg: &str) -> Result<(), Error> {
+    terminal::status::Status::new()
+        .bold()
+        .color(color)
+        .status(status)
+        .print_stdout(msg)
+        .map_err(|e| ErrorKind::Io.context(e).into())
+}
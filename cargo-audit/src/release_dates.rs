@@ -0,0 +1,119 @@
+//! Lookup of the dates package versions were released on, using the
+//! crates.io web API
+
+use crate::{
+    config::ReleaseDatesConfig,
+    error::{Error, ErrorKind},
+};
+use rustsec::{advisory::Date, package::Package, Version};
+use serde::Deserialize;
+use std::{collections::BTreeMap, time::Duration};
+
+/// Default base URL of the crates.io API
+const DEFAULT_API_URL: &str = "https://crates.io/api/v1";
+
+/// How long to wait for the API to respond
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Looks up (and caches) the release dates of the versions of packages
+pub struct ReleaseDates {
+    /// HTTP client used to query the API
+    client: reqwest::blocking::Client,
+
+    /// Base URL of the API
+    api_url: String,
+
+    /// Releases of the packages looked up so far, keyed by package name
+    cache: BTreeMap<String, Vec<(Version, Date)>>,
+}
+
+/// Response of the `crates/{name}/versions` endpoint
+#[derive(Deserialize)]
+struct VersionsResponse {
+    versions: Vec<VersionInfo>,
+}
+
+/// Information about a version in [`VersionsResponse`]
+#[derive(Deserialize)]
+struct VersionInfo {
+    /// Version number
+    num: Version,
+
+    /// RFC 3339 timestamp of when the version was published
+    created_at: String,
+
+    /// Has this version been yanked?
+    #[serde(default)]
+    yanked: bool,
+}
+
+impl ReleaseDates {
+    /// Create a release date lookup from the given configuration, if enabled
+    pub fn new(config: &ReleaseDatesConfig) -> Result<Option<Self>, Error> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(concat!("cargo-audit/", env!("CARGO_PKG_VERSION")))
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| ErrorKind::Registry.context(e))?;
+
+        Ok(Some(Self {
+            client,
+            api_url: config
+                .api_url
+                .as_deref()
+                .unwrap_or(DEFAULT_API_URL)
+                .trim_end_matches('/')
+                .to_owned(),
+            cache: BTreeMap::new(),
+        }))
+    }
+
+    /// Get the versions of the given package which haven't been yanked,
+    /// along with the dates they were released on.
+    ///
+    /// Packages which aren't from crates.io have no known releases.
+    pub fn releases(&mut self, package: &Package) -> Result<Vec<(Version, Date)>, Error> {
+        if !package
+            .source
+            .as_ref()
+            .is_some_and(|source| source.is_default_registry())
+        {
+            return Ok(vec![]);
+        }
+
+        let name = package.name.as_str();
+        if let Some(releases) = self.cache.get(name) {
+            return Ok(releases.clone());
+        }
+
+        let url = format!("{}/crates/{}/versions", self.api_url, name);
+        let response: VersionsResponse = self
+            .client
+            .get(url)
+            .send()
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.text())
+            .map_err(|e| ErrorKind::Registry.context(e))
+            .and_then(|body| {
+                serde_json::from_str(&body).map_err(|e| ErrorKind::Registry.context(e))
+            })?;
+
+        let releases: Vec<_> = response
+            .versions
+            .into_iter()
+            .filter(|version| !version.yanked)
+            .filter_map(|version| {
+                // Only the date part of the timestamp is needed
+                let date = version.created_at.get(..10)?.parse().ok()?;
+                Some((version.num, date))
+            })
+            .collect();
+
+        self.cache.insert(name.to_owned(), releases.clone());
+        Ok(releases)
+    }
+}
@@ -0,0 +1,57 @@
+//! Bounded pool of worker threads scanning binaries concurrently, e.g. when
+//! auditing all of the binaries of a container image.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{mpsc, Mutex},
+    thread,
+};
+
+/// Default number of worker threads: one per available CPU
+pub fn default_jobs() -> NonZeroUsize {
+    thread::available_parallelism().unwrap_or(NonZeroUsize::MIN)
+}
+
+/// Run `scan` on each of the `items` using up to `jobs` worker threads,
+/// passing each item and its result to `on_result` on the calling thread as
+/// soon as it completes.
+///
+/// Results are therefore passed in the order scans complete, rather than the
+/// order of `items`.
+pub fn run<T, R>(
+    items: Vec<T>,
+    jobs: NonZeroUsize,
+    scan: impl Fn(&T) -> R + Sync,
+    mut on_result: impl FnMut(T, R),
+) where
+    T: Send,
+    R: Send,
+{
+    let workers = jobs.get().min(items.len());
+    let pending = Mutex::new(items.into_iter());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (pending, scan) = (&pending, &scan);
+            scope.spawn(move || loop {
+                let item = match pending.lock().unwrap().next() {
+                    Some(item) => item,
+                    None => break,
+                };
+
+                let result = scan(&item);
+                if sender.send((item, result)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // Only the workers' senders remain, so the loop ends once they're done
+        drop(sender);
+        for (item, result) in receiver {
+            on_result(item, result);
+        }
+    });
+}
@@ -0,0 +1,216 @@
+//! JSON-RPC server answering audit requests, for use by editor integrations
+//! and bots which would otherwise pay the cost of loading the advisory
+//! database on every audit.
+//!
+//! Requests and responses are [JSON-RPC 2.0] messages, one per line.
+//! Supported methods:
+//!
+//! - `audit`: audit the lockfile whose contents are given in the `lockfile`
+//!   parameter, returning the report in the same format as `cargo audit --json`
+//! - `refresh`: reload (and if configured, fetch) the advisory database,
+//!   returning the number of advisories in the `advisories` field
+//! - `shutdown`: stop the server
+//!
+//! [JSON-RPC 2.0]: https://www.jsonrpc.org/specification
+
+use crate::{auditor::Auditor, config::AuditConfig, error::display_err_with_source};
+use rustsec::Lockfile;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+
+/// Invalid JSON was received
+const PARSE_ERROR: i64 = -32700;
+
+/// The JSON sent is not a valid request object
+const INVALID_REQUEST: i64 = -32600;
+
+/// The method does not exist
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Invalid method parameters
+const INVALID_PARAMS: i64 = -32602;
+
+/// The request was valid, but couldn't be carried out (e.g. the advisory
+/// database couldn't be refreshed)
+const SERVER_ERROR: i64 = -32000;
+
+/// Audit server keeping the advisory database loaded between requests
+pub struct Server {
+    /// Auditor holding the loaded advisory database
+    auditor: Auditor,
+
+    /// Configuration to use when reloading the advisory database
+    config: AuditConfig,
+}
+
+/// JSON-RPC request
+#[derive(Deserialize)]
+struct Request {
+    /// Request ID, which is absent for notifications
+    #[serde(default)]
+    id: Option<Value>,
+
+    /// Method to call
+    method: String,
+
+    /// Parameters of the method
+    #[serde(default)]
+    params: Value,
+}
+
+/// Parameters of the `audit` method
+#[derive(Deserialize)]
+struct AuditParams {
+    /// Contents of the `Cargo.lock` file to audit
+    lockfile: String,
+}
+
+/// JSON-RPC response
+#[derive(Serialize)]
+struct Response {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ResponseError>,
+}
+
+/// JSON-RPC error object
+#[derive(Serialize)]
+struct ResponseError {
+    code: i64,
+    message: String,
+}
+
+/// Outcome of handling a request
+enum Outcome {
+    /// Keep serving requests
+    Continue,
+
+    /// Stop serving requests
+    Shutdown,
+}
+
+impl Server {
+    /// Start a server with the given configuration, loading the advisory
+    /// database (and crates.io index) up front
+    pub fn new(config: AuditConfig) -> Self {
+        Self {
+            auditor: Auditor::new(&config),
+            config,
+        }
+    }
+
+    /// Answer the requests read from `reader` until it is closed or a
+    /// `shutdown` request is received, returning whether the latter happened
+    pub fn serve(&mut self, reader: impl BufRead, mut writer: impl Write) -> io::Result<bool> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (response, outcome) = self.handle(&line);
+
+            if let Some(response) = response {
+                serde_json::to_writer(&mut writer, &response)?;
+                writer.write_all(b"\n")?;
+                writer.flush()?;
+            }
+
+            if let Outcome::Shutdown = outcome {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Handle a single request, returning the response to send (if any)
+    fn handle(&mut self, line: &str) -> (Option<Response>, Outcome) {
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => {
+                let response = Response::error(Value::Null, PARSE_ERROR, e.to_string());
+                return (Some(response), Outcome::Continue);
+            }
+        };
+
+        let request: Request = match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(e) => {
+                let response = Response::error(Value::Null, INVALID_REQUEST, e.to_string());
+                return (Some(response), Outcome::Continue);
+            }
+        };
+
+        let mut outcome = Outcome::Continue;
+        let result = match request.method.as_str() {
+            "audit" => self.audit(request.params),
+            "refresh" => self.refresh(),
+            "shutdown" => {
+                outcome = Outcome::Shutdown;
+                Ok(Value::Null)
+            }
+            method => Err((METHOD_NOT_FOUND, format!("unknown method: {}", method))),
+        };
+
+        // Notifications don't get a response
+        let response = request.id.map(|id| match result {
+            Ok(result) => Response::result(id, result),
+            Err((code, message)) => Response::error(id, code, message),
+        });
+
+        (response, outcome)
+    }
+
+    /// Handle an `audit` request
+    fn audit(&mut self, params: Value) -> Result<Value, (i64, String)> {
+        let params: AuditParams =
+            serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+
+        let lockfile: Lockfile = params.lockfile.parse().map_err(|e| {
+            (
+                INVALID_PARAMS,
+                format!("invalid lockfile: {}", display_err_with_source(&e)),
+            )
+        })?;
+
+        let report = self.auditor.report(&lockfile);
+        serde_json::to_value(report).map_err(|e| (SERVER_ERROR, e.to_string()))
+    }
+
+    /// Handle a `refresh` request
+    fn refresh(&mut self) -> Result<Value, (i64, String)> {
+        let count = self
+            .auditor
+            .reload_database(&self.config)
+            .map_err(|e| (SERVER_ERROR, display_err_with_source(&e)))?;
+
+        Ok(json!({ "advisories": count }))
+    }
+}
+
+impl Response {
+    /// Successful response
+    fn result(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    /// Error response
+    fn error(id: Value, code: i64, message: String) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(ResponseError { code, message }),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Sinks which reports are written to in machine-readable formats, so that a
+//! single audit can emit several formats (e.g. JSON on stdout and YAML in a
+//! file) without being run once per format
+
+use crate::{
+    config::{OutputFile, OutputFormat},
+    diagnostics::{self, Workspace},
+    error::{Error, ErrorKind},
+};
+use rustsec::{
+    cargo_lock::{dependency::Tree, Lockfile},
+    Report, WarningKind,
+};
+use std::{
+    collections::BTreeSet,
+    fmt::Display,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Destination of reports in one of the machine-readable output formats
+#[derive(Clone, Debug)]
+pub enum Sink {
+    /// Standard output
+    Stdout(OutputFormat),
+
+    /// File, which the report is written to if the audit generates a single
+    /// one. When it generates several (e.g. one per audited binary), each is
+    /// written to a file of its own next to it instead, named after the
+    /// input (e.g. `audit.app.json` for the binary `app`).
+    File {
+        /// Format and path of the file
+        output: OutputFile,
+
+        /// Input of the first report written to the file, which is moved to
+        /// a file of its own once a second report is written
+        first_input: Option<Option<PathBuf>>,
+
+        /// Files written so far
+        written: Vec<PathBuf>,
+    },
+}
+
+impl Sink {
+    /// Sink writing to the given file
+    pub fn file(output: OutputFile) -> Self {
+        Sink::File {
+            output,
+            first_input: None,
+            written: vec![],
+        }
+    }
+
+    /// Write the report generated by an audit of the given lockfile, located
+    /// at `lockfile_path` if it was read from disk, to the sink. `input` is
+    /// the path of the audited binary, if it was one.
+    pub fn write_report(
+        &mut self,
+        report: &Report,
+        lockfile: &Lockfile,
+        lockfile_path: Option<&Path>,
+        input: Option<&Path>,
+        denied_warning_kinds: &BTreeSet<WarningKind>,
+    ) -> Result<(), Error> {
+        match self {
+            Sink::Stdout(format) => {
+                let mut stdout = io::stdout().lock();
+                render(
+                    *format,
+                    &mut stdout,
+                    report,
+                    lockfile,
+                    lockfile_path,
+                    denied_warning_kinds,
+                )
+                .and_then(|()| stdout.flush())
+                .map_err(|e| write_error("stdout", e))
+            }
+            Sink::File {
+                output,
+                first_input,
+                written,
+            } => {
+                let path = match first_input {
+                    None => {
+                        *first_input = Some(input.map(Path::to_owned));
+                        output.path.clone()
+                    }
+                    Some(first) => {
+                        // Concatenated reports aren't valid JSON, TOML or
+                        // YAML, so the first one gets a file of its own too
+                        if written.len() == 1 {
+                            let moved = input_path(&output.path, first.as_deref(), 1, &[]);
+                            fs::rename(&output.path, &moved)
+                                .map_err(|e| write_error(moved.display(), e))?;
+                            written[0] = moved;
+                        }
+                        input_path(&output.path, input, written.len() + 1, written)
+                    }
+                };
+
+                let mut file = File::create(&path).map_err(|e| write_error(path.display(), e))?;
+                written.push(path.clone());
+
+                render(
+                    output.format,
+                    &mut file,
+                    report,
+                    lockfile,
+                    lockfile_path,
+                    denied_warning_kinds,
+                )
+                .map_err(|e| write_error(path.display(), e))
+            }
+        }
+    }
+}
+
+fn write_error(target: impl Display, e: io::Error) -> Error {
+    ErrorKind::Io
+        .context(format!("couldn't write report to {}: {}", target, e))
+        .into()
+}
+
+/// Path of the file the report with the given number about the given input
+/// is written to when an audit generates several reports: `path` with the
+/// input's file name (or the report's number) inserted before its
+/// extension, made unique among the files already written
+fn input_path(path: &Path, input: Option<&Path>, number: usize, written: &[PathBuf]) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let label = match input.and_then(Path::file_name) {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => number.to_string(),
+    };
+    let file_name = |label: &str| match path.extension() {
+        Some(extension) => format!("{}.{}.{}", stem, label, extension.to_string_lossy()),
+        None => format!("{}.{}", stem, label),
+    };
+
+    let candidate = path.with_file_name(file_name(&label));
+    if written.contains(&candidate) {
+        path.with_file_name(file_name(&format!("{}.{}", label, number)))
+    } else {
+        candidate
+    }
+}
+
+/// Dependency tree of the lockfile, for rendering the report as a graph
+fn tree(lockfile: &Lockfile) -> io::Result<Tree> {
+    Tree::new(lockfile).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Render the report in the given machine-readable format
+fn render(
+    format: OutputFormat,
+    writer: &mut dyn Write,
+    report: &Report,
+    lockfile: &Lockfile,
+    lockfile_path: Option<&Path>,
+    denied_warning_kinds: &BTreeSet<WarningKind>,
+) -> io::Result<()> {
+    let invalid_data = |e: rustsec::Error| io::Error::new(io::ErrorKind::InvalidData, e);
+
+    match format {
+        OutputFormat::Json => serde_json::to_writer(&mut *writer, report)?,
+        OutputFormat::Toml => write!(writer, "{}", report.to_toml_string().map_err(invalid_data)?)?,
+        OutputFormat::Yaml => write!(writer, "{}", report.to_yaml_string().map_err(invalid_data)?)?,
+        OutputFormat::Dot => write!(writer, "{}", report.graph(&tree(lockfile)?).to_dot())?,
+        OutputFormat::GraphMl => write!(writer, "{}", report.graph(&tree(lockfile)?).to_graphml())?,
+        OutputFormat::Diagnostics => {
+            // Findings are still reported if the workspace can't be inspected
+            // (e.g. when auditing a lockfile without its manifests), just
+            // without any locations
+            let workspace = lockfile_path.and_then(|path| {
+                let dir = match path.parent() {
+                    Some(dir) if !dir.as_os_str().is_empty() => dir,
+                    _ => Path::new("."),
+                };
+                Workspace::load(dir, false).ok()
+            });
+
+            for diagnostic in
+                diagnostics::diagnostics(report, lockfile, workspace.as_ref(), denied_warning_kinds)
+            {
+                serde_json::to_writer(&mut *writer, &diagnostic)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Terminal => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "terminal output can't be written to a sink",
+            ))
+        }
+    }
+
+    Ok(())
+}
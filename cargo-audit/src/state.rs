@@ -0,0 +1,112 @@
+//! State file recording the findings which have already been reported, so
+//! that periodic audits only alert once per issue
+
+use crate::error::{Error, ErrorKind};
+use rustsec::{report::VulnerabilityInfo, Report};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Findings which have already been reported, and the file they are stored in
+#[derive(Debug)]
+pub struct StateFile {
+    /// Path to the state file
+    path: PathBuf,
+
+    /// Contents of the state file
+    state: State,
+}
+
+/// Serialized contents of a state file
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct State {
+    /// Fingerprints of the findings which have already been reported
+    reported: BTreeSet<String>,
+}
+
+impl StateFile {
+    /// Load the state file at the given path, which is empty if it doesn't
+    /// exist yet
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let state = match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| {
+                ErrorKind::Parse.context(format!("invalid state file {}: {}", path.display(), e))
+            })?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => State::default(),
+            Err(e) => {
+                return Err(ErrorKind::Io
+                    .context(format!("couldn't read {}: {}", path.display(), e))
+                    .into())
+            }
+        };
+
+        Ok(Self {
+            path: path.to_owned(),
+            state,
+        })
+    }
+
+    /// Remove the findings which have already been reported from the report.
+    ///
+    /// The report's summary must be updated afterwards.
+    pub fn filter_reported(&self, report: &mut Report) {
+        let reported = &self.state.reported;
+
+        let list = report
+            .vulnerabilities
+            .list
+            .drain(..)
+            .filter(|vulnerability| !reported.contains(&vulnerability.fingerprint()))
+            .collect();
+        report.vulnerabilities = VulnerabilityInfo::new(list);
+
+        for warnings in report.warnings.values_mut() {
+            warnings.retain(|warning| !reported.contains(&warning.fingerprint()));
+        }
+        report.warnings.retain(|_, warnings| !warnings.is_empty());
+
+        // Downgraded vulnerabilities in dev-only or build-only packages don't
+        // fail the audit
+        let settings = &report.settings;
+        report.vulnerabilities.found = report.vulnerabilities.list.iter().any(|vulnerability| {
+            !settings.is_downgraded(vulnerability.dev_only, vulnerability.build_only)
+        });
+    }
+
+    /// Record the findings in the report as reported, so they're left out of
+    /// later reports
+    pub fn record(&mut self, report: &Report) {
+        let reported = &mut self.state.reported;
+
+        reported.extend(
+            report
+                .vulnerabilities
+                .list
+                .iter()
+                .map(|vulnerability| vulnerability.fingerprint()),
+        );
+        reported.extend(
+            report
+                .warnings
+                .values()
+                .flatten()
+                .map(|warning| warning.fingerprint()),
+        );
+    }
+
+    /// Write the findings reported so far to the state file
+    pub fn save(&self) -> Result<(), Error> {
+        let json =
+            serde_json::to_string_pretty(&self.state).map_err(|e| ErrorKind::Parse.context(e))?;
+
+        fs::write(&self.path, json).map_err(|e| {
+            ErrorKind::Io
+                .context(format!("couldn't write {}: {}", self.path.display(), e))
+                .into()
+        })
+    }
+}
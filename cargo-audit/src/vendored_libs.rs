@@ -0,0 +1,126 @@
+//! Detection of C libraries statically linked into binaries by `-src`/`-sys`
+//! crates, from the version strings the libraries embed.
+//!
+//! Detected libraries are correlated with the crate which vendors them by
+//! looking for a published version of that crate whose build metadata records
+//! the same library version (e.g. `openssl-src` `111.15.0+1.1.1k` for
+//! OpenSSL 1.1.1k), so that advisories against the crate apply to binaries
+//! without dependency information.
+
+use rustsec::{package::Package, Version};
+use std::str::FromStr;
+
+/// C library embedding a detectable version string, and the crate vendoring it
+struct KnownLibrary {
+    /// Name of the library
+    name: &'static str,
+
+    /// Marker preceding the version in the library's version string
+    marker: &'static [u8],
+
+    /// Crate which builds and statically links the library
+    source_crate: &'static str,
+}
+
+/// Libraries whose vendoring crates record the library version in their
+/// build metadata
+const KNOWN_LIBRARIES: &[KnownLibrary] = &[
+    // e.g. `OpenSSL 1.1.1k  25 Mar 2021`
+    KnownLibrary {
+        name: "OpenSSL",
+        marker: b"OpenSSL ",
+        source_crate: "openssl-src",
+    },
+    // the default user agent, e.g. `git/2.0 (libgit2 1.5.1)`
+    KnownLibrary {
+        name: "libgit2",
+        marker: b"(libgit2 ",
+        source_crate: "libgit2-sys",
+    },
+];
+
+/// C library found statically linked into a binary
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VendoredLibrary {
+    /// Name of the library (e.g. `OpenSSL`)
+    pub name: &'static str,
+
+    /// Version of the library (e.g. `1.1.1k`)
+    pub version: String,
+
+    /// Crate which vendors the library (e.g. `openssl-src`)
+    pub source_crate: &'static str,
+}
+
+impl VendoredLibrary {
+    /// Find the package of the source crate which vendors this version of the
+    /// library, given the published versions of the crate.
+    ///
+    /// When several versions of the crate vendor the same library version, the
+    /// oldest one is assumed, so fixes made to the crate rather than to the
+    /// library aren't taken into account.
+    pub fn source_package(&self, published: &[Version]) -> Option<Package> {
+        let version = published
+            .iter()
+            .filter(|version| {
+                let build = version.build.as_str();
+                build == self.version || build.ends_with(&format!("-{}", self.version))
+            })
+            .min()?;
+
+        Some(Package {
+            name: rustsec::package::Name::from_str(self.source_crate).ok()?,
+            version: version.clone(),
+            source: Some(rustsec::SourceId::default()),
+            checksum: None,
+            dependencies: Vec::new(),
+            replace: None,
+        })
+    }
+}
+
+/// Find the known C libraries whose version strings are embedded in the
+/// given binary data.
+///
+/// Each library is only reported once, with the first version found.
+pub fn detect(data: &[u8]) -> Vec<VendoredLibrary> {
+    KNOWN_LIBRARIES
+        .iter()
+        .filter_map(|library| {
+            let version = data
+                .windows(library.marker.len())
+                .enumerate()
+                .filter(|(_, window)| *window == library.marker)
+                .find_map(|(offset, _)| version_at(&data[offset + library.marker.len()..]))?;
+
+            Some(VendoredLibrary {
+                name: library.name,
+                version,
+                source_crate: library.source_crate,
+            })
+        })
+        .collect()
+}
+
+/// Parse a library version (e.g. `1.1.1k` or `3.0.8`) at the start of `data`
+fn version_at(data: &[u8]) -> Option<String> {
+    let end = data
+        .iter()
+        .position(|byte| !(byte.is_ascii_alphanumeric() || *byte == b'.'))
+        .unwrap_or(data.len());
+    let version = std::str::from_utf8(&data[..end]).ok()?;
+
+    // Require at least `MAJOR.MINOR`, to skip unrelated strings like
+    // `OpenSSL library` or `OpenSSL 1`
+    let mut parts = version.split('.');
+    let major = parts.next()?;
+    let minor = parts.next()?;
+    if major.is_empty() || !major.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if !minor.bytes().next()?.is_ascii_digit() {
+        return None;
+    }
+
+    Some(version.to_owned())
+}
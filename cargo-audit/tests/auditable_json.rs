@@ -0,0 +1,108 @@
+//! Tests for auditing dependency JSON already extracted from binaries
+
+#![cfg(feature = "binary-scanning")]
+
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+use tempfile::TempDir;
+
+mod support;
+use support::{db_cmd_runner, write_advisory_db, ADVISORY};
+
+/// Dependency JSON embedded by `cargo auditable` in a binary depending on
+/// the given version of `base`
+fn auditable_json(base_version: &str) -> String {
+    format!(
+        r#"{{"packages":[
+            {{"name":"app","version":"0.1.0","source":"local","dependencies":[1],"root":true}},
+            {{"name":"base","version":"{}","source":"crates.io"}}
+        ]}}"#,
+        base_version
+    )
+}
+
+/// Audit the given JSON file against the database in `dir`, returning the
+/// exit code
+fn audit_json_file(dir: &Path, json_path: &Path) -> i32 {
+    db_cmd_runner(dir)
+        .args(["bin", "--auditable-json"])
+        .arg(json_path)
+        .status()
+        .code()
+}
+
+#[test]
+fn vulnerable_json_file_fails() {
+    let dir = TempDir::new().unwrap();
+    write_advisory_db(dir.path(), ADVISORY);
+
+    let json_path = dir.path().join("vulnerable.json");
+    fs::write(&json_path, auditable_json("1.0.0")).unwrap();
+    assert_eq!(audit_json_file(dir.path(), &json_path), 1);
+
+    let json_path = dir.path().join("patched.json");
+    fs::write(&json_path, auditable_json("1.2.3")).unwrap();
+    assert_eq!(audit_json_file(dir.path(), &json_path), 0);
+}
+
+#[test]
+fn malformed_json_file_is_an_error() {
+    let dir = TempDir::new().unwrap();
+    write_advisory_db(dir.path(), ADVISORY);
+
+    let json_path = dir.path().join("malformed.json");
+    fs::write(&json_path, "{\"packages\":").unwrap();
+    assert_eq!(audit_json_file(dir.path(), &json_path), 2);
+}
+
+#[test]
+fn json_is_read_from_stdin() {
+    let dir = TempDir::new().unwrap();
+    write_advisory_db(dir.path(), ADVISORY);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cargo-audit"))
+        .args(["audit", "--no-fetch", "--db"])
+        .arg(dir.path().join("db"))
+        .args(["bin", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(auditable_json("1.0.0").as_bytes())
+        .unwrap();
+    assert_eq!(child.wait().unwrap().code(), Some(1));
+}
+
+#[test]
+fn one_output_file_per_input() {
+    let dir = TempDir::new().unwrap();
+    write_advisory_db(dir.path(), ADVISORY);
+    fs::write(dir.path().join("app"), auditable_json("1.0.0")).unwrap();
+    fs::write(dir.path().join("cli"), auditable_json("1.2.3")).unwrap();
+
+    let mut runner = db_cmd_runner(dir.path());
+    runner
+        .arg("--output")
+        .arg(format!("json={}", dir.path().join("audit.json").display()))
+        .args(["bin", "--auditable-json"])
+        .arg(dir.path().join("app"))
+        .arg(dir.path().join("cli"));
+    assert_eq!(runner.status().code(), 1);
+
+    let report = |name: &str| -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(dir.path().join(name)).unwrap()).unwrap()
+    };
+    assert!(!dir.path().join("audit.json").exists());
+    assert_eq!(report("audit.app.json")["vulnerabilities"]["count"], 1);
+    assert_eq!(report("audit.cli.json")["vulnerabilities"]["count"], 0);
+}
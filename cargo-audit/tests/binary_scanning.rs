@@ -1,6 +1,6 @@
 #![cfg(feature = "binary-scanning")]
 
-use std::path::PathBuf;
+use std::{fs, io::Read, path::PathBuf};
 
 use abscissa_core::testing::prelude::*;
 use once_cell::sync::Lazy;
@@ -66,3 +66,55 @@ fn auditable_binary_with_vulnerabilities_fails() {
     binary_path.push("binary-with-vuln");
     assert_eq!(cmd_runner().arg(binary_path).status().code(), 1);
 }
+
+#[test]
+fn directory_is_scanned_recursively() {
+    let db_dir = TempDir::new().unwrap();
+    let mut runner = CmdRunner::default();
+    runner
+        .args(["audit", "--no-fetch", "--color", "never", "--db"])
+        .arg(db_dir.path())
+        .arg("bin")
+        .arg(binaries_dir())
+        .capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    assert!(stdout.contains("Found 4 binaries in "), "{}", stdout);
+    assert_eq!(process.wait().unwrap().code(), 0);
+}
+
+#[test]
+fn binaries_are_scanned_concurrently() {
+    let db_dir = TempDir::new().unwrap();
+    let bin_dir = TempDir::new().unwrap();
+    for i in 0..8 {
+        fs::copy(
+            binaries_dir().join("binary-with-audit-info"),
+            bin_dir.path().join(format!("program-{}", i)),
+        )
+        .unwrap();
+    }
+
+    let mut runner = CmdRunner::default();
+    runner
+        .args(["audit", "--no-fetch", "--color", "never", "--db"])
+        .arg(db_dir.path())
+        .args(["bin", "--jobs", "3"])
+        .arg(bin_dir.path())
+        .capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    assert!(stdout.contains("Found 8 binaries in "), "{}", stdout);
+    for i in 0..8 {
+        let scanned = format!(
+            "'cargo auditable' data in {}",
+            bin_dir.path().join(format!("program-{}", i)).display()
+        );
+        assert!(stdout.contains(&scanned), "{}", stdout);
+    }
+    assert_eq!(process.wait().unwrap().code(), 0);
+}
@@ -0,0 +1,129 @@
+//! Tests for detecting findings in crates only used at build time
+
+use abscissa_core::testing::prelude::*;
+use std::{fs, io::Read, path::Path, process::Command};
+use tempfile::TempDir;
+
+mod support;
+use support::{write_advisory_db, ADVISORY};
+
+/// Create an advisory database, a `base` crate, a proc macro depending on
+/// it, and a project with the given dependency tables
+fn setup(dir: &Path, dependencies: &str) {
+    write_advisory_db(dir, ADVISORY);
+
+    write_crate(&dir.join("base"), "base", "1.0.0", "");
+    write_crate(
+        &dir.join("macros"),
+        "macros",
+        "0.1.0",
+        "[lib]\nproc-macro = true\n\n[dependencies]\nbase = { path = \"../base\" }\n",
+    );
+    write_crate(&dir.join("app"), "app", "0.1.0", dependencies);
+
+    let status = Command::new("cargo")
+        .args(["generate-lockfile", "--offline"])
+        .current_dir(dir.join("app"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+/// Write a library crate with the given extra manifest contents
+fn write_crate(dir: &Path, name: &str, version: &str, extra: &str) {
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("src").join("lib.rs"), "").unwrap();
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"{}\"\nedition = \"2021\"\n\n{}",
+            name, version, extra
+        ),
+    )
+    .unwrap();
+}
+
+/// Get a `CmdRunner` auditing the project with the given build-dependencies option
+fn cmd_runner(dir: &Path, build_dependencies: &str) -> CmdRunner {
+    let mut runner = support::cmd_runner(dir, &dir.join("app").join("Cargo.lock"));
+    runner.args(["--build-dependencies", build_dependencies]);
+    runner
+}
+
+/// Audit the project, returning the JSON report
+fn json_report(dir: &Path, build_dependencies: &str) -> serde_json::Value {
+    let mut runner = cmd_runner(dir, build_dependencies);
+    runner.arg("--json").capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    process.wait().unwrap();
+    serde_json::from_str(&stdout).unwrap()
+}
+
+#[test]
+fn build_dependency_vulnerability_marked() {
+    let dir = TempDir::new().unwrap();
+    setup(
+        dir.path(),
+        "[build-dependencies]\nbase = { path = \"../base\" }\n",
+    );
+
+    let report = json_report(dir.path(), "report");
+    let vulnerability = &report["vulnerabilities"]["list"][0];
+    assert_eq!(vulnerability["package"]["name"], "base");
+    assert_eq!(vulnerability["build_only"], true);
+    assert_eq!(report["summary"]["build_only"], 1);
+}
+
+#[test]
+fn proc_macro_dependency_vulnerability_marked() {
+    let dir = TempDir::new().unwrap();
+    setup(
+        dir.path(),
+        "[dependencies]\nmacros = { path = \"../macros\" }\n",
+    );
+
+    let report = json_report(dir.path(), "report");
+    let vulnerability = &report["vulnerabilities"]["list"][0];
+    assert_eq!(vulnerability["package"]["name"], "base");
+    assert_eq!(vulnerability["build_only"], true);
+}
+
+#[test]
+fn runtime_dependency_vulnerability_not_marked() {
+    let dir = TempDir::new().unwrap();
+    setup(
+        dir.path(),
+        "[dependencies]\nbase = { path = \"../base\" }\n\n\
+        [build-dependencies]\nbase = { path = \"../base\" }\n",
+    );
+
+    let report = json_report(dir.path(), "downgrade");
+    let vulnerability = &report["vulnerabilities"]["list"][0];
+    assert_eq!(vulnerability["build_only"], false);
+    assert_eq!(report["vulnerabilities"]["found"], true);
+}
+
+#[test]
+fn build_dependency_vulnerability_downgraded() {
+    let dir = TempDir::new().unwrap();
+    setup(
+        dir.path(),
+        "[build-dependencies]\nbase = { path = \"../base\" }\n",
+    );
+
+    let mut runner = cmd_runner(dir.path(), "downgrade");
+    runner.capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    process.wait().unwrap().expect_code(0);
+    assert!(
+        stdout.contains("1 vulnerability found only in build-time dependencies"),
+        "{}",
+        stdout
+    );
+}
@@ -0,0 +1,89 @@
+//! Tests for verifying lockfile checksums against the crates.io index
+
+use cargo_audit::auditor::Auditor;
+use rustsec::WarningKind;
+use std::{env, fs, path::Path};
+use tame_index::{index::SparseIndex, utils::flock::FileLock, IndexKrate, IndexLocation, IndexUrl};
+use tempfile::TempDir;
+
+mod support;
+use support::{library_config, Silent};
+
+/// Checksum of `base` 1.0.0 in the crates.io index
+const PUBLISHED: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+
+/// Checksum of `base` 1.0.0 in the audited lockfile
+const LOCKED: &str = "2222222222222222222222222222222222222222222222222222222222222222";
+
+/// Cache `base` 1.0.0 with the given checksum in the sparse crates.io index
+/// of the given Cargo home
+fn cache_index_entry(cargo_home: &Path, checksum: &str) {
+    let index = SparseIndex::new(
+        IndexLocation::new(IndexUrl::CratesIoSparse)
+            .with_root(Some(cargo_home.to_owned().try_into().unwrap())),
+    )
+    .unwrap();
+    let krate = IndexKrate::from_slice(
+        format!(
+            r#"{{"name":"base","vers":"1.0.0","deps":[],"cksum":"{}","features":{{}},"yanked":false}}"#,
+            checksum
+        )
+        .as_bytes(),
+    )
+    .unwrap();
+    index
+        .cache()
+        .write_to_cache(&krate, "etag: test", &FileLock::unlocked())
+        .unwrap();
+}
+
+/// Audit a lockfile locking `base` 1.0.0 with the `LOCKED` checksum
+fn audit(dir: &Path, verify_checksums: bool) -> rustsec::Report {
+    fs::create_dir_all(dir.join("db").join("crates")).unwrap();
+    let lockfile_path = dir.join("Cargo.lock");
+    fs::write(
+        &lockfile_path,
+        format!(
+            "version = 3\n\n[[package]]\nname = \"base\"\nversion = \"1.0.0\"\n\
+            source = \"registry+https://github.com/rust-lang/crates.io-index\"\n\
+            checksum = \"{}\"\n",
+            LOCKED
+        ),
+    )
+    .unwrap();
+
+    let mut config = library_config(dir);
+    config.checksums.enabled = verify_checksums;
+
+    let mut auditor = Auditor::with_presenter(&config, Silent).unwrap();
+    auditor.audit_lockfile(&lockfile_path).unwrap()
+}
+
+#[test]
+fn checksum_mismatches_are_reported() {
+    let dir = TempDir::new().unwrap();
+    let cargo_home = dir.path().join("cargo-home");
+    cache_index_entry(&cargo_home, PUBLISHED);
+
+    // This is the only test in this binary, so nothing else observes the
+    // Cargo home being changed
+    env::set_var("CARGO_HOME", &cargo_home);
+    env::set_var("CARGO_REGISTRIES_CRATES_IO_PROTOCOL", "sparse");
+
+    // Checksums aren't verified unless enabled
+    let report = audit(dir.path(), false);
+    assert!(!report.warnings.contains_key(&WarningKind::Checksum));
+
+    let report = audit(dir.path(), true);
+    let mismatched = &report.warnings[&WarningKind::Checksum];
+    assert_eq!(mismatched.len(), 1);
+    assert_eq!(mismatched[0].package.name.as_str(), "base");
+    assert_eq!(
+        mismatched[0]
+            .registry_checksum
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        PUBLISHED
+    );
+}
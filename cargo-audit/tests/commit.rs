@@ -0,0 +1,127 @@
+//! Tests for committing the fixes applied by `cargo audit fix --commit`
+
+#![cfg(feature = "fix")]
+
+use cargo_audit::commit::Committer;
+use rustsec::{Advisory, Lockfile, Vulnerability};
+use std::{fs, path::Path, process::Command};
+use tempfile::TempDir;
+
+mod support;
+use support::ADVISORY;
+
+/// Lockfile depending on the given versions of `base`
+fn lockfile(base_versions: &[&str]) -> String {
+    let mut lockfile = "version = 3\n".to_owned();
+    for version in base_versions {
+        lockfile.push_str(&format!(
+            "\n[[package]]\nname = \"base\"\nversion = \"{}\"\n\
+            source = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            version
+        ));
+    }
+    lockfile
+}
+
+/// Run `git` in the given directory, returning its output
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Create a git repository with a committed `Cargo.toml` and `Cargo.lock`
+/// depending on the given versions of `base`
+fn setup(dir: &Path, base_versions: &[&str]) {
+    git(dir, &["init", "--quiet"]);
+    git(dir, &["config", "user.name", "Test"]);
+    git(dir, &["config", "user.email", "test@example.com"]);
+    fs::write(dir.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+    fs::write(dir.join("Cargo.lock"), lockfile(base_versions)).unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "--quiet", "-m", "Initial commit"]);
+}
+
+/// Vulnerability in the given lockfile's vulnerable version of `base`
+fn vulnerability(lockfile: &Lockfile) -> Vulnerability {
+    let advisory: Advisory = ADVISORY.parse().unwrap();
+    let package = lockfile
+        .packages
+        .iter()
+        .find(|package| advisory.versions.is_vulnerable(&package.version))
+        .unwrap();
+    Vulnerability::new(&advisory, package)
+}
+
+#[test]
+fn commits_only_the_lockfile() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path(), &["1.0.0", "3.0.0"]);
+
+    let lockfile_path = dir.path().join("Cargo.lock");
+    let before = Lockfile::load(&lockfile_path).unwrap();
+    let vulnerability = vulnerability(&before);
+
+    // Unrelated staged changes must not be swept into the commit
+    fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"b\"\n").unwrap();
+    git(dir.path(), &["add", "Cargo.toml"]);
+
+    fs::write(&lockfile_path, lockfile(&["1.2.4", "3.0.0"])).unwrap();
+
+    let committer = Committer::new(&lockfile_path).unwrap();
+    let upgraded = committer.commit(&before, &vulnerability).unwrap();
+    // The upgraded version rather than the highest locked one
+    assert_eq!(upgraded.unwrap().to_string(), "1.2.4");
+
+    let message = git(dir.path(), &["log", "-1", "--format=%B"]);
+    assert!(
+        message.starts_with("Update base 1.0.0 \u{2192} 1.2.4 to fix RUSTSEC-2001-2101\n"),
+        "{}",
+        message
+    );
+    assert!(message.contains("RUSTSEC-2001-2101: All your base are belong to us"));
+
+    let committed = git(dir.path(), &["show", "--name-only", "--format=", "HEAD"]);
+    assert_eq!(committed.trim(), "Cargo.lock");
+    assert_eq!(
+        git(dir.path(), &["status", "--porcelain"]),
+        "M  Cargo.toml\n"
+    );
+}
+
+#[test]
+fn nothing_to_commit_if_still_vulnerable() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path(), &["1.0.0"]);
+
+    let lockfile_path = dir.path().join("Cargo.lock");
+    let before = Lockfile::load(&lockfile_path).unwrap();
+
+    let committer = Committer::new(&lockfile_path).unwrap();
+    assert!(committer
+        .commit(&before, &vulnerability(&before))
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        git(dir.path(), &["rev-list", "--count", "HEAD"]).trim(),
+        "1"
+    );
+}
+
+#[test]
+fn uncommitted_lockfile_changes_are_rejected() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path(), &["1.0.0"]);
+
+    let lockfile_path = dir.path().join("Cargo.lock");
+    let committer = Committer::new(&lockfile_path).unwrap();
+    assert!(committer.ensure_clean().is_ok());
+
+    fs::write(&lockfile_path, lockfile(&["1.0.0", "3.0.0"])).unwrap();
+    let err = committer.ensure_clean().unwrap_err();
+    assert!(err.to_string().contains("uncommitted changes"), "{}", err);
+}
@@ -2,8 +2,11 @@
 
 use std::{fs, path::Path};
 
-use cargo_audit::config::AuditConfig;
-use rustsec::platforms::{Arch, OS};
+use cargo_audit::config::{AuditConfig, DenyOption, ExitCodes, OutputFormat};
+use rustsec::{
+    platforms::{Arch, OS},
+    report::SourceRule,
+};
 
 /// Ensure `audit.toml.example` parses as a valid config file
 #[test]
@@ -11,6 +14,19 @@ fn parse_audit_toml_example() {
     let toml_string = fs::read_to_string("audit.toml.example").unwrap();
     let config: AuditConfig = toml::from_str(&toml_string).unwrap();
 
+    let ci = config.with_profile("ci").unwrap();
+    assert_eq!(ci.output.deny, [DenyOption::Warnings]);
+    assert_eq!(ci.output.format, OutputFormat::Json);
+    assert_eq!(config.report_settings().banned_packages.len(), 2);
+    assert_eq!(config.database.retry_policy().max_retries(), 5);
+    assert!(config.database.offline_fallback);
+    assert_eq!(config.report_settings().include_sources.len(), 2);
+    assert_eq!(config.report_settings().exclude_sources, [SourceRule::Path]);
+    assert_eq!(config.enrich.sources.len(), 4);
+    assert_eq!(config.enrich.requests_per_second, Some(5));
+    assert_eq!(config.report_settings().promote_informational.len(), 1);
+    assert_eq!(config.report_settings().demote.len(), 1);
+
     assert_eq!(
         config.database.path.unwrap(),
         Path::new("~/.cargo/advisory-db")
@@ -19,8 +35,29 @@ fn parse_audit_toml_example() {
         config.database.url.unwrap(),
         "https://github.com/RustSec/advisory-db.git"
     );
+    assert!(config.checksums.enabled);
     assert_eq!(config.target.arch(), vec![Arch::X86_64]);
     assert_eq!(config.target.os(), vec![OS::Linux, OS::Windows]);
+    assert_eq!(
+        config.notify.url.unwrap(),
+        "https://hooks.example.com/audit"
+    );
+    assert_eq!(config.notify.headers["Authorization"], "Bearer TOKEN");
+}
+
+/// Ensure exit codes use the same kind names in `audit.toml` as with
+/// `--exit-code`
+#[test]
+fn exit_codes_match_command_line_names() {
+    let exit_codes: ExitCodes = toml::from_str("denied-warnings = 2\nstale-database = 3").unwrap();
+
+    let mut expected = ExitCodes::default();
+    expected.set("denied-warnings", 2).unwrap();
+    expected.set("stale-database", 3).unwrap();
+    assert_eq!(exit_codes, expected);
+    assert_eq!(exit_codes.vulnerabilities, 1);
+
+    assert!(toml::from_str::<ExitCodes>("denied_warnings = 2").is_err());
 }
 
 /// Ensure `target.arch` and `target.os` continue to parse when they
@@ -33,3 +70,129 @@ fn parser_audit_toml_example() {
     assert_eq!(config.target.arch(), vec![Arch::X86_64]);
     assert_eq!(config.target.os(), vec![OS::Linux]);
 }
+
+/// Ensure ignored advisories can be given by ID alone or along with a reason
+#[test]
+fn parse_ignore_reasons() {
+    let config: AuditConfig = toml::from_str(
+        r#"
+        [advisories]
+        ignore = ["RUSTSEC-2019-0001", { id = "RUSTSEC-2020-0002", reason = "unused API" }]
+        "#,
+    )
+    .unwrap();
+
+    let settings = config.report_settings();
+    assert_eq!(settings.ignore.len(), 2);
+    assert_eq!(settings.ignore_reasons.len(), 1);
+    assert_eq!(
+        settings.ignore_reasons[&"RUSTSEC-2020-0002".parse().unwrap()],
+        "unused API"
+    );
+}
+
+#[test]
+fn parse_scoped_ignores() {
+    let config: AuditConfig = toml::from_str(
+        r#"
+        [advisories]
+        ignore = [
+            "RUSTSEC-2019-0001",
+            { id = "RUSTSEC-2020-0002", package = "foo", reason = "unused API" },
+            { id = "RUSTSEC-2020-0003", package = "foo", version = "1.2.*" },
+        ]
+        "#,
+    )
+    .unwrap();
+
+    let settings = config.report_settings();
+    assert_eq!(settings.ignore, ["RUSTSEC-2019-0001".parse().unwrap()]);
+    assert!(settings.ignore_reasons.is_empty());
+    assert_eq!(settings.scoped_ignores.len(), 2);
+
+    let scoped = &settings.scoped_ignores[1];
+    assert_eq!(scoped.id, "RUSTSEC-2020-0003".parse().unwrap());
+    assert_eq!(scoped.package.as_ref().unwrap().as_str(), "foo");
+    assert_eq!(scoped.versions, Some("1.2.*".parse().unwrap()));
+    assert_eq!(
+        settings.scoped_ignores[0].reason.as_deref(),
+        Some("unused API")
+    );
+}
+
+/// Ensure banned and allowed packages can be given by name alone or along
+/// with a version requirement and reason
+#[test]
+fn parse_package_lists() {
+    let config: AuditConfig = toml::from_str(
+        r#"
+        [packages]
+        banned = ["openssl", { name = "base", version = "< 2", reason = "use base2" }]
+        allowed = [{ name = "base", version = "1.2.*" }]
+        "#,
+    )
+    .unwrap();
+
+    let settings = config.report_settings();
+    assert_eq!(settings.banned_packages.len(), 2);
+    assert_eq!(settings.banned_packages[0].name.as_str(), "openssl");
+    assert_eq!(settings.banned_packages[0].versions, None);
+
+    let banned = &settings.banned_packages[1];
+    assert_eq!(banned.versions, Some("< 2".parse().unwrap()));
+    assert_eq!(banned.reason.as_deref(), Some("use base2"));
+    assert_eq!(
+        settings.allowed_packages[0].versions,
+        Some("1.2.*".parse().unwrap())
+    );
+
+    assert!(toml::from_str::<AuditConfig>(
+        "[packages]
+banned = [{ id = \"x\" }]"
+    )
+    .is_err());
+}
+
+#[test]
+fn resolve_profiles() {
+    let config: AuditConfig = toml::from_str(
+        r#"
+        [advisories]
+        ignore = ["RUSTSEC-2019-0001"]
+        severity_threshold = "low"
+
+        [output]
+        deny = ["unmaintained"]
+        quiet = false
+
+        [profile.ci.advisories]
+        ignore = ["RUSTSEC-2020-0002"]
+
+        [profile.ci.output]
+        format = "json"
+        "#,
+    )
+    .unwrap();
+
+    let ci = config.with_profile("ci").unwrap();
+    let settings = ci.report_settings();
+    assert_eq!(settings.ignore, ["RUSTSEC-2020-0002".parse().unwrap()]);
+    assert_eq!(settings.severity, Some(rustsec::advisory::Severity::Low));
+    assert_eq!(ci.output.format, OutputFormat::Json);
+    assert_eq!(ci.output.deny, [DenyOption::Unmaintained]);
+
+    assert!(config.with_profile("local").is_err());
+}
+
+#[test]
+fn invalid_profile() {
+    let config: AuditConfig = toml::from_str(
+        r#"
+        [profile.ci.output]
+        colour = "never"
+        "#,
+    )
+    .unwrap();
+
+    assert!(config.with_profile("ci").is_err());
+}
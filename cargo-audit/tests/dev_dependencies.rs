@@ -0,0 +1,109 @@
+//! Tests for detecting findings in crates only used by dev-dependencies
+
+use abscissa_core::testing::prelude::*;
+use std::{fs, io::Read, path::Path};
+use tempfile::TempDir;
+
+mod support;
+use support::{write_advisory_db, ADVISORY};
+
+/// Manifest of a package which depends on `base` through the `middle`
+/// dev-dependency
+const MANIFEST: &str = r#"[package]
+name = "app"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+unrelated = "1"
+
+[dev-dependencies]
+middle = "1"
+"#;
+
+/// Lockfile for [`MANIFEST`]
+const LOCKFILE: &str = r#"version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["middle", "unrelated"]
+
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "middle"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["base"]
+
+[[package]]
+name = "unrelated"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+/// Create an advisory database and a project dev-depending on `base`
+fn setup(dir: &Path) {
+    write_advisory_db(dir, ADVISORY);
+
+    let project = dir.join("app");
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src").join("lib.rs"), "").unwrap();
+    fs::write(project.join("Cargo.toml"), MANIFEST).unwrap();
+    fs::write(project.join("Cargo.lock"), LOCKFILE).unwrap();
+}
+
+/// Get a `CmdRunner` auditing the project with the given dev-dependencies option
+fn cmd_runner(dir: &Path, dev_dependencies: &str) -> CmdRunner {
+    let mut runner = support::cmd_runner(dir, &dir.join("app").join("Cargo.lock"));
+    runner.args(["--dev-dependencies", dev_dependencies]);
+    runner
+}
+
+#[test]
+fn dev_only_vulnerability_marked() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path());
+
+    let mut runner = cmd_runner(dir.path(), "report");
+    runner.arg("--json").capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    process.wait().unwrap().expect_code(1);
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let vulnerability = &report["vulnerabilities"]["list"][0];
+    assert_eq!(vulnerability["package"]["name"], "base");
+    assert_eq!(vulnerability["dev_only"], true);
+}
+
+#[test]
+fn dev_only_vulnerability_downgraded() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path());
+
+    assert_eq!(cmd_runner(dir.path(), "downgrade").status().code(), 0);
+}
+
+#[test]
+fn dev_only_vulnerability_skipped() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path());
+
+    let mut runner = cmd_runner(dir.path(), "skip");
+    runner.arg("--json").capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    process.wait().unwrap().expect_code(0);
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(report["vulnerabilities"]["count"], 0);
+}
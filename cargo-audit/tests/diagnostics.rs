@@ -0,0 +1,101 @@
+//! Tests for diagnostics located in `Cargo.toml`
+
+use abscissa_core::testing::prelude::*;
+use serde_json::Value;
+use std::{fs, io::Read, path::Path};
+use tempfile::TempDir;
+
+mod support;
+use support::{write_advisory_db, ADVISORY};
+
+/// Manifest of a package which depends on `base` through `middle`
+const MANIFEST: &str = r#"[package]
+name = "app"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+unrelated = "1"
+middle = "1"
+"#;
+
+/// Lockfile for [`MANIFEST`]
+const LOCKFILE: &str = r#"version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["middle", "unrelated"]
+
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "middle"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["base"]
+
+[[package]]
+name = "unrelated"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+/// Create an advisory database and a project depending on `base`
+fn setup(dir: &Path) {
+    write_advisory_db(dir, ADVISORY);
+
+    let project = dir.join("app");
+    fs::create_dir_all(project.join("src")).unwrap();
+    fs::write(project.join("src").join("lib.rs"), "").unwrap();
+    fs::write(project.join("Cargo.toml"), MANIFEST).unwrap();
+    fs::write(project.join("Cargo.lock"), LOCKFILE).unwrap();
+}
+
+#[test]
+fn vulnerability_located_at_direct_dependency() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path());
+
+    let mut runner = CmdRunner::default();
+    runner
+        .args(["audit", "--no-fetch", "--diagnostics", "--db"])
+        .arg(dir.path().join("db"))
+        .arg("--file")
+        .arg(dir.path().join("app").join("Cargo.lock"))
+        .capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    process.wait().unwrap().expect_code(1);
+
+    let diagnostics: Vec<Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(diagnostics.len(), 1);
+
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic["level"], "error");
+    assert_eq!(diagnostic["kind"], "vulnerability");
+    assert_eq!(diagnostic["advisory"], "RUSTSEC-2001-2101");
+    assert_eq!(diagnostic["package"]["name"], "base");
+
+    let locations = diagnostic["locations"].as_array().unwrap();
+    assert_eq!(locations.len(), 1);
+    let location = &locations[0];
+    assert_eq!(location["member"], "app");
+    assert_eq!(location["dependency"], "middle");
+    assert_eq!(location["line"], 8);
+    assert_eq!(location["column"], 1);
+    assert_eq!(location["end_line"], 8);
+    assert_eq!(location["end_column"], 7);
+    assert!(location["manifest_path"]
+        .as_str()
+        .unwrap()
+        .ends_with("Cargo.toml"));
+}
@@ -0,0 +1,217 @@
+//! Tests for enriching reports with data from external sources
+
+#![cfg(feature = "enrich")]
+
+use abscissa_core::testing::prelude::*;
+use cargo_audit::{
+    auditor::Auditor,
+    config::{EnrichConfig, EnrichSource},
+    enrich::Enricher,
+};
+use rustsec::{retry::RetryPolicy, Report};
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::Path,
+    thread,
+};
+use tempfile::TempDir;
+
+mod support;
+use support::{library_config, setup, Silent};
+
+/// Advisory against `base` versions before 1.2.3, with a CVE alias
+const ADVISORY: &str = r#"```toml
+[advisory]
+id = "RUSTSEC-2001-2101"
+package = "base"
+date = "2001-02-03"
+aliases = ["CVE-2001-2101"]
+
+[versions]
+patched = [">= 1.2.3"]
+```
+
+# All your base are belong to us
+
+You have no chance to survive. Make your time.
+"#;
+
+/// Audit a project depending on a vulnerable version of `base`
+fn audit(dir: &Path) -> Report {
+    let lockfile_path = setup(dir, ADVISORY, "1.0.0");
+    let mut auditor = Auditor::with_presenter(&library_config(dir), Silent).unwrap();
+    auditor.audit_lockfile(&lockfile_path).unwrap()
+}
+
+/// Answer `count` requests on the given listener with the responses of a fake
+/// OSV, EPSS, KEV and GitHub API, returning the requests, starting with their
+/// request lines
+fn serve(listener: TcpListener, count: usize) -> Vec<String> {
+    let mut requests = vec![];
+
+    for stream in listener.incoming().take(count) {
+        let mut reader = BufReader::new(stream.unwrap());
+        let mut request = String::new();
+        reader.read_line(&mut request).unwrap();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                content_length = length.trim().parse().unwrap();
+            }
+            request.push_str(&line);
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        request.push_str(&String::from_utf8(body).unwrap());
+
+        let path = request.split(' ').nth(1).unwrap().to_owned();
+        let body = if path == "/osv/querybatch" {
+            r#"{"results":[{"vulns":[
+                {"id":"RUSTSEC-2001-2101","modified":"2001-02-03T00:00:00Z"},
+                {"id":"GHSA-4fc7-8qqw-x6w6","modified":"2001-02-03T00:00:00Z"}
+            ]}]}"#
+        } else if path.starts_with("/epss") {
+            r#"{"status":"OK","data":[{"cve":"CVE-2001-2101","epss":"0.912340000","percentile":"0.998760000","date":"2001-02-03"}]}"#
+        } else if path.starts_with("/kev") {
+            r#"{"vulnerabilities":[
+                {"cveID":"CVE-2000-0001","dateAdded":"2021-11-03"},
+                {"cveID":"CVE-2001-2101","dateAdded":"2022-01-10"}
+            ]}"#
+        } else if path.starts_with("/github/advisories/") {
+            r#"{"ghsa_id":"GHSA-4fc7-8qqw-x6w6","severity":"critical"}"#
+        } else {
+            panic!("unexpected request: {}", request);
+        };
+
+        write!(
+            reader.into_inner(),
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nconnection: close\r\n\
+            content-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        requests.push(request);
+    }
+
+    requests
+}
+
+/// Enrichment configuration pointing every source at the given server
+fn config(listener: &TcpListener) -> EnrichConfig {
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    EnrichConfig {
+        requests_per_second: Some(100),
+        osv_url: Some(format!("{}/osv", url)),
+        epss_url: Some(format!("{}/epss", url)),
+        kev_url: Some(format!("{}/kev", url)),
+        github_api_url: Some(format!("{}/github", url)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn reports_are_enriched_from_all_sources() {
+    let dir = TempDir::new().unwrap();
+    let mut report = audit(dir.path());
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let config = config(&listener);
+    let server = thread::spawn(move || serve(listener, 4));
+
+    Enricher::new(&config, RetryPolicy::none())
+        .unwrap()
+        .enrich(&mut report)
+        .unwrap();
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("POST /osv/querybatch "));
+    assert!(requests[0].ends_with(
+        r#"{"queries":[{"package":{"name":"base","ecosystem":"crates.io"},"version":"1.0.0"}]}"#
+    ));
+    assert!(requests[1].starts_with("GET /epss?cve=CVE-2001-2101 "));
+    assert!(requests[2].starts_with("GET /kev "));
+    assert!(requests[3].starts_with("GET /github/advisories/GHSA-4fc7-8qqw-x6w6 "));
+
+    // The advisory itself isn't one of its aliases
+    let enrichment = report.vulnerabilities.list[0].enrichment.clone().unwrap();
+    assert_eq!(enrichment.aliases.len(), 1);
+    assert_eq!(enrichment.aliases[0].as_str(), "GHSA-4fc7-8qqw-x6w6");
+    let epss = enrichment.epss.unwrap();
+    assert_eq!(epss.cve.as_str(), "CVE-2001-2101");
+    assert_eq!(epss.score.value(), 0.91234);
+    assert_eq!(epss.percentile.value(), 0.99876);
+    assert_eq!(
+        enrichment.known_exploited.unwrap().date_added,
+        Some("2022-01-10".parse().unwrap())
+    );
+    assert_eq!(enrichment.ghsa_severity.as_deref(), Some("critical"));
+}
+
+#[test]
+fn only_configured_sources_are_queried() {
+    let dir = TempDir::new().unwrap();
+    let mut report = audit(dir.path());
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let config = EnrichConfig {
+        sources: vec![EnrichSource::Kev],
+        ..config(&listener)
+    };
+    let server = thread::spawn(move || serve(listener, 1));
+
+    Enricher::new(&config, RetryPolicy::none())
+        .unwrap()
+        .enrich(&mut report)
+        .unwrap();
+
+    // The CVE comes from the advisory's own aliases when OSV isn't queried
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /kev "));
+
+    let enrichment = report.vulnerabilities.list[0].enrichment.clone().unwrap();
+    assert!(enrichment.aliases.is_empty());
+    assert!(enrichment.epss.is_none());
+    assert!(enrichment.known_exploited.is_some());
+}
+
+#[test]
+fn github_token_is_only_sent_to_github() {
+    let dir = TempDir::new().unwrap();
+    let mut report = audit(dir.path());
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let config = config(&listener);
+    let server = thread::spawn(move || serve(listener, 4));
+
+    env::set_var("GITHUB_TOKEN", "secret");
+    Enricher::new(&config, RetryPolicy::none())
+        .unwrap()
+        .enrich(&mut report)
+        .unwrap();
+
+    let requests = server.join().unwrap();
+    assert!(requests[3].starts_with("GET /github/advisories/"));
+    assert!(requests
+        .iter()
+        .all(|request| !request.to_lowercase().contains("authorization")));
+}
+
+#[test]
+fn invalid_reports_are_rejected() {
+    let dir = TempDir::new().unwrap();
+    let report_path = dir.path().join("report.json");
+    fs::write(&report_path, "not a report").unwrap();
+
+    let mut runner = CmdRunner::default();
+    runner.args(["audit", "enrich"]).arg(&report_path);
+    assert_eq!(runner.status().code(), 2);
+}
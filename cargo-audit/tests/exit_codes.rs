@@ -0,0 +1,37 @@
+//! Exit code configuration tests
+
+use std::io::Read;
+use tempfile::TempDir;
+
+mod support;
+use support::{cmd_runner, setup, ADVISORY};
+
+#[test]
+fn vulnerabilities_exit_code() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path(), ADVISORY, "1.0.0");
+
+    assert_eq!(cmd_runner(dir.path(), &lockfile).status().code(), 1);
+    assert_eq!(
+        cmd_runner(dir.path(), &lockfile)
+            .args(["--exit-code", "vulnerabilities=3"])
+            .status()
+            .code(),
+        3
+    );
+}
+
+#[test]
+fn quiet_success_prints_nothing() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path(), ADVISORY, "1.2.3");
+
+    let mut runner = cmd_runner(dir.path(), &lockfile);
+    runner.arg("--quiet-success").capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    assert_eq!(stdout, "");
+    assert_eq!(process.wait().unwrap().code(), 0);
+}
@@ -0,0 +1,53 @@
+//! Tests for only reporting fixable vulnerabilities
+
+use std::io::Read;
+use tempfile::TempDir;
+
+mod support;
+use support::{cmd_runner, setup};
+
+/// Advisory against all versions of `base`, with no patched release
+const ADVISORY: &str = r#"```toml
+[advisory]
+id = "RUSTSEC-2001-2101"
+package = "base"
+date = "2001-02-03"
+
+[versions]
+patched = []
+```
+
+# All your base are belong to us
+
+You have no chance to survive. Make your time.
+"#;
+
+#[test]
+fn unfixable_vulnerabilities_reported_by_default() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path(), ADVISORY, "1.0.0");
+
+    let mut runner = cmd_runner(dir.path(), &lockfile);
+    runner.arg("--json").capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    process.wait().unwrap().expect_code(1);
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let vulnerability = &report["vulnerabilities"]["list"][0];
+    assert_eq!(vulnerability["advisory"]["id"], "RUSTSEC-2001-2101");
+    assert_eq!(vulnerability["is_fixable"], false);
+}
+
+#[test]
+fn fixable_only_skips_unfixable_vulnerabilities() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path(), ADVISORY, "1.0.0");
+
+    let mut runner = cmd_runner(dir.path(), &lockfile);
+    runner.arg("--fixable-only");
+    let code = runner.status().code();
+    assert_eq!(code, 0);
+}
@@ -0,0 +1,87 @@
+//! `cargo audit installed` tests
+
+#![cfg(feature = "binary-scanning")]
+
+use abscissa_core::testing::prelude::*;
+use std::{fs, io::Read, path::Path};
+use tempfile::TempDir;
+
+mod support;
+use support::{write_advisory_db, ADVISORY};
+
+/// Create an advisory database and an install root in which the given
+/// version of `base` was installed, with one of its binaries missing
+fn setup(dir: &Path, base_version: &str) {
+    write_advisory_db(dir, ADVISORY);
+
+    let bin_dir = dir.join("root").join("bin");
+    fs::create_dir_all(&bin_dir).unwrap();
+    fs::copy(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("support")
+            .join("binaries")
+            .join("binary-without-audit-info"),
+        bin_dir.join("hello"),
+    )
+    .unwrap();
+
+    fs::write(
+        dir.join("root").join(".crates2.json"),
+        format!(
+            r#"{{"installs":{{"base {} (registry+https://github.com/rust-lang/crates.io-index)":{{"version_req":null,"bins":["hello","missing"]}}}}}}"#,
+            base_version
+        ),
+    )
+    .unwrap();
+}
+
+/// Audit the install root in the given directory, returning the output
+/// (stdout followed by stderr) and exit code
+fn audit_installed(dir: &Path) -> (String, i32) {
+    let mut runner = CmdRunner::default();
+    runner
+        .args(["audit", "--no-fetch", "--color", "never", "--db"])
+        .arg(dir.join("db"))
+        .args(["installed", "--root"])
+        .arg(dir.join("root"))
+        .capture_stdout()
+        .capture_stderr();
+
+    let mut process = runner.run();
+    let mut output = String::new();
+    process.stdout().read_to_string(&mut output).unwrap();
+    process.stderr().read_to_string(&mut output).unwrap();
+    let code = process.wait().unwrap().code();
+    (output, code)
+}
+
+#[test]
+fn vulnerable_installed_program_fails() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path(), "1.0.0");
+
+    let (output, code) = audit_installed(dir.path());
+    assert!(output.contains("RUSTSEC-2001-2101"), "{}", output);
+    assert!(
+        output.contains("1 installed program is built against vulnerable crates"),
+        "{}",
+        output
+    );
+    assert!(output.contains("but is missing, skipping"), "{}", output);
+    assert_eq!(code, 1);
+}
+
+#[test]
+fn patched_installed_program_passes() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path(), "1.2.3");
+
+    let (output, code) = audit_installed(dir.path());
+    assert!(
+        output.contains("no installed programs are built against vulnerable crates"),
+        "{}",
+        output
+    );
+    assert_eq!(code, 0);
+}
@@ -0,0 +1,224 @@
+//! Tests for embedding audits with the library API
+
+use cargo_audit::{
+    auditor::Auditor,
+    config::{AuditConfig, IgnoreEntry, PackageDetails, PackageEntry},
+    error::Error,
+    presenter::Presenter,
+};
+use rustsec::{Lockfile, WarningKind};
+use std::{
+    cell::RefCell,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use tempfile::TempDir;
+
+mod support;
+use support::{base_lockfile, library_config, ADVISORY};
+
+/// Presenter recording what it was asked to display
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<String>>>);
+
+impl Presenter for Recorder {
+    fn status(&mut self, status: &str, message: &str) {
+        self.0.borrow_mut().push(format!("{} {}", status, message));
+    }
+
+    fn warning(&mut self, message: &str) {
+        self.0.borrow_mut().push(format!("warning: {}", message));
+    }
+
+    fn before_report(&mut self, path: &Path, _lockfile: &Lockfile) {
+        self.0.borrow_mut().push(format!(
+            "scanning {}",
+            path.file_name().unwrap().to_str().unwrap()
+        ));
+    }
+
+    fn print_report(
+        &mut self,
+        report: &rustsec::Report,
+        _lockfile: &Lockfile,
+        _path: Option<&Path>,
+    ) -> Result<(), Error> {
+        self.0
+            .borrow_mut()
+            .push(format!("{} vulnerabilities", report.vulnerabilities.count));
+        Ok(())
+    }
+}
+
+/// Write the advisory database and lockfile to `dir`, returning a config
+/// using that database along with the lockfile's path
+fn setup(dir: &Path) -> (AuditConfig, PathBuf) {
+    let lockfile_path = support::setup(dir, ADVISORY, "1.0.0");
+    (library_config(dir), lockfile_path)
+}
+
+#[test]
+fn audit_with_custom_presenter() {
+    let dir = TempDir::new().unwrap();
+    let (config, lockfile_path) = setup(dir.path());
+
+    let recorder = Recorder::default();
+    let mut auditor = Auditor::with_presenter(&config, recorder.clone()).unwrap();
+    let report = auditor.audit_lockfile(&lockfile_path).unwrap();
+
+    assert!(report.vulnerabilities.found);
+    assert_ne!(auditor.exit_code(&report), 0);
+
+    let events = recorder.0.borrow();
+    assert!(events[0].starts_with("Loaded 1 security advisories"));
+    assert_eq!(events[1..], ["scanning Cargo.lock", "1 vulnerabilities"]);
+}
+
+#[test]
+fn future_lockfile_versions_are_audited() {
+    let dir = TempDir::new().unwrap();
+    let (config, lockfile_path) = setup(dir.path());
+    fs::write(
+        &lockfile_path,
+        base_lockfile("1.0.0").replace("version = 3", "version = 99"),
+    )
+    .unwrap();
+
+    let recorder = Recorder::default();
+    let mut auditor = Auditor::with_presenter(&config, recorder.clone()).unwrap();
+    let report = auditor.audit_lockfile(&lockfile_path).unwrap();
+
+    assert!(report.vulnerabilities.found);
+    assert!(recorder.0.borrow().contains(
+        &"warning: unsupported Cargo.lock format version `99`; parsing as version 4".to_owned()
+    ));
+}
+
+#[test]
+fn cvss_details_are_included_on_request() {
+    let dir = TempDir::new().unwrap();
+    let lockfile_path = support::setup(
+        dir.path(),
+        &ADVISORY.replace(
+            "date = \"2001-02-03\"\n",
+            "date = \"2001-02-03\"\ncvss = \"CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H\"\n",
+        ),
+        "1.0.0",
+    );
+    let mut config = library_config(dir.path());
+
+    let mut auditor = Auditor::with_presenter(&config, Recorder::default()).unwrap();
+    let report = auditor.audit_lockfile(&lockfile_path).unwrap();
+    assert!(report.vulnerabilities.list[0].cvss_details.is_none());
+    drop(auditor);
+
+    config.output.cvss_details = true;
+    let mut auditor = Auditor::with_presenter(&config, Recorder::default()).unwrap();
+    let report = auditor.audit_lockfile(&lockfile_path).unwrap();
+    let json = serde_json::to_value(&report.vulnerabilities.list[0]).unwrap();
+    assert_eq!(json["cvss_details"]["score"], 9.8);
+    assert_eq!(json["cvss_details"]["severity"], "critical");
+}
+
+#[test]
+fn missing_database_is_an_error() {
+    let dir = TempDir::new().unwrap();
+
+    let mut config = library_config(dir.path());
+    config.database.path = Some(dir.path().join("missing"));
+    config.database.revision = Some("main".to_owned());
+
+    assert!(Auditor::with_presenter(&config, Recorder::default()).is_err());
+}
+
+#[cfg(feature = "release-dates")]
+#[test]
+fn patched_release_dates_are_looked_up() {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    let dir = TempDir::new().unwrap();
+    let (mut config, lockfile_path) = setup(dir.path());
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    config.release_dates.enabled = true;
+    config.release_dates.api_url = Some(format!("http://{}", listener.local_addr().unwrap()));
+
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+        }
+
+        let body = r#"{"versions":[
+            {"num":"1.3.0","created_at":"2001-04-01T00:00:00.000000+00:00","yanked":false},
+            {"num":"1.2.4","created_at":"2001-03-01T00:00:00.000000+00:00","yanked":true},
+            {"num":"1.2.3","created_at":"2001-03-02T12:34:56.000000+00:00","yanked":false},
+            {"num":"1.0.0","created_at":"2000-01-01T00:00:00.000000+00:00","yanked":false}
+        ]}"#;
+        write!(
+            reader.into_inner(),
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        request_line
+    });
+
+    let mut auditor = Auditor::with_presenter(&config, Recorder::default()).unwrap();
+    let report = auditor.audit_lockfile(&lockfile_path).unwrap();
+
+    let request_line = server.join().unwrap();
+    assert!(
+        request_line.starts_with("GET /crates/base/versions "),
+        "{}",
+        request_line
+    );
+
+    let vulnerability = &report.vulnerabilities.list[0];
+    assert_eq!(
+        vulnerability.patched_release_date,
+        Some("2001-03-02".parse().unwrap())
+    );
+    assert!(vulnerability.advisory_age_days.unwrap() > 365 * 25);
+}
+
+#[test]
+fn banned_packages_fail_the_audit() {
+    let dir = TempDir::new().unwrap();
+    let (mut config, lockfile_path) = setup(dir.path());
+    config.packages.banned = vec![PackageEntry::Detailed(PackageDetails {
+        name: "base".parse().unwrap(),
+        version: None,
+        reason: Some("use base2".to_owned()),
+    })];
+    config.advisories.ignore = vec![IgnoreEntry::Id("RUSTSEC-2001-2101".parse().unwrap())];
+
+    let mut auditor = Auditor::with_presenter(&config, Recorder::default()).unwrap();
+    let report = auditor.audit_lockfile(&lockfile_path).unwrap();
+
+    assert!(!report.vulnerabilities.found);
+    let banned = &report.warnings[&WarningKind::Banned];
+    assert_eq!(banned[0].package.name.as_str(), "base");
+    assert_eq!(banned[0].ban_reason.as_deref(), Some("use base2"));
+    assert_ne!(auditor.exit_code(&report), 0);
+    drop(auditor);
+
+    config.packages.allowed = vec![PackageEntry::Name("base".parse().unwrap())];
+    let mut auditor = Auditor::with_presenter(&config, Recorder::default()).unwrap();
+    let report = auditor.audit_lockfile(&lockfile_path).unwrap();
+    assert!(!report.warnings.contains_key(&WarningKind::Banned));
+    assert_eq!(auditor.exit_code(&report), 0);
+}
@@ -0,0 +1,134 @@
+//! Webhook notification tests
+
+#![cfg(feature = "notify")]
+
+use abscissa_core::testing::prelude::*;
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    thread,
+};
+use tempfile::TempDir;
+
+mod support;
+use support::{write_advisory_db, ADVISORY};
+
+/// Accept a single HTTP request on the given listener, returning its headers
+/// (lowercased) and body
+fn receive_request(listener: TcpListener) -> (String, String) {
+    let (stream, _) = listener.accept().unwrap();
+    let mut reader = BufReader::new(stream);
+    let mut headers = String::new();
+
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if line == "\r\n" {
+            break;
+        }
+        headers.push_str(&line.to_lowercase());
+    }
+
+    let content_length = headers
+        .lines()
+        .find_map(|line| line.strip_prefix("content-length:"))
+        .map_or(0, |len| len.trim().parse().unwrap());
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+
+    reader
+        .into_inner()
+        .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+        .unwrap();
+
+    (headers, String::from_utf8(body).unwrap())
+}
+
+#[test]
+fn report_is_posted_to_notify_url() {
+    let db_dir = TempDir::new().unwrap();
+    let lockfile: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "tests",
+        "support",
+        "no_vulns",
+        "Cargo.lock",
+    ]
+    .iter()
+    .collect();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let server = thread::spawn(move || receive_request(listener));
+
+    let mut runner = CmdRunner::default();
+    runner
+        .args(["audit", "--no-fetch", "--quiet", "--db"])
+        .arg(db_dir.path())
+        .arg("--file")
+        .arg(lockfile)
+        .args(["--notify-url", &url, "--notify-header", "X-Token: secret"]);
+    assert_eq!(runner.status().code(), 0);
+
+    let (headers, body) = server.join().unwrap();
+    assert!(headers.starts_with("post /hook "), "{}", headers);
+    assert!(headers.contains("x-token: secret"), "{}", headers);
+    assert!(
+        headers.contains("content-type: application/json"),
+        "{}",
+        headers
+    );
+
+    let report: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(report["vulnerabilities"]["found"], false);
+}
+
+#[cfg(feature = "binary-scanning")]
+#[test]
+fn reports_of_several_binaries_are_posted_once() {
+    let dir = TempDir::new().unwrap();
+    write_advisory_db(dir.path(), ADVISORY);
+
+    let mut json_paths = vec![];
+    for base_version in ["1.0.0", "1.2.3"] {
+        let json_path = dir.path().join(format!("base-{}.json", base_version));
+        fs::write(
+            &json_path,
+            format!(
+                r#"{{"packages":[
+                    {{"name":"app","version":"0.1.0","source":"local","dependencies":[1],"root":true}},
+                    {{"name":"base","version":"{}","source":"crates.io"}}
+                ]}}"#,
+                base_version
+            ),
+        )
+        .unwrap();
+        json_paths.push(json_path);
+    }
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let server = thread::spawn(move || {
+        let request = receive_request(listener.try_clone().unwrap());
+        (request, listener)
+    });
+
+    let mut runner = CmdRunner::default();
+    runner
+        .args(["audit", "--no-fetch", "--quiet", "--db"])
+        .arg(dir.path().join("db"))
+        .args(["--notify-url", &url, "bin", "--auditable-json"])
+        .args(&json_paths);
+    assert_eq!(runner.status().code(), 1);
+
+    let ((_, body), listener) = server.join().unwrap();
+    let report: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(report["report-count"], 2);
+    assert_eq!(report["vulnerabilities"].as_array().unwrap().len(), 1);
+
+    // No other request was made
+    listener.set_nonblocking(true).unwrap();
+    assert!(listener.accept().is_err());
+}
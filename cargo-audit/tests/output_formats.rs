@@ -0,0 +1,148 @@
+//! Tests for the TOML, YAML and graph report output formats, and for writing
+//! reports to several outputs
+
+use std::{fs, io::Read, path::Path};
+use tempfile::TempDir;
+
+mod support;
+use support::{cmd_runner, write_advisory_db, ADVISORY};
+
+/// Lockfile depending on a vulnerable version of `base`
+const LOCKFILE: &str = r#"version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["base"]
+
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+/// Create an advisory database and a lockfile depending on `base`
+fn setup(dir: &Path) {
+    write_advisory_db(dir, ADVISORY);
+    fs::write(dir.join("Cargo.lock"), LOCKFILE).unwrap();
+}
+
+/// Audit the lockfile in `dir` with the given output format, returning what
+/// was printed
+fn audit(dir: &Path, format: &str) -> String {
+    let mut runner = cmd_runner(dir, &dir.join("Cargo.lock"));
+    runner.args(["--format", format]).capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    process.wait().unwrap().expect_code(1);
+    stdout
+}
+
+#[test]
+fn toml_report() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path());
+
+    let report: toml::Table = audit(dir.path(), "toml").parse().unwrap();
+    let vulnerabilities = &report["vulnerabilities"];
+    assert_eq!(vulnerabilities["count"].as_integer(), Some(1));
+    assert_eq!(
+        vulnerabilities["list"][0]["advisory"]["id"].as_str(),
+        Some("RUSTSEC-2001-2101")
+    );
+}
+
+#[test]
+fn yaml_report() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path());
+
+    let report = audit(dir.path(), "yaml");
+    assert!(report.contains("\nvulnerabilities:\n"), "{}", report);
+    assert!(report.contains("\n  count: 1\n"), "{}", report);
+    assert!(report.contains("id: RUSTSEC-2001-2101\n"), "{}", report);
+}
+
+#[test]
+fn graph_reports() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path());
+
+    let dot = audit(dir.path(), "dot");
+    assert!(dot.starts_with("digraph audit {\n"), "{}", dot);
+    assert!(
+        dot.contains("n0 [label=\"app 0.1.0\", kind=root"),
+        "{}",
+        dot
+    );
+    assert!(dot.contains("n0 -> n1 [kind=depends_on];"), "{}", dot);
+    assert!(dot.contains("n1 -> n2 [kind=affected_by"), "{}", dot);
+
+    let graphml = audit(dir.path(), "graphml");
+    assert!(
+        graphml.contains("<data key=\"label\">RUSTSEC-2001-2101</data>"),
+        "{}",
+        graphml
+    );
+}
+
+#[test]
+fn several_outputs() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path());
+
+    let mut runner = cmd_runner(dir.path(), &dir.path().join("Cargo.lock"));
+    runner
+        .args(["--format", "yaml"])
+        .arg("--output")
+        .arg(format!("json={}", dir.path().join("audit.json").display()))
+        .arg("--output")
+        .arg(format!("toml={}", dir.path().join("audit.toml").display()))
+        .capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    process.wait().unwrap().expect_code(1);
+    assert!(stdout.contains("\n  count: 1\n"), "{}", stdout);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dir.path().join("audit.json")).unwrap()).unwrap();
+    assert_eq!(json["vulnerabilities"]["count"], 1);
+
+    let toml: toml::Table = fs::read_to_string(dir.path().join("audit.toml"))
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(toml["vulnerabilities"]["count"].as_integer(), Some(1));
+}
+
+#[test]
+fn terminal_output_file_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path());
+
+    let mut runner = cmd_runner(dir.path(), &dir.path().join("Cargo.lock"));
+    runner
+        .args(["--output", "terminal=audit.txt"])
+        .capture_stderr();
+
+    let process = runner.run();
+    process.wait().unwrap().expect_code(2);
+}
+
+#[test]
+fn lenient_lockfile_warnings_keep_json_parseable() {
+    let dir = TempDir::new().unwrap();
+    setup(dir.path());
+    fs::write(
+        dir.path().join("Cargo.lock"),
+        LOCKFILE.replace("version = 3", "version = 99\nfuture-field = true"),
+    )
+    .unwrap();
+
+    let report: serde_json::Value = serde_json::from_str(&audit(dir.path(), "json")).unwrap();
+    assert_eq!(report["vulnerabilities"]["count"], 1);
+}
@@ -0,0 +1,96 @@
+//! `cargo audit server` tests
+
+use serde_json::{json, Value};
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Command, Stdio},
+};
+use tempfile::TempDir;
+
+mod support;
+use support::{base_lockfile, write_advisory_db, ADVISORY};
+
+#[test]
+fn answers_requests_over_stdio() {
+    let dir = TempDir::new().unwrap();
+    write_advisory_db(dir.path(), ADVISORY);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cargo-audit"))
+        .args(["audit", "--no-fetch", "--db"])
+        .arg(dir.path().join("db"))
+        .arg("server")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let requests = [
+        json!({"jsonrpc": "2.0", "id": 1, "method": "audit", "params": {"lockfile": base_lockfile("1.0.0")}}),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "audit", "params": {"lockfile": base_lockfile("1.2.3")}}),
+        json!({"jsonrpc": "2.0", "method": "refresh"}),
+        json!({"jsonrpc": "2.0", "id": 3, "method": "frobnicate"}),
+        json!({"jsonrpc": "2.0", "id": 4, "method": "audit", "params": {"lockfile": "[[package]]"}}),
+        json!({"jsonrpc": "2.0", "id": 5, "method": "refresh"}),
+        json!({"jsonrpc": "2.0", "id": 6, "method": "shutdown"}),
+    ];
+
+    let mut stdin = child.stdin.take().unwrap();
+    for request in &requests {
+        writeln!(stdin, "{}", request).unwrap();
+    }
+    drop(stdin);
+
+    let responses: Vec<Value> = BufReader::new(child.stdout.take().unwrap())
+        .lines()
+        .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+        .collect();
+    assert!(child.wait().unwrap().success());
+
+    // The `refresh` notification doesn't get a response
+    assert_eq!(responses.len(), 6);
+
+    assert_eq!(responses[0]["id"], 1);
+    let vulnerabilities = &responses[0]["result"]["vulnerabilities"];
+    assert_eq!(vulnerabilities["count"], 1);
+    assert_eq!(
+        vulnerabilities["list"][0]["advisory"]["id"],
+        "RUSTSEC-2001-2101"
+    );
+
+    assert_eq!(responses[1]["id"], 2);
+    assert_eq!(responses[1]["result"]["vulnerabilities"]["count"], 0);
+
+    assert_eq!(responses[2]["id"], 3);
+    assert_eq!(responses[2]["error"]["code"], -32601);
+
+    assert_eq!(responses[3]["id"], 4);
+    assert_eq!(responses[3]["error"]["code"], -32602);
+
+    assert_eq!(responses[4]["id"], 5);
+    assert_eq!(responses[4]["result"]["advisories"], 1);
+
+    assert_eq!(responses[5]["id"], 6);
+    assert_eq!(responses[5]["result"], Value::Null);
+}
+
+#[cfg(unix)]
+#[test]
+fn refuses_to_replace_files_with_a_socket() {
+    let dir = TempDir::new().unwrap();
+    write_advisory_db(dir.path(), ADVISORY);
+    let path = dir.path().join("not-a-socket");
+    std::fs::write(&path, "keep me").unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_cargo-audit"))
+        .args(["audit", "--no-fetch", "--db"])
+        .arg(dir.path().join("db"))
+        .args(["server", "--socket"])
+        .arg(&path)
+        .stderr(Stdio::null())
+        .status()
+        .unwrap();
+
+    assert_eq!(status.code(), Some(2));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+}
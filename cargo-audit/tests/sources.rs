@@ -0,0 +1,70 @@
+//! Tests for warnings about packages from sources the advisory database
+//! doesn't cover
+
+use std::{fs, io::Read, path::Path};
+use tempfile::TempDir;
+
+mod support;
+use support::cmd_runner;
+
+/// Create an empty advisory database and a project depending on a crate from
+/// an unpinned git repository, returning the path to the project's `Cargo.lock`
+fn setup(dir: &Path) -> std::path::PathBuf {
+    fs::create_dir_all(dir.join("db").join("crates")).unwrap();
+
+    let lockfile = dir.join("Cargo.lock");
+    fs::write(
+        &lockfile,
+        "version = 3\n\n[[package]]\nname = \"base\"\nversion = \"1.0.0\"\n\
+        source = \"git+https://github.com/example/base?branch=main#0123456789abcdef0123456789abcdef01234567\"\n",
+    )
+    .unwrap();
+    lockfile
+}
+
+#[test]
+fn sources_not_checked_by_default() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path());
+
+    let mut runner = cmd_runner(dir.path(), &lockfile);
+    runner.arg("--json").capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    process.wait().unwrap().expect_code(0);
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(report["warnings"].get("source").is_none());
+}
+
+#[test]
+fn unpinned_git_source_warning() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path());
+
+    let mut runner = cmd_runner(dir.path(), &lockfile);
+    runner.args(["--check-sources", "--json"]).capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    process.wait().unwrap().expect_code(0);
+
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let warnings = report["warnings"]["source"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["package"]["name"], "base");
+}
+
+#[test]
+fn unpinned_git_source_denied() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path());
+
+    let mut runner = cmd_runner(dir.path(), &lockfile);
+    runner.args(["--check-sources", "--deny", "warnings"]);
+    let code = runner.status().code();
+    assert_eq!(code, 1);
+}
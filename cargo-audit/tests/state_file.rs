@@ -0,0 +1,119 @@
+//! Tests for only reporting findings not recorded in a state file
+
+use abscissa_core::testing::prelude::*;
+use cargo_audit::{
+    auditor::Auditor,
+    error::{Error, ErrorKind},
+    presenter::Presenter,
+};
+use rustsec::{Lockfile, Report};
+use std::{fs, io, io::Read, path::Path};
+use tempfile::TempDir;
+
+mod support;
+use support::{library_config, setup, ADVISORY};
+
+/// Audit the given lockfile against the temporary database using the state
+/// file in `dir`, returning the exit code and the JSON report
+fn audit(dir: &Path, lockfile: &Path) -> (i32, serde_json::Value) {
+    let mut runner = CmdRunner::default();
+    runner
+        .args(["audit", "--no-fetch", "--json", "--db"])
+        .arg(dir.join("db"))
+        .arg("--file")
+        .arg(lockfile)
+        .arg("--state-file")
+        .arg(dir.join("state.json"))
+        .capture_stdout();
+
+    let mut process = runner.run();
+    let mut stdout = String::new();
+    process.stdout().read_to_string(&mut stdout).unwrap();
+    let code = process.wait().unwrap().code();
+    (code, serde_json::from_str(&stdout).unwrap())
+}
+
+#[test]
+fn findings_are_only_reported_once() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path(), ADVISORY, "1.0.0");
+
+    let (code, report) = audit(dir.path(), &lockfile);
+    assert_eq!(code, 1);
+    assert_eq!(report["vulnerabilities"]["count"], 1);
+
+    let state = fs::read_to_string(dir.path().join("state.json")).unwrap();
+    assert!(state.contains("RUSTSEC-2001-2101:base@1.0.0"), "{}", state);
+
+    let (code, report) = audit(dir.path(), &lockfile);
+    assert_eq!(code, 0);
+    assert_eq!(report["vulnerabilities"]["found"], false);
+    assert_eq!(report["vulnerabilities"]["count"], 0);
+
+    // New advisories are reported even though the package was seen before
+    let advisory_dir = dir.path().join("db").join("crates").join("base");
+    fs::write(
+        advisory_dir.join("RUSTSEC-2001-2102.md"),
+        ADVISORY.replace("RUSTSEC-2001-2101", "RUSTSEC-2001-2102"),
+    )
+    .unwrap();
+
+    let (code, report) = audit(dir.path(), &lockfile);
+    assert_eq!(code, 1);
+    let list = report["vulnerabilities"]["list"].as_array().unwrap();
+    assert_eq!(list.len(), 1);
+    assert_eq!(list[0]["advisory"]["id"], "RUSTSEC-2001-2102");
+}
+
+#[test]
+fn invalid_state_file_is_an_error() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path(), ADVISORY, "1.0.0");
+    fs::write(dir.path().join("state.json"), "not json").unwrap();
+
+    let mut runner = CmdRunner::default();
+    runner
+        .args(["audit", "--no-fetch", "--db"])
+        .arg(dir.path().join("db"))
+        .arg("--file")
+        .arg(&lockfile)
+        .arg("--state-file")
+        .arg(dir.path().join("state.json"));
+    assert_eq!(runner.status().code(), 1);
+}
+
+/// Presenter which fails to display reports
+struct Failing;
+
+impl Presenter for Failing {
+    fn warning(&mut self, _message: &str) {}
+
+    fn print_report(
+        &mut self,
+        _report: &Report,
+        _lockfile: &Lockfile,
+        _path: Option<&Path>,
+    ) -> Result<(), Error> {
+        Err(ErrorKind::Io
+            .context(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe"))
+            .into())
+    }
+}
+
+#[test]
+fn findings_are_only_recorded_once_reported() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path(), ADVISORY, "1.0.0");
+    let state_path = dir.path().join("state.json");
+
+    let mut config = library_config(dir.path());
+    config.output.state_file = Some(state_path.clone());
+
+    let mut auditor = Auditor::with_presenter(&config, Failing).unwrap();
+    assert!(auditor.audit_lockfile(&lockfile).is_err());
+    assert!(!state_path.exists());
+
+    let (code, report) = audit(dir.path(), &lockfile);
+    assert_eq!(code, 1);
+    assert_eq!(report["vulnerabilities"]["count"], 1);
+}
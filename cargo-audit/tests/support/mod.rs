@@ -0,0 +1,101 @@
+//! Helpers shared by the integration tests, which each use only some of them
+
+#![allow(dead_code)]
+
+use abscissa_core::testing::prelude::*;
+use cargo_audit::{config::AuditConfig, error::Error, presenter::Presenter};
+use rustsec::{Lockfile, Report};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Advisory against `base` versions before 1.2.3
+pub const ADVISORY: &str = r#"```toml
+[advisory]
+id = "RUSTSEC-2001-2101"
+package = "base"
+date = "2001-02-03"
+
+[versions]
+patched = [">= 1.2.3"]
+```
+
+# All your base are belong to us
+
+You have no chance to survive. Make your time.
+"#;
+
+/// Create an advisory database in `dir/db` containing the given advisory
+/// against `base`
+pub fn write_advisory_db(dir: &Path, advisory: &str) {
+    let advisory_dir = dir.join("db").join("crates").join("base");
+    fs::create_dir_all(&advisory_dir).unwrap();
+    fs::write(advisory_dir.join("RUSTSEC-2001-2101.md"), advisory).unwrap();
+}
+
+/// Lockfile depending on the given version of `base` from crates.io
+pub fn base_lockfile(base_version: &str) -> String {
+    format!(
+        "version = 3\n\n[[package]]\nname = \"base\"\nversion = \"{}\"\n\
+        source = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+        base_version
+    )
+}
+
+/// Create an advisory database containing the given advisory and a project
+/// depending on the given version of `base`, returning the path to the
+/// project's `Cargo.lock`
+pub fn setup(dir: &Path, advisory: &str, base_version: &str) -> PathBuf {
+    write_advisory_db(dir, advisory);
+
+    let lockfile = dir.join("Cargo.lock");
+    fs::write(&lockfile, base_lockfile(base_version)).unwrap();
+    lockfile
+}
+
+/// Get a `CmdRunner` auditing against the database in `dir/db`, without
+/// fetching it
+pub fn db_cmd_runner(dir: &Path) -> CmdRunner {
+    let mut runner = CmdRunner::default();
+    runner
+        .args(["audit", "--no-fetch", "--color", "never", "--db"])
+        .arg(dir.join("db"));
+    runner
+}
+
+/// Get a `CmdRunner` auditing the given lockfile against the database in
+/// `dir/db`, without fetching it
+pub fn cmd_runner(dir: &Path, lockfile: &Path) -> CmdRunner {
+    let mut runner = db_cmd_runner(dir);
+    runner.arg("--file").arg(lockfile);
+    runner
+}
+
+/// Configuration auditing against the database in `dir/db` without fetching
+/// it or opening the crates.io index
+pub fn library_config(dir: &Path) -> AuditConfig {
+    let mut config = AuditConfig::default();
+    config.database.path = Some(dir.join("db"));
+    config.database.fetch = false;
+    config.yanked.enabled = false;
+    config.yanked.update_index = false;
+    config.checksums.enabled = false;
+    config
+}
+
+/// Presenter which doesn't display anything
+pub struct Silent;
+
+impl Presenter for Silent {
+    fn warning(&mut self, _message: &str) {}
+
+    fn print_report(
+        &mut self,
+        _report: &Report,
+        _lockfile: &Lockfile,
+        _path: Option<&Path>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+}
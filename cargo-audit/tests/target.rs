@@ -0,0 +1,84 @@
+//! Target platform filtering tests
+
+use std::{fs, io::Read, path::Path};
+use tempfile::TempDir;
+
+mod support;
+use support::{cmd_runner, setup};
+
+/// Advisory against `base` versions before 1.2.3 which only affects x86_64
+const ADVISORY: &str = r#"```toml
+[advisory]
+id = "RUSTSEC-2001-2101"
+package = "base"
+date = "2001-02-03"
+
+[affected]
+arch = ["x86_64"]
+
+[versions]
+patched = [">= 1.2.3"]
+```
+
+# All your base are belong to us
+
+You have no chance to survive. Make your time.
+"#;
+
+/// Abridged custom target specification for a Cortex-M4 board
+const TARGET_SPEC: &str = r#"{
+    "arch": "arm",
+    "data-layout": "e-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64",
+    "llvm-target": "thumbv7em-none-eabihf",
+    "panic-strategy": "abort",
+    "target-pointer-width": "32"
+}"#;
+
+/// Audit the given lockfile against the temporary database for the given
+/// target, returning the exit code
+fn audit_for_target(dir: &Path, lockfile: &Path, target: &Path) -> i32 {
+    cmd_runner(dir, lockfile)
+        .arg("--target")
+        .arg(target)
+        .status()
+        .code()
+}
+
+#[test]
+fn target_triple() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path(), ADVISORY, "1.0.0");
+
+    let code = audit_for_target(dir.path(), &lockfile, Path::new("x86_64-unknown-linux-gnu"));
+    assert_eq!(code, 1);
+
+    let code = audit_for_target(dir.path(), &lockfile, Path::new("thumbv7em-none-eabihf"));
+    assert_eq!(code, 0);
+}
+
+#[test]
+fn custom_target_spec() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path(), ADVISORY, "1.0.0");
+    let target = dir.path().join("cortex-m4.json");
+    fs::write(&target, TARGET_SPEC).unwrap();
+
+    assert_eq!(audit_for_target(dir.path(), &lockfile, &target), 0);
+}
+
+#[test]
+fn unknown_target() {
+    let dir = TempDir::new().unwrap();
+    let lockfile = setup(dir.path(), ADVISORY, "1.0.0");
+
+    let mut runner = cmd_runner(dir.path(), &lockfile);
+    runner
+        .args(["--target", "not-a-real-target"])
+        .capture_stderr();
+
+    let mut process = runner.run();
+    let mut stderr = String::new();
+    process.stderr().read_to_string(&mut stderr).unwrap();
+    process.wait().unwrap().expect_code(1);
+    assert!(stderr.contains("unknown target triple: not-a-real-target"));
+}
@@ -0,0 +1,115 @@
+//! Tests for detecting C libraries statically linked into binaries
+
+#![cfg(feature = "binary-scanning")]
+
+use cargo_audit::{
+    auditor::Auditor, config::AuditConfig, error::Error, presenter::Presenter, vendored_libs,
+};
+use rustsec::{Lockfile, Version};
+use std::{
+    cell::RefCell,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use tempfile::TempDir;
+
+/// Version strings of OpenSSL and libgit2, as embedded in binaries
+const BINARY: &[u8] = b"\0OpenSSL library\0OpenSSL 1.1.1k  25 Mar 2021\0\
+    \0git/2.0 (libgit2 1.5.1)\0";
+
+/// Presenter recording the warnings it was asked to display
+#[derive(Clone, Default)]
+struct Warnings(Rc<RefCell<Vec<String>>>);
+
+impl Presenter for Warnings {
+    fn warning(&mut self, message: &str) {
+        self.0.borrow_mut().push(message.to_owned());
+    }
+
+    fn print_report(
+        &mut self,
+        _report: &rustsec::Report,
+        _lockfile: &Lockfile,
+        _path: Option<&Path>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn detect_library_versions() {
+    let libraries = vendored_libs::detect(BINARY);
+    assert_eq!(libraries.len(), 2);
+
+    assert_eq!(libraries[0].name, "OpenSSL");
+    assert_eq!(libraries[0].version, "1.1.1k");
+    assert_eq!(libraries[0].source_crate, "openssl-src");
+
+    assert_eq!(libraries[1].name, "libgit2");
+    assert_eq!(libraries[1].version, "1.5.1");
+    assert_eq!(libraries[1].source_crate, "libgit2-sys");
+
+    assert!(vendored_libs::detect(b"OpenSSL 1 and OpenSSL library").is_empty());
+}
+
+#[test]
+fn oldest_vendoring_version_is_assumed() {
+    let library = &vendored_libs::detect(BINARY)[0];
+    let published: Vec<Version> = [
+        "111.14.0+1.1.1j",
+        "111.15.1+1.1.1k",
+        "111.15.0+1.1.1k",
+        "111.16.0+1.1.1l",
+        "300.0.0+3.0.0",
+    ]
+    .iter()
+    .map(|version| version.parse().unwrap())
+    .collect();
+
+    let package = library.source_package(&published).unwrap();
+    assert_eq!(package.name.as_str(), "openssl-src");
+    assert_eq!(package.version.to_string(), "111.15.0+1.1.1k");
+    assert!(library.source_package(&published[..1]).is_none());
+}
+
+#[test]
+fn vendored_libraries_need_registry_index() {
+    let dir = TempDir::new().unwrap();
+    fs::create_dir_all(dir.path().join("db").join("crates")).unwrap();
+    // Append the version strings to a binary built with `cargo auditable`
+    let mut binary = fs::read(
+        [
+            env!("CARGO_MANIFEST_DIR"),
+            "tests",
+            "support",
+            "binaries",
+            "binary-with-audit-info",
+        ]
+        .iter()
+        .collect::<PathBuf>(),
+    )
+    .unwrap();
+    binary.extend_from_slice(BINARY);
+    let binary_path = dir.path().join("program");
+    fs::write(&binary_path, binary).unwrap();
+
+    let mut config = AuditConfig::default();
+    config.database.path = Some(dir.path().join("db"));
+    config.database.fetch = false;
+    config.yanked.enabled = false;
+    config.checksums.enabled = false;
+
+    let warnings = Warnings::default();
+    let mut auditor = Auditor::with_presenter(&config, warnings.clone()).unwrap();
+    let summary = auditor.audit_binaries(&[&binary_path]);
+
+    assert!(!summary.errors_encountered);
+    let warnings = warnings.0.borrow();
+    assert_eq!(warnings.len(), 2);
+    assert!(
+        warnings[0].starts_with("OpenSSL 1.1.1k is statically linked in"),
+        "{}",
+        warnings[0]
+    );
+}
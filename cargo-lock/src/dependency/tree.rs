@@ -9,7 +9,8 @@ use super::{
     graph::{EdgeDirection, Graph, NodeIndex, Nodes},
     Dependency,
 };
-use crate::{error::Error, lockfile::Lockfile, Map};
+use crate::{error::Error, lockfile::Lockfile, package::Package, Map};
+use petgraph::visit::{Bfs, Reversed};
 use std::{collections::BTreeSet as Set, io};
 
 /// Dependency tree computed from a `Cargo.lock` file
@@ -23,6 +24,9 @@ pub struct Tree {
 }
 
 impl Tree {
+    /// Maximum number of paths returned by [`Tree::paths_to_roots`]
+    pub const MAX_PATHS_TO_ROOTS: usize = 100;
+
     /// Construct a new dependency tree for the given [`Lockfile`].
     pub fn new(lockfile: &Lockfile) -> Result<Self, Error> {
         let mut graph = Graph::new();
@@ -83,6 +87,112 @@ impl Tree {
         self.graph.externals(EdgeDirection::Incoming).collect()
     }
 
+    /// Get the packages which directly depend on the given package
+    pub fn dependents(&self, package: &Package) -> Vec<&Package> {
+        let mut dependents: Vec<_> = self
+            .node_index(package)
+            .into_iter()
+            .flat_map(|index| {
+                self.graph
+                    .neighbors_directed(index, EdgeDirection::Incoming)
+            })
+            .map(|index| &self.graph[index])
+            .collect();
+
+        dependents.sort();
+        dependents.dedup();
+        dependents
+    }
+
+    /// Get the packages which depend on the given package, either directly
+    /// or transitively
+    pub fn all_dependents(&self, package: &Package) -> Vec<&Package> {
+        let mut dependents = vec![];
+
+        if let Some(index) = self.node_index(package) {
+            let mut bfs = Bfs::new(Reversed(&self.graph), index);
+            while let Some(dependent) = bfs.next(Reversed(&self.graph)) {
+                if dependent != index {
+                    dependents.push(&self.graph[dependent]);
+                }
+            }
+        }
+
+        dependents.sort();
+        dependents
+    }
+
+    /// Get the paths through the dependency graph from the given package to
+    /// a root package, explaining why the package is in the dependency tree.
+    ///
+    /// Each path starts with the given package and ends with a root, so the
+    /// second to last package is the direct dependency of the root which
+    /// pulls the package in. A root package has a single path containing
+    /// only itself, and packages which aren't in the tree have none.
+    ///
+    /// The number of paths can grow exponentially with the size of the
+    /// graph, so at most [`Tree::MAX_PATHS_TO_ROOTS`] of them are returned
+    /// (see [`Tree::paths_to_roots_with_limit`]).
+    pub fn paths_to_roots(&self, package: &Package) -> Vec<Vec<&Package>> {
+        self.paths_to_roots_with_limit(package, Self::MAX_PATHS_TO_ROOTS)
+    }
+
+    /// Get at most `limit` paths through the dependency graph from the given
+    /// package to a root package (see [`Tree::paths_to_roots`]).
+    ///
+    /// The search stops once `limit` paths have been found, so which paths
+    /// are returned when there are more is unspecified, but the work done is
+    /// bounded by `limit` times the depth of the graph.
+    pub fn paths_to_roots_with_limit(&self, package: &Package, limit: usize) -> Vec<Vec<&Package>> {
+        let mut paths = vec![];
+
+        if let Some(index) = self.node_index(package) {
+            self.find_paths_to_roots(index, &mut vec![], &mut paths, limit);
+        }
+
+        paths.sort();
+        paths
+    }
+
+    /// Extend `path` with `index` and the packages depending on it, adding
+    /// each complete path to a root to `paths` until there are `limit` of them
+    fn find_paths_to_roots<'a>(
+        &'a self,
+        index: NodeIndex,
+        path: &mut Vec<NodeIndex>,
+        paths: &mut Vec<Vec<&'a Package>>,
+        limit: usize,
+    ) {
+        if paths.len() >= limit {
+            return;
+        }
+
+        path.push(index);
+
+        let mut dependents = self
+            .graph
+            .neighbors_directed(index, EdgeDirection::Incoming)
+            .peekable();
+
+        if dependents.peek().is_none() {
+            paths.push(path.iter().map(|&index| &self.graph[index]).collect());
+        }
+
+        for dependent in dependents {
+            // Guard against cycles in malformed lockfiles
+            if !path.contains(&dependent) {
+                self.find_paths_to_roots(dependent, path, paths, limit);
+            }
+        }
+
+        path.pop();
+    }
+
+    /// Get the index of the given package's node in the dependency graph
+    fn node_index(&self, package: &Package) -> Option<NodeIndex> {
+        self.nodes.get(&Dependency::from(package)).copied()
+    }
+
     /// Get the `petgraph` dependency graph.
     pub fn graph(&self) -> &Graph {
         &self.graph
@@ -226,4 +336,65 @@ mod tests {
         let root_package = &tree.graph[roots[0]];
         assert_eq!(root_package.name.as_str(), "cargo-lock");
     }
+
+    /// Find the package with the given name in the tree
+    fn package<'a>(tree: &'a Tree, name: &str) -> &'a Package {
+        tree.graph
+            .node_weights()
+            .find(|package| package.name.as_str() == name)
+            .unwrap()
+    }
+
+    /// Get the names of the given packages
+    fn names(packages: &[&Package]) -> Vec<String> {
+        packages
+            .iter()
+            .map(|package| package.name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn compute_dependents() {
+        let tree = Tree::new(&load_lockfile()).unwrap();
+        let serde = package(&tree, "serde");
+
+        assert_eq!(
+            names(&tree.dependents(serde)),
+            ["cargo-lock", "semver", "toml"]
+        );
+        assert_eq!(
+            names(&tree.all_dependents(package(&tree, "serde_derive"))),
+            ["cargo-lock", "semver", "serde", "toml"]
+        );
+        assert!(tree.dependents(package(&tree, "cargo-lock")).is_empty());
+    }
+
+    #[test]
+    fn compute_paths_to_roots() {
+        let tree = Tree::new(&load_lockfile()).unwrap();
+        let paths = tree.paths_to_roots(package(&tree, "serde"));
+        let paths: Vec<_> = paths.iter().map(|path| names(path)).collect();
+
+        assert_eq!(
+            paths,
+            [
+                vec!["serde", "cargo-lock"],
+                vec!["serde", "semver", "cargo-lock"],
+                vec!["serde", "toml", "cargo-lock"]
+            ]
+        );
+
+        let root = package(&tree, "cargo-lock");
+        assert_eq!(tree.paths_to_roots(root), [vec![root]]);
+    }
+
+    #[test]
+    fn limit_paths_to_roots() {
+        let tree = Tree::new(&load_lockfile()).unwrap();
+        let serde = package(&tree, "serde");
+
+        assert_eq!(tree.paths_to_roots_with_limit(serde, 2).len(), 2);
+        assert!(tree.paths_to_roots_with_limit(serde, 0).is_empty());
+        assert_eq!(tree.paths_to_roots_with_limit(serde, 10).len(), 3);
+    }
 }
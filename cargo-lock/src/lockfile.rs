@@ -42,6 +42,29 @@ impl Lockfile {
         fs::read_to_string(path.as_ref())?.parse()
     }
 
+    /// Load lock data from a `Cargo.lock` file, tolerating format versions
+    /// and fields unknown to this crate.
+    ///
+    /// See [`Lockfile::from_str_lenient`] for details.
+    pub fn load_lenient(path: impl AsRef<Path>) -> Result<(Self, Vec<String>)> {
+        Self::from_str_lenient(&fs::read_to_string(path.as_ref())?)
+    }
+
+    /// Parse lock data, tolerating format versions and fields unknown to this
+    /// crate instead of failing, so lockfiles written by newer versions of
+    /// Cargo can still be inspected.
+    ///
+    /// Lockfiles with a newer format version are parsed as the latest
+    /// [`ResolveVersion`] supported by this crate, and unknown fields are
+    /// ignored. Returns the lockfile along with a warning describing each of
+    /// these.
+    pub fn from_str_lenient(toml_string: &str) -> Result<(Self, Vec<String>)> {
+        let mut table: toml::Table = toml::from_str(toml_string)?;
+        let warnings = encoding::relax(&mut table);
+        let lockfile = toml::Value::Table(table).try_into()?;
+        Ok((lockfile, warnings))
+    }
+
     /// Get the dependency tree for this `Lockfile`. Returns an error if the
     /// contents of this lockfile aren't well structured.
     ///
@@ -54,6 +54,74 @@ pub(super) struct EncodableLockfile {
     pub(super) patch: Patch,
 }
 
+/// Fields of [`EncodableLockfile`]
+const LOCKFILE_FIELDS: &[&str] = &["version", "package", "root", "metadata", "patch"];
+
+/// Fields of [`EncodablePackage`]
+const PACKAGE_FIELDS: &[&str] = &[
+    "name",
+    "version",
+    "source",
+    "checksum",
+    "dependencies",
+    "replace",
+];
+
+/// Prepare a lockfile for lenient parsing by downgrading a format version
+/// newer than [`ResolveVersion::LATEST`] and removing unknown fields from the
+/// lockfile and its packages, returning warnings describing the changes made
+pub(super) fn relax(table: &mut toml::Table) -> Vec<String> {
+    let mut warnings = vec![];
+    let latest = i64::from(u32::from(ResolveVersion::LATEST));
+
+    if let Some(version) = table.get_mut("version") {
+        if let Some(n) = version.as_integer().filter(|&n| n > latest) {
+            warnings.push(format!(
+                "unsupported Cargo.lock format version `{}`; parsing as version {}",
+                n, latest
+            ));
+            *version = toml::Value::Integer(latest);
+        }
+    }
+
+    remove_unknown_fields(table, LOCKFILE_FIELDS, "Cargo.lock", &mut warnings);
+
+    let mut packages = vec![];
+    for (key, value) in table.iter_mut() {
+        match key.as_str() {
+            "package" => packages.extend(value.as_array_mut().into_iter().flatten()),
+            "root" => packages.push(value),
+            _ => (),
+        }
+    }
+
+    for package in packages.into_iter().filter_map(toml::Value::as_table_mut) {
+        let location = match package.get("name").and_then(toml::Value::as_str) {
+            Some(name) => format!("package `{}`", name),
+            None => "package".to_owned(),
+        };
+        remove_unknown_fields(package, PACKAGE_FIELDS, &location, &mut warnings);
+    }
+
+    warnings
+}
+
+/// Remove the fields of `table` which aren't in `known`, warning about each
+fn remove_unknown_fields(
+    table: &mut toml::Table,
+    known: &[&str],
+    location: &str,
+    warnings: &mut Vec<String>,
+) {
+    table.retain(|key, _| {
+        let is_known = known.contains(&key);
+        if !is_known {
+            warnings.push(format!("ignoring unknown field `{}` in {}", key, location));
+        }
+        is_known
+    });
+}
+
 impl EncodableLockfile {
     /// Attempt to find a checksum for a package in a V1 lockfile
     pub fn find_checksum(&self, package: &Package) -> Option<Checksum> {
@@ -157,6 +225,11 @@ impl From<&Lockfile> for EncodableLockfile {
                 }
             }
 
+            // Since V4, Git references in source URLs are percent-encoded
+            if lockfile.version >= ResolveVersion::V4 {
+                raw_pkg.url_encode_sources();
+            }
+
             packages.push(raw_pkg);
         }
 
@@ -320,6 +393,19 @@ impl EncodablePackage {
             dependency.v2(packages);
         }
     }
+
+    /// Percent-encode the Git references in the sources of this package and
+    /// its dependencies, as `ResolveVersion::V4` lockfiles do
+    fn url_encode_sources(&mut self) {
+        let dependencies = self.dependencies.iter_mut().chain(self.replace.as_mut());
+        let sources = dependencies
+            .filter_map(|dependency| dependency.source.as_mut())
+            .chain(self.source.as_mut());
+
+        for source in sources {
+            *source = source.with_url_encoded_reference();
+        }
+    }
 }
 
 /// Note: this only works for `ResolveVersion::V1` dependencies.
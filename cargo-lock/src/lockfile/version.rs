@@ -30,9 +30,19 @@ pub enum ResolveVersion {
     /// For more information, see:
     /// <https://internals.rust-lang.org/t/upcoming-changes-to-cargo-lock/14017>
     V3 = 3,
+
+    /// Percent-encodes the Git references in the URLs of Git sources (e.g.
+    /// `?branch=feature%2Fx`).
+    ///
+    /// For more information, see:
+    /// <https://github.com/rust-lang/cargo/pull/12852>
+    V4 = 4,
 }
 
 impl ResolveVersion {
+    /// Latest lockfile version supported by this crate
+    pub(super) const LATEST: Self = ResolveVersion::V4;
+
     /// Autodetect the version of a lockfile from the packages
     pub(super) fn detect(packages: &[EncodablePackage], metadata: &Metadata) -> Result<Self> {
         // V1: look for [[metadata]] keys beginning with checksum
@@ -90,6 +100,7 @@ impl TryFrom<u32> for ResolveVersion {
             1 => Ok(ResolveVersion::V1),
             2 => Ok(ResolveVersion::V2),
             3 => Ok(ResolveVersion::V3),
+            4 => Ok(ResolveVersion::V4),
             _ => Err(Error::Parse(format!(
                 "invalid Cargo.lock format version: `{}`",
                 num
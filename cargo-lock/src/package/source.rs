@@ -10,7 +10,7 @@
 use crate::error::{Error, Result};
 use serde::{de, ser, Deserialize, Serialize};
 use std::{fmt, str::FromStr};
-use url::Url;
+use url::{form_urlencoded, Url};
 
 #[cfg(any(unix, windows))]
 use std::path::Path;
@@ -184,6 +184,34 @@ impl SourceId {
         &self.url
     }
 
+    /// Get a copy of this source whose Git reference (if any) is
+    /// percent-encoded, to be written to a `ResolveVersion::V4` lockfile.
+    ///
+    /// References are percent-decoded when parsing source URLs, so this is
+    /// only used for encoding lockfiles.
+    pub(crate) fn with_url_encoded_reference(&self) -> Self {
+        let encode =
+            |reference: &str| form_urlencoded::byte_serialize(reference.as_bytes()).collect();
+
+        let kind = match &self.kind {
+            SourceKind::Git(GitReference::Branch(branch)) if branch != DEFAULT_BRANCH => {
+                SourceKind::Git(GitReference::Branch(encode(branch)))
+            }
+            SourceKind::Git(GitReference::Tag(tag)) => {
+                SourceKind::Git(GitReference::Tag(encode(tag)))
+            }
+            SourceKind::Git(GitReference::Rev(rev)) => {
+                SourceKind::Git(GitReference::Rev(encode(rev)))
+            }
+            kind => kind.clone(),
+        };
+
+        Self {
+            kind,
+            ..self.clone()
+        }
+    }
+
     /// Get the kind of source.
     pub fn kind(&self) -> &SourceKind {
         &self.kind
@@ -4,7 +4,10 @@ use std::str::FromStr;
 
 // TODO(tarcieri): add more example `Cargo.lock` files which cover more scenarios
 
-use cargo_lock::{Lockfile, MetadataKey, ResolveVersion, Version};
+use cargo_lock::{
+    package::{GitReference, SourceKind},
+    Lockfile, MetadataKey, ResolveVersion, Version,
+};
 
 /// Path to a V1 `Cargo.lock` file.
 const V1_LOCKFILE_PATH: &str = "tests/examples/Cargo.lock.v1";
@@ -58,6 +61,73 @@ fn load_example_v3_lockfile() {
     assert_eq!(lockfile.metadata.len(), 0);
 }
 
+/// Leniently load a lockfile with a future format version and unknown fields
+#[test]
+fn load_lenient_future_lockfile() {
+    let lockfile_str = r#"version = 99
+future-field = true
+
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "d468802bab17cbc0cc575e9b053f41e72aa36bfa6b7f55e3529ffa43161b97fa"
+provenance = "attested"
+"#;
+    assert!(Lockfile::from_str(lockfile_str).is_err());
+
+    let (lockfile, warnings) = Lockfile::from_str_lenient(lockfile_str).unwrap();
+    assert_eq!(lockfile.version, ResolveVersion::V4);
+    assert_eq!(lockfile.packages.len(), 1);
+    assert_eq!(
+        warnings,
+        [
+            "unsupported Cargo.lock format version `99`; parsing as version 4",
+            "ignoring unknown field `future-field` in Cargo.lock",
+            "ignoring unknown field `provenance` in package `base`",
+        ]
+    );
+
+    let (lockfile, warnings) = Lockfile::load_lenient(V3_LOCKFILE_PATH).unwrap();
+    assert_eq!(lockfile, Lockfile::load(V3_LOCKFILE_PATH).unwrap());
+    assert!(warnings.is_empty());
+}
+
+/// Load a V4 lockfile, whose Git references are percent-encoded, and ensure
+/// it's encoded the same way again
+#[test]
+fn load_and_serialize_v4() {
+    let lockfile_str = r#"version = 4
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["base"]
+
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "git+https://github.com/example/base?branch=feature%2Fx#8f6d2a1"
+"#;
+    let lockfile = Lockfile::from_str(lockfile_str).unwrap();
+    assert_eq!(lockfile.version, ResolveVersion::V4);
+
+    let source = lockfile.packages[1].source.as_ref().unwrap();
+    assert_eq!(
+        source.kind(),
+        &SourceKind::Git(GitReference::Branch("feature/x".to_owned()))
+    );
+
+    let reserialized = lockfile.to_string();
+    assert!(reserialized.contains("version = 4\n"), "{}", reserialized);
+    assert!(
+        reserialized.contains("?branch=feature%2Fx#8f6d2a1"),
+        "{}",
+        reserialized
+    );
+    assert_eq!(reserialized.parse::<Lockfile>().unwrap(), lockfile);
+}
+
 /// Ensure V3 lockfiles encode their version correctly.
 #[test]
 fn serialize_v3() {
@@ -12,27 +12,38 @@
 //! `CVSS:3.0` and `CVSS:3.1` Base Metric Group vector strings as described in
 //! the [CVSS v3.1 Specification].
 //!
+//! Older vulnerabilities often only carry CVSS v2 vectors, which can be parsed
+//! and scored with the [`v2::Base`] type as described in the
+//! [CVSS v2 Complete Documentation]. Both produce the same [`Score`] type.
+//!
 //! Serde support is available through the optional `serde` Cargo feature.
+//! Vectors are serialized as strings, or as structured objects containing the
+//! parsed metrics and computed score with [`v3::structured`].
 //!
 //! [CVSS v3.1 Specification]: https://www.first.org/cvss/specification-document
+//! [CVSS v2 Complete Documentation]: https://www.first.org/cvss/v2/guide
 
-// TODO(tarcieri): other CVSS versions, CVSS v3.1 Temporal and Environmental Groups
+// TODO(tarcieri): CVSS v3.1 Temporal and Environmental Groups
 
 extern crate alloc;
 
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "v2")]
+pub mod v2;
 #[cfg(feature = "v3")]
 pub mod v3;
 
 mod error;
 mod metric;
+mod score;
 mod severity;
 
 pub use crate::{
     error::{Error, Result},
     metric::{Metric, MetricType},
+    score::Score,
     severity::Severity,
 };
 
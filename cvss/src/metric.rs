@@ -17,7 +17,7 @@ pub trait Metric: Copy + Clone + Debug + Display + Eq + FromStr + Ord {
         Self::TYPE.name()
     }
 
-    /// Get CVSS v3.1 score for this metric.
+    /// Get the score for this metric used by the base score equations.
     fn score(self) -> f64;
 
     /// Get `str` describing this metric's value
@@ -31,12 +31,15 @@ pub enum MetricType {
     /// Availability Impact (A)
     A,
 
-    /// Attack Complexity (AC)
+    /// Attack Complexity (AC), or Access Complexity in CVSS v2
     AC,
 
-    /// Attack Vector (AV)
+    /// Attack Vector (AV), or Access Vector in CVSS v2
     AV,
 
+    /// Authentication (Au), CVSS v2 only
+    Au,
+
     /// Confidentiality Impact (C)
     C,
 
@@ -60,6 +63,7 @@ impl MetricType {
             Self::A => "A",
             Self::AC => "AC",
             Self::AV => "AV",
+            Self::Au => "Au",
             Self::C => "C",
             Self::I => "I",
             Self::PR => "PR",
@@ -74,6 +78,7 @@ impl MetricType {
             Self::A => "Availability Impact",
             Self::AC => "Attack Complexity",
             Self::AV => "Attack Vector",
+            Self::Au => "Authentication",
             Self::C => "Confidentiality Impact",
             Self::I => "Integrity Impact",
             Self::PR => "Privileges Required",
@@ -97,6 +102,7 @@ impl FromStr for MetricType {
             "A" => Ok(Self::A),
             "AC" => Ok(Self::AC),
             "AV" => Ok(Self::AV),
+            "Au" => Ok(Self::Au),
             "C" => Ok(Self::C),
             "I" => Ok(Self::I),
             "PR" => Ok(Self::PR),
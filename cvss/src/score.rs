@@ -1,11 +1,11 @@
-//! CVSS v3.1 scores
+//! CVSS scores
 
 use crate::severity::Severity;
 
-/// CVSS V3.1 scores.
+/// CVSS scores, ranging from 0.0 to 10.0.
 ///
-/// Formula described in CVSS v3.1 Specification: Section 5:
-/// <https://www.first.org/cvss/specification-document#t20>
+/// Shared by all supported CVSS versions, so scores computed from vectors of
+/// different versions can be compared with each other.
 #[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
 pub struct Score(f64);
 
@@ -36,7 +36,11 @@ impl Score {
         }
     }
 
-    /// Convert the numeric score into a `Severity`
+    /// Convert the numeric score into a `Severity` according to the CVSS v3.1
+    /// Qualitative Severity Rating Scale.
+    ///
+    /// Described in CVSS v3.1 Specification: Section 5:
+    /// <https://www.first.org/cvss/specification-document#t17>
     pub fn severity(self) -> Severity {
         if self.0 < 0.1 {
             Severity::None
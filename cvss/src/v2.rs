@@ -0,0 +1,10 @@
+//! Common Vulnerability Scoring System (v2)
+//!
+//! <https://www.first.org/cvss/v2/guide>
+
+// TODO: Environmental and Temporal Metrics
+
+pub mod base;
+
+pub use self::base::Base;
+pub use crate::Score;
@@ -0,0 +1,191 @@
+//! CVSS v2 Base Metric Group
+
+mod a;
+mod ac;
+mod au;
+mod av;
+mod c;
+mod i;
+
+pub use self::{
+    a::Availability, ac::AccessComplexity, au::Authentication, av::AccessVector,
+    c::Confidentiality, i::Integrity,
+};
+
+use super::Score;
+use crate::{Error, Metric, MetricType, Result};
+use alloc::borrow::ToOwned;
+use core::{fmt, str::FromStr};
+
+#[cfg(feature = "serde")]
+use {
+    alloc::string::{String, ToString},
+    serde::{de, ser, Deserialize, Serialize},
+};
+
+#[cfg(feature = "std")]
+use crate::Severity;
+
+/// Prefix used by the NVD for CVSS v2 vectors in some data feeds
+const NVD_PREFIX: &str = "CVSS2#";
+
+/// CVSS v2 Base Metric Group
+///
+/// Described in CVSS v2 Complete Documentation: Section 2.1:
+/// <https://www.first.org/cvss/v2/guide#2-1-Base-Metrics>
+///
+/// Unlike CVSS v3 vectors, CVSS v2 vectors have no version prefix, e.g.
+/// `AV:N/AC:L/Au:N/C:P/I:P/A:P`. When parsing, the `CVSS2#` prefix and
+/// surrounding parentheses used by some data feeds are accepted as well.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Base {
+    /// Access Vector (AV)
+    pub av: Option<AccessVector>,
+
+    /// Access Complexity (AC)
+    pub ac: Option<AccessComplexity>,
+
+    /// Authentication (Au)
+    pub au: Option<Authentication>,
+
+    /// Confidentiality Impact (C)
+    pub c: Option<Confidentiality>,
+
+    /// Integrity Impact (I)
+    pub i: Option<Integrity>,
+
+    /// Availability Impact (A)
+    pub a: Option<Availability>,
+}
+
+impl Base {
+    /// Calculate Base CVSS score: overall value for determining the severity
+    /// of a vulnerability, ranging from 0.0 to 10.0.
+    ///
+    /// Described in CVSS v2 Complete Documentation: Section 3.2.1:
+    /// <https://www.first.org/cvss/v2/guide#3-2-1-Base-Equation>
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn score(&self) -> Score {
+        let impact = self.impact().value();
+        let exploitability = self.exploitability().value();
+        let f_impact = if impact == 0.0 { 0.0 } else { 1.176 };
+
+        let score = ((0.6 * impact) + (0.4 * exploitability) - 1.5) * f_impact;
+        Score::new((score * 10.0).round() / 10.0)
+    }
+
+    /// Calculate Base Exploitability sub-score, ranging from 0.0 to 10.0.
+    pub fn exploitability(&self) -> Score {
+        let av_score = self.av.map(|av| av.score()).unwrap_or(0.0);
+        let ac_score = self.ac.map(|ac| ac.score()).unwrap_or(0.0);
+        let au_score = self.au.map(|au| au.score()).unwrap_or(0.0);
+
+        (20.0 * av_score * ac_score * au_score).into()
+    }
+
+    /// Calculate Base Impact sub-score, ranging from 0.0 to 10.0.
+    pub fn impact(&self) -> Score {
+        let c_score = self.c.map(|c| c.score()).unwrap_or(0.0);
+        let i_score = self.i.map(|i| i.score()).unwrap_or(0.0);
+        let a_score = self.a.map(|a| a.score()).unwrap_or(0.0);
+
+        (10.41 * (1.0 - (1.0 - c_score) * (1.0 - i_score) * (1.0 - a_score))).into()
+    }
+
+    /// Calculate Base CVSS `Severity`.
+    ///
+    /// CVSS v2 doesn't define a qualitative severity rating scale, so this
+    /// uses the one from CVSS v3.1 for consistency with CVSS v3 scores.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn severity(&self) -> Severity {
+        self.score().severity()
+    }
+}
+
+macro_rules! write_metrics {
+    ($f:expr, $first:expr, $($metric:expr),+) => {
+        if let Some(metric) = $first {
+            write!($f, "{}", metric)?;
+        }
+        $(
+            if let Some(metric) = $metric {
+                write!($f, "/{}", metric)?;
+            }
+        )+
+    };
+}
+
+impl fmt::Display for Base {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_metrics!(f, self.av, self.ac, self.au, self.c, self.i, self.a);
+        Ok(())
+    }
+}
+
+impl FromStr for Base {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let vector = s
+            .strip_prefix('(')
+            .and_then(|vector| vector.strip_suffix(')'))
+            .unwrap_or(s);
+        let vector = vector.strip_prefix(NVD_PREFIX).unwrap_or(vector);
+
+        let mut metrics = Self::default();
+
+        for component in vector.split('/') {
+            let mut parts = component.split(':');
+
+            let (id, value) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(id), Some(value), None) => (id, value),
+                _ => {
+                    return Err(Error::InvalidComponent {
+                        component: component.to_owned(),
+                    })
+                }
+            };
+
+            match id.parse::<MetricType>()? {
+                MetricType::AV => metrics.av = Some(value.parse()?),
+                MetricType::AC => metrics.ac = Some(value.parse()?),
+                MetricType::Au => metrics.au = Some(value.parse()?),
+                MetricType::C => metrics.c = Some(value.parse()?),
+                MetricType::I => metrics.i = Some(value.parse()?),
+                MetricType::A => metrics.a = Some(value.parse()?),
+                MetricType::PR | MetricType::S | MetricType::UI => {
+                    return Err(Error::UnknownMetric {
+                        name: id.to_owned(),
+                    })
+                }
+            }
+        }
+
+        Ok(metrics)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> Deserialize<'de> for Base {
+    fn deserialize<D: de::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl Serialize for Base {
+    fn serialize<S: ser::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
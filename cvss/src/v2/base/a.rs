@@ -0,0 +1,73 @@
+//! CVSS v2 Base Metric Group - Availability Impact (A)
+
+use crate::{Error, Metric, MetricType};
+use alloc::borrow::ToOwned;
+use core::{fmt, str::FromStr};
+
+/// Availability Impact (A) - CVSS v2 Base Metric Group
+///
+/// Described in CVSS v2 Complete Documentation: Section 2.1.6:
+/// <https://www.first.org/cvss/v2/guide#2-1-Base-Metrics>
+///
+/// This metric measures the impact to availability of a successfully
+/// exploited vulnerability.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Availability {
+    /// None (N)
+    ///
+    /// There is no impact to the availability of the system.
+    None,
+
+    /// Partial (P)
+    ///
+    /// There is reduced performance or there are interruptions in resource
+    /// availability, but the system isn't made entirely unavailable.
+    Partial,
+
+    /// Complete (C)
+    ///
+    /// There is a total compromise of the availability of the system.
+    Complete,
+}
+
+impl Metric for Availability {
+    const TYPE: MetricType = MetricType::A;
+
+    fn score(self) -> f64 {
+        match self {
+            Availability::None => 0.0,
+            Availability::Partial => 0.275,
+            Availability::Complete => 0.66,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Availability::None => "N",
+            Availability::Partial => "P",
+            Availability::Complete => "C",
+        }
+    }
+}
+
+impl fmt::Display for Availability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", Self::name(), self.as_str())
+    }
+}
+
+impl FromStr for Availability {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "N" => Ok(Availability::None),
+            "P" => Ok(Availability::Partial),
+            "C" => Ok(Availability::Complete),
+            _ => Err(Error::InvalidMetric {
+                metric_type: Self::TYPE,
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! CVSS v2 Base Metric Group - Access Vector (AV)
+
+use crate::{Error, Metric, MetricType};
+use alloc::borrow::ToOwned;
+use core::{fmt, str::FromStr};
+
+/// Access Vector (AV) - CVSS v2 Base Metric Group
+///
+/// Described in CVSS v2 Complete Documentation: Section 2.1.1:
+/// <https://www.first.org/cvss/v2/guide#2-1-Base-Metrics>
+///
+/// This metric reflects how the vulnerability is exploited. The more remote
+/// an attacker can be to attack a host, the greater the vulnerability score.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum AccessVector {
+    /// Local (L)
+    ///
+    /// The vulnerability is only exploitable with physical access or a local
+    /// (shell) account on the vulnerable system.
+    Local,
+
+    /// Adjacent Network (A)
+    ///
+    /// The vulnerability is exploitable from the same broadcast or collision
+    /// domain as the vulnerable system (e.g. the local subnet).
+    AdjacentNetwork,
+
+    /// Network (N)
+    ///
+    /// The vulnerability is exploitable from anywhere on the network, i.e. it
+    /// is "remotely exploitable".
+    Network,
+}
+
+impl Metric for AccessVector {
+    const TYPE: MetricType = MetricType::AV;
+
+    fn score(self) -> f64 {
+        match self {
+            AccessVector::Local => 0.395,
+            AccessVector::AdjacentNetwork => 0.646,
+            AccessVector::Network => 1.0,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            AccessVector::Local => "L",
+            AccessVector::AdjacentNetwork => "A",
+            AccessVector::Network => "N",
+        }
+    }
+}
+
+impl fmt::Display for AccessVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", Self::name(), self.as_str())
+    }
+}
+
+impl FromStr for AccessVector {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "L" => Ok(AccessVector::Local),
+            "A" => Ok(AccessVector::AdjacentNetwork),
+            "N" => Ok(AccessVector::Network),
+            _ => Err(Error::InvalidMetric {
+                metric_type: Self::TYPE,
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! CVSS v2 Base Metric Group - Confidentiality Impact (C)
+
+use crate::{Error, Metric, MetricType};
+use alloc::borrow::ToOwned;
+use core::{fmt, str::FromStr};
+
+/// Confidentiality Impact (C) - CVSS v2 Base Metric Group
+///
+/// Described in CVSS v2 Complete Documentation: Section 2.1.4:
+/// <https://www.first.org/cvss/v2/guide#2-1-Base-Metrics>
+///
+/// This metric measures the impact on confidentiality of a successfully
+/// exploited vulnerability.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Confidentiality {
+    /// None (N)
+    ///
+    /// There is no impact to the confidentiality of the system.
+    None,
+
+    /// Partial (P)
+    ///
+    /// There is considerable information disclosure, but the attacker doesn't
+    /// control what information is obtained, or the scope of the loss is
+    /// limited.
+    Partial,
+
+    /// Complete (C)
+    ///
+    /// There is a total compromise of the confidentiality of the system.
+    Complete,
+}
+
+impl Metric for Confidentiality {
+    const TYPE: MetricType = MetricType::C;
+
+    fn score(self) -> f64 {
+        match self {
+            Confidentiality::None => 0.0,
+            Confidentiality::Partial => 0.275,
+            Confidentiality::Complete => 0.66,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Confidentiality::None => "N",
+            Confidentiality::Partial => "P",
+            Confidentiality::Complete => "C",
+        }
+    }
+}
+
+impl fmt::Display for Confidentiality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", Self::name(), self.as_str())
+    }
+}
+
+impl FromStr for Confidentiality {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "N" => Ok(Confidentiality::None),
+            "P" => Ok(Confidentiality::Partial),
+            "C" => Ok(Confidentiality::Complete),
+            _ => Err(Error::InvalidMetric {
+                metric_type: Self::TYPE,
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! CVSS v2 Base Metric Group - Integrity Impact (I)
+
+use crate::{Error, Metric, MetricType};
+use alloc::borrow::ToOwned;
+use core::{fmt, str::FromStr};
+
+/// Integrity Impact (I) - CVSS v2 Base Metric Group
+///
+/// Described in CVSS v2 Complete Documentation: Section 2.1.5:
+/// <https://www.first.org/cvss/v2/guide#2-1-Base-Metrics>
+///
+/// This metric measures the impact to integrity of a successfully exploited
+/// vulnerability.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Integrity {
+    /// None (N)
+    ///
+    /// There is no impact to the integrity of the system.
+    None,
+
+    /// Partial (P)
+    ///
+    /// Modification of some system files or information is possible, but the
+    /// attacker doesn't control what can be modified, or the scope of what
+    /// can be affected is limited.
+    Partial,
+
+    /// Complete (C)
+    ///
+    /// There is a total compromise of the integrity of the system.
+    Complete,
+}
+
+impl Metric for Integrity {
+    const TYPE: MetricType = MetricType::I;
+
+    fn score(self) -> f64 {
+        match self {
+            Integrity::None => 0.0,
+            Integrity::Partial => 0.275,
+            Integrity::Complete => 0.66,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Integrity::None => "N",
+            Integrity::Partial => "P",
+            Integrity::Complete => "C",
+        }
+    }
+}
+
+impl fmt::Display for Integrity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", Self::name(), self.as_str())
+    }
+}
+
+impl FromStr for Integrity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "N" => Ok(Integrity::None),
+            "P" => Ok(Integrity::Partial),
+            "C" => Ok(Integrity::Complete),
+            _ => Err(Error::InvalidMetric {
+                metric_type: Self::TYPE,
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
@@ -5,7 +5,9 @@
 // TODO(tarcieri): Environmental and Temporal Metrics
 
 pub mod base;
+#[cfg(all(feature = "serde", feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "serde", feature = "std"))))]
+pub mod structured;
 
-mod score;
-
-pub use self::{base::Base, score::Score};
+pub use self::base::Base;
+pub use crate::Score;
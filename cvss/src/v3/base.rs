@@ -260,6 +260,11 @@ impl FromStr for Base {
                 MetricType::C => metrics.c = Some(value.parse()?),
                 MetricType::I => metrics.i = Some(value.parse()?),
                 MetricType::A => metrics.a = Some(value.parse()?),
+                MetricType::Au => {
+                    return Err(Error::UnknownMetric {
+                        name: component.0.to_owned(),
+                    })
+                }
             }
         }
 
@@ -0,0 +1,122 @@
+//! Structured serde representation of [`Base`] metric groups.
+//!
+//! [`Base`] serializes as a vector string by default. This representation
+//! instead exposes the parsed metrics along with the computed score and
+//! severity, for consumers which don't want to parse vector strings:
+//!
+//! ```json
+//! {
+//!   "vector": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H",
+//!   "version": "3.1",
+//!   "metrics": { "A": "H", "AC": "L", "AV": "N", "C": "H", "I": "H", "PR": "N", "S": "U", "UI": "N" },
+//!   "score": 9.8,
+//!   "severity": "critical"
+//! }
+//! ```
+//!
+//! Select it with `#[serde(with = "cvss::v3::structured")]`, or with
+//! `#[serde(with = "cvss::v3::structured::option")]` for `Option<Base>`.
+//! Only the `vector` is used when deserializing, the other fields being
+//! derived from it.
+
+use super::Base;
+use crate::{Metric, Severity};
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serialized form of a [`Base`] metric group
+#[derive(Serialize)]
+struct Structured {
+    /// Vector string
+    vector: String,
+
+    /// CVSS version (e.g. `3.1`)
+    version: String,
+
+    /// Values of the metrics present in the vector, keyed by metric name
+    metrics: BTreeMap<&'static str, &'static str>,
+
+    /// Base score
+    score: f64,
+
+    /// Qualitative severity rating of the base score
+    severity: Severity,
+}
+
+/// Deserialized form of a [`Base`] metric group
+#[derive(Deserialize)]
+struct Vector {
+    /// Vector string
+    vector: String,
+}
+
+impl From<&Base> for Structured {
+    fn from(base: &Base) -> Self {
+        let mut metrics = BTreeMap::new();
+
+        fn insert<M: Metric>(
+            metrics: &mut BTreeMap<&'static str, &'static str>,
+            metric: Option<M>,
+        ) {
+            if let Some(metric) = metric {
+                metrics.insert(M::name(), metric.as_str());
+            }
+        }
+
+        insert(&mut metrics, base.av);
+        insert(&mut metrics, base.ac);
+        insert(&mut metrics, base.pr);
+        insert(&mut metrics, base.ui);
+        insert(&mut metrics, base.s);
+        insert(&mut metrics, base.c);
+        insert(&mut metrics, base.i);
+        insert(&mut metrics, base.a);
+
+        Self {
+            vector: base.to_string(),
+            version: format!("3.{}", base.minor_version),
+            metrics,
+            score: base.score().value(),
+            severity: base.severity(),
+        }
+    }
+}
+
+/// Serialize a [`Base`] metric group in the structured representation
+pub fn serialize<S: Serializer>(base: &Base, serializer: S) -> Result<S::Ok, S::Error> {
+    Structured::from(base).serialize(serializer)
+}
+
+/// Deserialize a [`Base`] metric group from the structured representation
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Base, D::Error> {
+    Vector::deserialize(deserializer)?
+        .vector
+        .parse()
+        .map_err(de::Error::custom)
+}
+
+/// Structured representation of optional [`Base`] metric groups
+pub mod option {
+    use super::{Base, Structured, Vector};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Serialize an optional [`Base`] metric group in the structured
+    /// representation
+    pub fn serialize<S: Serializer>(base: &Option<Base>, serializer: S) -> Result<S::Ok, S::Error> {
+        base.as_ref().map(Structured::from).serialize(serializer)
+    }
+
+    /// Deserialize an optional [`Base`] metric group from the structured
+    /// representation
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Base>, D::Error> {
+        Option::<Vector>::deserialize(deserializer)?
+            .map(|vector| vector.vector.parse().map_err(de::Error::custom))
+            .transpose()
+    }
+}
@@ -0,0 +1,66 @@
+//! Structured serde representation tests
+
+#![cfg(all(feature = "v3", feature = "std", feature = "serde"))]
+
+use serde::{Deserialize, Serialize};
+
+/// Container selecting the structured representation
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Report {
+    #[serde(with = "cvss::v3::structured")]
+    cvss: cvss::v3::Base,
+
+    #[serde(default, with = "cvss::v3::structured::option")]
+    other: Option<cvss::v3::Base>,
+}
+
+#[test]
+fn structured_round_trip() {
+    let report = Report {
+        cvss: "CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"
+            .parse()
+            .unwrap(),
+        other: None,
+    };
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "cvss": {
+                "vector": "CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N",
+                "version": "3.1",
+                "metrics": {
+                    "A": "N",
+                    "AC": "L",
+                    "AV": "N",
+                    "C": "L",
+                    "I": "L",
+                    "PR": "N",
+                    "S": "C",
+                    "UI": "R",
+                },
+                "score": 6.1,
+                "severity": "medium",
+            },
+            "other": null,
+        })
+    );
+    assert_eq!(serde_json::from_value::<Report>(json).unwrap(), report);
+}
+
+#[test]
+fn structured_deserialize_uses_vector() {
+    let report: Report = serde_json::from_str(
+        r#"{
+            "cvss": {"vector": "CVSS:3.0/AV:N/AC:L/PR:L/UI:N/S:C/C:L/I:L/A:N"},
+            "other": {"vector": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H", "score": 0.0}
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(report.cvss.minor_version, 0);
+    assert_eq!(report.other.unwrap().score().value(), 9.8);
+
+    assert!(serde_json::from_str::<Report>(r#"{"cvss": {"vector": "AV:N"}}"#).is_err());
+}
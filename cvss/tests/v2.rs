@@ -0,0 +1,84 @@
+#![cfg(all(feature = "v2", feature = "v3", feature = "std"))]
+/// CVSS v2 Base Metrics tests
+use core::str::FromStr;
+use cvss::{v2::Base, Severity};
+
+/// CVE-2002-0392
+#[test]
+fn cve_2002_0392() {
+    let vector = "AV:N/AC:L/Au:N/C:N/I:N/A:C";
+    let base = Base::from_str(vector).unwrap();
+    assert_eq!(&base.to_string(), vector);
+    assert_eq!(base.score().value(), 7.8);
+    assert_eq!(base.severity(), Severity::High);
+}
+
+/// CVE-2003-0818
+#[test]
+fn cve_2003_0818() {
+    let vector = "AV:N/AC:L/Au:N/C:C/I:C/A:C";
+    let base = Base::from_str(vector).unwrap();
+    assert_eq!(&base.to_string(), vector);
+    assert_eq!(base.score().value(), 10.0);
+    assert_eq!(base.severity(), Severity::Critical);
+}
+
+/// CVE-2003-0062
+#[test]
+fn cve_2003_0062() {
+    let vector = "AV:L/AC:H/Au:N/C:C/I:C/A:C";
+    let base = Base::from_str(vector).unwrap();
+    assert_eq!(&base.to_string(), vector);
+    assert_eq!(base.score().value(), 6.2);
+}
+
+#[test]
+fn partial_impact() {
+    let base = Base::from_str("AV:N/AC:M/Au:N/C:N/I:P/A:N").unwrap();
+    assert_eq!(base.score().value(), 4.3);
+    assert_eq!(base.severity(), Severity::Medium);
+}
+
+#[test]
+fn no_impact() {
+    let base = Base::from_str("AV:N/AC:L/Au:N/C:N/I:N/A:N").unwrap();
+    assert_eq!(base.score().value(), 0.0);
+    assert_eq!(base.severity(), Severity::None);
+}
+
+/// Parentheses and the `CVSS2#` prefix used by some data feeds
+#[test]
+fn nvd_formats() {
+    let expected = Base::from_str("AV:N/AC:L/Au:S/C:P/I:P/A:P").unwrap();
+    assert_eq!(
+        Base::from_str("(AV:N/AC:L/Au:S/C:P/I:P/A:P)").unwrap(),
+        expected
+    );
+    assert_eq!(
+        Base::from_str("CVSS2#AV:N/AC:L/Au:S/C:P/I:P/A:P").unwrap(),
+        expected
+    );
+    assert_eq!(expected.score().value(), 6.5);
+}
+
+/// CVSS v3 vectors and metrics aren't accepted
+#[test]
+fn rejects_v3() {
+    assert!(Base::from_str("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N").is_err());
+    assert!(Base::from_str("AV:N/AC:L/PR:N/C:P/I:P/A:P").is_err());
+    assert!(Base::from_str("AV:P/AC:L/Au:N/C:P/I:P/A:P").is_err());
+    assert!(cvss::v3::Base::from_str("CVSS:3.1/AV:N/AC:L/Au:N/C:L/I:L/A:N").is_err());
+}
+
+/// Scores of both versions can be compared
+#[test]
+fn unified_score() {
+    let v2 = Base::from_str("AV:N/AC:L/Au:N/C:P/I:P/A:P")
+        .unwrap()
+        .score();
+    let v3 = cvss::v3::Base::from_str("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H")
+        .unwrap()
+        .score();
+    assert_eq!(v2.value(), 7.5);
+    assert!(v2 < v3);
+}
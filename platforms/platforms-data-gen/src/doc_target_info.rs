@@ -1,9 +1,6 @@
 //! Parses the contents of the Markdown file
 //! https://github.com/rust-lang/rust/blob/master/src/doc/rustc/src/platform-support.md
-//! to extract platform tiers and notes.
-//!
-//! There is extra information contained there like std support that we currently do not parse;
-//! it might be added in the future.
+//! to extract platform tiers, `std` support and notes.
 
 use std::collections::HashMap;
 
@@ -14,6 +11,8 @@ use regex::Regex;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DocTargetInfo {
     pub tier: u8,
+    /// Is the full standard library available?
+    pub has_std: bool,
     pub notes: String,
 }
 
@@ -42,6 +41,7 @@ pub fn parse_file(input: &str) -> DocTargetsInfo {
     for (header, section) in section_headers.iter().zip(sections) {
         // There are no Tier 1 platforms without host tools, so that header does not contain a table
         if let Some(table_header) = table_header_regex.find(section) {
+            let std_column = std_column(table_header.as_str());
             let after_table_header = &section[table_header.end()..];
             for table_row in after_table_header
                 .lines()
@@ -51,6 +51,7 @@ pub fn parse_file(input: &str) -> DocTargetsInfo {
                 let (arch, notes) = parse_table_row(table_row);
                 let target_info = DocTargetInfo {
                     tier: header_to_tier(header),
+                    has_std: std_column.map_or(true, |column| has_std(table_row, column)),
                     notes: notes.to_string(),
                 };
                 // The same target triple can appear several times in the documentation.
@@ -92,6 +93,22 @@ fn parse_table_row(line: &str) -> (&str, &str) {
     (arch, notes)
 }
 
+/// Accepts a table header string and returns the index of the `std` column, if any.
+///
+/// Tables without one only list targets with full standard library support.
+#[must_use]
+fn std_column(header: &str) -> Option<usize> {
+    header.split('|').position(|column| column.trim() == "std")
+}
+
+/// Accepts a table line string and the index of its `std` column, and returns
+/// whether the full standard library is available (`✓`, as opposed to `*` for
+/// `no_std` only or `?` for unknown)
+#[must_use]
+fn has_std(line: &str, std_column: usize) -> bool {
+    line.split('|').nth(std_column).map(str::trim) == Some("✓")
+}
+
 #[must_use]
 fn header_to_tier(header: &str) -> u8 {
     header
@@ -166,9 +183,11 @@ host tools.
 
 target | std | host | notes
 -------|:---:|:----:|-------
-`aarch64-apple-ios-macabi` | ? |  | Apple Catalyst on ARM64
-`aarch64-apple-tvos` | * |  | ARM64 tvOS
+`aarch64-apple-ios-macabi` | ✓ |  | Apple Catalyst on ARM64
+`aarch64-apple-tvos` | ✓ |  | ARM64 tvOS
 [`aarch64-kmc-solid_asp3`](platform-support/kmc-solid.md) | ✓ |  | ARM64 SOLID with TOPPERS/ASP3
+[`armv4t-none-eabi`](platform-support/armv4t-none-eabi.md) | * |  | Bare ARMv4T
+`mips64-openwrt-linux-musl` | ? |  | MIPS64 for OpenWrt Linux musl 1.2.3
 `i686-pc-windows-msvc` | * |  | 32-bit Windows XP support
 
 blah blah I guess
@@ -202,6 +221,15 @@ blah blah I guess
         );
     }
 
+    #[test]
+    fn test_std_parser() {
+        assert_eq!(std_column("target | notes"), None);
+        assert_eq!(std_column("target | std | host | notes"), Some(1));
+        assert!(has_std("`aarch64-apple-ios` | ✓ | ARM64 iOS", 1));
+        assert!(!has_std("`armv4t-none-eabi` | * |  | Bare ARMv4T", 1));
+        assert!(!has_std("`mips64-openwrt-linux-musl` | ? |  | MIPS64 for OpenWrt Linux musl 1.2.3", 1));
+    }
+
     #[test]
     fn test_section_parser() {
         let section_headers = section_headers(SAMPLE_DATA);
@@ -233,5 +261,11 @@ blah blah I guess
             &result["i686-pc-windows-msvc"].notes,
             "32-bit MSVC (Windows 7+)"
         );
+
+        assert!(result["aarch64-unknown-linux-gnu"].has_std);
+        assert!(result["aarch64-apple-ios"].has_std);
+        assert!(result["aarch64-apple-tvos"].has_std);
+        assert!(!result["armv4t-none-eabi"].has_std);
+        assert!(!result["mips64-openwrt-linux-musl"].has_std);
     }
 }
@@ -96,6 +96,7 @@ pub(crate) const {}: Platform = Platform {{
         writeln!(out, "    {}: {},", key, value)?;
     }
     writeln!(out, "    tier: {},", tier_to_enum_variant(doc_info.tier))?;
+    writeln!(out, "    has_std: {},", doc_info.has_std)?;
     writeln!(out, "}};")?;
     Ok(())
 }
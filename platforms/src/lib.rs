@@ -31,4 +31,4 @@ pub use crate::{
 };
 
 #[cfg(feature = "std")]
-pub use crate::platform::PlatformReq;
+pub use crate::platform::{CustomPlatform, PlatformReq};
@@ -2,6 +2,8 @@
 
 mod platforms;
 
+#[cfg(feature = "std")]
+mod custom;
 #[cfg(feature = "std")]
 mod req;
 mod tier;
@@ -9,7 +11,7 @@ mod tier;
 pub use self::tier::Tier;
 
 #[cfg(feature = "std")]
-pub use self::req::PlatformReq;
+pub use self::{custom::CustomPlatform, req::PlatformReq};
 
 use self::platforms::ALL;
 use crate::target::*;
@@ -54,6 +56,12 @@ pub struct Platform {
     /// - `Tier::Two`: guaranteed to build
     /// - `Tier::Three`: unofficially supported with no guarantees
     pub tier: Tier,
+
+    /// Is the full standard library available on this platform?
+    ///
+    /// `false` for platforms which only support `no_std` development, as well
+    /// as those where `std` support is unknown or a work in progress.
+    pub has_std: bool,
 }
 
 impl Platform {
@@ -62,6 +70,26 @@ impl Platform {
     /// Note that this list will evolve over time, and platforms will be both added and removed.
     pub const ALL: &'static [Platform] = ALL;
 
+    /// Iterate over all valid Rust platforms, e.g. to select the ones with
+    /// particular capabilities:
+    ///
+    /// ```
+    /// use platforms::{target::PointerWidth, Platform, Tier};
+    ///
+    /// let tier2_with_std = Platform::all()
+    ///     .filter(|p| p.tier == Tier::Two && p.has_std)
+    ///     .count();
+    /// assert!(tier2_with_std > 0);
+    ///
+    /// // `Tier` is ordered from most to least supported
+    /// let well_supported_64bit = Platform::all()
+    ///     .filter(|p| p.tier <= Tier::Two && p.target_pointer_width == PointerWidth::U64);
+    /// assert!(well_supported_64bit.count() > 0);
+    /// ```
+    pub fn all() -> impl Iterator<Item = &'static Platform> {
+        Self::ALL.iter()
+    }
+
     /// Find a Rust platform by its "target triple", e.g. `i686-apple-darwin`
     pub fn find(target_triple: &str) -> Option<&'static Platform> {
         Self::ALL
@@ -99,6 +127,27 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn capability_queries() {
+        let linux = Platform::find("x86_64-unknown-linux-gnu").unwrap();
+        assert_eq!(linux.tier, Tier::One);
+        assert!(linux.has_std);
+
+        let bare_metal = Platform::find("thumbv7em-none-eabi").unwrap();
+        assert!(!bare_metal.has_std);
+
+        let mut no_std = Platform::all().filter(|p| !p.has_std);
+        assert!(no_std.any(|p| p == bare_metal));
+        assert!(!Platform::all().filter(|p| !p.has_std).any(|p| p == linux));
+
+        assert!(Platform::all()
+            .filter(|p| p.tier == Tier::One)
+            .all(|p| p.has_std));
+        assert!(Platform::all()
+            .filter(|p| p.target_endian == Endian::Big)
+            .all(|p| p.tier != Tier::One));
+    }
+
     /// `platforms` v2.0 used to provide various constants passed as `cfg` values,
     /// and attempted to detect the target triple based on that.
     /// This test is meant to check whether such detection can be accurate.
@@ -0,0 +1,210 @@
+//! Custom platforms described by rustc target specification files
+
+use crate::platform::Platform;
+use crate::target::*;
+use std::{fmt, string::String};
+
+#[cfg(feature = "serde")]
+use serde::{de, de::Error as DeError, Deserialize};
+
+/// Platform which isn't necessarily supported by mainline rustc, such as one
+/// described by a custom target specification (`--target custom.json`).
+///
+/// With the `serde` feature enabled, this type can be deserialized from the
+/// JSON target specification format understood by rustc, e.g. the output of
+/// `rustc -Z unstable-options --print target-spec-json`. Only the fields
+/// needed to evaluate `cfg` attributes are read; all others are ignored.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub struct CustomPlatform {
+    /// "Target triple" string identifying the platform.
+    ///
+    /// For target specification files this is the `llvm-target`.
+    pub target_triple: String,
+
+    /// Target architecture `cfg` attribute (i.e. `cfg(target_arch)`)
+    pub target_arch: Arch,
+
+    /// Target OS `cfg` attribute (i.e. `cfg(target_os)`).
+    pub target_os: OS,
+
+    /// Target environment `cfg` attribute (i.e. `cfg(target_env)`).
+    pub target_env: Env,
+
+    /// Target pointer width `cfg` attribute, in bits (i.e. `cfg(target_pointer_width)`).
+    pub target_pointer_width: PointerWidth,
+
+    /// Target endianness `cfg` attribute (i.e. `cfg(target_endian)`).
+    pub target_endian: Endian,
+}
+
+impl From<&Platform> for CustomPlatform {
+    fn from(platform: &Platform) -> Self {
+        Self {
+            target_triple: platform.target_triple.into(),
+            target_arch: platform.target_arch,
+            target_os: platform.target_os,
+            target_env: platform.target_env,
+            target_pointer_width: platform.target_pointer_width,
+            target_endian: platform.target_endian,
+        }
+    }
+}
+
+impl fmt::Display for CustomPlatform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.target_triple)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CustomPlatform {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(TargetSpecVisitor)
+    }
+}
+
+/// Visitor for rustc target specification JSON
+#[cfg(feature = "serde")]
+struct TargetSpecVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> de::Visitor<'de> for TargetSpecVisitor {
+    type Value = CustomPlatform;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a rustc target specification")
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut target_triple = None;
+        let mut target_arch = None;
+        let mut target_pointer_width = None;
+
+        // These are optional in target specifications, defaulting to the
+        // values rustc uses for bare metal targets
+        let mut target_os = OS::None;
+        let mut target_env = Env::None;
+        let mut target_endian = Endian::Little;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "llvm-target" => target_triple = Some(map.next_value()?),
+                "arch" => target_arch = Some(map.next_value()?),
+                "os" => {
+                    // Custom targets may name an OS rustc doesn't know about
+                    let os = map.next_value::<String>()?;
+                    target_os = os.parse().unwrap_or(OS::Unknown);
+                }
+                "env" => target_env = map.next_value()?,
+                "target-endian" => target_endian = map.next_value()?,
+                "target-pointer-width" => {
+                    target_pointer_width = Some(map.next_value::<PointerWidthValue>()?.0)
+                }
+                _ => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(CustomPlatform {
+            target_triple: target_triple.ok_or_else(|| A::Error::missing_field("llvm-target"))?,
+            target_arch: target_arch.ok_or_else(|| A::Error::missing_field("arch"))?,
+            target_os,
+            target_env,
+            target_pointer_width: target_pointer_width
+                .ok_or_else(|| A::Error::missing_field("target-pointer-width"))?,
+            target_endian,
+        })
+    }
+}
+
+/// Target pointer width, which newer versions of rustc write to target
+/// specifications as a number rather than a string
+#[cfg(feature = "serde")]
+struct PointerWidthValue(PointerWidth);
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for PointerWidthValue {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(PointerWidthVisitor)
+    }
+}
+
+/// Visitor for [`PointerWidthValue`]
+#[cfg(feature = "serde")]
+struct PointerWidthVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> de::Visitor<'de> for PointerWidthVisitor {
+    type Value = PointerWidthValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a target pointer width")
+    }
+
+    fn visit_u64<E: DeError>(self, value: u64) -> Result<Self::Value, E> {
+        self.visit_str(&std::format!("{}", value))
+    }
+
+    fn visit_str<E: DeError>(self, value: &str) -> Result<Self::Value, E> {
+        value.parse().map(PointerWidthValue).map_err(|_| {
+            E::custom(std::format!(
+                "Unrecognized value '{}' for target_pointer_width",
+                value
+            ))
+        })
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::CustomPlatform;
+    use crate::target::*;
+
+    /// Abridged target specification for a Cortex-M4 board
+    const TARGET_SPEC: &str = r#"{
+        "arch": "arm",
+        "data-layout": "e-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64",
+        "emit-debug-gdb-scripts": false,
+        "llvm-target": "thumbv7em-none-eabihf",
+        "max-atomic-width": 32,
+        "panic-strategy": "abort",
+        "target-pointer-width": "32"
+    }"#;
+
+    #[test]
+    fn parse_target_spec() {
+        let platform: CustomPlatform = serde_json::from_str(TARGET_SPEC).unwrap();
+        assert_eq!(platform.target_triple, "thumbv7em-none-eabihf");
+        assert_eq!(platform.target_arch, Arch::Arm);
+        assert_eq!(platform.target_os, OS::None);
+        assert_eq!(platform.target_env, Env::None);
+        assert_eq!(platform.target_pointer_width, PointerWidth::U32);
+        assert_eq!(platform.target_endian, Endian::Little);
+    }
+
+    #[test]
+    fn parse_numeric_pointer_width() {
+        let spec = r#"{"arch": "x86_64", "llvm-target": "x86_64-unknown-linux-gnu",
+            "os": "linux", "env": "gnu", "target-pointer-width": 64}"#;
+        let platform: CustomPlatform = serde_json::from_str(spec).unwrap();
+        assert_eq!(platform.target_os, OS::Linux);
+        assert_eq!(platform.target_env, Env::Gnu);
+        assert_eq!(platform.target_pointer_width, PointerWidth::U64);
+    }
+
+    #[test]
+    fn parse_unrecognized_os() {
+        let spec = r#"{"arch": "arm", "llvm-target": "armv7-myos-eabi",
+            "os": "myos", "target-pointer-width": "32"}"#;
+        let platform: CustomPlatform = serde_json::from_str(spec).unwrap();
+        assert_eq!(platform.target_os, OS::Unknown);
+    }
+
+    #[test]
+    fn reject_incomplete_target_spec() {
+        let spec = r#"{"llvm-target": "foo-none-elf", "target-pointer-width": "32"}"#;
+        assert!(serde_json::from_str::<CustomPlatform>(spec).is_err());
+    }
+}
@@ -263,6 +263,7 @@ pub(crate) const AARCH64_APPLE_DARWIN: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARM64 iOS
@@ -274,6 +275,7 @@ pub(crate) const AARCH64_APPLE_IOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// Apple Catalyst on ARM64
@@ -285,6 +287,7 @@ pub(crate) const AARCH64_APPLE_IOS_MACABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Apple iOS Simulator on ARM64
@@ -296,6 +299,7 @@ pub(crate) const AARCH64_APPLE_IOS_SIM: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARM64 tvOS
@@ -307,6 +311,7 @@ pub(crate) const AARCH64_APPLE_TVOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 tvOS Simulator
@@ -318,6 +323,7 @@ pub(crate) const AARCH64_APPLE_TVOS_SIM: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 Apple WatchOS
@@ -329,6 +335,7 @@ pub(crate) const AARCH64_APPLE_WATCHOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 Apple WatchOS Simulator
@@ -340,6 +347,7 @@ pub(crate) const AARCH64_APPLE_WATCHOS_SIM: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Alias for `aarch64-unknown-fuchsia`
@@ -351,6 +359,7 @@ pub(crate) const AARCH64_FUCHSIA: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARM64 SOLID with TOPPERS/ASP3
@@ -362,6 +371,7 @@ pub(crate) const AARCH64_KMC_SOLID_ASP3: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 Android
@@ -373,6 +383,7 @@ pub(crate) const AARCH64_LINUX_ANDROID: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARM64 Nintendo Switch, Horizon
@@ -384,6 +395,7 @@ pub(crate) const AARCH64_NINTENDO_SWITCH_FREESTANDING: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 pub(crate) const AARCH64_PC_WINDOWS_GNULLVM: Platform = Platform {
@@ -394,6 +406,7 @@ pub(crate) const AARCH64_PC_WINDOWS_GNULLVM: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 Windows MSVC
@@ -405,6 +418,7 @@ pub(crate) const AARCH64_PC_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARM64 FreeBSD
@@ -416,6 +430,7 @@ pub(crate) const AARCH64_UNKNOWN_FREEBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 Fuchsia
@@ -427,6 +442,7 @@ pub(crate) const AARCH64_UNKNOWN_FUCHSIA: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARM64 Hermit
@@ -438,6 +454,7 @@ pub(crate) const AARCH64_UNKNOWN_HERMIT: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 illumos
@@ -449,6 +466,7 @@ pub(crate) const AARCH64_UNKNOWN_ILLUMOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 Linux (kernel 4.1, glibc 2.17+)
@@ -460,6 +478,7 @@ pub(crate) const AARCH64_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::One,
+    has_std: true,
 };
 
 /// ARM64 Linux (ILP32 ABI)
@@ -471,6 +490,7 @@ pub(crate) const AARCH64_UNKNOWN_LINUX_GNU_ILP32: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 Linux with musl 1.2.3
@@ -482,6 +502,7 @@ pub(crate) const AARCH64_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARM64 OpenHarmony
@@ -493,6 +514,7 @@ pub(crate) const AARCH64_UNKNOWN_LINUX_OHOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARM64 NetBSD
@@ -504,6 +526,7 @@ pub(crate) const AARCH64_UNKNOWN_NETBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare ARM64, hardfloat
@@ -515,6 +538,7 @@ pub(crate) const AARCH64_UNKNOWN_NONE: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Bare ARM64, softfloat
@@ -526,6 +550,7 @@ pub(crate) const AARCH64_UNKNOWN_NONE_SOFTFLOAT: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: false,
 };
 
 pub(crate) const AARCH64_UNKNOWN_NTO_QNX710: Platform = Platform {
@@ -536,6 +561,7 @@ pub(crate) const AARCH64_UNKNOWN_NTO_QNX710: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 OpenBSD
@@ -547,6 +573,7 @@ pub(crate) const AARCH64_UNKNOWN_OPENBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 Redox OS
@@ -558,6 +585,7 @@ pub(crate) const AARCH64_UNKNOWN_REDOX: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 pub(crate) const AARCH64_UNKNOWN_TEEOS: Platform = Platform {
@@ -568,6 +596,7 @@ pub(crate) const AARCH64_UNKNOWN_TEEOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// ARM64 UEFI
@@ -579,6 +608,7 @@ pub(crate) const AARCH64_UNKNOWN_UEFI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: false,
 };
 
 pub(crate) const AARCH64_UWP_WINDOWS_MSVC: Platform = Platform {
@@ -589,6 +619,7 @@ pub(crate) const AARCH64_UWP_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 pub(crate) const AARCH64_WRS_VXWORKS: Platform = Platform {
@@ -599,6 +630,7 @@ pub(crate) const AARCH64_WRS_VXWORKS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 Linux (big-endian)
@@ -610,6 +642,7 @@ pub(crate) const AARCH64_BE_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 Linux (big-endian, ILP32 ABI)
@@ -621,6 +654,7 @@ pub(crate) const AARCH64_BE_UNKNOWN_LINUX_GNU_ILP32: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64 NetBSD (big-endian)
@@ -632,6 +666,7 @@ pub(crate) const AARCH64_BE_UNKNOWN_NETBSD: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARMv6 Android
@@ -643,6 +678,7 @@ pub(crate) const ARM_LINUX_ANDROIDEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv6 Linux (kernel 3.2, glibc 2.17)
@@ -654,6 +690,7 @@ pub(crate) const ARM_UNKNOWN_LINUX_GNUEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv6 Linux, hardfloat (kernel 3.2, glibc 2.17)
@@ -665,6 +702,7 @@ pub(crate) const ARM_UNKNOWN_LINUX_GNUEABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv6 Linux with musl 1.2.3
@@ -676,6 +714,7 @@ pub(crate) const ARM_UNKNOWN_LINUX_MUSLEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv6 Linux with musl 1.2.3, hardfloat
@@ -687,6 +726,7 @@ pub(crate) const ARM_UNKNOWN_LINUX_MUSLEABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARM Apple WatchOS 64-bit with 32-bit pointers
@@ -698,6 +738,7 @@ pub(crate) const ARM64_32_APPLE_WATCHOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64e Apple Darwin
@@ -709,6 +750,7 @@ pub(crate) const ARM64E_APPLE_DARWIN: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM64e Apple iOS
@@ -720,6 +762,7 @@ pub(crate) const ARM64E_APPLE_IOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Arm64EC Windows MSVC
@@ -731,6 +774,7 @@ pub(crate) const ARM64EC_PC_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM BE8 the default ARM big-endian architecture since [ARMv6](https://developer.arm.com/documentation/101754/0616/armlink-Reference/armlink-Command-line-Options/--be8?lang=en).
@@ -742,6 +786,7 @@ pub(crate) const ARMEB_UNKNOWN_LINUX_GNUEABI: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare ARMv7-R, Big Endian
@@ -753,6 +798,7 @@ pub(crate) const ARMEBV7R_NONE_EABI: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Bare ARMv7-R, Big Endian, hardfloat
@@ -764,6 +810,7 @@ pub(crate) const ARMEBV7R_NONE_EABIHF: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Bare ARMv4T
@@ -775,6 +822,7 @@ pub(crate) const ARMV4T_NONE_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// ARMv4T Linux
@@ -786,6 +834,7 @@ pub(crate) const ARMV4T_UNKNOWN_LINUX_GNUEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// Bare ARMv5TE
@@ -797,6 +846,7 @@ pub(crate) const ARMV5TE_NONE_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// ARMv5TE Linux (kernel 4.4, glibc 2.23)
@@ -808,6 +858,7 @@ pub(crate) const ARMV5TE_UNKNOWN_LINUX_GNUEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv5TE Linux with musl 1.2.3
@@ -819,6 +870,7 @@ pub(crate) const ARMV5TE_UNKNOWN_LINUX_MUSLEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv5TE Linux with uClibc
@@ -830,6 +882,7 @@ pub(crate) const ARMV5TE_UNKNOWN_LINUX_UCLIBCEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// ARMv6 FreeBSD
@@ -841,6 +894,7 @@ pub(crate) const ARMV6_UNKNOWN_FREEBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARMv6 NetBSD w/hard-float
@@ -852,6 +906,7 @@ pub(crate) const ARMV6_UNKNOWN_NETBSD_EABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARMv6K Nintendo 3DS, Horizon (Requires devkitARM toolchain)
@@ -863,6 +918,7 @@ pub(crate) const ARMV6K_NINTENDO_3DS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// ARMv7-A Android
@@ -874,6 +930,7 @@ pub(crate) const ARMV7_LINUX_ANDROIDEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv7-A Cortex-A9 Sony PlayStation Vita (requires VITASDK toolchain)
@@ -885,6 +942,7 @@ pub(crate) const ARMV7_SONY_VITA_NEWLIBEABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARMv7-A FreeBSD
@@ -896,6 +954,7 @@ pub(crate) const ARMV7_UNKNOWN_FREEBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARMv7-A Linux (kernel 4.15, glibc 2.27)
@@ -907,6 +966,7 @@ pub(crate) const ARMV7_UNKNOWN_LINUX_GNUEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv7-A Linux, hardfloat (kernel 3.2, glibc 2.17)
@@ -918,6 +978,7 @@ pub(crate) const ARMV7_UNKNOWN_LINUX_GNUEABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv7-A Linux with musl 1.2.3
@@ -929,6 +990,7 @@ pub(crate) const ARMV7_UNKNOWN_LINUX_MUSLEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv7-A Linux with musl 1.2.3, hardfloat
@@ -940,6 +1002,7 @@ pub(crate) const ARMV7_UNKNOWN_LINUX_MUSLEABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv7-A OpenHarmony
@@ -951,6 +1014,7 @@ pub(crate) const ARMV7_UNKNOWN_LINUX_OHOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// ARMv7-A Linux with uClibc, softfloat
@@ -962,6 +1026,7 @@ pub(crate) const ARMV7_UNKNOWN_LINUX_UCLIBCEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARMv7-A Linux with uClibc, hardfloat
@@ -973,6 +1038,7 @@ pub(crate) const ARMV7_UNKNOWN_LINUX_UCLIBCEABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARMv7-A NetBSD w/hard-float
@@ -984,6 +1050,7 @@ pub(crate) const ARMV7_UNKNOWN_NETBSD_EABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARMv7-A for VxWorks
@@ -995,6 +1062,7 @@ pub(crate) const ARMV7_WRS_VXWORKS_EABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM SOLID with TOPPERS/ASP3
@@ -1006,6 +1074,7 @@ pub(crate) const ARMV7A_KMC_SOLID_ASP3_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// ARM SOLID with TOPPERS/ASP3, hardfloat
@@ -1017,6 +1086,7 @@ pub(crate) const ARMV7A_KMC_SOLID_ASP3_EABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare ARMv7-A
@@ -1028,6 +1098,7 @@ pub(crate) const ARMV7A_NONE_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Bare ARMv7-A, hardfloat
@@ -1039,6 +1110,7 @@ pub(crate) const ARMV7A_NONE_EABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// ARMv7-A Apple WatchOS
@@ -1050,6 +1122,7 @@ pub(crate) const ARMV7K_APPLE_WATCHOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare ARMv7-R
@@ -1061,6 +1134,7 @@ pub(crate) const ARMV7R_NONE_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Bare ARMv7-R, hardfloat
@@ -1072,6 +1146,7 @@ pub(crate) const ARMV7R_NONE_EABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// ARMv7-A Apple-A6 Apple iOS
@@ -1083,6 +1158,7 @@ pub(crate) const ARMV7S_APPLE_IOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare ARMv8-R, hardfloat
@@ -1094,6 +1170,7 @@ pub(crate) const ARMV8R_NONE_EABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// AVR. Requires `-Z build-std=core`
@@ -1105,6 +1182,7 @@ pub(crate) const AVR_UNKNOWN_GNU_ATMEGA328: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U16,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// BPF (big endian)
@@ -1116,6 +1194,7 @@ pub(crate) const BPFEB_UNKNOWN_NONE: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// BPF (little endian)
@@ -1127,6 +1206,7 @@ pub(crate) const BPFEL_UNKNOWN_NONE: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// C-SKY abiv2 Linux (little endian)
@@ -1138,6 +1218,7 @@ pub(crate) const CSKY_UNKNOWN_LINUX_GNUABIV2: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// C-SKY abiv2 Linux, hardfloat (little endian)
@@ -1149,6 +1230,7 @@ pub(crate) const CSKY_UNKNOWN_LINUX_GNUABIV2HF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Hexagon Linux with musl 1.2.3
@@ -1160,6 +1242,7 @@ pub(crate) const HEXAGON_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare Hexagon (v60+, HVX)
@@ -1171,6 +1254,7 @@ pub(crate) const HEXAGON_UNKNOWN_NONE_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// 32-bit x86 iOS [^x86_32-floats-return-ABI]
@@ -1182,6 +1266,7 @@ pub(crate) const I386_APPLE_IOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 32-bit x86 QNX Neutrino 7.0 RTOS  [^x86_32-floats-return-ABI]
@@ -1193,6 +1278,7 @@ pub(crate) const I586_PC_NTO_QNX700: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// 32-bit Windows w/o SSE [^x86_32-floats-x87]
@@ -1204,6 +1290,7 @@ pub(crate) const I586_PC_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 32-bit Linux w/o SSE (kernel 3.2, glibc 2.17) [^x86_32-floats-x87]
@@ -1215,6 +1302,7 @@ pub(crate) const I586_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 32-bit Linux w/o SSE, musl 1.2.3 [^x86_32-floats-x87]
@@ -1226,6 +1314,7 @@ pub(crate) const I586_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 32-bit x86, restricted to Pentium
@@ -1237,6 +1326,7 @@ pub(crate) const I586_UNKNOWN_NETBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 32-bit macOS (10.12+, Sierra+) [^x86_32-floats-return-ABI]
@@ -1248,6 +1338,7 @@ pub(crate) const I686_APPLE_DARWIN: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 32-bit x86 Android [^x86_32-floats-return-ABI]
@@ -1259,6 +1350,7 @@ pub(crate) const I686_LINUX_ANDROID: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 32-bit MinGW (Windows 10+) [^x86_32-floats-return-ABI]
@@ -1270,6 +1362,7 @@ pub(crate) const I686_PC_WINDOWS_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::One,
+    has_std: true,
 };
 
 /// [^x86_32-floats-return-ABI]
@@ -1281,6 +1374,7 @@ pub(crate) const I686_PC_WINDOWS_GNULLVM: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 32-bit MSVC (Windows 10+) [^x86_32-floats-return-ABI]
@@ -1292,6 +1386,7 @@ pub(crate) const I686_PC_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::One,
+    has_std: true,
 };
 
 /// 32-bit FreeBSD [^x86_32-floats-return-ABI]
@@ -1303,6 +1398,7 @@ pub(crate) const I686_UNKNOWN_FREEBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 32-bit Haiku [^x86_32-floats-return-ABI]
@@ -1314,6 +1410,7 @@ pub(crate) const I686_UNKNOWN_HAIKU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 32-bit GNU/Hurd [^x86_32-floats-return-ABI]
@@ -1325,6 +1422,7 @@ pub(crate) const I686_UNKNOWN_HURD_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 32-bit Linux (kernel 3.2+, glibc 2.17+) [^x86_32-floats-return-ABI]
@@ -1336,6 +1434,7 @@ pub(crate) const I686_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::One,
+    has_std: true,
 };
 
 /// 32-bit Linux with musl 1.2.3 [^x86_32-floats-return-ABI]
@@ -1347,6 +1446,7 @@ pub(crate) const I686_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// NetBSD/i386 with SSE2 [^x86_32-floats-return-ABI]
@@ -1358,6 +1458,7 @@ pub(crate) const I686_UNKNOWN_NETBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 32-bit OpenBSD [^x86_32-floats-return-ABI]
@@ -1369,6 +1470,7 @@ pub(crate) const I686_UNKNOWN_OPENBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 32-bit UEFI
@@ -1380,6 +1482,7 @@ pub(crate) const I686_UNKNOWN_UEFI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// [^x86_32-floats-return-ABI]
@@ -1391,6 +1494,7 @@ pub(crate) const I686_UWP_WINDOWS_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// [^x86_32-floats-return-ABI]
@@ -1402,6 +1506,7 @@ pub(crate) const I686_UWP_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 32-bit Windows 7 support [^x86_32-floats-return-ABI]
@@ -1413,6 +1518,7 @@ pub(crate) const I686_WIN7_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// [^x86_32-floats-return-ABI]
@@ -1424,6 +1530,7 @@ pub(crate) const I686_WRS_VXWORKS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// LoongArch64 Linux, LP64D ABI (kernel 5.19, glibc 2.36)
@@ -1435,6 +1542,7 @@ pub(crate) const LOONGARCH64_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// LoongArch64 Linux (LP64D ABI) with musl 1.2.3
@@ -1446,6 +1554,7 @@ pub(crate) const LOONGARCH64_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// LoongArch64 Bare-metal (LP64D ABI)
@@ -1457,6 +1566,7 @@ pub(crate) const LOONGARCH64_UNKNOWN_NONE: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// LoongArch64 Bare-metal (LP64S ABI)
@@ -1468,6 +1578,7 @@ pub(crate) const LOONGARCH64_UNKNOWN_NONE_SOFTFLOAT: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Motorola 680x0 Linux
@@ -1479,6 +1590,7 @@ pub(crate) const M68K_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// MIPS Linux (kernel 4.4, glibc 2.23)
@@ -1490,6 +1602,7 @@ pub(crate) const MIPS_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// MIPS Linux with musl 1.2.3
@@ -1501,6 +1614,7 @@ pub(crate) const MIPS_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// MIPS Linux with uClibc
@@ -1512,6 +1626,7 @@ pub(crate) const MIPS_UNKNOWN_LINUX_UCLIBC: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// MIPS64 for OpenWrt Linux musl 1.2.3
@@ -1523,6 +1638,7 @@ pub(crate) const MIPS64_OPENWRT_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// MIPS64 Linux, N64 ABI (kernel 4.4, glibc 2.23)
@@ -1534,6 +1650,7 @@ pub(crate) const MIPS64_UNKNOWN_LINUX_GNUABI64: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// MIPS64 Linux, N64 ABI, musl 1.2.3
@@ -1545,6 +1662,7 @@ pub(crate) const MIPS64_UNKNOWN_LINUX_MUSLABI64: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// MIPS64 (little endian) Linux, N64 ABI (kernel 4.4, glibc 2.23)
@@ -1556,6 +1674,7 @@ pub(crate) const MIPS64EL_UNKNOWN_LINUX_GNUABI64: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// MIPS64 (little endian) Linux, N64 ABI, musl 1.2.3
@@ -1567,6 +1686,7 @@ pub(crate) const MIPS64EL_UNKNOWN_LINUX_MUSLABI64: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// MIPS (LE) Sony PlayStation Portable (PSP)
@@ -1578,6 +1698,7 @@ pub(crate) const MIPSEL_SONY_PSP: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// MIPS (LE) Sony PlayStation 1 (PSX)
@@ -1589,6 +1710,7 @@ pub(crate) const MIPSEL_SONY_PSX: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// MIPS (little endian) Linux (kernel 4.4, glibc 2.23)
@@ -1600,6 +1722,7 @@ pub(crate) const MIPSEL_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// MIPS (little endian) Linux with musl 1.2.3
@@ -1611,6 +1734,7 @@ pub(crate) const MIPSEL_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// MIPS (LE) Linux with uClibc
@@ -1622,6 +1746,7 @@ pub(crate) const MIPSEL_UNKNOWN_LINUX_UCLIBC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 32-bit MIPS (LE), requires mips32 cpu support
@@ -1633,6 +1758,7 @@ pub(crate) const MIPSEL_UNKNOWN_NETBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare MIPS (LE) softfloat
@@ -1644,6 +1770,7 @@ pub(crate) const MIPSEL_UNKNOWN_NONE: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// 32-bit MIPS Release 6 Big Endian
@@ -1655,6 +1782,7 @@ pub(crate) const MIPSISA32R6_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// 32-bit MIPS Release 6 Little Endian
@@ -1666,6 +1794,7 @@ pub(crate) const MIPSISA32R6EL_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// 64-bit MIPS Release 6 Big Endian
@@ -1677,6 +1806,7 @@ pub(crate) const MIPSISA64R6_UNKNOWN_LINUX_GNUABI64: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// 64-bit MIPS Release 6 Little Endian
@@ -1688,6 +1818,7 @@ pub(crate) const MIPSISA64R6EL_UNKNOWN_LINUX_GNUABI64: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 16-bit MSP430 microcontrollers
@@ -1699,6 +1830,7 @@ pub(crate) const MSP430_NONE_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U16,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// --emit=asm generates PTX code that [runs on NVIDIA GPUs]
@@ -1710,6 +1842,7 @@ pub(crate) const NVPTX64_NVIDIA_CUDA: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// PowerPC FreeBSD
@@ -1721,6 +1854,7 @@ pub(crate) const POWERPC_UNKNOWN_FREEBSD: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// PowerPC Linux (kernel 3.2, glibc 2.17)
@@ -1732,6 +1866,7 @@ pub(crate) const POWERPC_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// PowerPC SPE Linux
@@ -1743,6 +1878,7 @@ pub(crate) const POWERPC_UNKNOWN_LINUX_GNUSPE: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// PowerPC Linux with musl 1.2.3
@@ -1754,6 +1890,7 @@ pub(crate) const POWERPC_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// NetBSD 32-bit powerpc systems
@@ -1765,6 +1902,7 @@ pub(crate) const POWERPC_UNKNOWN_NETBSD: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 pub(crate) const POWERPC_UNKNOWN_OPENBSD: Platform = Platform {
@@ -1775,6 +1913,7 @@ pub(crate) const POWERPC_UNKNOWN_OPENBSD: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 pub(crate) const POWERPC_WRS_VXWORKS: Platform = Platform {
@@ -1785,6 +1924,7 @@ pub(crate) const POWERPC_WRS_VXWORKS: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 pub(crate) const POWERPC_WRS_VXWORKS_SPE: Platform = Platform {
@@ -1795,6 +1935,7 @@ pub(crate) const POWERPC_WRS_VXWORKS_SPE: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 64-bit AIX (7.2 and newer)
@@ -1806,6 +1947,7 @@ pub(crate) const POWERPC64_IBM_AIX: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// PPC64 FreeBSD (ELFv1 and ELFv2)
@@ -1817,6 +1959,7 @@ pub(crate) const POWERPC64_UNKNOWN_FREEBSD: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// PPC64 Linux (kernel 3.2, glibc 2.17)
@@ -1828,6 +1971,7 @@ pub(crate) const POWERPC64_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 64-bit PowerPC Linux with musl 1.2.3
@@ -1839,6 +1983,7 @@ pub(crate) const POWERPC64_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// OpenBSD/powerpc64
@@ -1850,6 +1995,7 @@ pub(crate) const POWERPC64_UNKNOWN_OPENBSD: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 pub(crate) const POWERPC64_WRS_VXWORKS: Platform = Platform {
@@ -1860,6 +2006,7 @@ pub(crate) const POWERPC64_WRS_VXWORKS: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// PPC64LE FreeBSD
@@ -1871,6 +2018,7 @@ pub(crate) const POWERPC64LE_UNKNOWN_FREEBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// PPC64LE Linux (kernel 3.10, glibc 2.17)
@@ -1882,6 +2030,7 @@ pub(crate) const POWERPC64LE_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 64-bit PowerPC Linux with musl 1.2.3, Little Endian
@@ -1893,6 +2042,7 @@ pub(crate) const POWERPC64LE_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// RISC-V Linux (kernel 5.4, glibc 2.33)
@@ -1904,6 +2054,7 @@ pub(crate) const RISCV32GC_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// RISC-V Linux (kernel 5.4, musl 1.2.3 + RISCV32 support patches)
@@ -1915,6 +2066,7 @@ pub(crate) const RISCV32GC_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// Bare RISC-V (RV32I ISA)
@@ -1926,6 +2078,7 @@ pub(crate) const RISCV32I_UNKNOWN_NONE_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// RISC Zero's zero-knowledge Virtual Machine (RV32IM ISA)
@@ -1937,6 +2090,7 @@ pub(crate) const RISCV32IM_RISC0_ZKVM_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// Bare RISC-V (RV32IM ISA)
@@ -1948,6 +2102,7 @@ pub(crate) const RISCV32IM_UNKNOWN_NONE_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Bare RISC-V (RV32IMA ISA)
@@ -1959,6 +2114,7 @@ pub(crate) const RISCV32IMA_UNKNOWN_NONE_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// RISC-V ESP-IDF
@@ -1970,6 +2126,7 @@ pub(crate) const RISCV32IMAC_ESP_ESPIDF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare RISC-V (RV32IMAC ISA)
@@ -1981,6 +2138,7 @@ pub(crate) const RISCV32IMAC_UNKNOWN_NONE_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// RISC-V Xous (RV32IMAC ISA)
@@ -1992,6 +2150,7 @@ pub(crate) const RISCV32IMAC_UNKNOWN_XOUS_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// RISC-V ESP-IDF
@@ -2003,6 +2162,7 @@ pub(crate) const RISCV32IMAFC_ESP_ESPIDF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare RISC-V (RV32IMAFC ISA)
@@ -2014,6 +2174,7 @@ pub(crate) const RISCV32IMAFC_UNKNOWN_NONE_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// RISC-V ESP-IDF
@@ -2025,6 +2186,7 @@ pub(crate) const RISCV32IMC_ESP_ESPIDF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare RISC-V (RV32IMC ISA)
@@ -2036,6 +2198,7 @@ pub(crate) const RISCV32IMC_UNKNOWN_NONE_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// RISC-V 64-bit Android
@@ -2047,6 +2210,7 @@ pub(crate) const RISCV64_LINUX_ANDROID: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// RISC-V FreeBSD
@@ -2058,6 +2222,7 @@ pub(crate) const RISCV64GC_UNKNOWN_FREEBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// RISC-V Fuchsia
@@ -2069,6 +2234,7 @@ pub(crate) const RISCV64GC_UNKNOWN_FUCHSIA: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// RISC-V Hermit
@@ -2080,6 +2246,7 @@ pub(crate) const RISCV64GC_UNKNOWN_HERMIT: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// RISC-V Linux (kernel 4.20, glibc 2.29)
@@ -2091,6 +2258,7 @@ pub(crate) const RISCV64GC_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// RISC-V Linux (kernel 4.20, musl 1.2.3)
@@ -2102,6 +2270,7 @@ pub(crate) const RISCV64GC_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// RISC-V NetBSD
@@ -2113,6 +2282,7 @@ pub(crate) const RISCV64GC_UNKNOWN_NETBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare RISC-V (RV64IMAFDC ISA)
@@ -2124,6 +2294,7 @@ pub(crate) const RISCV64GC_UNKNOWN_NONE_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// OpenBSD/riscv64
@@ -2135,6 +2306,7 @@ pub(crate) const RISCV64GC_UNKNOWN_OPENBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare RISC-V (RV64IMAC ISA)
@@ -2146,6 +2318,7 @@ pub(crate) const RISCV64IMAC_UNKNOWN_NONE_ELF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// S390x Linux (kernel 3.2, glibc 2.17)
@@ -2157,6 +2330,7 @@ pub(crate) const S390X_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// S390x Linux (kernel 3.2, musl 1.2.3)
@@ -2168,6 +2342,7 @@ pub(crate) const S390X_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 32-bit SPARC Linux
@@ -2179,6 +2354,7 @@ pub(crate) const SPARC_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Bare 32-bit SPARC V7+
@@ -2190,6 +2366,7 @@ pub(crate) const SPARC_UNKNOWN_NONE_ELF: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// SPARC Linux (kernel 4.4, glibc 2.23)
@@ -2201,6 +2378,7 @@ pub(crate) const SPARC64_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// NetBSD/sparc64
@@ -2212,6 +2390,7 @@ pub(crate) const SPARC64_UNKNOWN_NETBSD: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// OpenBSD/sparc64
@@ -2223,6 +2402,7 @@ pub(crate) const SPARC64_UNKNOWN_OPENBSD: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// SPARC Solaris 11, illumos
@@ -2234,6 +2414,7 @@ pub(crate) const SPARCV9_SUN_SOLARIS: Platform = Platform {
     target_endian: Endian::Big,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// Thumb-mode Bare ARMv4T
@@ -2245,6 +2426,7 @@ pub(crate) const THUMBV4T_NONE_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// Thumb-mode Bare ARMv5TE
@@ -2256,6 +2438,7 @@ pub(crate) const THUMBV5TE_NONE_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// Bare ARMv6-M
@@ -2267,6 +2450,7 @@ pub(crate) const THUMBV6M_NONE_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 pub(crate) const THUMBV7A_PC_WINDOWS_MSVC: Platform = Platform {
@@ -2277,6 +2461,7 @@ pub(crate) const THUMBV7A_PC_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 pub(crate) const THUMBV7A_UWP_WINDOWS_MSVC: Platform = Platform {
@@ -2287,6 +2472,7 @@ pub(crate) const THUMBV7A_UWP_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// Bare ARMv7E-M
@@ -2298,6 +2484,7 @@ pub(crate) const THUMBV7EM_NONE_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Bare ARMV7E-M, hardfloat
@@ -2309,6 +2496,7 @@ pub(crate) const THUMBV7EM_NONE_EABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Bare ARMv7-M
@@ -2320,6 +2508,7 @@ pub(crate) const THUMBV7M_NONE_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Thumb2-mode ARMv7-A Android with NEON
@@ -2331,6 +2520,7 @@ pub(crate) const THUMBV7NEON_LINUX_ANDROIDEABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// Thumb2-mode ARMv7-A Linux with NEON (kernel 4.4, glibc 2.23)
@@ -2342,6 +2532,7 @@ pub(crate) const THUMBV7NEON_UNKNOWN_LINUX_GNUEABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// Thumb2-mode ARMv7-A Linux with NEON, musl 1.2.3
@@ -2353,6 +2544,7 @@ pub(crate) const THUMBV7NEON_UNKNOWN_LINUX_MUSLEABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// Bare ARMv8-M Baseline
@@ -2364,6 +2556,7 @@ pub(crate) const THUMBV8M_BASE_NONE_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Bare ARMv8-M Mainline
@@ -2375,6 +2568,7 @@ pub(crate) const THUMBV8M_MAIN_NONE_EABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// Bare ARMv8-M Mainline, hardfloat
@@ -2386,6 +2580,7 @@ pub(crate) const THUMBV8M_MAIN_NONE_EABIHF: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// WebAssembly via Emscripten
@@ -2397,6 +2592,7 @@ pub(crate) const WASM32_UNKNOWN_EMSCRIPTEN: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// WebAssembly
@@ -2408,6 +2604,7 @@ pub(crate) const WASM32_UNKNOWN_UNKNOWN: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// WebAssembly with WASI (undergoing a [rename to `wasm32-wasip1`][wasi-rename])
@@ -2419,6 +2616,7 @@ pub(crate) const WASM32_WASI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// WebAssembly with WASI
@@ -2430,6 +2628,7 @@ pub(crate) const WASM32_WASIP1: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// WebAssembly with WASI Preview 1 and threads
@@ -2441,6 +2640,7 @@ pub(crate) const WASM32_WASIP1_THREADS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// WebAssembly
@@ -2452,6 +2652,7 @@ pub(crate) const WASM32_WASIP2: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// WebAssembly
@@ -2463,6 +2664,7 @@ pub(crate) const WASM64_UNKNOWN_UNKNOWN: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// 64-bit macOS (10.12+, Sierra+)
@@ -2474,6 +2676,7 @@ pub(crate) const X86_64_APPLE_DARWIN: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::One,
+    has_std: true,
 };
 
 /// 64-bit x86 iOS
@@ -2485,6 +2688,7 @@ pub(crate) const X86_64_APPLE_IOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// Apple Catalyst on x86_64
@@ -2496,6 +2700,7 @@ pub(crate) const X86_64_APPLE_IOS_MACABI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// x86 64-bit tvOS
@@ -2507,6 +2712,7 @@ pub(crate) const X86_64_APPLE_TVOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// x86 64-bit Apple WatchOS simulator
@@ -2518,6 +2724,7 @@ pub(crate) const X86_64_APPLE_WATCHOS_SIM: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// [Fortanix ABI] for 64-bit Intel SGX
@@ -2529,6 +2736,7 @@ pub(crate) const X86_64_FORTANIX_UNKNOWN_SGX: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// Alias for `x86_64-unknown-fuchsia`
@@ -2540,6 +2748,7 @@ pub(crate) const X86_64_FUCHSIA: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 64-bit x86 Android
@@ -2551,6 +2760,7 @@ pub(crate) const X86_64_LINUX_ANDROID: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 pub(crate) const X86_64_PC_NTO_QNX710: Platform = Platform {
@@ -2561,6 +2771,7 @@ pub(crate) const X86_64_PC_NTO_QNX710: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 64-bit Solaris 11, illumos
@@ -2572,6 +2783,7 @@ pub(crate) const X86_64_PC_SOLARIS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 64-bit MinGW (Windows 10+)
@@ -2583,6 +2795,7 @@ pub(crate) const X86_64_PC_WINDOWS_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::One,
+    has_std: true,
 };
 
 pub(crate) const X86_64_PC_WINDOWS_GNULLVM: Platform = Platform {
@@ -2593,6 +2806,7 @@ pub(crate) const X86_64_PC_WINDOWS_GNULLVM: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 64-bit MSVC (Windows 10+)
@@ -2604,6 +2818,7 @@ pub(crate) const X86_64_PC_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::One,
+    has_std: true,
 };
 
 /// 64-bit Unikraft with musl 1.2.3
@@ -2615,6 +2830,7 @@ pub(crate) const X86_64_UNIKRAFT_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 64-bit DragonFlyBSD
@@ -2626,6 +2842,7 @@ pub(crate) const X86_64_UNKNOWN_DRAGONFLY: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 64-bit FreeBSD
@@ -2637,6 +2854,7 @@ pub(crate) const X86_64_UNKNOWN_FREEBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 64-bit x86 Fuchsia
@@ -2648,6 +2866,7 @@ pub(crate) const X86_64_UNKNOWN_FUCHSIA: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 64-bit Haiku
@@ -2659,6 +2878,7 @@ pub(crate) const X86_64_UNKNOWN_HAIKU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// x86_64 Hermit
@@ -2670,6 +2890,7 @@ pub(crate) const X86_64_UNKNOWN_HERMIT: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// illumos
@@ -2681,6 +2902,7 @@ pub(crate) const X86_64_UNKNOWN_ILLUMOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 pub(crate) const X86_64_UNKNOWN_L4RE_UCLIBC: Platform = Platform {
@@ -2691,6 +2913,7 @@ pub(crate) const X86_64_UNKNOWN_L4RE_UCLIBC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: false,
 };
 
 /// 64-bit Linux (kernel 3.2+, glibc 2.17+)
@@ -2702,6 +2925,7 @@ pub(crate) const X86_64_UNKNOWN_LINUX_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::One,
+    has_std: true,
 };
 
 /// 64-bit Linux (x32 ABI) (kernel 4.15, glibc 2.27)
@@ -2713,6 +2937,7 @@ pub(crate) const X86_64_UNKNOWN_LINUX_GNUX32: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U32,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 64-bit Linux with musl 1.2.3
@@ -2724,6 +2949,7 @@ pub(crate) const X86_64_UNKNOWN_LINUX_MUSL: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// x86_64 OpenHarmony
@@ -2735,6 +2961,7 @@ pub(crate) const X86_64_UNKNOWN_LINUX_OHOS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// NetBSD/amd64
@@ -2746,6 +2973,7 @@ pub(crate) const X86_64_UNKNOWN_NETBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// Freestanding/bare-metal x86_64, softfloat
@@ -2757,6 +2985,7 @@ pub(crate) const X86_64_UNKNOWN_NONE: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: false,
 };
 
 /// 64-bit OpenBSD
@@ -2768,6 +2997,7 @@ pub(crate) const X86_64_UNKNOWN_OPENBSD: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// Redox OS
@@ -2779,6 +3009,7 @@ pub(crate) const X86_64_UNKNOWN_REDOX: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: true,
 };
 
 /// 64-bit UEFI
@@ -2790,6 +3021,7 @@ pub(crate) const X86_64_UNKNOWN_UEFI: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Two,
+    has_std: false,
 };
 
 pub(crate) const X86_64_UWP_WINDOWS_GNU: Platform = Platform {
@@ -2800,6 +3032,7 @@ pub(crate) const X86_64_UWP_WINDOWS_GNU: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 pub(crate) const X86_64_UWP_WINDOWS_MSVC: Platform = Platform {
@@ -2810,6 +3043,7 @@ pub(crate) const X86_64_UWP_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// 64-bit Windows 7 support
@@ -2821,6 +3055,7 @@ pub(crate) const X86_64_WIN7_WINDOWS_MSVC: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 pub(crate) const X86_64_WRS_VXWORKS: Platform = Platform {
@@ -2831,6 +3066,7 @@ pub(crate) const X86_64_WRS_VXWORKS: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
 
 /// macOS with late-gen Intel (at least Haswell)
@@ -2842,4 +3078,5 @@ pub(crate) const X86_64H_APPLE_DARWIN: Platform = Platform {
     target_endian: Endian::Little,
     target_pointer_width: PointerWidth::U64,
     tier: Tier::Three,
+    has_std: true,
 };
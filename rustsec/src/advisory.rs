@@ -3,36 +3,47 @@
 pub mod affected;
 mod category;
 mod date;
+mod fixed_by;
 mod id;
 mod informational;
 mod keyword;
+mod labeled_cvss;
 mod license;
 pub mod linter;
+pub mod markdown;
 mod metadata;
+mod namespace;
 mod parts;
+mod reference;
+mod template;
 pub(crate) mod versions;
 
 pub use self::{
     affected::Affected,
     category::Category,
     date::Date,
+    fixed_by::FixedBy,
     id::{Id, IdKind},
     informational::Informational,
     keyword::Keyword,
+    labeled_cvss::{CvssSelection, LabeledCvss},
     license::License,
     linter::Linter,
     metadata::Metadata,
+    namespace::Namespace,
     parts::Parts,
+    reference::{Reference, ReferenceKind},
+    template::Template,
     versions::Versions,
 };
 pub use cvss::Severity;
 
-use crate::{
-    error::{Error, ErrorKind},
-    fs,
-};
+use crate::error::{Error, ErrorKind};
 use serde::{Deserialize, Serialize};
-use std::{path::Path, str::FromStr};
+use std::str::FromStr;
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use {crate::fs, std::path::Path};
 
 /// RustSec Security Advisories
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -46,19 +57,35 @@ pub struct Advisory {
 
     /// Versions related to this advisory which are patched or unaffected.
     pub versions: Versions,
+
+    /// The (optional) `[fixed_by]` section of a RustSec advisory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed_by: Option<FixedBy>,
 }
 
 impl Advisory {
     /// Load an advisory from a `RUSTSEC-20XX-NNNN.md` file
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn load_file(path: impl AsRef<Path>) -> Result<Self, Error> {
         let path = path.as_ref();
 
-        let advisory_data = fs::read_to_string(path)
-            .map_err(|e| format_err!(ErrorKind::Io, "couldn't open {}: {}", path.display(), e))?;
-
-        advisory_data
-            .parse()
-            .map_err(|e| format_err!(ErrorKind::Parse, "error parsing {}: {}", path.display(), e))
+        let advisory_data = fs::read_to_string(path).map_err(|e| {
+            Error::with_source(
+                ErrorKind::Io,
+                format!("couldn't open {}", path.display()),
+                e,
+            )
+            .with_path(path)
+        })?;
+
+        advisory_data.parse().map_err(|e: Error| {
+            Error::with_source(
+                ErrorKind::Parse,
+                format!("error parsing {}", path.display()),
+                e,
+            )
+            .with_path(path)
+        })
     }
 
     /// Get advisory ID
@@ -76,14 +103,29 @@ impl Advisory {
         self.metadata.description.as_ref()
     }
 
+    /// Get advisory description with its Markdown formatting removed
+    pub fn description_plaintext(&self) -> String {
+        markdown::to_plaintext(self.description())
+    }
+
     /// Get the date the underlying issue was reported on
     pub fn date(&self) -> &Date {
         &self.metadata.date
     }
 
-    /// Get the severity of this advisory if it has a CVSS v3 associated
+    /// Get the severity of this advisory if it has a CVSS v3 associated.
+    ///
+    /// If the advisory has several CVSS scores, the highest one is used.
     pub fn severity(&self) -> Option<Severity> {
-        self.metadata.cvss.as_ref().map(|cvss| cvss.severity())
+        self.severity_with(&CvssSelection::default())
+    }
+
+    /// Get the severity of this advisory according to the CVSS score selected
+    /// by the given [`CvssSelection`]
+    pub fn severity_with(&self, selection: &CvssSelection) -> Option<Severity> {
+        self.metadata
+            .selected_cvss(selection)
+            .map(|(_, cvss)| cvss.severity())
     }
 
     /// Whether the advisory has been withdrawn, i.e. soft-deleted
@@ -5,6 +5,7 @@ use serde::{de, Deserialize, Serialize};
 use std::{
     fmt::{self, Display},
     str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 /// Minimum allowed year on advisory dates
@@ -38,6 +39,46 @@ impl Date {
         self.0.as_ref()
     }
 
+    /// Get the current date (in UTC)
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        Self::from_days_since_epoch((secs / 86_400) as i64)
+    }
+
+    /// Get the number of days from `earlier` to this date (negative if
+    /// `earlier` is actually a later date)
+    pub fn days_since(&self, earlier: &Date) -> i64 {
+        self.days_since_epoch() - earlier.days_since_epoch()
+    }
+
+    /// Number of days since 1970-01-01 in the proleptic Gregorian calendar
+    pub(crate) fn days_since_epoch(&self) -> i64 {
+        let (month, day) = (i64::from(self.month()), i64::from(self.day()));
+        let year = i64::from(self.year()) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Date the given number of days after 1970-01-01
+    fn from_days_since_epoch(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date(format!("{:04}-{:02}-{:02}", year, month, day))
+    }
+
     /// Get a specific component of the date by numerical offset
     fn component(&self, index: usize) -> Option<u32> {
         self.0
@@ -142,4 +183,23 @@ mod tests {
         assert_eq!(date.month(), 1);
         assert_eq!(date.day(), 2);
     }
+
+    #[test]
+    fn days_since_test() {
+        let date = |s| Date::from_str(s).unwrap();
+        assert_eq!(date("2000-01-02").days_since(&date("2000-01-01")), 1);
+        assert_eq!(date("2000-03-01").days_since(&date("2000-02-28")), 2);
+        assert_eq!(date("2001-03-01").days_since(&date("2001-02-28")), 1);
+        assert_eq!(date("2024-01-01").days_since(&date("2023-01-01")), 365);
+        assert_eq!(date("2023-01-01").days_since(&date("2024-01-01")), -365);
+
+        for s in ["2000-01-01", "2000-02-29", "2023-12-31", "2099-03-01"] {
+            assert_eq!(
+                Date::from_days_since_epoch(date(s).days_since_epoch()),
+                date(s)
+            );
+        }
+        assert_eq!(date("2000-01-01").days_since_epoch(), 10_957);
+        assert!(Date::from_str(Date::today().as_str()).is_ok());
+    }
 }
@@ -0,0 +1,41 @@
+//! The `[fixed_by]` subsection of an advisory: links to the change which
+//! patched the issue.
+
+use super::{Reference, ReferenceKind};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The `[fixed_by]` subsection of an advisory: machine-readable links to the
+/// commit and pull request (or release) which patched the issue, so tooling
+/// can point directly at the fix.
+///
+/// ```toml
+/// [fixed_by]
+/// commit = "https://github.com/example/base/commit/8f6d2a1"
+/// pr = "https://github.com/example/base/pull/42"
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixedBy {
+    /// URL of the commit which fixed the issue
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<Url>,
+
+    /// URL of the pull request or release which fixed the issue
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr: Option<Url>,
+}
+
+impl FixedBy {
+    /// Get the links to the fix as [`ReferenceKind::Fix`] references
+    pub fn references(&self) -> Vec<Reference> {
+        [&self.commit, &self.pr]
+            .into_iter()
+            .flatten()
+            .map(|url| Reference {
+                kind: ReferenceKind::Fix,
+                url: url.clone(),
+            })
+            .collect()
+    }
+}
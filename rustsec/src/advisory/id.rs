@@ -1,6 +1,9 @@
 //! Advisory identifiers
 
-use super::date::{YEAR_MAX, YEAR_MIN};
+use super::{
+    date::{YEAR_MAX, YEAR_MIN},
+    namespace,
+};
 use crate::error::{Error, ErrorKind};
 use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
@@ -59,11 +62,26 @@ impl Id {
         self.kind == IdKind::Talos
     }
 
+    /// Is this advisory ID in a custom [`Namespace`][`super::Namespace`]?
+    pub fn is_custom(&self) -> bool {
+        self.kind == IdKind::Custom
+    }
+
     /// Is this an unknown kind of advisory ID?
     pub fn is_other(&self) -> bool {
         self.kind == IdKind::Other
     }
 
+    /// Get the prefix of the custom [`Namespace`][`super::Namespace`] this
+    /// advisory ID belongs to (if any)
+    pub fn namespace(&self) -> Option<&str> {
+        if self.is_custom() {
+            self.string.split('-').next()
+        } else {
+            None
+        }
+    }
+
     /// Get the year this vulnerability was published (if known)
     pub fn year(&self) -> Option<u32> {
         self.year
@@ -83,7 +101,10 @@ impl Id {
             .and_then(|s| str::parse(s).ok())
     }
 
-    /// Get a URL to a web page with more information on this advisory
+    /// Get a URL to a web page with more information on this advisory.
+    ///
+    /// URLs of IDs in custom namespaces are provided by
+    /// [`Namespace::advisory_url`][`super::Namespace::advisory_url`] instead.
     // TODO(tarcieri): look up GHSA URLs via the GraphQL API?
     // <https://developer.github.com/v4/object/securityadvisory/>
     pub fn url(&self) -> Option<String> {
@@ -144,7 +165,9 @@ impl FromStr for Id {
 
         // Ensure known advisory types are well-formed
         let year = match kind {
-            IdKind::RustSec | IdKind::Cve | IdKind::Talos => Some(parse_year(advisory_id)?),
+            IdKind::RustSec | IdKind::Cve | IdKind::Talos | IdKind::Custom => {
+                Some(parse_year(advisory_id)?)
+            }
             _ => None,
         };
 
@@ -184,6 +207,10 @@ pub enum IdKind {
     /// Cisco Talos identifiers
     Talos,
 
+    /// Identifiers of the form `PREFIX-YYYY-NNNN` in a custom
+    /// [`Namespace`][`super::Namespace`]
+    Custom,
+
     /// Other types of advisory identifiers we don't know about
     Other,
 }
@@ -199,12 +226,32 @@ impl IdKind {
             IdKind::Talos
         } else if string.starts_with("GHSA-") {
             IdKind::Ghsa
+        } else if is_custom(string) {
+            IdKind::Custom
         } else {
             IdKind::Other
         }
     }
 }
 
+/// Is the given string of the form `PREFIX-YYYY-NNNN`, with a prefix which is
+/// valid for a custom namespace?
+fn is_custom(advisory_id: &str) -> bool {
+    let parts = advisory_id.split('-').collect::<Vec<_>>();
+    let is_number = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+
+    match parts.as_slice() {
+        [prefix, year, number] => {
+            namespace::is_valid_prefix(prefix)
+                && !namespace::is_reserved_prefix(prefix)
+                && year.len() == 4
+                && is_number(year)
+                && is_number(number)
+        }
+        _ => false,
+    }
+}
+
 /// Parse the year from an advisory identifier
 fn parse_year(advisory_id: &str) -> Result<u32, Error> {
     let mut parts = advisory_id.split('-');
@@ -309,6 +356,24 @@ mod tests {
         assert_eq!(talos_id.numerical_part().unwrap(), 468);
     }
 
+    #[test]
+    fn custom_id_test() {
+        let custom_id = "IDTEST-2023-0007".parse::<Id>().unwrap();
+        assert!(custom_id.is_custom());
+        assert_eq!(custom_id.namespace().unwrap(), "IDTEST");
+        assert_eq!(custom_id.year().unwrap(), 2023);
+        assert!(custom_id.url().is_none());
+        assert_eq!(custom_id.numerical_part().unwrap(), 7);
+
+        assert!("IDTEST-1900-0001".parse::<Id>().is_err());
+
+        // IDs not of the form `PREFIX-YYYY-NNNN` are other kinds of IDs
+        assert!("IDTEST-2023".parse::<Id>().unwrap().is_other());
+        assert!("IDTEST-2023-abc".parse::<Id>().unwrap().is_other());
+        assert!("idtest-2023-0007".parse::<Id>().unwrap().is_other());
+        assert!("IDTEST-23-0007".parse::<Id>().unwrap().is_other());
+    }
+
     #[test]
     fn other_id_test() {
         let other_id = EXAMPLE_UNKNOWN_ID.parse::<Id>().unwrap();
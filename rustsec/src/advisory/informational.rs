@@ -24,6 +24,10 @@ pub enum Informational {
     /// [Undefined Behavior]: https://doc.rust-lang.org/reference/behavior-considered-undefined.html
     Unsound,
 
+    /// Crate has been formally deprecated by its authors, usually in favor of
+    /// another crate
+    Deprecated,
+
     /// Other types of informational advisories: left open-ended to add
     /// more of them in the future.
     Other(String),
@@ -36,6 +40,7 @@ impl Informational {
             Self::Notice => "notice",
             Self::Unmaintained => "unmaintained",
             Self::Unsound => "unsound",
+            Self::Deprecated => "deprecated",
             Self::Other(other) => other,
         }
     }
@@ -55,6 +60,11 @@ impl Informational {
         *self == Self::Unsound
     }
 
+    /// Is this informational advisory for a `deprecated` crate?
+    pub fn is_deprecated(&self) -> bool {
+        *self == Self::Deprecated
+    }
+
     /// Is this informational advisory of an unknown kind?
     pub fn is_other(&self) -> bool {
         matches!(self, Self::Other(_))
@@ -66,6 +76,7 @@ impl Informational {
             Self::Notice => Some(warning::WarningKind::Notice),
             Self::Unmaintained => Some(warning::WarningKind::Unmaintained),
             Self::Unsound => Some(warning::WarningKind::Unsound),
+            Self::Deprecated => Some(warning::WarningKind::Deprecated),
             Self::Other(_) => None,
         }
     }
@@ -85,6 +96,7 @@ impl FromStr for Informational {
             "notice" => Self::Notice,
             "unmaintained" => Self::Unmaintained,
             "unsound" => Self::Unsound,
+            "deprecated" => Self::Deprecated,
             other => Self::Other(other.to_owned()),
         })
     }
@@ -122,6 +134,17 @@ mod tests {
         assert_eq!("unmaintained", unmaintained.as_str());
     }
 
+    #[test]
+    fn parse_deprecated() {
+        let deprecated = "deprecated".parse::<Informational>().unwrap();
+        assert_eq!(Informational::Deprecated, deprecated);
+        assert_eq!("deprecated", deprecated.as_str());
+        assert_eq!(
+            deprecated.warning_kind(),
+            Some(crate::warning::WarningKind::Deprecated)
+        );
+    }
+
     #[test]
     fn parse_other() {
         let other = "foobar".parse::<Informational>().unwrap();
@@ -0,0 +1,38 @@
+//! CVSS scores which only apply to some configurations of a crate
+
+use serde::{Deserialize, Serialize};
+
+/// CVSS v3.1 vector labeled with the configuration or platform it applies to,
+/// for issues whose severity depends on how the affected crate is used.
+///
+/// Example:
+///
+/// ```toml
+/// cvss_scores = [
+///     { label = "windows", cvss = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H" },
+/// ]
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LabeledCvss {
+    /// Configuration or platform this score applies to (e.g. `windows`)
+    pub label: String,
+
+    /// CVSS v3.1 Base Metrics vector string
+    pub cvss: cvss::v3::Base,
+}
+
+/// Which of an advisory's CVSS scores determines its severity
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CvssSelection {
+    /// Use the highest of all scores
+    #[default]
+    Highest,
+
+    /// Use the lowest of all scores
+    Lowest,
+
+    /// Use the score with the given label, falling back to the highest of all
+    /// scores if the advisory has no score with that label
+    Label(String),
+}
@@ -5,9 +5,11 @@
 
 use super::{parts, Advisory, Category};
 use crate::advisory::license::License;
-use crate::fs;
+use std::fmt;
 use std::str::FromStr;
-use std::{fmt, path::Path};
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use {crate::fs, std::path::Path};
 
 /// Lint information about a particular advisory
 #[derive(Debug)]
@@ -21,6 +23,7 @@ pub struct Linter {
 
 impl Linter {
     /// Lint the advisory TOML file located at the given path
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn lint_file<P: AsRef<Path>>(path: P) -> Result<Self, crate::Error> {
         let path = path.as_ref();
 
@@ -84,6 +87,7 @@ impl Linter {
                     "advisory" => self.lint_metadata(value),
                     "versions" => self.lint_versions(value),
                     "affected" => self.lint_affected(value),
+                    "fixed_by" => self.lint_fixed_by(value),
                     _ => self.errors.push(Error {
                         kind: ErrorKind::key(key),
                         section: None,
@@ -108,7 +112,10 @@ impl Linter {
             for (key, value) in table {
                 match key.as_str() {
                     "id" => {
-                        if self.advisory.metadata.id.is_other() {
+                        // IDs in custom namespaces are only meant for private
+                        // databases, not for advisories submitted to this one
+                        let id = &self.advisory.metadata.id;
+                        if id.is_other() || id.is_custom() {
                             self.errors.push(Error {
                                 kind: ErrorKind::value("id", value.to_string()),
                                 section: Some("advisory"),
@@ -216,8 +223,9 @@ impl Linter {
                             }
                         }
                     }
-                    "aliases" | "cvss" | "keywords" | "package" | "references" | "related"
-                    | "title" | "withdrawn" | "description" => (),
+                    "aliases" | "alternatives" | "cvss" | "cvss_scores" | "keywords"
+                    | "package" | "references" | "related" | "superseded_by" | "title"
+                    | "withdrawn" | "description" => (),
                     _ => self.errors.push(Error {
                         kind: ErrorKind::key(key),
                         section: Some("advisory"),
@@ -250,6 +258,22 @@ impl Linter {
         }
     }
 
+    /// Lint the `[fixed_by]` section of an advisory
+    fn lint_fixed_by(&mut self, fixed_by: &toml::Value) {
+        if let Some(table) = fixed_by.as_table() {
+            for (key, _) in table {
+                match key.as_str() {
+                    "commit" | "pr" => (),
+                    _ => self.errors.push(Error {
+                        kind: ErrorKind::key(key),
+                        section: Some("fixed_by"),
+                        message: None,
+                    }),
+                }
+            }
+        }
+    }
+
     /// Lint the `[affected]` section of an advisory
     fn lint_affected(&mut self, affected: &toml::Value) {
         if let Some(table) = affected.as_table() {
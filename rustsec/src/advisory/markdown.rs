@@ -0,0 +1,338 @@
+//! Rendering of Markdown advisory descriptions, e.g. to plain text or to a
+//! terminal
+
+use comrak::{
+    arena_tree::Node,
+    nodes::{Ast, ListType, NodeValue},
+    Arena, Options,
+};
+use std::{cell::RefCell, convert::Infallible};
+
+/// Style of the text being rendered
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Style {
+    /// Bold text, e.g. `**strong**` emphasis or headings
+    pub bold: bool,
+
+    /// Italic text, e.g. `_emphasis_`
+    pub italic: bool,
+
+    /// Underlined text, e.g. headings and links
+    pub underline: bool,
+
+    /// Code, e.g. `` `code` `` spans and code blocks
+    pub code: bool,
+}
+
+/// Destination of rendered Markdown
+pub trait Output {
+    /// Error writing to the output
+    type Error;
+
+    /// Write text, which doesn't contain newlines unless it's a single `\n`
+    fn write_str(&mut self, text: &str) -> Result<(), Self::Error>;
+
+    /// Change the style of the text written from now on
+    fn set_style(&mut self, _style: Style) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl Output for String {
+    type Error = Infallible;
+
+    fn write_str(&mut self, text: &str) -> Result<(), Infallible> {
+        self.push_str(text);
+        Ok(())
+    }
+}
+
+/// Render the given Markdown text to the given output.
+///
+/// Blocks are separated by blank lines, list items are marked with `- ` (or
+/// their number), block quotes and code blocks are indented, and links are
+/// rendered as `text (url)`. Formatting is conveyed through [`Style`]s.
+pub fn render<O: Output + ?Sized>(markdown: &str, out: &mut O) -> Result<(), O::Error> {
+    let arena = Arena::new();
+    let root = comrak::parse_document(&arena, markdown, &Options::default());
+
+    let mut renderer = Renderer {
+        out,
+        style: Style::default(),
+        indent: String::new(),
+        at_line_start: true,
+        needs_blank_line: false,
+    };
+    renderer.children(root)?;
+    renderer.out.set_style(Style::default())
+}
+
+/// Strip Markdown formatting from the given text
+pub(crate) fn to_plaintext(markdown: &str) -> String {
+    let mut output = String::new();
+    match render(markdown, &mut output) {
+        Ok(()) => output.trim_end().to_owned(),
+        Err(never) => match never {},
+    }
+}
+
+/// Renderer state
+struct Renderer<'o, O: Output + ?Sized> {
+    /// Output the text is rendered to
+    out: &'o mut O,
+
+    /// Style of the text currently being written
+    style: Style,
+
+    /// Prefix written at the start of each line (e.g. within list items)
+    indent: String,
+
+    /// Is the cursor at the start of a line?
+    at_line_start: bool,
+
+    /// Should a blank line be written before the next block?
+    needs_blank_line: bool,
+}
+
+impl<'o, O: Output + ?Sized> Renderer<'o, O> {
+    /// Render all children of the given node
+    fn children<'a>(&mut self, node: &'a Node<'a, RefCell<Ast>>) -> Result<(), O::Error> {
+        for child in node.children() {
+            self.node(child)?;
+        }
+        Ok(())
+    }
+
+    /// Render the given node
+    fn node<'a>(&mut self, node: &'a Node<'a, RefCell<Ast>>) -> Result<(), O::Error> {
+        let value = node.data.borrow().value.clone();
+
+        match value {
+            NodeValue::Heading(_) => {
+                self.start_block()?;
+                self.styled(node, |style| {
+                    style.bold = true;
+                    style.underline = true;
+                })?;
+                self.end_block()
+            }
+            NodeValue::Paragraph => {
+                self.start_block()?;
+                self.children(node)?;
+                self.end_block()
+            }
+            NodeValue::BlockQuote => self.indented(node, "  "),
+            NodeValue::List(list) => {
+                self.start_block()?;
+                for (index, item) in node.children().enumerate() {
+                    let marker = match list.list_type {
+                        ListType::Bullet => "- ".to_owned(),
+                        ListType::Ordered => format!("{}. ", list.start + index),
+                    };
+                    self.text(&marker)?;
+                    let indent = " ".repeat(marker.chars().count());
+                    self.indent.push_str(&indent);
+                    // Suppress the blank line before the item's first block
+                    self.needs_blank_line = false;
+                    self.at_line_start = false;
+                    self.children(item)?;
+                    self.indent.truncate(self.indent.len() - indent.len());
+                    self.needs_blank_line = !list.tight;
+                }
+                self.end_block()
+            }
+            NodeValue::CodeBlock(code) => {
+                self.start_block()?;
+                self.set_style(|style| style.code = true)?;
+                for line in code.literal.lines() {
+                    self.text("    ")?;
+                    self.text(line)?;
+                    self.newline()?;
+                }
+                self.set_style(|style| style.code = false)?;
+                self.end_block()
+            }
+            NodeValue::ThematicBreak => {
+                self.start_block()?;
+                self.text("---")?;
+                self.end_block()
+            }
+            NodeValue::HtmlBlock(html) => {
+                self.start_block()?;
+                self.text(html.literal.trim_end())?;
+                self.end_block()
+            }
+            NodeValue::Text(text) => self.text(&text),
+            NodeValue::Code(code) => {
+                self.set_style(|style| style.code = true)?;
+                self.text(&code.literal)?;
+                self.set_style(|style| style.code = false)
+            }
+            NodeValue::HtmlInline(html) => self.text(&html),
+            NodeValue::SoftBreak | NodeValue::LineBreak => self.newline(),
+            NodeValue::Emph => self.styled(node, |style| style.italic = true),
+            NodeValue::Strong => self.styled(node, |style| style.bold = true),
+            NodeValue::Link(link) => {
+                self.styled(node, |style| style.underline = true)?;
+                if collect_text(node) != link.url {
+                    self.text(&format!(" ({})", link.url))?;
+                }
+                Ok(())
+            }
+            _ => self.children(node),
+        }
+    }
+
+    /// Render the children of the given node with the given line prefix
+    fn indented<'a>(
+        &mut self,
+        node: &'a Node<'a, RefCell<Ast>>,
+        prefix: &str,
+    ) -> Result<(), O::Error> {
+        self.start_block()?;
+        self.indent.push_str(prefix);
+        self.needs_blank_line = false;
+        self.children(node)?;
+        self.indent.truncate(self.indent.len() - prefix.len());
+        self.end_block()
+    }
+
+    /// Render the children of the given node with a modified style
+    fn styled<'a>(
+        &mut self,
+        node: &'a Node<'a, RefCell<Ast>>,
+        modify: impl Fn(&mut Style),
+    ) -> Result<(), O::Error> {
+        let previous = self.style;
+        self.set_style(modify)?;
+        self.children(node)?;
+        self.style = previous;
+        self.out.set_style(self.style)
+    }
+
+    /// Modify the current style
+    fn set_style(&mut self, modify: impl Fn(&mut Style)) -> Result<(), O::Error> {
+        modify(&mut self.style);
+        self.out.set_style(self.style)
+    }
+
+    /// Write a blank line if the previous block requires one
+    fn start_block(&mut self) -> Result<(), O::Error> {
+        if self.needs_blank_line {
+            self.newline()?;
+            self.needs_blank_line = false;
+        }
+        Ok(())
+    }
+
+    /// Finish the current block
+    fn end_block(&mut self) -> Result<(), O::Error> {
+        if !self.at_line_start {
+            self.newline()?;
+        }
+        self.needs_blank_line = true;
+        Ok(())
+    }
+
+    /// Write text which doesn't contain newlines
+    fn text(&mut self, text: &str) -> Result<(), O::Error> {
+        if self.at_line_start && !self.indent.is_empty() {
+            self.out.write_str(&self.indent)?;
+        }
+        self.at_line_start = false;
+        self.out.write_str(text)
+    }
+
+    /// Start a new line
+    fn newline(&mut self) -> Result<(), O::Error> {
+        if self.at_line_start && !self.indent.is_empty() {
+            // Avoid trailing whitespace on blank lines within indented blocks
+            self.out.write_str(self.indent.trim_end())?;
+        }
+        self.out.write_str("\n")?;
+        self.at_line_start = true;
+        Ok(())
+    }
+}
+
+/// Collect the plain text contained in the given node
+fn collect_text<'a>(node: &'a Node<'a, RefCell<Ast>>) -> String {
+    node.descendants()
+        .filter_map(|descendant| match &descendant.data.borrow().value {
+            NodeValue::Text(text) => Some(text.clone()),
+            NodeValue::Code(code) => Some(code.literal.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, to_plaintext, Output, Style};
+    use std::convert::Infallible;
+
+    #[test]
+    fn strips_inline_formatting() {
+        assert_eq!(
+            to_plaintext("Use **`Foo::new`** in _all_ of `snake_case` code * 2"),
+            "Use Foo::new in all of snake_case code * 2"
+        );
+        assert_eq!(
+            to_plaintext(
+                "See [the issue](https://example.com/1 \"title\") or <https://example.com>"
+            ),
+            "See the issue (https://example.com/1) or https://example.com"
+        );
+        assert_eq!(to_plaintext(r"literal \*stars\*"), "literal *stars*");
+    }
+
+    #[test]
+    fn strips_block_formatting() {
+        let markdown =
+            "## Impact\n\n> quoted\n\n* one\n  + two\n\n---\n\n```rust\nlet _x = 1;\n```\n";
+        assert_eq!(
+            to_plaintext(markdown),
+            "Impact\n\n  quoted\n\n- one\n\n  - two\n\n---\n\n    let _x = 1;"
+        );
+    }
+
+    #[test]
+    fn styles_formatted_text() {
+        /// Records the text written in each style
+        #[derive(Default)]
+        struct Spans(Vec<(Style, String)>, Style);
+
+        impl Output for Spans {
+            type Error = Infallible;
+
+            fn write_str(&mut self, text: &str) -> Result<(), Infallible> {
+                self.0.push((self.1, text.to_owned()));
+                Ok(())
+            }
+
+            fn set_style(&mut self, style: Style) -> Result<(), Infallible> {
+                self.1 = style;
+                Ok(())
+            }
+        }
+
+        let mut spans = Spans::default();
+        render("**bold `code`**", &mut spans).unwrap();
+
+        let bold = Style {
+            bold: true,
+            ..Style::default()
+        };
+        let bold_code = Style { code: true, ..bold };
+        assert_eq!(
+            spans.0,
+            [
+                (bold, "bold ".to_owned()),
+                (bold_code, "code".to_owned()),
+                (Style::default(), "\n".to_owned()),
+            ]
+        );
+        assert_eq!(spans.1, Style::default());
+    }
+}
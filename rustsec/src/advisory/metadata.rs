@@ -1,11 +1,18 @@
 //! Advisory information (i.e. the `[advisory]` section)
 
 use super::{
-    category::Category, date::Date, id::Id, informational::Informational, keyword::Keyword,
+    category::Category,
+    date::Date,
+    id::Id,
+    informational::Informational,
+    keyword::Keyword,
+    labeled_cvss::{CvssSelection, LabeledCvss},
+    reference::{Reference, ReferenceKind},
 };
 use crate::advisory::license::License;
 use crate::{collection::Collection, package, SourceId};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use url::Url;
 
 /// The `[advisory]` section of a RustSec security advisory
@@ -60,13 +67,28 @@ pub struct Metadata {
     /// ```
     pub cvss: Option<cvss::v3::Base>,
 
+    /// Additional CVSS v3.1 vectors for issues whose severity depends on the
+    /// configuration or platform, each labeled with the one it applies to
+    #[serde(default)]
+    pub cvss_scores: Vec<LabeledCvss>,
+
     /// Informational advisories can be used to warn users about issues
     /// affecting a particular crate without failing the build.
     pub informational: Option<Informational>,
 
-    /// Additional reference URLs with more information related to this advisory
+    /// Crate which officially replaces the affected one (e.g. because the
+    /// affected crate was renamed or merged into another)
+    #[serde(default)]
+    pub superseded_by: Option<package::Name>,
+
+    /// Other crates which can be used instead of the affected one, e.g. as
+    /// replacements for an unmaintained crate
     #[serde(default)]
-    pub references: Vec<Url>,
+    pub alternatives: Vec<package::Name>,
+
+    /// Additional references with more information related to this advisory
+    #[serde(default)]
+    pub references: Vec<Reference>,
 
     /// Source URL where the vulnerable package is located/published.
     ///
@@ -87,3 +109,47 @@ pub struct Metadata {
     #[serde(default)]
     pub license: License,
 }
+
+impl Metadata {
+    /// Iterate over the URLs of the references of the given kind, e.g. to
+    /// find the commits or pull requests fixing the issue
+    pub fn references_of_kind(&self, kind: ReferenceKind) -> impl Iterator<Item = &Url> {
+        self.references
+            .iter()
+            .filter(move |reference| reference.kind == kind)
+            .map(|reference| &reference.url)
+    }
+
+    /// Iterate over all of the CVSS vectors of this advisory, starting with
+    /// the unlabeled `cvss` vector (if any), along with their labels
+    pub fn all_cvss(&self) -> impl Iterator<Item = (Option<&str>, &cvss::v3::Base)> {
+        self.cvss.iter().map(|cvss| (None, cvss)).chain(
+            self.cvss_scores
+                .iter()
+                .map(|labeled| (Some(labeled.label.as_str()), &labeled.cvss)),
+        )
+    }
+
+    /// Get the CVSS vector which determines the severity of this advisory
+    /// according to the given selection, along with its label
+    pub fn selected_cvss(
+        &self,
+        selection: &CvssSelection,
+    ) -> Option<(Option<&str>, &cvss::v3::Base)> {
+        let by_score = |a: &(Option<&str>, &cvss::v3::Base),
+                        b: &(Option<&str>, &cvss::v3::Base)| {
+            a.1.score()
+                .partial_cmp(&b.1.score())
+                .unwrap_or(Ordering::Equal)
+        };
+
+        match selection {
+            CvssSelection::Highest => self.all_cvss().max_by(by_score),
+            CvssSelection::Lowest => self.all_cvss().min_by(by_score),
+            CvssSelection::Label(label) => self
+                .all_cvss()
+                .find(|(l, _)| *l == Some(label.as_str()))
+                .or_else(|| self.all_cvss().max_by(by_score)),
+        }
+    }
+}
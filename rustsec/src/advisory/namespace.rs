@@ -0,0 +1,200 @@
+//! Custom advisory ID namespaces
+
+use super::{Advisory, Id};
+use crate::{
+    collection::Collection,
+    error::{Error, ErrorKind},
+};
+
+/// Prefixes of the advisory ID kinds known to this crate, which can't be
+/// used by custom namespaces
+const RESERVED_PREFIXES: &[&str] = &["RUSTSEC", "CVE", "GHSA", "TALOS"];
+
+/// Custom advisory ID namespace, e.g. for advisories in a private database.
+///
+/// IDs of the form `PREFIX-YYYY-NNNN` with a valid custom prefix are always
+/// parsed as [`IdKind::Custom`][`super::IdKind::Custom`] IDs, with their year
+/// validated like those of RustSec advisories. A `Namespace` describes the
+/// advisories with a given prefix, and is passed explicitly to the code which
+/// needs it, e.g. [`Database::check_namespaces`][`crate::Database::check_namespaces`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Namespace {
+    /// Prefix of the IDs in this namespace (e.g. `ACME` for `ACME-2023-0001`)
+    prefix: String,
+
+    /// Collection the advisories in this namespace must belong to (if any)
+    collection: Option<Collection>,
+
+    /// Template for the URLs of advisories, where `{id}` is replaced with the ID
+    url: Option<String>,
+}
+
+impl Namespace {
+    /// Create a namespace for IDs with the given prefix.
+    ///
+    /// Prefixes must consist of uppercase ASCII letters and digits.
+    pub fn new(prefix: impl Into<String>) -> Result<Self, Error> {
+        let prefix = prefix.into();
+
+        if !is_valid_prefix(&prefix) {
+            fail!(
+                ErrorKind::Parse,
+                "invalid advisory ID namespace prefix: {:?}",
+                prefix
+            );
+        }
+
+        if is_reserved_prefix(&prefix) {
+            fail!(
+                ErrorKind::Parse,
+                "reserved advisory ID namespace prefix: {}",
+                prefix
+            );
+        }
+
+        Ok(Self {
+            prefix,
+            collection: None,
+            url: None,
+        })
+    }
+
+    /// Only allow advisories in this namespace to be filed in the given collection
+    pub fn collection(mut self, collection: Collection) -> Self {
+        self.collection = Some(collection);
+        self
+    }
+
+    /// Set the template for the URLs of advisories in this namespace, where
+    /// `{id}` is replaced with the advisory's ID
+    /// (e.g. `https://security.example.com/advisories/{id}`)
+    pub fn url(mut self, template: impl Into<String>) -> Self {
+        self.url = Some(template.into());
+        self
+    }
+
+    /// Get the prefix of the IDs in this namespace
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Get the collection advisories in this namespace must belong to (if any)
+    pub fn required_collection(&self) -> Option<Collection> {
+        self.collection
+    }
+
+    /// Does the given advisory ID belong to this namespace?
+    pub fn contains(&self, id: &Id) -> bool {
+        id.namespace() == Some(self.prefix.as_str())
+    }
+
+    /// Get the URL of the advisory with the given ID, if it belongs to this
+    /// namespace
+    pub fn advisory_url(&self, id: &Id) -> Option<String> {
+        if !self.contains(id) {
+            return None;
+        }
+
+        self.url
+            .as_ref()
+            .map(|template| template.replace("{id}", id.as_str()))
+    }
+
+    /// Ensure the given advisory is filed in the collection this namespace
+    /// requires, if it belongs to it
+    pub fn check(&self, advisory: &Advisory) -> Result<(), Error> {
+        let id = &advisory.metadata.id;
+
+        if let (true, Some(required)) = (self.contains(id), self.collection) {
+            if advisory.metadata.collection != Some(required) {
+                fail!(
+                    ErrorKind::Repo,
+                    "expected {} to be in the {} collection",
+                    id,
+                    required
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Is the given string a valid prefix for IDs in a custom namespace?
+pub(crate) fn is_valid_prefix(prefix: &str) -> bool {
+    !prefix.is_empty()
+        && prefix
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+/// Is the given prefix reserved for a kind of advisory ID known to this crate?
+pub(crate) fn is_reserved_prefix(prefix: &str) -> bool {
+    RESERVED_PREFIXES.contains(&prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Namespace;
+    use crate::{advisory::Advisory, collection::Collection, error::ErrorKind};
+
+    #[test]
+    fn invalid_prefixes() {
+        assert!(Namespace::new("").is_err());
+        assert!(Namespace::new("acme").is_err());
+        assert!(Namespace::new("AC-ME").is_err());
+        assert!(Namespace::new("RUSTSEC").is_err());
+        assert!(Namespace::new("CVE").is_err());
+    }
+
+    #[test]
+    fn advisory_urls() {
+        let namespace = Namespace::new("NSTEST")
+            .unwrap()
+            .url("https://example.com/{id}.html");
+
+        assert_eq!(
+            namespace
+                .advisory_url(&"NSTEST-2023-0001".parse().unwrap())
+                .unwrap(),
+            "https://example.com/NSTEST-2023-0001.html"
+        );
+        assert!(namespace
+            .advisory_url(&"OTHER-2023-0001".parse().unwrap())
+            .is_none());
+        assert!(Namespace::new("NSTEST")
+            .unwrap()
+            .advisory_url(&"NSTEST-2023-0001".parse().unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn required_collection() {
+        let namespace = Namespace::new("NSTEST")
+            .unwrap()
+            .collection(Collection::Rust);
+
+        let template = std::fs::read_to_string("./tests/support/example_advisory_v3.md").unwrap();
+        let mut advisory: Advisory = template
+            .replace("RUSTSEC-2001-2101", "NSTEST-2001-0001")
+            .parse()
+            .unwrap();
+
+        advisory.metadata.collection = Some(Collection::Crates);
+        let err = namespace.check(&advisory).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Repo);
+        assert!(err
+            .to_string()
+            .ends_with("expected NSTEST-2001-0001 to be in the rust collection"));
+
+        advisory.metadata.collection = Some(Collection::Rust);
+        assert!(namespace.check(&advisory).is_ok());
+
+        // Advisories in other namespaces aren't checked
+        let other = Namespace::new("OTHER")
+            .unwrap()
+            .collection(Collection::Rust);
+        advisory.metadata.collection = Some(Collection::Crates);
+        assert!(other.check(&advisory).is_ok());
+    }
+}
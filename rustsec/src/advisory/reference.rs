@@ -0,0 +1,157 @@
+//! Typed references to more information about an advisory
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use url::Url;
+
+/// Reference URL with more information related to an advisory, along with
+/// the kind of information it points to.
+///
+/// References can either be given as tables or, like in advisories which
+/// predate typed references, as plain URLs, in which case their kind is
+/// guessed from the URL:
+///
+/// ```toml
+/// references = [
+///     { type = "FIX", url = "https://github.com/example/base/pull/1" },
+///     "https://github.com/example/base/issues/2",
+/// ]
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct Reference {
+    /// Kind of information the URL points to
+    #[serde(rename = "type")]
+    pub kind: ReferenceKind,
+
+    /// Reference URL
+    pub url: Url,
+}
+
+/// Kinds of advisory references, as defined by the [OSV schema]
+///
+/// [OSV schema]: https://ossf.github.io/osv-schema/#references-field
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReferenceKind {
+    /// Published security advisory for the vulnerability
+    Advisory,
+
+    /// Article or blog post describing the vulnerability
+    Article,
+
+    /// Report of the vulnerability, e.g. in an issue tracker
+    Report,
+
+    /// Source code change fixing the vulnerability, e.g. a commit or pull request
+    Fix,
+
+    /// Home page of the affected package
+    Package,
+
+    /// Any other web page
+    Web,
+}
+
+impl ReferenceKind {
+    /// Get a `str` representing a [`ReferenceKind`]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Advisory => "ADVISORY",
+            Self::Article => "ARTICLE",
+            Self::Report => "REPORT",
+            Self::Fix => "FIX",
+            Self::Package => "PACKAGE",
+            Self::Web => "WEB",
+        }
+    }
+
+    /// Guess the kind of information an untyped reference URL points to
+    pub fn guess(url: &Url) -> Self {
+        let str = url.as_str();
+        let forge = str.contains("://github.com/") || str.contains("://gitlab.");
+
+        if forge && str.contains("/issues/") {
+            Self::Report
+        } else if forge
+            && (str.contains("/commit/")
+                || str.contains("/pull/")
+                || str.contains("/merge_requests/"))
+        {
+            Self::Fix
+        // the check for "/advisories/" matches both RustSec and GHSA URLs
+        } else if str.contains("/advisories/") || str.contains("://cve.mitre.org/") {
+            Self::Advisory
+        } else if str.contains("://crates.io/crates/") {
+            Self::Package
+        } else {
+            Self::Web
+        }
+    }
+}
+
+impl fmt::Display for ReferenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<Url> for Reference {
+    fn from(url: Url) -> Self {
+        Self {
+            kind: ReferenceKind::guess(&url),
+            url,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Reference {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Url(Url),
+            Typed {
+                #[serde(rename = "type")]
+                kind: ReferenceKind,
+                url: Url,
+            },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Url(url) => url.into(),
+            Repr::Typed { kind, url } => Self { kind, url },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Reference, ReferenceKind};
+
+    #[test]
+    fn guess_kind() {
+        let kind = |url: &str| Reference::from(url.parse::<url::Url>().unwrap()).kind;
+
+        assert_eq!(
+            kind("https://github.com/example/base/issues/1"),
+            ReferenceKind::Report
+        );
+        assert_eq!(
+            kind("https://github.com/example/base/pull/2"),
+            ReferenceKind::Fix
+        );
+        assert_eq!(
+            kind("https://gitlab.com/example/base/-/commit/abcdef"),
+            ReferenceKind::Fix
+        );
+        assert_eq!(
+            kind("https://rustsec.org/advisories/RUSTSEC-2001-2101.html"),
+            ReferenceKind::Advisory
+        );
+        assert_eq!(
+            kind("https://crates.io/crates/base"),
+            ReferenceKind::Package
+        );
+        assert_eq!(kind("https://example.com/blog"), ReferenceKind::Web);
+    }
+}
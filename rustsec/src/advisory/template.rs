@@ -0,0 +1,241 @@
+//! Generating skeletons of new advisories
+
+use super::{Advisory, Category, Date, Id, Informational, Keyword};
+use crate::{error::Error, package};
+use semver::VersionReq;
+use std::fmt::Write;
+use url::Url;
+
+/// Description used when none is provided, to be filled in by hand
+const DESCRIPTION_PLACEHOLDER: &str = "TODO: describe the issue, its impact, and how to fix it.";
+
+/// Skeleton of a new advisory in the Markdown ("V3") format of the advisory
+/// database, e.g. for bots filing advisories about unmaintained crates.
+///
+/// The advisory gets the [`Id::PLACEHOLDER`] ID, which is replaced with a
+/// real one when it's merged into the database (see `rustsec-admin assign-id`).
+#[derive(Clone, Debug)]
+pub struct Template {
+    /// Name of the affected package
+    package: package::Name,
+
+    /// One-line title of the advisory
+    title: String,
+
+    /// Markdown description of the advisory
+    description: Option<String>,
+
+    /// Date the issue was reported on
+    date: Date,
+
+    /// URL with more details about the issue
+    url: Option<Url>,
+
+    /// Kind of informational advisory, if it isn't a vulnerability
+    informational: Option<Informational>,
+
+    /// Categories of the vulnerability
+    categories: Vec<Category>,
+
+    /// Freeform keywords
+    keywords: Vec<Keyword>,
+
+    /// Versions which fix the issue
+    patched: Vec<VersionReq>,
+
+    /// Versions which were never affected by the issue
+    unaffected: Vec<VersionReq>,
+}
+
+impl Template {
+    /// Start a new advisory with the given title about the given package,
+    /// reported today
+    pub fn new(package: package::Name, title: impl Into<String>) -> Self {
+        Self {
+            package,
+            title: title.into(),
+            description: None,
+            date: Date::today(),
+            url: None,
+            informational: None,
+            categories: Vec::new(),
+            keywords: Vec::new(),
+            patched: Vec::new(),
+            unaffected: Vec::new(),
+        }
+    }
+
+    /// Set the Markdown description of the advisory.
+    ///
+    /// By default, a placeholder to be filled in by hand is used.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the date the issue was reported on (default: today)
+    pub fn date(mut self, date: Date) -> Self {
+        self.date = date;
+        self
+    }
+
+    /// Set the URL with more details about the issue (e.g. a bug report)
+    pub fn url(mut self, url: Url) -> Self {
+        self.url = Some(url);
+        self
+    }
+
+    /// Make this an informational advisory of the given kind (e.g.
+    /// [`Informational::Unmaintained`]) rather than a vulnerability
+    pub fn informational(mut self, informational: Informational) -> Self {
+        self.informational = Some(informational);
+        self
+    }
+
+    /// Set the categories of the vulnerability
+    pub fn categories(mut self, categories: Vec<Category>) -> Self {
+        self.categories = categories;
+        self
+    }
+
+    /// Set freeform keywords describing the issue
+    pub fn keywords(mut self, keywords: Vec<Keyword>) -> Self {
+        self.keywords = keywords;
+        self
+    }
+
+    /// Set the ranges of versions which fix the issue.
+    ///
+    /// Advisories without patched versions affect every version of the
+    /// package, which is typical of unmaintained crates.
+    pub fn patched(mut self, patched: Vec<VersionReq>) -> Self {
+        self.patched = patched;
+        self
+    }
+
+    /// Set the ranges of versions which were never affected by the issue
+    pub fn unaffected(mut self, unaffected: Vec<VersionReq>) -> Self {
+        self.unaffected = unaffected;
+        self
+    }
+
+    /// Render the advisory as the contents of a `RUSTSEC-0000-0000.md` file.
+    ///
+    /// The result is parsed back to ensure it's a valid advisory, so this
+    /// fails if e.g. the patched and unaffected ranges overlap.
+    pub fn render(&self) -> Result<String, Error> {
+        let mut out = String::new();
+
+        // Writing to a `String` can't fail
+        writeln!(out, "```toml\n[advisory]").unwrap();
+        writeln!(out, "id = {}", toml_str(Id::PLACEHOLDER)).unwrap();
+        writeln!(out, "package = {}", toml_str(self.package.as_str())).unwrap();
+        writeln!(out, "date = {}", toml_str(self.date.as_str())).unwrap();
+        if let Some(url) = &self.url {
+            writeln!(out, "url = {}", toml_str(url.as_str())).unwrap();
+        }
+        if let Some(informational) = &self.informational {
+            writeln!(out, "informational = {}", toml_str(informational.as_str())).unwrap();
+        }
+        if !self.categories.is_empty() {
+            writeln!(out, "categories = {}", toml_list(&self.categories)).unwrap();
+        }
+        if !self.keywords.is_empty() {
+            let keywords: Vec<_> = self.keywords.iter().map(Keyword::as_str).collect();
+            writeln!(out, "keywords = {}", toml_list(&keywords)).unwrap();
+        }
+
+        writeln!(out, "\n[versions]").unwrap();
+        writeln!(out, "patched = {}", toml_list(&self.patched)).unwrap();
+        if !self.unaffected.is_empty() {
+            writeln!(out, "unaffected = {}", toml_list(&self.unaffected)).unwrap();
+        }
+
+        writeln!(out, "```\n\n# {}\n", self.title.trim()).unwrap();
+        let description = self
+            .description
+            .as_deref()
+            .unwrap_or(DESCRIPTION_PLACEHOLDER);
+        writeln!(out, "{}", description.trim()).unwrap();
+
+        out.parse::<Advisory>()?;
+        Ok(out)
+    }
+}
+
+/// Format a string as a TOML basic string
+fn toml_str(s: &str) -> String {
+    toml::Value::String(s.to_owned()).to_string()
+}
+
+/// Format the given items as a TOML array of strings
+fn toml_list<T: ToString>(items: &[T]) -> String {
+    let items: Vec<_> = items
+        .iter()
+        .map(|item| toml_str(&item.to_string()))
+        .collect();
+    format!("[{}]", items.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advisory::Linter;
+
+    #[test]
+    fn render_unmaintained() {
+        let rendered = Template::new("base".parse().unwrap(), "`base` is unmaintained")
+            .date("2001-02-03".parse().unwrap())
+            .url("https://example.com/base/issues/1".parse().unwrap())
+            .informational(Informational::Unmaintained)
+            .render()
+            .unwrap();
+
+        assert_eq!(
+            rendered,
+            r#"```toml
+[advisory]
+id = "RUSTSEC-0000-0000"
+package = "base"
+date = "2001-02-03"
+url = "https://example.com/base/issues/1"
+informational = "unmaintained"
+
+[versions]
+patched = []
+```
+
+# `base` is unmaintained
+
+TODO: describe the issue, its impact, and how to fix it.
+"#
+        );
+
+        let linter = Linter::lint_string(&rendered).unwrap();
+        assert!(linter.errors().is_empty(), "{:?}", linter.errors());
+    }
+
+    #[test]
+    fn render_vulnerability() {
+        let rendered = Template::new("base".parse().unwrap(), "Out-of-bounds \"read\"")
+            .description("The `All` function reads past the end of its buffer.")
+            .categories(vec![Category::MemoryCorruption])
+            .patched(vec![">= 1.2.3".parse().unwrap()])
+            .unaffected(vec!["< 1.0.0".parse().unwrap()])
+            .render()
+            .unwrap();
+
+        let advisory: Advisory = rendered.parse().unwrap();
+        assert!(advisory.id().is_placeholder());
+        assert_eq!(advisory.title(), "Out-of-bounds \"read\"");
+        assert_eq!(advisory.metadata.categories, [Category::MemoryCorruption]);
+        assert!(advisory.versions.is_vulnerable(&"1.2.2".parse().unwrap()));
+        assert!(!advisory.versions.is_vulnerable(&"0.9.0".parse().unwrap()));
+        assert_eq!(advisory.date(), &Date::today());
+
+        let overlapping = Template::new("base".parse().unwrap(), "Overlap")
+            .patched(vec![">= 1.0.0".parse().unwrap()])
+            .unaffected(vec![">= 1.2.0".parse().unwrap()]);
+        assert!(overlapping.render().is_err());
+    }
+}
@@ -1,17 +1,43 @@
-//! An efficient way to check whether a given package has been yanked
+//! An efficient way to check whether a given package has been yanked, or
+//! whether its checksum differs from the one published in the index, and to
+//! list the published versions of a crate
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     time::Duration,
 };
 
 use crate::{
-    error::{Error, ErrorKind},
-    package::{self, Package},
+    error::{is_tame_network_error, Error, ErrorKind},
+    package::{self, Checksum, Package},
+    retry::RetryPolicy,
+    Version,
 };
 
 pub use tame_index::external::reqwest::ClientBuilder;
+use tame_index::external::tokio;
 use tame_index::utils::flock::{FileLock, LockOptions};
 
+/// This is the timeout per individual crate. If a crate fails to be
+/// requested for a retriable reason then it will be retried until
+/// this time limit is reached
+const REQUEST_TIMEOUT: Option<Duration> = Some(Duration::from_secs(10));
+
+/// Time to wait for the configuration of a sparse index when checking
+/// whether it can be reached
+const CONFIG_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Results of looking up crates in a sparse index, keyed by crate name
+type KrateResults = BTreeMap<String, Result<Option<tame_index::IndexKrate>, tame_index::Error>>;
+
+/// Information about a single version of a crate in the index
+struct IndexedVersion {
+    /// Has this version been yanked?
+    yanked: bool,
+
+    /// SHA-256 checksum of the published `.crate` file
+    checksum: Checksum,
+}
+
 enum Index {
     Git(tame_index::index::RemoteGitIndex),
     SparseCached(tame_index::index::SparseIndex),
@@ -48,12 +74,14 @@ impl Index {
 /// will not be possible while this lock is held.
 pub struct CachedIndex {
     index: Index,
-    /// The inner hash map is logically HashMap<Version, IsYanked>
+    /// The inner hash map is logically HashMap<Version, IndexedVersion>
     /// but we don't parse semver because crates.io registry contains invalid semver:
     /// <https://github.com/rustsec/rustsec/issues/759>
-    cache: HashMap<package::Name, Result<Option<HashMap<String, bool>>, Error>>,
+    cache: HashMap<package::Name, Result<Option<HashMap<String, IndexedVersion>>, Error>>,
     /// The lock we hold on the Cargo cache directory
     lock: FileLock,
+    /// Policy for retrying crates which failed to download from a sparse index
+    retry: RetryPolicy,
 }
 
 impl CachedIndex {
@@ -72,24 +100,62 @@ impl CachedIndex {
     ///
     /// If `lock_timeout` is set to `std::time::Duration::from_secs(0)`, it will not wait at all,
     /// and instead return an error immediately if it fails to aquire the lock.
-    pub fn fetch(client: Option<ClientBuilder>, lock_timeout: Duration) -> Result<Self, Error> {
-        Self::fetch_inner(client, lock_timeout).map_err(Error::from_tame)
+    ///
+    /// Fetching a git index, or checking that a sparse one is reachable, is
+    /// retried according to `retry`, as is downloading crates from a sparse
+    /// index later on. If that keeps failing, it is reported as an
+    /// [`ErrorKind::Network`] error.
+    pub fn fetch(
+        client: Option<ClientBuilder>,
+        lock_timeout: Duration,
+        retry: RetryPolicy,
+    ) -> Result<Self, Error> {
+        Self::fetch_inner(client, lock_timeout, retry)
+    }
+
+    /// Async variant of [`CachedIndex::fetch`], which opens the index on
+    /// tokio's blocking thread pool.
+    ///
+    /// Must be called from within a tokio runtime.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub async fn fetch_async(
+        client: Option<ClientBuilder>,
+        lock_timeout: Duration,
+        retry: RetryPolicy,
+    ) -> Result<Self, Error> {
+        crate::task::spawn_blocking(ErrorKind::Registry, move || {
+            Self::fetch(client, lock_timeout, retry)
+        })
+        .await
     }
 
     fn fetch_inner(
         client: Option<ClientBuilder>,
         lock_timeout: Duration,
-    ) -> Result<Self, tame_index::Error> {
-        let index = tame_index::index::ComboIndexCache::new(tame_index::IndexLocation::new(
-            tame_index::IndexUrl::crates_io(None, None, None)?,
-        ))?;
+        retry: RetryPolicy,
+    ) -> Result<Self, Error> {
+        let index = tame_index::IndexUrl::crates_io(None, None, None)
+            .and_then(|url| {
+                tame_index::index::ComboIndexCache::new(tame_index::IndexLocation::new(url))
+            })
+            .map_err(Error::from_tame)?;
 
-        let lock = acquire_cargo_package_lock(lock_timeout)?;
+        let lock = acquire_cargo_package_lock(lock_timeout).map_err(Error::from_tame)?;
 
         let index = match index {
             tame_index::index::ComboIndexCache::Git(gi) => {
-                let mut rgi = tame_index::index::RemoteGitIndex::new(gi, &lock)?;
-                rgi.fetch(&lock)?;
+                let mut rgi =
+                    tame_index::index::RemoteGitIndex::new(gi, &lock).map_err(Error::from_tame)?;
+                retry.run(|| {
+                    rgi.fetch(&lock).map_err(|err| {
+                        Error::with_source(
+                            ErrorKind::Network,
+                            "failed to fetch crates.io index".to_owned(),
+                            err,
+                        )
+                    })
+                })?;
                 Index::Git(rgi)
             }
             tame_index::index::ComboIndexCache::Sparse(si) => {
@@ -97,7 +163,10 @@ impl CachedIndex {
                 // note: this would need to change if rustsec ever adds the capability
                 // to query other indices that _might_ not support HTTP/2, but
                 // hopefully that would never need to happen
-                let client = client_builder.build().map_err(tame_index::Error::from)?;
+                let client = client_builder
+                    .build()
+                    .map_err(|err| Error::from_tame(err.into()))?;
+                retry.run(|| check_reachable(&client, si.url()))?;
 
                 Index::SparseRemote(tame_index::index::AsyncRemoteSparseIndex::new(si, client))
             }
@@ -108,6 +177,7 @@ impl CachedIndex {
             index,
             cache: Default::default(),
             lock,
+            retry,
         })
     }
 
@@ -149,6 +219,8 @@ impl CachedIndex {
             index,
             cache: Default::default(),
             lock,
+            // Nothing is downloaded from an index opened locally
+            retry: RetryPolicy::none(),
         })
     }
 
@@ -164,7 +236,7 @@ impl CachedIndex {
             }
             Index::SparseRemote(rsi) => {
                 // Ensure we have a runtime
-                let rt = tame_index::external::tokio::runtime::Runtime::new().map_err(|err| {
+                let rt = tokio::runtime::Runtime::new().map_err(|err| {
                     format_err!(
                         ErrorKind::Registry,
                         "unable to start a tokio runtime: {}",
@@ -173,41 +245,74 @@ impl CachedIndex {
                 })?;
                 let _rt = rt.enter();
 
-                /// This is the timeout per individual crate. If a crate fails to be
-                /// requested for a retriable reason then it will be retried until
-                /// this time limit is reached
-                const REQUEST_TIMEOUT: Option<Duration> = Some(Duration::from_secs(10));
-
-                let results = rsi
-                    .krates_blocking(
-                        packages
-                            .into_iter()
-                            .map(|p| p.as_str().to_owned())
-                            .collect(),
-                        true,
-                        REQUEST_TIMEOUT,
-                        &self.lock,
-                    )
-                    .map_err(|err| {
-                        format_err!(
-                            ErrorKind::Registry,
-                            "unable to acquire tokio runtime: {}",
-                            err
-                        )
-                    })?;
-
-                for (name, res) in results {
-                    self.insert(
-                        name.parse().expect("this was a package name before"),
-                        res.map_err(Error::from_tame),
-                    );
-                }
+                let mut pending = crate_names(packages);
+                let mut results = KrateResults::new();
+                let downloaded = self.retry.run(|| {
+                    let attempt = rsi
+                        .krates_blocking(pending.clone(), true, REQUEST_TIMEOUT, &self.lock)
+                        .map_err(|err| {
+                            format_err!(
+                                ErrorKind::Registry,
+                                "unable to acquire tokio runtime: {}",
+                                err
+                            )
+                        })?;
+
+                    record_downloads(attempt, &mut pending, &mut results)
+                });
+
+                self.insert_all(results);
+                downloaded?;
             }
         }
 
         Ok(())
     }
 
+    /// Populates the cache entries for all of the specified crates, without
+    /// blocking the async runtime when downloading them from a sparse index.
+    #[cfg(feature = "tokio")]
+    async fn populate_cache_async(
+        &mut self,
+        mut packages: BTreeSet<&package::Name>,
+    ) -> Result<(), Error> {
+        packages.retain(|pkg| !self.cache.contains_key(pkg));
+        let Index::SparseRemote(rsi) = &self.index else {
+            // git and locally cached indexes are only read from disk
+            return self.populate_cache(packages);
+        };
+
+        let mut pending = crate_names(packages);
+        let mut results = KrateResults::new();
+        let mut retry = 0;
+        let downloaded = loop {
+            let attempt = rsi
+                .krates(pending.clone(), true, REQUEST_TIMEOUT, &self.lock)
+                .await;
+
+            match record_downloads(attempt, &mut pending, &mut results) {
+                Err(err) if err.is_transient() && retry < self.retry.max_retries() => {
+                    tokio::time::sleep(self.retry.backoff(retry)).await;
+                    retry += 1;
+                }
+                downloaded => break downloaded,
+            }
+        };
+
+        self.insert_all(results);
+        downloaded
+    }
+
+    /// Insert the results of a sparse index lookup into the cache
+    fn insert_all(&mut self, results: KrateResults) {
+        for (name, res) in results {
+            self.insert(
+                name.parse().expect("this was a package name before"),
+                res.map_err(Error::from_tame),
+            );
+        }
+    }
+
     #[inline]
     fn insert(
         &mut self,
@@ -218,7 +323,13 @@ impl CachedIndex {
             ik.map(|ik| {
                 ik.versions
                     .into_iter()
-                    .map(|v| (v.version.to_string(), v.is_yanked()))
+                    .map(|v| {
+                        let version = IndexedVersion {
+                            yanked: v.is_yanked(),
+                            checksum: Checksum::from(v.checksum.0),
+                        };
+                        (v.version.to_string(), version)
+                    })
                     .collect()
             })
         });
@@ -226,8 +337,8 @@ impl CachedIndex {
         self.cache.insert(package, krate_res);
     }
 
-    /// Is the given package yanked?
-    fn is_yanked(&mut self, package: &Package) -> Result<bool, Error> {
+    /// Look up the given version of a package in the index
+    fn version(&mut self, package: &Package) -> Result<&IndexedVersion, Error> {
         if !self.cache.contains_key(&package.name) {
             self.insert(
                 package.name.to_owned(),
@@ -237,7 +348,7 @@ impl CachedIndex {
 
         match &self.cache[&package.name] {
             Ok(Some(ik)) => match ik.get(&package.version.to_string()) {
-                Some(is_yanked) => Ok(*is_yanked),
+                Some(version) => Ok(version),
                 None => Err(format_err!(
                     ErrorKind::NotFound,
                     "No such version in crates.io index: {} {}",
@@ -259,6 +370,37 @@ impl CachedIndex {
         }
     }
 
+    /// Get the versions of the given crate published in the index which
+    /// haven't been yanked, in ascending order.
+    ///
+    /// Versions which aren't valid semver are skipped.
+    pub fn published_versions(&mut self, name: &package::Name) -> Result<Vec<Version>, Error> {
+        self.populate_cache([name].into_iter().collect())?;
+
+        match &self.cache[name] {
+            Ok(Some(ik)) => {
+                let mut versions: Vec<Version> = ik
+                    .iter()
+                    .filter(|(_, version)| !version.yanked)
+                    .filter_map(|(version, _)| version.parse().ok())
+                    .collect();
+                versions.sort();
+                Ok(versions)
+            }
+            Ok(None) => Err(format_err!(
+                ErrorKind::NotFound,
+                "No such crate in crates.io index: {}",
+                name,
+            )),
+            Err(err) => Err(format_err!(
+                ErrorKind::Registry,
+                "Failed to retrieve {} from crates.io index: {}",
+                name,
+                err,
+            )),
+        }
+    }
+
     /// Iterate over the provided packages, returning a vector of the
     /// packages which have been yanked.
     ///
@@ -269,12 +411,39 @@ impl CachedIndex {
     where
         I: IntoIterator<Item = &'a Package>,
     {
-        let mut yanked = Vec::new();
+        let dedup_packages: BTreeSet<&Package> = packages.into_iter().collect();
+        let populated = self.populate_cache(package_names(&dedup_packages));
+        self.yanked_packages(dedup_packages, populated)
+    }
 
+    /// Async variant of [`CachedIndex::find_yanked`], which downloads the
+    /// status of the packages from a sparse index without blocking the async
+    /// runtime.
+    ///
+    /// Must be called from within a tokio runtime.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub async fn find_yanked_async<'a, I>(&mut self, packages: I) -> Vec<Result<&'a Package, Error>>
+    where
+        I: IntoIterator<Item = &'a Package>,
+    {
         let dedup_packages: BTreeSet<&Package> = packages.into_iter().collect();
-        let package_names: BTreeSet<&package::Name> =
-            dedup_packages.iter().map(|p| &p.name).collect();
-        if let Err(e) = self.populate_cache(package_names) {
+        let populated = self
+            .populate_cache_async(package_names(&dedup_packages))
+            .await;
+        self.yanked_packages(dedup_packages, populated)
+    }
+
+    /// Look up the given packages in the populated cache, returning the ones
+    /// which have been yanked
+    fn yanked_packages<'a>(
+        &mut self,
+        dedup_packages: BTreeSet<&'a Package>,
+        populated: Result<(), Error>,
+    ) -> Vec<Result<&'a Package, Error>> {
+        let mut yanked = Vec::new();
+
+        if let Err(e) = populated {
             yanked.push(Err(Error::with_source(
                 ErrorKind::Registry,
                 "Failed to download crates.io index. \
@@ -285,15 +454,137 @@ impl CachedIndex {
         }
 
         for package in dedup_packages {
-            match self.is_yanked(package) {
-                Ok(false) => {} // not yanked, nothing to report
-                Ok(true) => yanked.push(Ok(package)),
+            match self.version(package) {
+                Ok(version) if version.yanked => yanked.push(Ok(package)),
+                Ok(_) => {} // not yanked, nothing to report
                 Err(error) => yanked.push(Err(error)),
             }
         }
 
         yanked
     }
+
+    /// Iterate over the provided packages, returning the ones whose checksum
+    /// differs from the one published in the index, along with the published
+    /// checksum.
+    ///
+    /// Packages without a checksum (e.g. ones from a `Cargo.lock` predating
+    /// checksums) are skipped. As with [`CachedIndex::find_yanked`], this
+    /// should be called with many packages at once.
+    pub fn find_checksum_mismatches<'a, I>(
+        &mut self,
+        packages: I,
+    ) -> Vec<Result<(&'a Package, Checksum), Error>>
+    where
+        I: IntoIterator<Item = &'a Package>,
+    {
+        let dedup_packages: BTreeSet<&Package> = packages
+            .into_iter()
+            .filter(|package| package.checksum.is_some())
+            .collect();
+        let populated = self.populate_cache(package_names(&dedup_packages));
+        let mut mismatches = Vec::new();
+
+        if let Err(e) = populated {
+            mismatches.push(Err(Error::with_source(
+                ErrorKind::Registry,
+                "Failed to download crates.io index. \
+                    Data may be missing or stale when verifying package checksums."
+                    .to_owned(),
+                e,
+            )));
+        }
+
+        for package in dedup_packages {
+            match self.version(package) {
+                Ok(version) if package.checksum.as_ref() != Some(&version.checksum) => {
+                    mismatches.push(Ok((package, version.checksum.clone())))
+                }
+                Ok(_) => {} // checksums match, nothing to report
+                Err(error) => mismatches.push(Err(error)),
+            }
+        }
+
+        mismatches
+    }
+}
+
+/// Names of the given crates, as they're looked up in a sparse index
+fn crate_names(packages: BTreeSet<&package::Name>) -> BTreeSet<String> {
+    packages
+        .into_iter()
+        .map(|p| p.as_str().to_owned())
+        .collect()
+}
+
+/// Record the results of downloading crates from a sparse index, keeping
+/// the ones which failed due to network errors pending so they can be
+/// downloaded again.
+///
+/// Fails with an [`ErrorKind::Network`] error if any of them did.
+fn record_downloads(
+    attempt: KrateResults,
+    pending: &mut BTreeSet<String>,
+    results: &mut KrateResults,
+) -> Result<(), Error> {
+    let mut failed = Vec::new();
+
+    for (name, result) in attempt {
+        match &result {
+            Err(err) if is_tame_network_error(err) => failed.push(format!("{}: {}", name, err)),
+            _ => {
+                pending.remove(&name);
+            }
+        }
+        results.insert(name, result);
+    }
+
+    if !failed.is_empty() {
+        fail!(
+            ErrorKind::Network,
+            "failed to download {} crate(s) from the crates.io index ({})",
+            failed.len(),
+            failed.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Check that a sparse index can be reached by requesting its configuration,
+/// as it's otherwise only contacted once crates are looked up in it
+fn check_reachable(client: &tame_index::external::reqwest::Client, url: &str) -> Result<(), Error> {
+    let rt = tokio::runtime::Runtime::new().map_err(|err| {
+        format_err!(
+            ErrorKind::Registry,
+            "unable to start a tokio runtime: {}",
+            err
+        )
+    })?;
+
+    let config_url = format!("{}/config.json", url.trim_end_matches('/'));
+    rt.block_on(async {
+        client
+            .get(config_url)
+            .timeout(CONFIG_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()
+    })
+    .map_err(|err| {
+        Error::with_source(
+            ErrorKind::Network,
+            "failed to fetch crates.io index".to_owned(),
+            err,
+        )
+    })?;
+
+    Ok(())
+}
+
+/// Names of the given packages, without duplicates
+fn package_names<'a>(packages: &BTreeSet<&'a Package>) -> BTreeSet<&'a package::Name> {
+    packages.iter().map(|p| &p.name).collect()
 }
 
 // We cannot expose these publicly because that would leak the `tame_index` SemVer into the public API
@@ -312,3 +603,99 @@ fn acquire_lock(
         lock_opts.lock(|_| Some(lock_timeout))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+    use tame_index::{index::AsyncRemoteSparseIndex, IndexLocation, IndexUrl};
+
+    /// Respond to every request on a local port with the given HTTP status,
+    /// returning the URL of the server and the number of requests it got
+    fn serve(status: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = requests.clone();
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut request = [0; 4096];
+                let _ = stream.read(&mut request);
+                counter.fetch_add(1, Ordering::SeqCst);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+            }
+        });
+
+        (url, requests)
+    }
+
+    /// Open the sparse index served at the given URL, caching it in `root`
+    fn sparse_index(url: &str, root: &std::path::Path, retry: RetryPolicy) -> CachedIndex {
+        let root = tame_index::PathBuf::from_path_buf(root.to_owned()).unwrap();
+        let location = IndexLocation::new(IndexUrl::NonCratesIo(format!("sparse+{}", url).into()))
+            .with_root(Some(root.join("index")));
+        let index = tame_index::index::SparseIndex::new(location).unwrap();
+        let lock_path = root.join("lock");
+
+        CachedIndex {
+            index: Index::SparseRemote(AsyncRemoteSparseIndex::new(index, Default::default())),
+            cache: Default::default(),
+            lock: LockOptions::new(&lock_path)
+                .exclusive(false)
+                .try_lock()
+                .unwrap(),
+            retry,
+        }
+    }
+
+    #[test]
+    fn retries_unavailable_sparse_index() {
+        let (url, requests) = serve("503 Service Unavailable");
+        let root = tempfile::tempdir().unwrap();
+        let mut index = sparse_index(
+            &url,
+            root.path(),
+            RetryPolicy::new()
+                .retries(2)
+                .initial_backoff(Duration::ZERO),
+        );
+
+        let name: package::Name = "base".parse().unwrap();
+        let err = index.published_versions(&name).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Network);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+        assert!(index.cache[&name].is_err());
+    }
+
+    #[test]
+    fn unreachable_sparse_index() {
+        let (url, _) = serve("503 Service Unavailable");
+        let client = Default::default();
+        let err = check_reachable(&client, &url).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Network);
+
+        // Nothing listens on the port once the listener is dropped
+        let url = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}/", listener.local_addr().unwrap())
+        };
+        let err = check_reachable(&client, &url).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Network);
+
+        let (url, _) = serve("200 OK");
+        assert!(check_reachable(&client, &url).is_ok());
+    }
+}
@@ -7,7 +7,10 @@ use std::{fmt, str::FromStr};
 /// Collections of packages (`crates` vs `rust`).
 ///
 /// Advisories are either filed against crates published to <https://crates.io>
-/// or packages provided by the Rust language itself (e.g. `std`, `rustdoc`)
+/// or packages provided by the Rust language itself (e.g. `std`, `rustdoc`).
+/// The latter are versioned along with the tool of the toolchain shipping
+/// them (see [`crate::toolchain::Tool`]), and can be queried with
+/// [`Query::toolchain_scope`](crate::database::Query::toolchain_scope).
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub enum Collection {
     /// Crates published through crates.io
@@ -1,24 +1,49 @@
 //! Database containing `RustSec` security advisories
 
+mod changelog;
 mod entries;
+mod explanation;
+mod history;
 mod index;
 mod query;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod shared;
+mod statistics;
 
-pub use self::query::Query;
+pub use self::{
+    explanation::{Explanation, Mismatch},
+    history::PointInTime,
+    query::Query,
+    statistics::Statistics,
+};
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub use self::shared::SharedDatabase;
 
-use self::{entries::Entries, index::Index};
+use self::{
+    entries::{Entries, Slot},
+    index::Index,
+};
 use crate::{
-    advisory::{self, Advisory},
+    advisory::{self, Advisory, Namespace},
     collection::Collection,
     error::Error,
-    fs,
+    report::AuditSource,
+    toolchain::ToolVersion,
     vulnerability::Vulnerability,
     Lockfile,
 };
-use std::path::Path;
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use {
+    crate::{fs, progress::Progress},
+    std::path::Path,
+};
 
 #[cfg(feature = "git")]
-use crate::repository::git;
+use crate::{error::ErrorKind, repository::git};
+#[cfg(feature = "git")]
+use std::{path::PathBuf, sync::Arc};
 
 /// Iterator over entries in the database
 pub type Iter<'a> = std::slice::Iter<'a, Advisory>;
@@ -38,11 +63,32 @@ pub struct Database {
     /// Information about the last git commit to the database
     #[cfg(feature = "git")]
     latest_commit: Option<git::Commit>,
+
+    /// Revision the database repository was pinned to, if any
+    #[cfg(feature = "git")]
+    revision: Option<String>,
+
+    /// Path to the git repository the database was loaded from, if any
+    #[cfg(feature = "git")]
+    repo_path: Option<PathBuf>,
+
+    /// Was the database loaded from a stale local copy because fetching the
+    /// latest one failed?
+    #[cfg(feature = "git")]
+    stale_cache: bool,
 }
 
 impl Database {
     /// Open [`Database`] located at the given local path
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn open(path: &Path) -> Result<Self, Error> {
+        Self::open_with_progress(path, &())
+    }
+
+    /// Open [`Database`] located at the given local path, notifying the given
+    /// observer as advisories are parsed
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn open_with_progress(path: &Path, progress: &dyn Progress) -> Result<Self, Error> {
         let mut advisory_paths = vec![];
 
         for collection in Collection::all() {
@@ -68,37 +114,90 @@ impl Database {
         }
 
         let mut advisories = Entries::new();
+        let slots = advisories.load_files(&advisory_paths, progress)?;
+        Ok(Self::from_entries(advisories, slots))
+    }
+
+    /// Index the given advisories, which were loaded into the given slots
+    #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), allow(dead_code))]
+    fn from_entries(advisories: Entries, slots: Vec<Slot>) -> Self {
         let mut rust_index = Index::new();
         let mut crate_index = Index::new();
 
-        for path in &advisory_paths {
-            if let Some(slot) = advisories.load_file(path)? {
-                let advisory = advisories.get(slot).unwrap();
-                match advisory.metadata.collection.unwrap() {
-                    Collection::Crates => {
-                        crate_index.insert(&advisory.metadata.package, slot);
-                    }
-                    Collection::Rust => {
-                        rust_index.insert(&advisory.metadata.package, slot);
-                    }
+        for slot in slots {
+            let advisory = advisories.get(slot).unwrap();
+            match advisory.metadata.collection.unwrap() {
+                Collection::Crates => {
+                    crate_index.insert(&advisory.metadata.package, slot);
+                }
+                Collection::Rust => {
+                    rust_index.insert(&advisory.metadata.package, slot);
                 }
             }
         }
 
-        Ok(Self {
+        Self {
             advisories,
             crate_index,
             rust_index,
             #[cfg(feature = "git")]
             latest_commit: None,
-        })
+            #[cfg(feature = "git")]
+            revision: None,
+            #[cfg(feature = "git")]
+            repo_path: None,
+            #[cfg(feature = "git")]
+            stale_cache: false,
+        }
     }
 
     /// Load [`Database`] from the given [`git::Repository`]
     #[cfg(feature = "git")]
     pub fn load_from_repo(repo: &git::Repository) -> Result<Self, Error> {
-        let mut db = Self::open(repo.path())?;
+        Self::load_from_repo_with_progress(repo, &())
+    }
+
+    /// Load [`Database`] from the given [`git::Repository`], notifying the
+    /// given observer as advisories are parsed
+    #[cfg(feature = "git")]
+    pub fn load_from_repo_with_progress(
+        repo: &git::Repository,
+        progress: &dyn Progress,
+    ) -> Result<Self, Error> {
+        let mut db = Self::open_with_progress(repo.path(), progress)?;
         db.latest_commit = Some(repo.latest_commit()?);
+        db.revision = repo.revision().map(ToOwned::to_owned);
+        db.repo_path = Some(repo.path().to_owned());
+        db.stale_cache = repo.fetch_error().is_some();
+        Ok(db)
+    }
+
+    /// Load [`Database`] from the given [`git::Repository`] as it was at the
+    /// given point in its history, e.g. to check whether a lockfile would have
+    /// passed an audit on a given date, or to reproduce an old audit exactly.
+    ///
+    /// Advisories are read from the git history without touching the working
+    /// tree, so the history up to that point must have been fetched (i.e. the
+    /// repository mustn't be a shallow clone). The [`Database::latest_commit`]
+    /// and [`Database::revision`] of the database are those of the commit it
+    /// was opened at.
+    #[cfg(feature = "git")]
+    pub fn open_at(repo: &git::Repository, at: &PointInTime) -> Result<Self, Error> {
+        let commit = at.commit(repo)?;
+        let revision = commit.commit_id.to_hex();
+
+        let mut files = vec![];
+        for collection in Collection::all() {
+            files.extend(repo.read_dir_at_revision(&revision, Path::new(collection.as_str()))?);
+        }
+
+        let mut advisories = Entries::new();
+        let slots = advisories.load_blobs(&files)?;
+
+        let mut db = Self::from_entries(advisories, slots);
+        db.latest_commit = Some(commit);
+        db.revision = Some(revision);
+        db.repo_path = Some(repo.path().to_owned());
         Ok(db)
     }
 
@@ -108,6 +207,29 @@ impl Database {
         git::Repository::fetch_default_repo().and_then(|repo| Self::load_from_repo(&repo))
     }
 
+    /// Fetch the default advisory database from GitHub, notifying the given
+    /// observer of the progress of the fetch and of parsing the advisories
+    #[cfg(feature = "git")]
+    pub fn fetch_with_progress(progress: Arc<dyn Progress>) -> Result<Self, Error> {
+        let options = git::FetchOptions::new().progress(progress.clone());
+        let repo = git::Repository::fetch_with_options(
+            git::DEFAULT_URL,
+            git::Repository::default_path(),
+            &options,
+        )?;
+        Self::load_from_repo_with_progress(&repo, &*progress)
+    }
+
+    /// Fetch the default advisory database from GitHub without blocking the
+    /// async runtime.
+    ///
+    /// Must be called from within a tokio runtime.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub async fn fetch_async() -> Result<Self, Error> {
+        crate::task::spawn_blocking(ErrorKind::Repo, Self::fetch).await
+    }
+
     /// Look up an advisory by an advisory ID (e.g. "RUSTSEC-YYYY-XXXX")
     pub fn get(&self, id: &advisory::Id) -> Option<&Advisory> {
         self.advisories.find_by_id(id)
@@ -115,43 +237,124 @@ impl Database {
 
     /// Query the database according to the given query object
     pub fn query(&self, query: &Query) -> Vec<&Advisory> {
-        // Use indexes if we know a package name and collection
-        if let Some(name) = &query.package_name {
-            if let Some(collection) = query.collection {
-                return match collection {
-                    Collection::Crates => self.crate_index.get(name),
-                    Collection::Rust => self.rust_index.get(name),
-                }
-                .map(|slots| {
-                    slots
-                        .map(|slot| self.advisories.get(*slot).unwrap())
-                        .filter(|advisory| query.matches(advisory))
-                        .collect()
-                })
-                .unwrap_or_else(Vec::new);
-            }
-        }
+        self.query_iter(query).collect()
+    }
+
+    /// Query the database according to the given query object, lazily
+    /// returning the matching advisories
+    pub fn query_iter<'a: 'q, 'q>(
+        &'a self,
+        query: &'q Query,
+    ) -> impl Iterator<Item = &'a Advisory> + 'q {
+        self.candidates(query)
+            .filter(move |advisory| query.matches(advisory))
+    }
+
+    /// Find vulnerabilities in the provided `Lockfile` (or other
+    /// [`AuditSource`]) which match a given query.
+    pub fn query_vulnerabilities<S>(&self, source: &S, query: &Query) -> Vec<Vulnerability>
+    where
+        S: AuditSource + ?Sized,
+    {
+        self.query_vulnerabilities_iter(source, query).collect()
+    }
+
+    /// Find vulnerabilities in the provided `Lockfile` (or other
+    /// [`AuditSource`]) which match a given query, lazily returning them one
+    /// package at a time
+    pub fn query_vulnerabilities_iter<'a, S>(
+        &'a self,
+        source: &'a S,
+        query: &'a Query,
+    ) -> impl Iterator<Item = Vulnerability> + 'a
+    where
+        S: AuditSource + ?Sized,
+    {
+        source.packages().iter().flat_map(move |package| {
+            let package_query = query.clone().package(package);
+
+            self.candidates(&package_query)
+                .filter(move |advisory| package_query.matches(advisory))
+                .map(move |advisory| Vulnerability::new(advisory, package))
+        })
+    }
+
+    /// Find vulnerabilities in the given version of a tool of the Rust
+    /// toolchain (e.g. `cargo 1.70.0`) which match a given query, such as
+    /// [`Query::toolchain_scope`]
+    pub fn query_toolchain(&self, tool_version: &ToolVersion, query: &Query) -> Vec<Vulnerability> {
+        tool_version
+            .packages()
+            .iter()
+            .flat_map(|package| {
+                let package_query = query.clone().package(package);
+
+                self.candidates(&package_query)
+                    .filter(move |advisory| package_query.matches(advisory))
+                    .map(move |advisory| Vulnerability::new(advisory, package))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Explain the outcome of [`Database::query_vulnerabilities`] for the
+    /// provided `Lockfile`: for each package, every advisory considered for
+    /// it, and why the advisory did or didn't match.
+    ///
+    /// This is meant for debugging why a package was (or wasn't) flagged.
+    pub fn explain_vulnerabilities<'a>(
+        &'a self,
+        lockfile: &'a Lockfile,
+        query: &Query,
+    ) -> Vec<Explanation<'a>> {
+        lockfile
+            .packages
+            .iter()
+            .flat_map(|package| {
+                let package_query = query.clone().package(package);
 
-        self.iter()
-            .filter(|advisory| query.matches(advisory))
+                self.candidates(&package_query)
+                    .map(move |advisory| Explanation {
+                        advisory,
+                        package,
+                        mismatch: package_query.mismatch(advisory),
+                    })
+                    .collect::<Vec<_>>()
+            })
             .collect()
     }
 
-    /// Find vulnerabilities in the provided `Lockfile` which match a given query.
-    pub fn query_vulnerabilities(&self, lockfile: &Lockfile, query: &Query) -> Vec<Vulnerability> {
-        let mut vulns = vec![];
+    /// Advisories which could match the given query, which still need to be
+    /// checked with [`Query::matches`]
+    fn candidates<'a>(&'a self, query: &Query) -> Box<dyn Iterator<Item = &'a Advisory> + 'a> {
+        // Use indexes if we know a package name, consulting the index of
+        // every collection if the query isn't restricted to one
+        if let Some(name) = &query.package_name {
+            let mut slots: Vec<_> = match query.collection {
+                Some(Collection::Crates) => {
+                    self.crate_index.get(name).into_iter().flatten().collect()
+                }
+                Some(Collection::Rust) => self.rust_index.get(name).into_iter().flatten().collect(),
+                None => self
+                    .crate_index
+                    .get(name)
+                    .into_iter()
+                    .flatten()
+                    .chain(self.rust_index.get(name).into_iter().flatten())
+                    .collect(),
+            };
 
-        for package in &lockfile.packages {
-            let advisories = self.query(&query.clone().package(package));
+            // Return matches in database order regardless of which index they came from
+            slots.sort();
 
-            vulns.extend(
-                advisories
-                    .iter()
-                    .map(|advisory| Vulnerability::new(advisory, package)),
+            return Box::new(
+                slots
+                    .into_iter()
+                    .map(|slot| self.advisories.get(*slot).unwrap()),
             );
         }
 
-        vulns
+        Box::new(self.iter())
     }
 
     /// Scan for vulnerabilities in the provided `Lockfile`.
@@ -164,11 +367,117 @@ impl Database {
         self.advisories.iter()
     }
 
+    /// Count the advisories in the database by year, severity, category,
+    /// informational kind, and whether they have been patched
+    pub fn statistics(&self) -> Statistics {
+        Statistics::new(self.iter())
+    }
+
+    /// Ensure the advisories in the given custom namespaces are filed in the
+    /// collections their namespaces require
+    pub fn check_namespaces(&self, namespaces: &[Namespace]) -> Result<(), Error> {
+        for advisory in self.iter() {
+            for namespace in namespaces {
+                namespace.check(advisory)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the advisories which are new since the given point in the history
+    /// of the database, e.g. for "what's new this week" notifications or
+    /// incremental syncs of downstream copies.
+    ///
+    /// Listing the changes since a [`PointInTime::Revision`] requires the database
+    /// to have been loaded from a git repository (see
+    /// [`Database::load_from_repo`]) containing that revision.
+    pub fn advisories_since(&self, since: &PointInTime) -> Result<Vec<&Advisory>, Error> {
+        match since {
+            PointInTime::Date(date) => Ok(self
+                .iter()
+                .filter(|advisory| changelog::is_new_since(date, advisory))
+                .collect()),
+            #[cfg(feature = "git")]
+            PointInTime::Revision(revision) => {
+                let repo_path = self.repo_path.as_ref().ok_or_else(|| {
+                    format_err!(
+                        ErrorKind::BadParam,
+                        "database wasn't loaded from a git repository"
+                    )
+                })?;
+
+                changelog::changed_since(repo_path, revision, self.iter())
+            }
+        }
+    }
+
+    /// Get the next RustSec advisory ID for the given year which isn't used by
+    /// any advisory in the database
+    pub fn next_available_id(&self, year: u32) -> Result<advisory::Id, Error> {
+        self.next_available_id_with_pending(year, &[])
+    }
+
+    /// Get the next RustSec advisory ID for the given year which isn't used by
+    /// any advisory in the database, nor by any of the `pending` IDs (e.g. ones
+    /// reserved by advisories in open pull requests)
+    pub fn next_available_id_with_pending(
+        &self,
+        year: u32,
+        pending: &[advisory::Id],
+    ) -> Result<advisory::Id, Error> {
+        let highest = self
+            .iter()
+            .map(|advisory| &advisory.metadata.id)
+            .chain(pending)
+            .filter(|id| id.is_rustsec() && id.year() == Some(year))
+            .filter_map(advisory::Id::numerical_part)
+            .max()
+            .unwrap_or_default();
+
+        format!("RUSTSEC-{}-{:04}", year, highest + 1).parse()
+    }
+
+    /// Find the `pending` IDs which collide with the ID of an advisory in the
+    /// database or with another pending ID.
+    ///
+    /// Each colliding ID is only returned once. Placeholder IDs are ignored.
+    pub fn id_collisions(&self, pending: &[advisory::Id]) -> Vec<advisory::Id> {
+        let mut collisions: Vec<advisory::Id> = vec![];
+
+        for (i, id) in pending.iter().enumerate() {
+            if id.is_placeholder() || collisions.contains(id) {
+                continue;
+            }
+
+            if self.get(id).is_some() || pending[..i].contains(id) {
+                collisions.push(id.clone());
+            }
+        }
+
+        collisions
+    }
+
     /// Get information about the latest commit to the repo
     #[cfg(feature = "git")]
     pub fn latest_commit(&self) -> Option<&git::Commit> {
         self.latest_commit.as_ref()
     }
+
+    /// Get the revision (branch, tag, or commit) the database was pinned to,
+    /// if it was loaded from a pinned repository
+    #[cfg(feature = "git")]
+    pub fn revision(&self) -> Option<&str> {
+        self.revision.as_deref()
+    }
+
+    /// Was the database loaded from the existing local copy of its repository
+    /// because fetching the latest one failed? (see
+    /// [`FetchOptions::offline_fallback`](git::FetchOptions::offline_fallback))
+    #[cfg(feature = "git")]
+    pub fn is_stale_cache(&self) -> bool {
+        self.stale_cache
+    }
 }
 
 impl IntoIterator for Database {
@@ -180,3 +489,316 @@ impl IntoIterator for Database {
         self.advisories.into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Write a copy of `tests/support/example_advisory_v3.md` for the given
+    /// package and ID into a database rooted at `root`, returning its path
+    pub(super) fn write_advisory(
+        root: &Path,
+        collection: Collection,
+        package: &str,
+        id: &str,
+    ) -> PathBuf {
+        let package_dir = root.join(collection.as_str()).join(package);
+        std::fs::create_dir_all(&package_dir).unwrap();
+        let advisory = std::fs::read_to_string("./tests/support/example_advisory_v3.md")
+            .unwrap()
+            .replace("RUSTSEC-2001-2101", id)
+            .replace("package = \"base\"", &format!("package = \"{}\"", package))
+            .replace("base::belongs::All", &format!("{}::belongs::All", package));
+        let path = package_dir.join(format!("{}.md", id));
+        std::fs::write(&path, advisory).unwrap();
+        path
+    }
+
+    #[test]
+    fn open_with_progress() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Records the highest number of parsed advisories reported
+        #[derive(Default)]
+        struct Parsed(AtomicUsize, AtomicUsize);
+
+        impl Progress for Parsed {
+            fn advisories_parsed(&self, parsed: usize, total: usize) {
+                self.0.fetch_max(parsed, Ordering::Relaxed);
+                self.1.store(total, Ordering::Relaxed);
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        for id in ["RUSTSEC-2001-2101", "RUSTSEC-2001-2102"] {
+            write_advisory(dir.path(), Collection::Crates, "base", id);
+        }
+
+        let progress = Parsed::default();
+        let db = Database::open_with_progress(dir.path(), &progress).unwrap();
+        assert_eq!(db.iter().count(), 2);
+        assert_eq!(progress.0.load(Ordering::Relaxed), 2);
+        assert_eq!(progress.1.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn query_by_name_uses_all_collections() {
+        let dir = tempfile::tempdir().unwrap();
+        write_advisory(dir.path(), Collection::Crates, "base", "RUSTSEC-2001-2101");
+        write_advisory(dir.path(), Collection::Rust, "base", "RUSTSEC-2001-2102");
+
+        let db = Database::open(dir.path()).unwrap();
+        let name: crate::package::Name = "base".parse().unwrap();
+
+        let all = db.query(&Query::new().package_name(name.clone()));
+        assert_eq!(all.len(), 2);
+
+        let crates = db.query(&Query::crate_scope().package_name(name.clone()));
+        assert_eq!(crates.len(), 1);
+        assert_eq!(crates[0].metadata.collection, Some(Collection::Crates));
+
+        let rust = db.query(&Query::new().collection(Collection::Rust).package_name(name));
+        assert_eq!(rust.len(), 1);
+        assert_eq!(rust[0].metadata.collection, Some(Collection::Rust));
+
+        let lockfile: Lockfile = r#"
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#
+        .parse()
+        .unwrap();
+
+        let query = Query::crate_scope();
+        let mut vulns = db.query_vulnerabilities_iter(&lockfile, &query);
+        assert_eq!(
+            vulns.next().unwrap().advisory.id.as_str(),
+            "RUSTSEC-2001-2101"
+        );
+        assert!(vulns.next().is_none());
+    }
+
+    #[test]
+    fn query_toolchain() {
+        let dir = tempfile::tempdir().unwrap();
+        write_advisory(dir.path(), Collection::Rust, "cargo", "RUSTSEC-2001-2102");
+        write_advisory(dir.path(), Collection::Rust, "std", "RUSTSEC-2001-2103");
+
+        let db = Database::open(dir.path()).unwrap();
+        let query = Query::toolchain_scope();
+
+        let cargo = "cargo 1.0.0 (abcdef123 2001-02-03)".parse().unwrap();
+        let vulns = db.query_toolchain(&cargo, &query);
+        assert_eq!(vulns.len(), 1);
+        assert_eq!(vulns[0].advisory.id.as_str(), "RUSTSEC-2001-2102");
+        assert_eq!(vulns[0].package.name.as_str(), "cargo");
+
+        let rustc = "rustc 1.0.0".parse().unwrap();
+        let vulns = db.query_toolchain(&rustc, &query);
+        assert_eq!(vulns.len(), 1);
+        assert_eq!(vulns[0].package.name.as_str(), "std");
+
+        let patched = "rustc 1.2.3".parse().unwrap();
+        assert!(db.query_toolchain(&patched, &query).is_empty());
+    }
+
+    #[test]
+    fn explain_vulnerabilities() {
+        use platforms::target::OS;
+
+        let dir = tempfile::tempdir().unwrap();
+        for id in ["RUSTSEC-2001-2101", "RUSTSEC-2001-2102"] {
+            write_advisory(dir.path(), Collection::Crates, "base", id);
+        }
+
+        let db = Database::open(dir.path()).unwrap();
+        let lockfile: Lockfile = r#"
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "other"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#
+        .parse()
+        .unwrap();
+
+        let query = Query::crate_scope().ignore(vec!["RUSTSEC-2001-2102".parse().unwrap()]);
+        let mut explanations: Vec<_> = db
+            .explain_vulnerabilities(&lockfile, &query)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        explanations.sort();
+        assert_eq!(
+            explanations,
+            [
+                "RUSTSEC-2001-2101 base 1.0.0: match",
+                "RUSTSEC-2001-2102 base 1.0.0: no match (ignored)"
+            ]
+        );
+
+        let query = Query::crate_scope().target_os(vec![OS::Linux]);
+        let explanations = db.explain_vulnerabilities(&lockfile, &query);
+        assert!(explanations
+            .iter()
+            .all(|e| e.mismatch == Some(Mismatch::TargetOs)));
+
+        let patched: Lockfile = lockfile
+            .to_string()
+            .replace("1.0.0", "1.2.3")
+            .parse()
+            .unwrap();
+        let explanations = db.explain_vulnerabilities(&patched, &Query::crate_scope());
+        assert_eq!(explanations.len(), 2);
+        assert!(explanations
+            .iter()
+            .all(|e| e.mismatch == Some(Mismatch::VersionRange)));
+
+        let explanations = db.explain_vulnerabilities(&lockfile, &Query::new().informational(true));
+        assert!(explanations
+            .iter()
+            .all(|e| e.mismatch == Some(Mismatch::Informational)));
+    }
+
+    #[test]
+    fn next_available_id() {
+        let dir = tempfile::tempdir().unwrap();
+        write_advisory(dir.path(), Collection::Crates, "base", "RUSTSEC-2001-2101");
+
+        let db = Database::open(dir.path()).unwrap();
+        let id = |s: &str| s.parse::<advisory::Id>().unwrap();
+
+        assert_eq!(db.next_available_id(2001).unwrap(), id("RUSTSEC-2001-2102"));
+        assert_eq!(db.next_available_id(2002).unwrap(), id("RUSTSEC-2002-0001"));
+
+        let pending = [
+            id("RUSTSEC-2001-2102"),
+            id("RUSTSEC-2001-2101"),
+            id("RUSTSEC-2002-0001"),
+            id("RUSTSEC-2001-2102"),
+            id("RUSTSEC-0000-0000"),
+            id("RUSTSEC-0000-0000"),
+        ];
+        assert_eq!(
+            db.next_available_id_with_pending(2001, &pending).unwrap(),
+            id("RUSTSEC-2001-2103")
+        );
+        assert_eq!(
+            db.next_available_id_with_pending(2002, &pending).unwrap(),
+            id("RUSTSEC-2002-0002")
+        );
+        assert_eq!(
+            db.id_collisions(&pending),
+            [id("RUSTSEC-2001-2101"), id("RUSTSEC-2001-2102")]
+        );
+    }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn advisories_since() {
+        use crate::repository::git::testing::git;
+
+        let dir = tempfile::tempdir().unwrap();
+        let write = |id: &str| write_advisory(dir.path(), Collection::Crates, "base", id);
+        let edit = |path: &Path, from: &str, to: &str| {
+            let advisory = std::fs::read_to_string(path).unwrap();
+            std::fs::write(path, advisory.replace(from, to)).unwrap();
+        };
+
+        let reformatted = write("RUSTSEC-2001-2101");
+        let withdrawn = write("RUSTSEC-2001-2103");
+        git(dir.path(), &["init", "--quiet"]);
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "--quiet", "-m", "first"]);
+        git(dir.path(), &["tag", "v1"]);
+
+        // Reformatting isn't a material change, unlike withdrawing an advisory
+        edit(&reformatted, "date = ", "date =  ");
+        let added = write("RUSTSEC-2001-2102");
+        edit(&added, "2001-02-03", "2002-01-05");
+        edit(
+            &withdrawn,
+            "[versions]",
+            "withdrawn = \"2002-02-01\"\n\n[versions]",
+        );
+        git(dir.path(), &["add", "."]);
+        git(dir.path(), &["commit", "--quiet", "-m", "second"]);
+
+        let db = Database::load_from_repo(&git::Repository::open(dir.path()).unwrap()).unwrap();
+        let ids = |since: &str| {
+            let mut ids: Vec<_> = db
+                .advisories_since(&since.parse().unwrap())
+                .unwrap()
+                .into_iter()
+                .map(|advisory| advisory.metadata.id.to_string())
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(ids("v1"), ["RUSTSEC-2001-2102", "RUSTSEC-2001-2103"]);
+        assert_eq!(
+            ids("2002-01-01"),
+            ["RUSTSEC-2001-2102", "RUSTSEC-2001-2103"]
+        );
+        assert_eq!(ids("2002-01-10"), ["RUSTSEC-2001-2103"]);
+        assert_eq!(ids("2001-01-01").len(), 3);
+        assert!(db
+            .advisories_since(&PointInTime::Revision("no-such-revision".to_owned()))
+            .is_err());
+
+        let unversioned = Database::open(dir.path()).unwrap();
+        assert!(unversioned
+            .advisories_since(&PointInTime::Revision("v1".to_owned()))
+            .is_err());
+    }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn open_at() {
+        use crate::repository::git::testing::git_at as git;
+
+        let dir = tempfile::tempdir().unwrap();
+        let write = |id: &str| write_advisory(dir.path(), Collection::Crates, "base", id);
+
+        let march = "2001-03-15T12:00:00Z";
+        let first = write("RUSTSEC-2001-2101");
+        git(dir.path(), march, &["init", "--quiet"]);
+        git(dir.path(), march, &["add", "."]);
+        git(dir.path(), march, &["commit", "--quiet", "-m", "first"]);
+        git(dir.path(), march, &["tag", "v1"]);
+
+        let june = "2001-06-15T12:00:00Z";
+        write("RUSTSEC-2001-2102");
+        git(dir.path(), june, &["add", "."]);
+        git(dir.path(), june, &["commit", "--quiet", "-m", "second"]);
+
+        let repo = git::Repository::open(dir.path()).unwrap();
+        let ids = |at: &str| {
+            let db = Database::open_at(&repo, &at.parse().unwrap()).unwrap();
+            let mut ids: Vec<_> = db.iter().map(|a| a.metadata.id.to_string()).collect();
+            ids.sort();
+            (ids, db.latest_commit().unwrap().summary.clone())
+        };
+
+        assert_eq!(
+            ids("v1"),
+            (vec!["RUSTSEC-2001-2101".to_owned()], "first".to_owned())
+        );
+        assert_eq!(ids("2001-03-15").1, "first");
+        assert_eq!(ids("2001-05-31").1, "first");
+        assert_eq!(ids("2001-06-15").0.len(), 2);
+        assert_eq!(ids("HEAD").1, "second");
+        assert!(Database::open_at(&repo, &"2001-03-14".parse().unwrap()).is_err());
+
+        // The working tree is left as is
+        let package_dir = first.parent().unwrap();
+        assert_eq!(std::fs::read_dir(package_dir).unwrap().count(), 2);
+    }
+}
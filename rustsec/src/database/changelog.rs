@@ -0,0 +1,73 @@
+//! Changes to the advisory database since a given point in its history
+
+use crate::advisory::{Advisory, Date};
+
+#[cfg(feature = "git")]
+use crate::{error::Error, repository::git};
+#[cfg(feature = "git")]
+use std::path::{Path, PathBuf};
+
+/// Was the given advisory published or withdrawn on or after the given date,
+/// according to its metadata?
+pub(crate) fn is_new_since(date: &Date, advisory: &Advisory) -> bool {
+    advisory.metadata.date >= *date
+        || advisory
+            .metadata
+            .withdrawn
+            .as_ref()
+            .map_or(false, |withdrawn| withdrawn >= date)
+}
+
+/// Select the advisories which were added, or whose parsed contents changed,
+/// since the given revision of the repository at `repo_path`
+#[cfg(feature = "git")]
+pub(crate) fn changed_since<'a>(
+    repo_path: &Path,
+    revision: &str,
+    advisories: impl Iterator<Item = &'a Advisory>,
+) -> Result<Vec<&'a Advisory>, Error> {
+    let advisories: Vec<&Advisory> = advisories.collect();
+    let paths: Vec<PathBuf> = advisories
+        .iter()
+        .map(|advisory| relative_path(advisory))
+        .collect();
+
+    let repo = git::Repository::open(repo_path)?;
+    let previous = repo.read_files_at_revision(revision, &paths)?;
+
+    Ok(advisories
+        .into_iter()
+        .zip(previous)
+        .filter(|(advisory, previous)| {
+            // Advisories which didn't exist or didn't parse back then are new
+            let previous = previous
+                .as_ref()
+                .and_then(|data| std::str::from_utf8(data).ok())
+                .and_then(|data| data.parse::<Advisory>().ok())
+                .map(|mut previous| {
+                    // The collection is only set when loading the database
+                    previous.metadata.collection = advisory.metadata.collection;
+                    previous
+                });
+
+            previous.as_ref() != Some(*advisory)
+        })
+        .map(|(advisory, _)| advisory)
+        .collect())
+}
+
+/// Path of the file an advisory is stored in, relative to the root of the
+/// database repository
+#[cfg(feature = "git")]
+fn relative_path(advisory: &Advisory) -> PathBuf {
+    let metadata = &advisory.metadata;
+    let collection = metadata.collection.expect("collection set when loading");
+
+    [
+        collection.as_str(),
+        metadata.package.as_str(),
+        &format!("{}.md", metadata.id),
+    ]
+    .iter()
+    .collect()
+}
@@ -3,13 +3,17 @@
 use super::Iter;
 use crate::{
     advisory::{self, Advisory},
-    collection::Collection,
     error::{Error, ErrorKind},
     map, Map,
 };
-use std::{
-    ffi::{OsStr, OsString},
-    path::Path,
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use {
+    crate::{collection::Collection, progress::Progress},
+    std::{
+        ffi::{OsStr, OsString},
+        path::{Path, PathBuf},
+    },
 };
 
 /// "Slots" identify the location in the entries table where a particular
@@ -27,94 +31,67 @@ pub(crate) struct Entries {
     advisories: Vec<Advisory>,
 }
 
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), allow(dead_code))]
 impl Entries {
     /// Create a new database entries collection
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Load an advisory from a file and insert it into the database entry table
-    // TODO(tarcieri): factor more of this into `advisory.rs`?
-    pub fn load_file(&mut self, path: &Path) -> Result<Option<Slot>, Error> {
-        let mut advisory = Advisory::load_file(path)?;
+    /// Load advisories from the given files and insert them into the database
+    /// entry table, in the order the paths are given.
+    ///
+    /// With the `parallel` feature enabled, the files are parsed concurrently
+    /// (except on WebAssembly targets, which can't spawn threads).
+    ///
+    /// The `progress` observer is notified as each file is parsed.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn load_files(
+        &mut self,
+        paths: &[PathBuf],
+        progress: &dyn Progress,
+    ) -> Result<Vec<Slot>, Error> {
+        let mut slots = Vec::with_capacity(paths.len());
 
-        // TODO(tarcieri): deprecate and remove legacy TOML-based advisory format
-        let expected_filename = match path.extension().and_then(|ext| ext.to_str()) {
-            Some("md") => OsString::from(format!("{}.md", advisory.metadata.id)),
-            _ => fail!(
-                ErrorKind::Repo,
-                "unexpected file extension: {}",
-                path.display()
-            ),
-        };
-
-        // Ensure advisory has the correct filename
-        if path.file_name().unwrap() != expected_filename {
-            fail!(
-                ErrorKind::Repo,
-                "expected {} to be named {:?}",
-                path.display(),
-                expected_filename
-            );
+        for advisory in parse_files(paths, progress) {
+            if let Some(slot) = self.insert(advisory?)? {
+                slots.push(slot);
+            }
         }
 
-        // Ensure advisory is in a directory named after its package
-        let package_dir = path.parent().ok_or_else(|| {
-            format_err!(
-                ErrorKind::Repo,
-                "advisory has no parent dir: {}",
-                path.display()
-            )
-        })?;
+        Ok(slots)
+    }
 
-        if package_dir.file_name().unwrap() != OsStr::new(advisory.metadata.package.as_str()) {
-            fail!(
-                ErrorKind::Repo,
-                "expected {} to be in {} directory (instead of \"{:?}\")",
-                advisory.metadata.id,
-                advisory.metadata.package,
-                package_dir
-            );
-        }
+    /// Load advisories from the given files, given by their path relative to
+    /// the root of the database along with their contents (e.g. as read from
+    /// the git history), and insert them into the database entry table
+    #[cfg(feature = "git")]
+    pub fn load_blobs(&mut self, files: &[(PathBuf, Vec<u8>)]) -> Result<Vec<Slot>, Error> {
+        let mut slots = Vec::with_capacity(files.len());
 
-        // Get the collection this advisory is part of
-        let collection_dir = package_dir
-            .parent()
-            .ok_or_else(|| {
-                format_err!(
-                    ErrorKind::Repo,
-                    "advisory has no collection: {}",
-                    path.display()
-                )
-            })?
-            .file_name()
-            .unwrap();
-
-        let collection = if collection_dir == OsStr::new(Collection::Crates.as_str()) {
-            Collection::Crates
-        } else if collection_dir == OsStr::new(Collection::Rust.as_str()) {
-            Collection::Rust
-        } else {
-            fail!(
-                ErrorKind::Repo,
-                "invalid package collection: {:?}",
-                collection_dir
-            );
-        };
-
-        match advisory.metadata.collection {
-            Some(c) => {
-                if c != collection {
-                    fail!(
-                        ErrorKind::Parse,
-                        "collection mismatch for {}",
-                        &advisory.metadata.id
-                    );
-                }
+        for (path, data) in files {
+            // skip dotfiles like .DS_Store
+            let file_name = path.file_name().and_then(|f| f.to_str());
+            if file_name.map_or(false, |f| f.starts_with('.')) {
+                continue;
+            }
+
+            let advisory = std::str::from_utf8(data)
+                .map_err(Error::from)
+                .and_then(str::parse)
+                .and_then(|advisory| check_location(path, advisory))
+                .map_err(|err| err.with_path(path))?;
+
+            if let Some(slot) = self.insert(advisory)? {
+                slots.push(slot);
             }
-            None => advisory.metadata.collection = Some(collection),
         }
 
+        Ok(slots)
+    }
+
+    /// Insert a loaded advisory into the database entry table
+    fn insert(&mut self, advisory: Advisory) -> Result<Option<Slot>, Error> {
         // Ensure placeholder advisories load and parse correctly, but
         // don't actually insert them into the advisory database
         if advisory.metadata.id.is_placeholder() {
@@ -162,3 +139,207 @@ impl IntoIterator for Entries {
         self.advisories.into_iter()
     }
 }
+
+/// Load an advisory from a file, ensuring it's named after its ID and located
+/// in the directory of its package and collection
+// TODO(tarcieri): factor more of this into `advisory.rs`?
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn load_advisory(path: &Path) -> Result<Advisory, Error> {
+    check_advisory(path).map_err(|err| err.with_path(path))
+}
+
+/// Load an advisory from a file and check where it's located
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn check_advisory(path: &Path) -> Result<Advisory, Error> {
+    check_location(path, Advisory::load_file(path)?)
+}
+
+/// Check that an advisory loaded from the given path is named after its ID
+/// and located in the directory of its package and collection, setting its
+/// collection accordingly
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn check_location(path: &Path, mut advisory: Advisory) -> Result<Advisory, Error> {
+    // TODO(tarcieri): deprecate and remove legacy TOML-based advisory format
+    let expected_filename = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("md") => OsString::from(format!("{}.md", advisory.metadata.id)),
+        _ => fail!(
+            ErrorKind::Repo,
+            "unexpected file extension: {}",
+            path.display()
+        ),
+    };
+
+    // Ensure advisory has the correct filename
+    if path.file_name().unwrap() != expected_filename {
+        fail!(
+            ErrorKind::Repo,
+            "expected {} to be named {:?}",
+            path.display(),
+            expected_filename
+        );
+    }
+
+    // Ensure advisory is in a directory named after its package
+    let package_dir = path.parent().ok_or_else(|| {
+        format_err!(
+            ErrorKind::Repo,
+            "advisory has no parent dir: {}",
+            path.display()
+        )
+    })?;
+
+    if package_dir.file_name().unwrap() != OsStr::new(advisory.metadata.package.as_str()) {
+        fail!(
+            ErrorKind::Repo,
+            "expected {} to be in {} directory (instead of \"{:?}\")",
+            advisory.metadata.id,
+            advisory.metadata.package,
+            package_dir
+        );
+    }
+
+    // Get the collection this advisory is part of
+    let collection_dir = package_dir
+        .parent()
+        .ok_or_else(|| {
+            format_err!(
+                ErrorKind::Repo,
+                "advisory has no collection: {}",
+                path.display()
+            )
+        })?
+        .file_name()
+        .unwrap();
+
+    let collection = if collection_dir == OsStr::new(Collection::Crates.as_str()) {
+        Collection::Crates
+    } else if collection_dir == OsStr::new(Collection::Rust.as_str()) {
+        Collection::Rust
+    } else {
+        fail!(
+            ErrorKind::Repo,
+            "invalid package collection: {:?}",
+            collection_dir
+        );
+    };
+
+    match advisory.metadata.collection {
+        Some(c) => {
+            if c != collection {
+                fail!(
+                    ErrorKind::Parse,
+                    "collection mismatch for {}",
+                    &advisory.metadata.id
+                );
+            }
+        }
+        None => advisory.metadata.collection = Some(collection),
+    }
+
+    Ok(advisory)
+}
+
+/// Load advisories from the given files one after the other
+#[cfg(all(
+    any(not(feature = "parallel"), target_family = "wasm"),
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+fn parse_files(paths: &[PathBuf], progress: &dyn Progress) -> Vec<Result<Advisory, Error>> {
+    paths
+        .iter()
+        .enumerate()
+        .map(|(parsed, path)| {
+            let advisory = load_advisory(path);
+            progress.advisories_parsed(parsed + 1, paths.len());
+            advisory
+        })
+        .collect()
+}
+
+/// Load advisories from the given files on a thread per available CPU,
+/// returning the results in the same order as the paths
+#[cfg(all(feature = "parallel", not(target_family = "wasm")))]
+fn parse_files(paths: &[PathBuf], progress: &dyn Progress) -> Vec<Result<Advisory, Error>> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let threads = std::thread::available_parallelism().map_or(1, usize::from);
+    let chunk_size = ((paths.len() + threads - 1) / threads).max(1);
+    let parsed = &AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|path| {
+                            let advisory = load_advisory(path);
+                            let parsed = parsed.fetch_add(1, Ordering::Relaxed) + 1;
+                            progress.advisories_parsed(parsed, paths.len());
+                            advisory
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("advisory parser thread panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_files_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let package_dir = dir.path().join("crates").join("base");
+        std::fs::create_dir_all(&package_dir).unwrap();
+
+        let template = std::fs::read_to_string("./tests/support/example_advisory_v3.md").unwrap();
+        let paths: Vec<PathBuf> = (0..20)
+            .map(|n| {
+                let id = format!("RUSTSEC-2001-{:04}", n);
+                let path = package_dir.join(format!("{}.md", id));
+                std::fs::write(&path, template.replace("RUSTSEC-2001-2101", &id)).unwrap();
+                path
+            })
+            .collect();
+
+        let mut entries = Entries::new();
+        let slots = entries.load_files(&paths, &()).unwrap();
+        assert_eq!(slots.len(), paths.len());
+
+        for (n, slot) in slots.into_iter().enumerate() {
+            let advisory = entries.get(slot).unwrap();
+            assert_eq!(
+                advisory.metadata.id.as_str(),
+                format!("RUSTSEC-2001-{:04}", n)
+            );
+            assert_eq!(advisory.metadata.collection, Some(Collection::Crates));
+        }
+    }
+
+    #[test]
+    fn malformed_advisory_error() {
+        use std::error::Error as _;
+
+        let dir = tempfile::tempdir().unwrap();
+        let package_dir = dir.path().join("crates").join("base");
+        std::fs::create_dir_all(&package_dir).unwrap();
+
+        let path = package_dir.join("RUSTSEC-2001-2101.md");
+        std::fs::write(&path, "```toml\n[advisory]\n```\n").unwrap();
+
+        let err = load_advisory(&path).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Parse);
+        assert_eq!(err.path(), Some(path.as_path()));
+        assert!(!err.is_transient());
+        assert!(err.source().is_some());
+    }
+}
@@ -0,0 +1,114 @@
+//! Explanations of why advisories did or didn't match a query
+
+use crate::{advisory::Advisory, package::Package};
+use std::fmt;
+
+/// Outcome of matching an advisory against a package of a lockfile, as
+/// returned by [`Database::explain_vulnerabilities`](super::Database::explain_vulnerabilities)
+#[derive(Clone, Debug)]
+pub struct Explanation<'a> {
+    /// Advisory which was considered
+    pub advisory: &'a Advisory,
+
+    /// Package the advisory was matched against
+    pub package: &'a Package,
+
+    /// Why the advisory didn't match, or `None` if it did
+    pub mismatch: Option<Mismatch>,
+}
+
+impl Explanation<'_> {
+    /// Did the advisory match the package?
+    pub fn is_match(&self) -> bool {
+        self.mismatch.is_none()
+    }
+}
+
+impl fmt::Display for Explanation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}: ",
+            self.advisory.id(),
+            self.package.name,
+            self.package.version
+        )?;
+
+        match &self.mismatch {
+            Some(mismatch) => write!(f, "no match ({})", mismatch),
+            None => write!(f, "match"),
+        }
+    }
+}
+
+/// Reason why an advisory didn't match a [`Query`](super::Query), i.e. the
+/// first of the query's filters which excluded it
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Mismatch {
+    /// Advisory is in a different collection
+    Collection,
+
+    /// Advisory is for a different package
+    PackageName,
+
+    /// Package version isn't in the advisory's affected version ranges
+    VersionRange,
+
+    /// Advisory is for a package from a different source
+    PackageSource,
+
+    /// Advisory is below the severity threshold
+    Severity,
+
+    /// Advisory only affects other target architectures
+    TargetArch,
+
+    /// Advisory only affects other target operating systems
+    TargetOs,
+
+    /// Advisory is from a different year
+    Year,
+
+    /// Advisory isn't in any of the queried categories
+    Category,
+
+    /// Advisory was (or wasn't) withdrawn
+    Withdrawn,
+
+    /// Advisory is (or isn't) informational
+    Informational,
+
+    /// Advisory has no patched versions
+    Unpatched,
+
+    /// Advisory is ignored by the query
+    Ignored,
+}
+
+impl Mismatch {
+    /// Describe the mismatch
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Mismatch::Collection => "collection filter",
+            Mismatch::PackageName => "different package",
+            Mismatch::VersionRange => "version range miss",
+            Mismatch::PackageSource => "package source filter",
+            Mismatch::Severity => "severity threshold",
+            Mismatch::TargetArch => "target arch filter",
+            Mismatch::TargetOs => "target OS filter",
+            Mismatch::Year => "year filter",
+            Mismatch::Category => "category filter",
+            Mismatch::Withdrawn => "withdrawn filter",
+            Mismatch::Informational => "informational filter",
+            Mismatch::Unpatched => "no patched versions",
+            Mismatch::Ignored => "ignored",
+        }
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
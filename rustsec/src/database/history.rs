@@ -0,0 +1,68 @@
+//! Points in the history of the advisory database
+
+use crate::{advisory::Date, error::Error};
+use std::{fmt, str::FromStr};
+
+#[cfg(feature = "git")]
+use crate::repository::git::{Commit, Repository};
+
+/// Point in the history of the advisory database, e.g. to open it at with
+/// [`Database::open_at`](super::Database::open_at), or to list the
+/// advisories which are new since with
+/// [`Database::advisories_since`](super::Database::advisories_since).
+///
+/// Parsing a `PointInTime` from a string yields a [`PointInTime::Date`] for
+/// `YYYY-MM-DD` strings, and a [`PointInTime::Revision`] otherwise.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PointInTime {
+    /// The end of the given day (in UTC), i.e. the latest commit made on or
+    /// before it. Advisories are new since then if they were published or
+    /// withdrawn on or after it, according to their `date` and `withdrawn`
+    /// fields.
+    Date(Date),
+
+    /// The given git revision (branch or tag name, or commit hash), e.g. the
+    /// one recorded in an old report. Advisories are new since then if they
+    /// were added, or their parsed contents changed, after it.
+    #[cfg(feature = "git")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "git")))]
+    Revision(String),
+}
+
+impl PointInTime {
+    /// Find the commit of the given repository this point in time refers to
+    #[cfg(feature = "git")]
+    pub(crate) fn commit(&self, repo: &Repository) -> Result<Commit, Error> {
+        match self {
+            PointInTime::Date(date) => repo.commit_at_date(date),
+            PointInTime::Revision(revision) => repo.commit_at_revision(revision),
+        }
+    }
+}
+
+impl FromStr for PointInTime {
+    type Err = Error;
+
+    #[cfg(feature = "git")]
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(s.parse()
+            .map(PointInTime::Date)
+            .unwrap_or_else(|_| PointInTime::Revision(s.to_owned())))
+    }
+
+    #[cfg(not(feature = "git"))]
+    fn from_str(s: &str) -> Result<Self, Error> {
+        s.parse().map(PointInTime::Date)
+    }
+}
+
+impl fmt::Display for PointInTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PointInTime::Date(date) => date.fmt(f),
+            #[cfg(feature = "git")]
+            PointInTime::Revision(revision) => f.write_str(revision),
+        }
+    }
+}
@@ -9,6 +9,7 @@ use crate::{map, package, Map, Set};
 #[derive(Debug, Default)]
 pub(crate) struct Index(Map<package::Name, Set<Slot>>);
 
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), allow(dead_code))]
 impl Index {
     /// Create a new index
     pub fn new() -> Self {
@@ -1,7 +1,8 @@
 //! Queries against the RustSec database
 //!
+use super::Mismatch;
 use crate::{
-    advisory::{Advisory, Severity},
+    advisory::{self, Advisory, Category, CvssSelection, Severity},
     collection::Collection,
     package::{self, Package},
     SourceId,
@@ -27,6 +28,10 @@ pub struct Query {
     /// Severity threshold (i.e. minimum severity)
     severity: Option<Severity>,
 
+    /// Which CVSS score of advisories with several is compared against the
+    /// severity threshold
+    cvss_selection: CvssSelection,
+
     /// Target architecture
     target_arch: Vec<Arch>,
 
@@ -36,6 +41,9 @@ pub struct Query {
     /// Year associated with the advisory ID
     year: Option<u32>,
 
+    /// Advisory categories to search for
+    categories: Vec<Category>,
+
     /// Query for withdrawn advisories
     /// (i.e. advisories which were soft-deleted from the database,
     /// as opposed to yanked crates)
@@ -43,6 +51,12 @@ pub struct Query {
 
     /// Query for informational advisories
     informational: Option<bool>,
+
+    /// Only match advisories with patched versions
+    patched_versions_only: bool,
+
+    /// IDs of advisories which never match
+    ignore: Vec<advisory::Id>,
 }
 
 impl Query {
@@ -62,11 +76,15 @@ impl Query {
             package_version: None,
             package_source: None,
             severity: None,
+            cvss_selection: CvssSelection::default(),
             target_arch: Default::default(),
             target_os: Default::default(),
             year: None,
+            categories: Default::default(),
             withdrawn: None,
             informational: None,
+            patched_versions_only: false,
+            ignore: Vec::new(),
         }
     }
 
@@ -82,6 +100,15 @@ impl Query {
             .informational(false)
     }
 
+    /// Create a new query which uses the default scope rules for the Rust
+    /// toolchain (e.g. `rustc` and `cargo` themselves):
+    ///
+    /// - Only `Collection::Rust`
+    /// - Ignore withdrawn advisories
+    pub fn toolchain_scope() -> Self {
+        Self::new().collection(Collection::Rust).withdrawn(false)
+    }
+
     /// Set collection to query against
     pub fn collection(mut self, collection: Collection) -> Self {
         self.collection = Some(collection);
@@ -119,11 +146,21 @@ impl Query {
     ///
     /// Vulnerabilities without associated CVSS information will always
     /// match regardless of what this is set to.
+    ///
+    /// Advisories with several CVSS scores are compared using the highest
+    /// one, unless configured otherwise with [`Query::cvss_selection`].
     pub fn severity(mut self, severity: Severity) -> Self {
         self.severity = Some(severity);
         self
     }
 
+    /// Set which CVSS score of advisories with several is compared against
+    /// the severity threshold
+    pub fn cvss_selection(mut self, selection: CvssSelection) -> Self {
+        self.cvss_selection = selection;
+        self
+    }
+
     /// Set target architectures
     pub fn target_arch(mut self, arch: Vec<Arch>) -> Self {
         self.target_arch = arch;
@@ -142,6 +179,21 @@ impl Query {
         self
     }
 
+    /// Query for advisories in the given category.
+    ///
+    /// May be called multiple times, in which case advisories matching any
+    /// of the given categories will be returned.
+    pub fn category(mut self, category: Category) -> Self {
+        self.categories.push(category);
+        self
+    }
+
+    /// Query for advisories in any of the given categories
+    pub fn categories(mut self, categories: Vec<Category>) -> Self {
+        self.categories = categories;
+        self
+    }
+
     /// Query for withdrawn advisories.
     ///
     /// By default they will be omitted from query results.
@@ -157,23 +209,42 @@ impl Query {
         self
     }
 
+    /// Only match advisories for which patched versions have been released,
+    /// i.e. vulnerabilities which can be fixed by upgrading.
+    pub fn with_patched_versions_only(mut self) -> Self {
+        self.patched_versions_only = true;
+        self
+    }
+
+    /// Never match the advisories with the given IDs
+    pub fn ignore(mut self, ids: Vec<advisory::Id>) -> Self {
+        self.ignore = ids;
+        self
+    }
+
     /// Does this query match a given advisory?
     pub fn matches(&self, advisory: &Advisory) -> bool {
+        self.mismatch(advisory).is_none()
+    }
+
+    /// Explain why this query doesn't match a given advisory, returning the
+    /// first filter which excludes it, or `None` if the query matches
+    pub fn mismatch(&self, advisory: &Advisory) -> Option<Mismatch> {
         if let Some(collection) = self.collection {
             if Some(collection) != advisory.metadata.collection {
-                return false;
+                return Some(Mismatch::Collection);
             }
         }
 
         if let Some(package_name) = &self.package_name {
             if package_name != &advisory.metadata.package {
-                return false;
+                return Some(Mismatch::PackageName);
             }
         }
 
         if let Some(package_version) = &self.package_version {
             if !advisory.versions.is_vulnerable(package_version) {
-                return false;
+                return Some(Mismatch::VersionRange);
             }
         }
 
@@ -189,14 +260,14 @@ impl Query {
             if advisory_source.kind() != package_source.kind()
                 || advisory_source.url() != package_source.url()
             {
-                return false;
+                return Some(Mismatch::PackageSource);
             }
         }
 
         if let Some(severity_threshold) = self.severity {
-            if let Some(advisory_severity) = advisory.severity() {
+            if let Some(advisory_severity) = advisory.severity_with(&self.cvss_selection) {
                 if advisory_severity < severity_threshold {
-                    return false;
+                    return Some(Mismatch::Severity);
                 }
             }
         }
@@ -209,7 +280,7 @@ impl Query {
                     .iter()
                     .any(|target_arch| affected.arch.contains(target_arch))
             {
-                return false;
+                return Some(Mismatch::TargetArch);
             }
 
             if !affected.os.is_empty()
@@ -219,31 +290,48 @@ impl Query {
                     .iter()
                     .any(|target_os| affected.os.contains(target_os))
             {
-                return false;
+                return Some(Mismatch::TargetOs);
             }
         }
 
         if let Some(query_year) = self.year {
             if let Some(advisory_year) = advisory.metadata.id.year() {
                 if query_year != advisory_year {
-                    return false;
+                    return Some(Mismatch::Year);
                 }
             }
         }
 
+        if !self.categories.is_empty()
+            && !self
+                .categories
+                .iter()
+                .any(|category| advisory.metadata.categories.contains(category))
+        {
+            return Some(Mismatch::Category);
+        }
+
         if let Some(withdrawn) = self.withdrawn {
             if withdrawn != advisory.metadata.withdrawn.is_some() {
-                return false;
+                return Some(Mismatch::Withdrawn);
             }
         }
 
         if let Some(informational) = self.informational {
             if informational != advisory.metadata.informational.is_some() {
-                return false;
+                return Some(Mismatch::Informational);
             }
         }
 
-        true
+        if self.patched_versions_only && advisory.versions.patched().is_empty() {
+            return Some(Mismatch::Unpatched);
+        }
+
+        if self.ignore.contains(&advisory.metadata.id) {
+            return Some(Mismatch::Ignored);
+        }
+
+        None
     }
 }
 
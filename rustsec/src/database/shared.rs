@@ -0,0 +1,185 @@
+//! Advisory database which can be refreshed while in use
+
+use super::Database;
+use crate::error::Error;
+use std::{
+    path::PathBuf,
+    sync::{Arc, PoisonError, RwLock, Weak},
+    thread,
+    time::Duration,
+};
+
+#[cfg(feature = "git")]
+use crate::repository::git;
+
+/// [`Database`] shared between threads, which can be reloaded in place.
+///
+/// Long-running services (e.g. ones scanning lockfiles on request) can keep a
+/// single `SharedDatabase` around and call [`SharedDatabase::load`] to get a
+/// snapshot of the current advisories for each scan. Calling
+/// [`SharedDatabase::refresh`] (or [`SharedDatabase::watch`] to do so
+/// periodically) loads the latest advisories and atomically swaps them in:
+/// scans already in progress keep using the snapshot they started with.
+#[derive(Debug)]
+pub struct SharedDatabase {
+    /// Currently loaded database
+    current: RwLock<Arc<Database>>,
+
+    /// Where the database is reloaded from
+    source: Source,
+}
+
+/// Location the database is loaded from
+#[derive(Debug)]
+enum Source {
+    /// Local directory
+    Path(PathBuf),
+
+    /// Git repository which is re-fetched on every refresh
+    #[cfg(feature = "git")]
+    Repository {
+        url: String,
+        path: PathBuf,
+        options: git::FetchOptions,
+    },
+}
+
+impl SharedDatabase {
+    /// Open the [`Database`] located at the given local path, which is
+    /// reloaded from the same path on each refresh
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        Self::load_source(Source::Path(path.into()))
+    }
+
+    /// Fetch the [`Database`] from the git repository at the given URL into
+    /// the given local path, which is fetched again on each refresh
+    #[cfg(feature = "git")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "git")))]
+    pub fn fetch(
+        url: impl Into<String>,
+        path: impl Into<PathBuf>,
+        options: git::FetchOptions,
+    ) -> Result<Self, Error> {
+        Self::load_source(Source::Repository {
+            url: url.into(),
+            path: path.into(),
+            options,
+        })
+    }
+
+    /// Get a snapshot of the currently loaded database
+    pub fn load(&self) -> Arc<Database> {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Reload the database from its source, replacing the current one.
+    ///
+    /// If reloading fails, the current database is kept.
+    pub fn refresh(&self) -> Result<Arc<Database>, Error> {
+        let db = Arc::new(self.source.load()?);
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = db.clone();
+        Ok(db)
+    }
+
+    /// Spawn a thread which refreshes the database every `interval`, passing
+    /// any errors to `on_error` (after which the current database is kept
+    /// until the next successful refresh).
+    ///
+    /// The thread exits once all other references to this `SharedDatabase`
+    /// have been dropped.
+    pub fn watch<F>(self: &Arc<Self>, interval: Duration, mut on_error: F) -> thread::JoinHandle<()>
+    where
+        F: FnMut(Error) + Send + 'static,
+    {
+        let shared: Weak<Self> = Arc::downgrade(self);
+
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+
+            let shared = match shared.upgrade() {
+                Some(shared) => shared,
+                None => return,
+            };
+
+            if let Err(err) = shared.refresh() {
+                on_error(err);
+            }
+        })
+    }
+
+    /// Load the database from the given source
+    fn load_source(source: Source) -> Result<Self, Error> {
+        let db = source.load()?;
+        Ok(Self {
+            current: RwLock::new(Arc::new(db)),
+            source,
+        })
+    }
+}
+
+impl Source {
+    /// Load the database from this source
+    fn load(&self) -> Result<Database, Error> {
+        match self {
+            Source::Path(path) => Database::open(path),
+            #[cfg(feature = "git")]
+            Source::Repository { url, path, options } => {
+                let repo = git::Repository::fetch_with_options(url, path.clone(), options)?;
+                Database::load_from_repo(&repo)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tests::write_advisory, SharedDatabase};
+    use crate::collection::Collection;
+    use std::fs;
+
+    #[test]
+    fn refresh_swaps_database() {
+        let db_dir = tempfile::tempdir().unwrap();
+        let shared = SharedDatabase::open(db_dir.path()).unwrap();
+        let snapshot = shared.load();
+        assert_eq!(snapshot.iter().count(), 0);
+
+        write_advisory(
+            db_dir.path(),
+            Collection::Crates,
+            "base",
+            "RUSTSEC-2001-2101",
+        );
+        shared.refresh().unwrap();
+
+        let id = "RUSTSEC-2001-2101".parse().unwrap();
+        assert!(shared.load().get(&id).is_some());
+
+        // Snapshots taken before the refresh are unaffected
+        assert!(snapshot.get(&id).is_none());
+    }
+
+    #[test]
+    fn failed_refresh_keeps_database() {
+        let db_dir = tempfile::tempdir().unwrap();
+        let path = write_advisory(
+            db_dir.path(),
+            Collection::Crates,
+            "base",
+            "RUSTSEC-2001-2101",
+        );
+
+        let shared = SharedDatabase::open(db_dir.path()).unwrap();
+        fs::write(
+            path.with_file_name("RUSTSEC-2001-2102.md"),
+            "not an advisory",
+        )
+        .unwrap();
+
+        assert!(shared.refresh().is_err());
+        assert_eq!(shared.load().iter().count(), 1);
+    }
+}
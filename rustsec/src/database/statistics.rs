@@ -0,0 +1,107 @@
+//! Aggregate statistics about the advisories in the database
+
+use crate::{
+    advisory::{Advisory, Category, Informational, Severity},
+    Map,
+};
+use serde::Serialize;
+
+/// Counts of the advisories in a [`Database`](super::Database).
+///
+/// Withdrawn advisories are only included in [`Statistics::withdrawn`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct Statistics {
+    /// Total number of (non-withdrawn) advisories
+    pub total: usize,
+
+    /// Number of withdrawn advisories
+    pub withdrawn: usize,
+
+    /// Number of advisories per year they were reported in
+    pub years: Map<u32, usize>,
+
+    /// Number of advisories per CVSS severity rating
+    pub severity: Map<Severity, usize>,
+
+    /// Number of advisories with no CVSS information
+    pub unscored: usize,
+
+    /// Number of advisories per category.
+    ///
+    /// Advisories with several categories are counted once for each of them.
+    pub categories: Map<Category, usize>,
+
+    /// Number of informational advisories per kind (e.g. `unmaintained`)
+    pub informational: Map<Informational, usize>,
+
+    /// Number of advisories with at least one patched version
+    pub patched: usize,
+
+    /// Number of advisories with no patched versions
+    pub unpatched: usize,
+}
+
+impl Statistics {
+    /// Compute statistics about the given advisories
+    pub fn new<'a>(advisories: impl IntoIterator<Item = &'a Advisory>) -> Self {
+        let mut stats = Self::default();
+
+        for advisory in advisories {
+            if advisory.withdrawn() {
+                stats.withdrawn += 1;
+                continue;
+            }
+
+            stats.total += 1;
+            *stats.years.entry(advisory.date().year()).or_default() += 1;
+
+            match advisory.severity() {
+                Some(severity) => *stats.severity.entry(severity).or_default() += 1,
+                None => stats.unscored += 1,
+            }
+
+            for category in &advisory.metadata.categories {
+                *stats.categories.entry(category.clone()).or_default() += 1;
+            }
+
+            if let Some(informational) = &advisory.metadata.informational {
+                *stats
+                    .informational
+                    .entry(informational.clone())
+                    .or_default() += 1;
+            }
+
+            if advisory.versions.patched().is_empty() {
+                stats.unpatched += 1;
+            } else {
+                stats.patched += 1;
+            }
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_advisories() {
+        let advisories = [
+            Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap(),
+            Advisory::load_file("./tests/support/example_advisory_v4.md").unwrap(),
+        ];
+
+        let stats = Statistics::new(&advisories);
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.withdrawn, 0);
+        assert_eq!(stats.years[&2001], 2);
+        assert_eq!(stats.severity[&Severity::Critical], 2);
+        assert_eq!(stats.unscored, 0);
+        assert_eq!(stats.categories[&Category::CodeExecution], 2);
+        assert!(stats.informational.is_empty());
+        assert_eq!(stats.patched, 2);
+        assert_eq!(stats.unpatched, 0);
+    }
+}
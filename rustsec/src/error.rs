@@ -3,6 +3,7 @@
 use std::{
     fmt::{self, Display},
     io,
+    path::{Path, PathBuf},
     str::Utf8Error,
 };
 use thiserror::Error;
@@ -34,6 +35,11 @@ macro_rules! fail {
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Error type
+///
+/// Use [`Error::kind()`] to distinguish between classes of failures (e.g. to
+/// retry [`ErrorKind::Network`] errors, or report [`ErrorKind::Parse`] errors
+/// upstream), [`Error::path()`] to find the file an error relates to, and
+/// [`std::error::Error::source()`] to walk the chain of underlying errors.
 #[derive(Debug)]
 pub struct Error {
     /// Kind of error
@@ -44,6 +50,9 @@ pub struct Error {
     /// This may be a complete error by itself, or it may provide context for `self.source`.
     msg: String,
 
+    /// File this error relates to (e.g. the advisory which failed to parse), if any.
+    path: Option<PathBuf>,
+
     /// Cause of this error.
     ///
     /// The specific type of this error should not be considered part of the stable interface of
@@ -63,6 +72,7 @@ impl Error {
         Self {
             kind,
             msg: description.to_string(),
+            path: None,
             source: None,
         }
     }
@@ -80,6 +90,7 @@ impl Error {
         Self {
             kind,
             msg,
+            path: None,
             source: Some(Box::new(source)),
         }
     }
@@ -88,6 +99,25 @@ impl Error {
     pub fn kind(&self) -> ErrorKind {
         self.kind
     }
+
+    /// Record the file this error relates to, unless one is already recorded.
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        if self.path.is_none() {
+            self.path = Some(path.into());
+        }
+
+        self
+    }
+
+    /// Get the file this error relates to, if any
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Is this error likely to be transient, so the operation is worth retrying?
+    pub fn is_transient(&self) -> bool {
+        self.kind.is_transient()
+    }
 }
 
 impl Display for Error {
@@ -123,10 +153,14 @@ pub enum ErrorKind {
     #[error("fix failed")]
     Fix,
 
-    /// An error occurred performing an I/O operation (e.g. network, file)
+    /// An error occurred performing an I/O operation (e.g. on a file)
     #[error("I/O operation failed")]
     Io,
 
+    /// A network operation failed (e.g. fetching the advisory database)
+    #[error("network operation failed")]
+    Network,
+
     /// Not found
     #[error("not found")]
     NotFound,
@@ -150,6 +184,21 @@ pub enum ErrorKind {
     /// Errors related to versions
     #[error("bad version")]
     Version,
+
+    /// Invalid or inconsistent version requirements (e.g. overlapping ranges)
+    #[error("invalid version specification")]
+    VersionSpec,
+}
+
+impl ErrorKind {
+    /// Are errors of this kind likely to be transient, so the operation is
+    /// worth retrying?
+    ///
+    /// Only [`ErrorKind::Network`] errors are: how long to wait for a
+    /// filesystem lock is up to the lock timeout of the operation instead.
+    pub fn is_transient(self) -> bool {
+        matches!(self, ErrorKind::Network)
+    }
 }
 
 impl From<Utf8Error> for Error {
@@ -189,12 +238,16 @@ impl Error {
         // Separate lock timeouts into their own LockTimeout variant.
         use tame_index::utils::flock::LockError;
         match err {
-            tame_index::Error::Lock(lock_err) => match &lock_err.source {
-                LockError::TimedOut | LockError::Contested => {
-                    format_err!(ErrorKind::LockTimeout, "{}", lock_err)
-                }
-                _ => format_err!(ErrorKind::Io, "{}", lock_err),
-            },
+            // Report failed requests to a sparse index as network errors, so
+            // they can be retried
+            err if is_tame_network_error(&err) => format_err!(ErrorKind::Network, "{}", err),
+            tame_index::Error::Lock(lock_err) => {
+                let kind = match &lock_err.source {
+                    LockError::TimedOut | LockError::Contested => ErrorKind::LockTimeout,
+                    _ => ErrorKind::Io,
+                };
+                format_err!(kind, "{}", lock_err)
+            }
             other => format_err!(ErrorKind::Registry, "{}", other),
         }
     }
@@ -208,6 +261,20 @@ impl Error {
     }
 }
 
+/// Did a request to a sparse index fail because it couldn't reach the
+/// index, timed out or hit a server error?
+#[cfg(feature = "git")]
+pub(crate) fn is_tame_network_error(err: &tame_index::Error) -> bool {
+    use tame_index::{external::http::StatusCode, HttpError};
+    match err {
+        tame_index::Error::Http(HttpError::Reqwest(_) | HttpError::Timeout) => true,
+        tame_index::Error::Http(HttpError::StatusCode { code, .. }) => {
+            code.is_server_error() || *code == StatusCode::TOO_MANY_REQUESTS
+        }
+        _ => false,
+    }
+}
+
 impl From<toml::ser::Error> for Error {
     fn from(other: toml::ser::Error) -> Self {
         format_err!(ErrorKind::Parse, &other)
@@ -2,10 +2,22 @@
 //!
 //! This module is **experimental**, and its behavior may change in the future.
 
-use crate::vulnerability::Vulnerability;
-use cargo_lock::{Lockfile, Package};
+use crate::{advisory, vulnerability::Vulnerability};
+use cargo_lock::{package, Lockfile, Package};
+use semver::{Version, VersionReq};
+use std::fmt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use url::Url;
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use {
+    crate::{
+        error::{Error, ErrorKind},
+        fs,
+    },
+    std::fmt::Write as _,
+};
 
 /// Auto-fixer for vulnerable dependencies
 #[cfg_attr(docsrs, doc(cfg(feature = "fix")))]
@@ -62,6 +74,222 @@ impl Fixer {
 
         command
     }
+
+    /// Plan the upgrades which would fix the given vulnerabilities, without
+    /// making any changes, e.g. so they can be reviewed before being applied
+    /// with [`Fixer::get_fix_command`].
+    pub fn plan<'a>(
+        &self,
+        vulnerabilities: impl IntoIterator<Item = &'a Vulnerability>,
+    ) -> Vec<PlannedFix<'a>> {
+        vulnerabilities
+            .into_iter()
+            .map(|vulnerability| {
+                let target = preferred_patch(vulnerability).and_then(minimum_version);
+                let semver_compatible = target.as_ref().map_or(false, |target| {
+                    is_semver_compatible(&vulnerability.package.version, target)
+                });
+
+                PlannedFix {
+                    vulnerability,
+                    target,
+                    semver_compatible,
+                }
+            })
+            .collect()
+    }
+
+    /// Suggest a `[patch.crates-io]` entry for a vulnerability which can't be
+    /// fixed by `cargo update`, e.g. because another crate's version
+    /// requirement is too tight to allow the patched release.
+    ///
+    /// Cargo requires patches to point at a different source than the one
+    /// being patched, so `fork` should be the URL of a git repository
+    /// containing the fixed code. Without it the suggestion can still be
+    /// presented to the user, but not written to a manifest.
+    ///
+    /// Returns `None` if the advisory lists no patched versions.
+    pub fn patch_suggestion(
+        &self,
+        vulnerability: &Vulnerability,
+        fork: Option<Url>,
+    ) -> Option<PatchSuggestion> {
+        let requirement = preferred_patch(vulnerability)?;
+
+        Some(PatchSuggestion {
+            advisory: vulnerability.advisory.id.clone(),
+            package: vulnerability.package.name.clone(),
+            version: vulnerability.package.version.clone(),
+            patched: requirement.clone(),
+            git: fork,
+        })
+    }
+
+    /// Append the given `[patch.crates-io]` suggestions to the `Cargo.toml`
+    /// file at `manifest_path`.
+    ///
+    /// Suggestions without a `git` source are skipped, as are packages which
+    /// already have a `[patch.crates-io]` entry in the manifest. Returns the
+    /// suggestions which were written.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    pub fn append_patches<'a>(
+        &self,
+        manifest_path: &Path,
+        suggestions: &'a [PatchSuggestion],
+    ) -> Result<Vec<&'a PatchSuggestion>, Error> {
+        let mut manifest = fs::read_to_string(manifest_path)?;
+        let parsed: toml::Table = toml::from_str(&manifest).map_err(|e| {
+            format_err!(
+                ErrorKind::Parse,
+                "couldn't parse {}: {}",
+                manifest_path.display(),
+                e
+            )
+        })?;
+
+        let existing = parsed
+            .get("patch")
+            .and_then(|patch| patch.get("crates-io"))
+            .and_then(|crates_io| crates_io.as_table());
+
+        let mut written = vec![];
+
+        for suggestion in suggestions {
+            if suggestion.git.is_none()
+                || existing.map_or(false, |t| t.contains_key(suggestion.package.as_str()))
+                || written
+                    .iter()
+                    .any(|s: &&PatchSuggestion| s.package == suggestion.package)
+            {
+                continue;
+            }
+
+            if !manifest.is_empty() && !manifest.ends_with('\n') {
+                manifest.push('\n');
+            }
+
+            write!(manifest, "\n{}", suggestion)?;
+            written.push(suggestion);
+        }
+
+        if !written.is_empty() {
+            fs::write(manifest_path, manifest)?;
+        }
+
+        Ok(written)
+    }
+}
+
+/// Upgrade planned by [`Fixer::plan`] to fix a vulnerability
+#[derive(Clone, Debug)]
+pub struct PlannedFix<'a> {
+    /// Vulnerability to be fixed
+    pub vulnerability: &'a Vulnerability,
+
+    /// Lowest patched version of the vulnerable package, or `None` if the
+    /// advisory lists no patched versions
+    pub target: Option<Version>,
+
+    /// Can the package be upgraded to `target` without a semver-incompatible
+    /// version bump (i.e. by `cargo update` alone)?
+    pub semver_compatible: bool,
+}
+
+/// Suggested `[patch.crates-io]` entry for a vulnerable package
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatchSuggestion {
+    /// Advisory the vulnerable package is affected by
+    pub advisory: advisory::Id,
+
+    /// Name of the package to patch
+    pub package: package::Name,
+
+    /// Vulnerable version of the package currently in `Cargo.lock`
+    pub version: Version,
+
+    /// Requirement matching the patched version to use
+    pub patched: VersionReq,
+
+    /// Git repository (e.g. a fork) containing the patched code
+    pub git: Option<Url>,
+}
+
+impl fmt::Display for PatchSuggestion {
+    /// Render this suggestion as a TOML table which can be added to `Cargo.toml`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# {}: {} {} is vulnerable",
+            self.advisory, self.package, self.version
+        )?;
+        writeln!(f, "[patch.crates-io.{}]", self.package)?;
+
+        if let Some(git) = &self.git {
+            writeln!(f, "git = {}", toml::Value::from(git.as_str()))?;
+        }
+
+        writeln!(
+            f,
+            "version = {}",
+            toml::Value::from(self.patched.to_string())
+        )
+    }
+}
+
+/// Pick the patched version requirement to upgrade a vulnerable package to.
+///
+/// Prefers a patched release with the same major version as the vulnerable
+/// one, since that's most likely to satisfy the existing version requirements
+/// in the dependency graph.
+fn preferred_patch(vulnerability: &Vulnerability) -> Option<&VersionReq> {
+    let patched = vulnerability.versions.patched();
+
+    patched
+        .iter()
+        .find(|req| {
+            req.comparators
+                .first()
+                .map_or(false, |c| c.major == vulnerability.package.version.major)
+        })
+        .or_else(|| patched.first())
+}
+
+/// Lowest version matching the first comparator of the given requirement
+fn minimum_version(req: &VersionReq) -> Option<Version> {
+    let comparator = req.comparators.first()?;
+    let mut version = Version::new(
+        comparator.major,
+        comparator.minor.unwrap_or(0),
+        comparator.patch.unwrap_or(0),
+    );
+    version.pre = comparator.pre.clone();
+
+    match comparator.op {
+        semver::Op::Exact
+        | semver::Op::GreaterEq
+        | semver::Op::Tilde
+        | semver::Op::Caret
+        | semver::Op::Wildcard => Some(version),
+        semver::Op::Greater => {
+            match (comparator.minor, comparator.patch) {
+                (_, Some(_)) => version.patch += 1,
+                (Some(_), None) => version.minor += 1,
+                (None, _) => version.major += 1,
+            }
+            version.pre = semver::Prerelease::EMPTY;
+            Some(version)
+        }
+        _ => None,
+    }
+}
+
+/// Are the given versions compatible according to Cargo's semver rules?
+fn is_semver_compatible(current: &Version, target: &Version) -> bool {
+    match (current.major, current.minor) {
+        (0, 0) => target.major == 0 && target.minor == 0 && target.patch == current.patch,
+        (0, minor) => target.major == 0 && target.minor == minor,
+        (major, _) => target.major == major,
+    }
 }
 
 /// Returns a Cargo unique identifier for a package.
@@ -75,3 +303,80 @@ fn pkgid(pkg: &Package) -> String {
         None => format!("{}@{}", pkg.name, pkg.version),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Advisory;
+
+    fn vulnerability() -> Vulnerability {
+        let advisory = Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap();
+        let package = Package {
+            name: "base".parse().unwrap(),
+            version: "1.0.0".parse().unwrap(),
+            source: None,
+            checksum: None,
+            dependencies: vec![],
+            replace: None,
+        };
+        Vulnerability::new(&advisory, &package)
+    }
+
+    fn fixer() -> Fixer {
+        let lockfile = "[[package]]\nname = \"base\"\nversion = \"1.0.0\"\n"
+            .parse()
+            .unwrap();
+        Fixer::new(lockfile, None, None)
+    }
+
+    #[test]
+    fn plan_fixes() {
+        let vulnerability = vulnerability();
+        let plan = fixer().plan([&vulnerability]);
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].target, Some("1.2.3".parse().unwrap()));
+        assert!(plan[0].semver_compatible);
+
+        let mut breaking = vulnerability.clone();
+        breaking.versions = toml::from_str("patched = [\">= 2.0.0\"]").unwrap();
+        let plan = fixer().plan([&breaking]);
+        assert_eq!(plan[0].target, Some("2.0.0".parse().unwrap()));
+        assert!(!plan[0].semver_compatible);
+
+        breaking.versions = toml::from_str("patched = []").unwrap();
+        assert_eq!(fixer().plan([&breaking])[0].target, None);
+    }
+
+    #[test]
+    fn suggest_and_append_patch() {
+        let fork: Url = "https://github.com/example/base".parse().unwrap();
+        let suggestion = fixer()
+            .patch_suggestion(&vulnerability(), Some(fork))
+            .unwrap();
+        assert_eq!(suggestion.patched.to_string(), ">=1.2.3");
+
+        let dir = tempfile::tempdir().unwrap();
+        let manifest_path = dir.path().join("Cargo.toml");
+        fs::write(&manifest_path, "[package]\nname = \"app\"\n").unwrap();
+
+        let suggestions = [suggestion];
+        let written = fixer()
+            .append_patches(&manifest_path, &suggestions)
+            .unwrap();
+        assert_eq!(written.len(), 1);
+
+        // Existing entries are not written twice
+        let written = fixer()
+            .append_patches(&manifest_path, &suggestions)
+            .unwrap();
+        assert!(written.is_empty());
+
+        let manifest: toml::Table = fs::read_to_string(&manifest_path).unwrap().parse().unwrap();
+        let patch = &manifest["patch"]["crates-io"]["base"];
+        assert_eq!(
+            patch["git"].as_str(),
+            Some("https://github.com/example/base")
+        );
+        assert_eq!(patch["version"].as_str(), Some(">=1.2.3"));
+    }
+}
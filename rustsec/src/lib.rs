@@ -12,8 +12,11 @@ mod collection;
 pub mod database;
 mod fixer;
 pub mod osv;
+pub mod progress;
 pub mod report;
 pub mod repository;
+pub mod retry;
+pub mod toolchain;
 mod vulnerability;
 mod warning;
 
@@ -21,6 +24,12 @@ mod warning;
 #[cfg_attr(docsrs, doc(cfg(feature = "git")))]
 mod cached_index;
 
+#[cfg(feature = "tokio")]
+mod task;
+
+#[cfg(all(feature = "git", target_arch = "wasm32", target_os = "unknown"))]
+compile_error!("the `git` feature isn't supported on wasm32-unknown-unknown");
+
 #[cfg(feature = "git")]
 #[cfg_attr(docsrs, doc(cfg(feature = "git")))]
 pub mod registry {
@@ -38,12 +47,12 @@ pub use crate::{
     collection::Collection,
     database::Database,
     error::{Error, ErrorKind, Result},
-    report::Report,
+    report::{AuditSource, Report},
     vulnerability::Vulnerability,
     warning::{Warning, WarningKind},
 };
 
-pub use crate::fixer::Fixer;
+pub use crate::fixer::{Fixer, PatchSuggestion, PlannedFix};
 
 #[cfg(feature = "git")]
 pub use crate::repository::git::Repository;
@@ -8,7 +8,9 @@ use tame_index::external::gix;
 use super::ranges_for_advisory;
 use crate::advisory::Versions;
 use crate::{
-    advisory::{affected::FunctionPath, Affected, Category, Id, Informational},
+    advisory::{
+        affected::FunctionPath, Affected, Category, Id, Informational, Reference, ReferenceKind,
+    },
     repository::git::{self, GitModificationTimes, GitPath},
     Advisory,
 };
@@ -133,9 +135,15 @@ pub struct OsvReference {
 
 impl From<Url> for OsvReference {
     fn from(url: Url) -> Self {
+        Reference::from(url).into()
+    }
+}
+
+impl From<Reference> for OsvReference {
+    fn from(reference: Reference) -> Self {
         OsvReference {
-            kind: guess_url_kind(&url),
-            url,
+            kind: reference.kind.into(),
+            url: reference.url,
         }
     }
 }
@@ -144,15 +152,26 @@ impl From<Url> for OsvReference {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OsvReferenceKind {
     ADVISORY,
-    #[allow(dead_code)]
     ARTICLE,
     REPORT,
-    #[allow(dead_code)]
     FIX,
     PACKAGE,
     WEB,
 }
 
+impl From<ReferenceKind> for OsvReferenceKind {
+    fn from(kind: ReferenceKind) -> Self {
+        match kind {
+            ReferenceKind::Advisory => OsvReferenceKind::ADVISORY,
+            ReferenceKind::Article => OsvReferenceKind::ARTICLE,
+            ReferenceKind::Report => OsvReferenceKind::REPORT,
+            ReferenceKind::Fix => OsvReferenceKind::FIX,
+            ReferenceKind::Package => OsvReferenceKind::PACKAGE,
+            ReferenceKind::Web => OsvReferenceKind::WEB,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OsvEcosystemSpecific {
     affects: Option<OsvEcosystemSpecificAffected>,
@@ -212,23 +231,33 @@ impl OsvAdvisory {
     ) -> Self {
         let metadata = advisory.metadata;
 
+        // Include the scores for specific configurations along with the main one
+        let severity = metadata
+            .all_cvss()
+            .map(|(_, cvss)| cvss.clone().into())
+            .collect();
+
         // Assemble the URLs to put into 'references' field
-        let mut reference_urls: Vec<Url> = Vec::new();
+        let mut reference_urls: Vec<Reference> = Vec::new();
         // link to the package on crates.io
         let package_url = "https://crates.io/crates/".to_owned() + metadata.package.as_str();
-        reference_urls.push(Url::parse(&package_url).unwrap());
+        reference_urls.push(Url::parse(&package_url).unwrap().into());
         // link to human-readable RustSec advisory
         let advisory_url = format!(
             "https://rustsec.org/advisories/{}.html",
             metadata.id.as_str()
         );
-        reference_urls.push(Url::parse(&advisory_url).unwrap());
+        reference_urls.push(Url::parse(&advisory_url).unwrap().into());
         // primary URL for the issue specified in the advisory
         if let Some(url) = metadata.url {
-            reference_urls.push(url);
+            reference_urls.push(url.into());
         }
         // other references
         reference_urls.extend(metadata.references);
+        // links to the fix
+        if let Some(fixed_by) = &advisory.fixed_by {
+            reference_urls.extend(fixed_by.references());
+        }
 
         OsvAdvisory {
             schema_version: None,
@@ -253,7 +282,7 @@ impl OsvAdvisory {
             aliases: metadata.aliases,
             related: metadata.related,
             summary: metadata.title,
-            severity: metadata.cvss.into_iter().map(|s| s.into()).collect(),
+            severity,
             details: metadata.description,
             references: osv_references(reference_urls),
             database_specific: MainOsvDatabaseSpecific {
@@ -308,22 +337,8 @@ impl OsvAdvisory {
     }
 }
 
-fn osv_references(references: Vec<Url>) -> Vec<OsvReference> {
-    references.into_iter().map(|u| u.into()).collect()
-}
-
-fn guess_url_kind(url: &Url) -> OsvReferenceKind {
-    let str = url.as_str();
-    if (str.contains("://github.com/") || str.contains("://gitlab.")) && str.contains("/issues/") {
-        OsvReferenceKind::REPORT
-    // the check for "/advisories/" matches both RustSec and GHSA URLs
-    } else if str.contains("/advisories/") || str.contains("://cve.mitre.org/") {
-        OsvReferenceKind::ADVISORY
-    } else if str.contains("://crates.io/crates/") {
-        OsvReferenceKind::PACKAGE
-    } else {
-        OsvReferenceKind::WEB
-    }
+fn osv_references(references: Vec<Reference>) -> Vec<OsvReference> {
+    references.into_iter().map(|r| r.into()).collect()
 }
 
 /// Generates the timeline of the bug being introduced and fixed for the
@@ -61,7 +61,7 @@ fn unaffected_to_osv_ranges(
         for b in unaffected[idx + 1..].iter() {
             if a.overlaps(b) {
                 fail!(
-                    crate::ErrorKind::BadParam,
+                    crate::ErrorKind::VersionSpec,
                     format!("Overlapping version ranges: {} and {}", a, b)
                 );
             }
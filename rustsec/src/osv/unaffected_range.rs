@@ -2,7 +2,7 @@
 //! Cargo-style version selectors (`>=`, `^`, `<`, etc) to OSV ranges.
 //! It is an implementation detail and is not exported outside OSV module.
 
-use crate::{Error, ErrorKind::BadParam};
+use crate::{Error, ErrorKind::VersionSpec};
 use semver::{Comparator, Op, Prerelease, Version};
 use std::fmt::Display;
 
@@ -65,7 +65,7 @@ impl UnaffectedRange {
             Ok(UnaffectedRange { start, end })
         } else {
             Err(format_err!(
-                BadParam,
+                VersionSpec,
                 "Invalid range: start must be <= end; if equal, both bounds must be inclusive"
             ))
         }
@@ -113,7 +113,7 @@ impl TryFrom<&semver::VersionReq> for UnaffectedRange {
     fn try_from(input: &semver::VersionReq) -> Result<Self, Self::Error> {
         if input.comparators.len() > 2 {
             fail!(
-                BadParam,
+                VersionSpec,
                 format!("Too many comparators in version specification: {}", input)
             );
         }
@@ -131,7 +131,7 @@ impl TryFrom<&semver::VersionReq> for UnaffectedRange {
                 Op::Greater => {
                     if start != Bound::Unbounded {
                         fail!(
-                            BadParam,
+                            VersionSpec,
                             format!("More than one lower bound in the same range: {}", input)
                         );
                     }
@@ -140,7 +140,7 @@ impl TryFrom<&semver::VersionReq> for UnaffectedRange {
                 Op::GreaterEq => {
                     if start != Bound::Unbounded {
                         fail!(
-                            BadParam,
+                            VersionSpec,
                             format!("More than one lower bound in the same range: {}", input)
                         );
                     }
@@ -149,7 +149,7 @@ impl TryFrom<&semver::VersionReq> for UnaffectedRange {
                 Op::Less => {
                     if end != Bound::Unbounded {
                         fail!(
-                            BadParam,
+                            VersionSpec,
                             format!("More than one upper bound in the same range: {}", input)
                         );
                     }
@@ -158,7 +158,7 @@ impl TryFrom<&semver::VersionReq> for UnaffectedRange {
                 Op::LessEq => {
                     if end != Bound::Unbounded {
                         fail!(
-                            BadParam,
+                            VersionSpec,
                             format!("More than one upper bound in the same range: {}", input)
                         );
                     }
@@ -166,14 +166,14 @@ impl TryFrom<&semver::VersionReq> for UnaffectedRange {
                 }
                 Op::Exact => {
                     if input.comparators.len() != 1 {
-                        fail!(BadParam, "Selectors that define an exact version (e.g. '=1.0') must be alone in their range");
+                        fail!(VersionSpec, "Selectors that define an exact version (e.g. '=1.0') must be alone in their range");
                     }
                     start = Bound::Inclusive(comp_to_ver(comparator));
                     end = Bound::Inclusive(comp_to_ver(comparator));
                 }
                 Op::Caret => {
                     if input.comparators.len() != 1 {
-                        fail!(BadParam, "Selectors that define both the upper and lower bound (e.g. '^1.0') must be alone in their range");
+                        fail!(VersionSpec, "Selectors that define both the upper and lower bound (e.g. '^1.0') must be alone in their range");
                     }
                     let start_version = comp_to_ver(comparator);
                     let mut end_version = if start_version.major == 0 {
@@ -199,7 +199,7 @@ impl TryFrom<&semver::VersionReq> for UnaffectedRange {
                 }
                 Op::Tilde => {
                     if input.comparators.len() != 1 {
-                        fail!(BadParam, "Selectors that define both the upper and lower bound (e.g. '~1.0') must be alone in their range");
+                        fail!(VersionSpec, "Selectors that define both the upper and lower bound (e.g. '~1.0') must be alone in their range");
                     }
                     let start_version = comp_to_ver(comparator);
                     let major = comparator.major;
@@ -219,7 +219,7 @@ impl TryFrom<&semver::VersionReq> for UnaffectedRange {
                 _ => {
                     // the struct is non-exhaustive, we have to do this
                     fail!(
-                        BadParam,
+                        VersionSpec,
                         "Unsupported operator in version specification: '{}'",
                         comparator
                     );
@@ -0,0 +1,26 @@
+//! Progress reporting for fetching and loading the advisory database
+
+/// Observer notified of the progress of fetching and loading the advisory
+/// database, e.g. to display a progress bar.
+///
+/// All methods do nothing by default, so observers only need to implement
+/// the ones they're interested in. The unit type `()` can be used as an
+/// observer which ignores all progress.
+pub trait Progress: Send + Sync {
+    /// Called periodically while fetching the repository with the number of
+    /// bytes received so far
+    fn bytes_received(&self, _bytes: usize) {}
+
+    /// Called periodically while indexing a fetched pack with the number of
+    /// objects resolved so far, out of `total` if known
+    fn objects_resolved(&self, _resolved: usize, _total: Option<usize>) {}
+
+    /// Called as advisories are loaded with the number of advisory files
+    /// parsed so far, out of `total`.
+    ///
+    /// With the `parallel` feature enabled, this is called from several
+    /// threads, so calls may be slightly out of order.
+    fn advisories_parsed(&self, _parsed: usize, _total: usize) {}
+}
+
+impl Progress for () {}
@@ -3,18 +3,49 @@
 //! These types map directly to the JSON report generated by `cargo-audit`,
 //! but also provide the core reporting functionality used in general.
 
+mod audit_source;
+mod diff;
+mod enrichment;
+#[cfg(feature = "dependency-tree")]
+mod graph;
+mod merge;
+mod summary;
+
+pub use self::{
+    audit_source::{AuditSource, CargoMetadata, Sbom},
+    diff::{Changes, ReportDiff},
+    enrichment::{Enrichment, Epss, KnownExploited, Probability},
+    merge::{MergedReport, Sourced},
+    summary::Summary,
+};
+
+#[cfg(feature = "dependency-tree")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dependency-tree")))]
+pub use self::graph::{Edge, EdgeKind, Graph, Node};
+
 use crate::{
-    advisory,
+    advisory::{self, Date},
     database::{Database, Query},
+    error::{Error, ErrorKind},
     map,
+    package::{self, GitReference, Package},
     platforms::target::{Arch, OS},
+    toolchain::{Tool, ToolVersion},
     vulnerability::Vulnerability,
     warning::{self, Warning},
-    Lockfile, Map,
+    Lockfile, Map, Version, VersionReq,
+};
+use serde::{de, ser, Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, VecDeque},
+    fmt,
+    str::FromStr,
 };
-use serde::{Deserialize, Serialize};
 
-/// Vulnerability report for a given lockfile
+/// Vulnerability report for a given lockfile.
+///
+/// Findings are sorted (see [`Report::sort`]) and all maps are ordered by
+/// key, so the serialized report only changes when the findings do.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Report {
     /// Information about the advisory database
@@ -33,30 +64,435 @@ pub struct Report {
 
     /// Warnings about dependencies (from e.g. informational advisories)
     pub warnings: WarningInfo,
+
+    /// Advisories which matched dependencies but were ignored
+    #[serde(default)]
+    pub ignored: Vec<IgnoredAdvisory>,
+
+    /// Aggregate counts of the vulnerabilities and warnings above
+    #[serde(default)]
+    pub summary: Summary,
 }
 
 impl Report {
-    /// Generate a report for the given advisory database and lockfile
-    pub fn generate(db: &Database, lockfile: &Lockfile, settings: &Settings) -> Self {
-        let vulnerabilities = db
-            .query_vulnerabilities(lockfile, &settings.query())
+    /// Generate a report for the given advisory database and audit source
+    /// (e.g. a [`Lockfile`])
+    pub fn generate<S>(db: &Database, source: &S, settings: &Settings) -> Self
+    where
+        S: AuditSource + ?Sized,
+    {
+        let packages = source.packages();
+        let mut query = settings.query();
+
+        // Informational advisories rarely have patched versions, so this only
+        // applies to vulnerabilities rather than warnings
+        if settings.fixable_only {
+            query = query.with_patched_versions_only();
+        }
+
+        // Informational advisories promoted to vulnerabilities are found with
+        // the same query, so the same filters (e.g. the severity threshold)
+        // apply to them
+        let promoted = db
+            .query_vulnerabilities(packages, &query.clone().informational(true))
+            .into_iter()
+            .filter(|vuln| settings.is_promoted(&vuln.advisory))
+            .map(|mut vuln| {
+                vuln.promoted_from = vuln.advisory.informational.clone();
+                vuln
+            });
+
+        let (ignored, vulnerabilities): (Vec<_>, Vec<_>) = db
+            .query_vulnerabilities(packages, &query)
+            .into_iter()
+            .chain(promoted)
+            .filter(|vuln| settings.is_source_audited(&vuln.package))
+            .partition(|vuln| settings.is_ignored(&vuln.advisory.id, &vuln.package));
+
+        let mut ignored: Vec<_> = ignored
             .into_iter()
-            .filter(|vuln| !settings.ignore.contains(&vuln.advisory.id))
+            .map(|vuln| IgnoredAdvisory::new(vuln.advisory, vuln.package, settings))
             .collect();
 
-        let warnings = find_warnings(db, lockfile, settings);
+        let mut warnings = collect_warnings(db, packages, settings, &mut ignored);
 
-        Self {
+        let (demoted, vulnerabilities): (Vec<_>, Vec<_>) = vulnerabilities
+            .into_iter()
+            .partition(|vuln| settings.is_demoted(&vuln.advisory.id));
+
+        for vulnerability in demoted {
+            let mut warning = Warning::new(
+                warning::WarningKind::Notice,
+                &vulnerability.package,
+                Some(vulnerability.advisory),
+                vulnerability.affected,
+                Some(vulnerability.versions),
+            );
+            warning.select_cvss(&settings.cvss_selection);
+            warning.demoted = true;
+            warnings.entry(warning.kind).or_default().push(warning);
+        }
+
+        let depths = dependency_depths(packages);
+        let vulnerabilities = VulnerabilityInfo::new(
+            vulnerabilities
+                .into_iter()
+                .map(|mut vulnerability| {
+                    let package = &vulnerability.package;
+                    vulnerability.depth = depths.get(&(&package.name, &package.version)).copied();
+                    vulnerability.direct = vulnerability.depth == Some(1);
+                    vulnerability
+                })
+                .collect(),
+        );
+
+        if settings.source_warnings {
+            let source_warnings: Vec<_> = find_source_warnings(packages)
+                .into_iter()
+                .filter(|warning| settings.is_source_audited(&warning.package))
+                .collect();
+            if !source_warnings.is_empty() {
+                warnings.insert(warning::WarningKind::Source, source_warnings);
+            }
+        }
+
+        let banned_warnings = find_banned_warnings(packages, settings);
+        if !banned_warnings.is_empty() {
+            warnings.insert(warning::WarningKind::Banned, banned_warnings);
+        }
+
+        let summary = Summary::new(
+            &vulnerabilities,
+            &warnings,
+            packages,
+            &settings.cvss_selection,
+        );
+
+        #[cfg(feature = "git")]
+        let mut database = DatabaseInfo::new(db);
+
+        #[cfg(feature = "git")]
+        if let Some(max_age_days) = settings.max_database_age_days {
+            database.stale |= database.is_older_than(max_age_days);
+        }
+
+        let mut report = Self {
             #[cfg(feature = "git")]
-            database: DatabaseInfo::new(db),
-            lockfile: LockfileInfo::new(lockfile),
+            database,
+            lockfile: LockfileInfo {
+                dependency_count: packages.len(),
+            },
             settings: settings.clone(),
-            vulnerabilities: VulnerabilityInfo::new(vulnerabilities),
+            vulnerabilities,
             warnings,
+            ignored,
+            summary,
+        };
+        report.sort();
+        report
+    }
+
+    /// Sort the vulnerabilities, warnings (of each kind), and ignored
+    /// advisories in this report by package name and version, then by
+    /// advisory ID, so that reports of the same findings serialize
+    /// identically regardless of the order they were found in.
+    ///
+    /// Generated reports are already sorted, so this only needs to be called
+    /// after adding findings to a report.
+    pub fn sort(&mut self) {
+        self.vulnerabilities.list.sort_by(|a, b| {
+            finding_order(
+                &a.package,
+                Some(&a.advisory.id),
+                &b.package,
+                Some(&b.advisory.id),
+            )
+        });
+
+        for warnings in self.warnings.values_mut() {
+            warnings.sort_by(|a, b| {
+                finding_order(
+                    &a.package,
+                    a.advisory.as_ref().map(|advisory| &advisory.id),
+                    &b.package,
+                    b.advisory.as_ref().map(|advisory| &advisory.id),
+                )
+            });
         }
+
+        self.ignored.sort_by(|a, b| {
+            finding_order(
+                &a.package,
+                Some(&a.advisory.id),
+                &b.package,
+                Some(&b.advisory.id),
+            )
+        });
+    }
+
+    /// Record how many days each vulnerability's advisory had been published
+    /// for as of the given date (e.g. [`Date::today`]).
+    ///
+    /// Ages aren't recorded by [`Report::generate`], so reports stay the same
+    /// no matter when they're generated unless this is called.
+    pub fn add_advisory_ages(&mut self, as_of: &Date) {
+        for vulnerability in &mut self.vulnerabilities.list {
+            vulnerability.advisory_age_days =
+                u64::try_from(as_of.days_since(&vulnerability.advisory.date)).ok();
+        }
+    }
+
+    /// Include the CVSS vector which determines the severity of each finding
+    /// in the structured representation of [`cvss::v3::structured`], for
+    /// consumers which don't want to parse vector strings.
+    ///
+    /// Vulnerabilities use the score chosen by [`Settings::cvss_selection`],
+    /// and warnings their [`Warning::cvss`].
+    pub fn add_cvss_details(&mut self) {
+        for vulnerability in &mut self.vulnerabilities.list {
+            vulnerability.cvss_details = vulnerability
+                .advisory
+                .selected_cvss(&self.settings.cvss_selection)
+                .map(|(_, cvss)| cvss.clone());
+        }
+
+        for warning in self.warnings.values_mut().flatten() {
+            warning.cvss_details.clone_from(&warning.cvss);
+        }
+    }
+
+    /// Record when the first patched version of each fixable vulnerable
+    /// package was released, given the release dates of each version of a
+    /// package as returned by `releases` (e.g. from the registry's API).
+    pub fn add_patched_release_dates<F>(&mut self, mut releases: F)
+    where
+        F: FnMut(&Package) -> Vec<(Version, Date)>,
+    {
+        for vulnerability in &mut self.vulnerabilities.list {
+            if !vulnerability.is_fixable {
+                continue;
+            }
+
+            vulnerability.patched_release_date = releases(&vulnerability.package)
+                .into_iter()
+                .filter(|(version, _)| vulnerability.is_patched_version(version))
+                .map(|(_, date)| date)
+                .min();
+        }
+    }
+
+    /// Advisories of the vulnerabilities and warnings in this report, each
+    /// listed once, e.g. to fetch more data about them for [`Report::enrich`]
+    pub fn advisories(&self) -> Vec<&advisory::Metadata> {
+        let mut ids = BTreeSet::new();
+        self.vulnerabilities
+            .list
+            .iter()
+            .map(|vulnerability| &vulnerability.advisory)
+            .chain(
+                self.warnings
+                    .values()
+                    .flatten()
+                    .filter_map(|warning| warning.advisory.as_ref()),
+            )
+            .filter(|advisory| ids.insert(&advisory.id))
+            .collect()
+    }
+
+    /// Attach data about the advisory of each vulnerability and warning, as
+    /// returned by `enrichment` (e.g. fetched from external sources by
+    /// `cargo audit enrich`), replacing any previously attached data.
+    pub fn enrich<F>(&mut self, mut enrichment: F)
+    where
+        F: FnMut(&advisory::Metadata) -> Option<Enrichment>,
+    {
+        for vulnerability in &mut self.vulnerabilities.list {
+            vulnerability.enrichment = enrichment(&vulnerability.advisory);
+        }
+
+        for warning in self.warnings.values_mut().flatten() {
+            if let Some(advisory) = &warning.advisory {
+                warning.enrichment = enrichment(advisory);
+            }
+        }
+    }
+
+    /// Mark the vulnerabilities and warnings about packages which are only
+    /// pulled into the build by dev-dependencies, as determined by
+    /// `is_dev_only` (e.g. from the dependency kinds reported by
+    /// `cargo metadata`), then handle them as configured in
+    /// [`Settings::dev_dependencies`].
+    ///
+    /// Call [`Report::update_summary`] afterwards to account for any
+    /// findings which were skipped.
+    pub fn mark_dev_only(&mut self, is_dev_only: impl Fn(&Package) -> bool) {
+        for vulnerability in &mut self.vulnerabilities.list {
+            vulnerability.dev_only = is_dev_only(&vulnerability.package);
+        }
+
+        for warning in self.warnings.values_mut().flatten() {
+            warning.dev_only = is_dev_only(&warning.package);
+        }
+
+        self.apply_scope_settings();
+    }
+
+    /// Mark the vulnerabilities and warnings about packages which are only
+    /// used at build time, i.e. by build scripts or proc macros, as
+    /// determined by `is_build_only` (e.g. from the dependency kinds and
+    /// targets reported by `cargo metadata`), then handle them as configured
+    /// in [`Settings::build_dependencies`].
+    ///
+    /// Call [`Report::update_summary`] afterwards to account for any
+    /// findings which were skipped, and to count the build-only findings.
+    pub fn mark_build_only(&mut self, is_build_only: impl Fn(&Package) -> bool) {
+        for vulnerability in &mut self.vulnerabilities.list {
+            vulnerability.build_only = is_build_only(&vulnerability.package);
+        }
+
+        for warning in self.warnings.values_mut().flatten() {
+            warning.build_only = is_build_only(&warning.package);
+        }
+
+        self.apply_scope_settings();
+    }
+
+    /// Skip or downgrade the findings marked as dev-only or build-only, as
+    /// configured in the settings
+    fn apply_scope_settings(&mut self) {
+        let settings = &self.settings;
+        let skipped = |dev_only: bool, build_only: bool| {
+            (dev_only && settings.dev_dependencies == DependencyScopePolicy::Skip)
+                || (build_only && settings.build_dependencies == DependencyScopePolicy::Skip)
+        };
+
+        if self
+            .vulnerabilities
+            .list
+            .iter()
+            .any(|vulnerability| skipped(vulnerability.dev_only, vulnerability.build_only))
+        {
+            let list = self
+                .vulnerabilities
+                .list
+                .drain(..)
+                .filter(|vulnerability| !skipped(vulnerability.dev_only, vulnerability.build_only))
+                .collect();
+            self.vulnerabilities = VulnerabilityInfo::new(list);
+        }
+
+        for warnings in self.warnings.values_mut() {
+            warnings.retain(|warning| !skipped(warning.dev_only, warning.build_only));
+        }
+        self.warnings.retain(|_, warnings| !warnings.is_empty());
+
+        self.vulnerabilities.found = self.vulnerabilities.list.iter().any(|vulnerability| {
+            !settings.is_downgraded(vulnerability.dev_only, vulnerability.build_only)
+        });
+    }
+
+    /// Recompute the [`Summary`] after the vulnerabilities or warnings in
+    /// this report have been modified (e.g. filtered or extended)
+    pub fn update_summary<S>(&mut self, source: &S)
+    where
+        S: AuditSource + ?Sized,
+    {
+        self.summary = Summary::new(
+            &self.vulnerabilities,
+            &self.warnings,
+            source,
+            &self.settings.cvss_selection,
+        );
+    }
+
+    /// Was the advisory database older than the configured
+    /// [`Settings::max_database_age_days`] when this report was generated, or
+    /// loaded from a stale local copy because fetching it failed?
+    pub fn is_database_stale(&self) -> bool {
+        #[cfg(feature = "git")]
+        return self.database.stale;
+
+        #[cfg(not(feature = "git"))]
+        false
+    }
+
+    /// Should this report be treated as a failure because the advisory
+    /// database was stale? (see [`Settings::deny_stale_database`])
+    pub fn is_stale_database_denied(&self) -> bool {
+        self.settings.deny_stale_database && self.is_database_stale()
+    }
+
+    /// Group the vulnerabilities and warnings in this report by the package
+    /// they were found in, e.g. to present all findings for one dependency
+    /// together
+    pub fn warnings_by_package(&self) -> Map<&Package, PackageFindings<'_>> {
+        let mut packages: Map<&Package, PackageFindings<'_>> = Map::new();
+
+        for vulnerability in &self.vulnerabilities.list {
+            packages
+                .entry(&vulnerability.package)
+                .or_default()
+                .vulnerabilities
+                .push(vulnerability);
+        }
+
+        for warning in self.warnings.values().flatten() {
+            packages
+                .entry(&warning.package)
+                .or_default()
+                .warnings
+                .push(warning);
+        }
+
+        packages
+    }
+
+    /// Add warnings for advisories affecting the given version of the Rust
+    /// toolchain, e.g. the `rustc` version a binary was built with
+    pub fn add_toolchain_warnings(&mut self, db: &Database, rustc_version: &Version) {
+        self.add_tool_warnings(db, &ToolVersion::new(Tool::Rustc, rustc_version.clone()));
+    }
+
+    /// Add warnings for advisories affecting the given version of a tool of
+    /// the Rust toolchain, e.g. the `cargo` a project is built with
+    pub fn add_tool_warnings(&mut self, db: &Database, tool_version: &ToolVersion) {
+        let mut toolchain_warnings = find_tool_warnings(db, tool_version, &self.settings);
+
+        if !toolchain_warnings.is_empty() {
+            self.warnings
+                .entry(warning::WarningKind::Toolchain)
+                .or_default()
+                .append(&mut toolchain_warnings);
+            self.sort();
+        }
+    }
+
+    /// Serialize this report as TOML
+    pub fn to_toml_string(&self) -> Result<String, Error> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Serialize this report as YAML.
+    ///
+    /// Fields which are absent are omitted rather than serialized as `null`,
+    /// as in the TOML serialization.
+    pub fn to_yaml_string(&self) -> Result<String, Error> {
+        let value = toml::Value::try_from(self)?;
+        serde_yaml::to_string(&value).map_err(|e| format_err!(ErrorKind::Parse, &e))
     }
 }
 
+/// Order of findings in reports: by package name and version, then by the
+/// ID of the advisory (if any)
+fn finding_order(
+    a: &Package,
+    a_id: Option<&advisory::Id>,
+    b: &Package,
+    b_id: Option<&advisory::Id>,
+) -> std::cmp::Ordering {
+    (&a.name, &a.version, a_id).cmp(&(&b.name, &b.version, b_id))
+}
+
 /// Options to use when generating the report
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Settings {
@@ -66,24 +502,154 @@ pub struct Settings {
     /// Operating system
     pub target_os: Vec<OS>,
 
-    /// Severity threshold to alert at
+    /// Severity threshold to alert at.
+    ///
+    /// This also applies to warnings, unless [`Settings::warning_severity`]
+    /// is set.
     pub severity: Option<advisory::Severity>,
 
+    /// Severity threshold to warn at for informational advisories and
+    /// advisories against the toolchain, overriding [`Settings::severity`]
+    /// for warnings
+    #[serde(default)]
+    pub warning_severity: Option<advisory::Severity>,
+
+    /// Which of an advisory's CVSS scores determines its severity, both when
+    /// comparing it to the severity thresholds and in the report
+    #[serde(default)]
+    pub cvss_selection: advisory::CvssSelection,
+
     /// List of advisory IDs to ignore
     pub ignore: Vec<advisory::Id>,
 
+    /// Reasons given for ignoring advisories, recorded alongside them in the
+    /// report's list of ignored advisories
+    #[serde(default)]
+    pub ignore_reasons: Map<advisory::Id, String>,
+
+    /// Advisories to ignore only for particular packages and/or versions
+    #[serde(default)]
+    pub scoped_ignores: Vec<ScopedIgnore>,
+
     /// Types of informational advisories to generate warnings for
     pub informational_warnings: Vec<advisory::Informational>,
+
+    /// Maximum age of the advisory database (i.e. of its latest commit) in
+    /// days, after which it is reported as stale
+    #[serde(default)]
+    pub max_database_age_days: Option<u64>,
+
+    /// Treat a stale advisory database as a failure
+    #[serde(default)]
+    pub deny_stale_database: bool,
+
+    /// Only report vulnerabilities for which patched versions are available
+    #[serde(default)]
+    pub fixable_only: bool,
+
+    /// Generate warnings for packages from sources the advisory database
+    /// doesn't cover (see [`find_source_warnings`])
+    #[serde(default)]
+    pub source_warnings: bool,
+
+    /// How to treat findings in packages which are only pulled in by
+    /// dev-dependencies (see [`Report::mark_dev_only`])
+    #[serde(default)]
+    pub dev_dependencies: DependencyScopePolicy,
+
+    /// How to treat findings in packages which are only used at build time,
+    /// by build scripts or proc macros (see [`Report::mark_build_only`])
+    #[serde(default)]
+    pub build_dependencies: DependencyScopePolicy,
+
+    /// Packages which must not be depended on regardless of advisories, e.g.
+    /// due to an organization's policy, reported as
+    /// [`WarningKind::Banned`](warning::WarningKind::Banned) warnings
+    #[serde(default)]
+    pub banned_packages: Vec<PackageRule>,
+
+    /// Exceptions to [`Settings::banned_packages`]: packages matching these
+    /// rules are allowed even if they're banned
+    #[serde(default)]
+    pub allowed_packages: Vec<PackageRule>,
+
+    /// Types of informational advisories to report as vulnerabilities rather
+    /// than warnings (see [`Vulnerability::promoted_from`]), even if they
+    /// aren't listed in [`Settings::informational_warnings`]. Like other
+    /// vulnerabilities, they're subject to [`Settings::severity`] and
+    /// [`Settings::fixable_only`]
+    #[serde(default)]
+    pub promote_informational: Vec<advisory::Informational>,
+
+    /// IDs of informational advisories to report as vulnerabilities rather
+    /// than warnings
+    #[serde(default)]
+    pub promote: Vec<advisory::Id>,
+
+    /// IDs of advisories to report as [`WarningKind::Notice`] warnings rather
+    /// than vulnerabilities (see [`Warning::demoted`]), which takes
+    /// precedence over promotions
+    ///
+    /// [`WarningKind::Notice`]: warning::WarningKind::Notice
+    #[serde(default)]
+    pub demote: Vec<advisory::Id>,
+
+    /// Only audit packages from these sources (default: all sources).
+    ///
+    /// This applies to both vulnerabilities and warnings.
+    #[serde(default)]
+    pub include_sources: Vec<SourceRule>,
+
+    /// Don't audit packages from these sources, e.g. [`SourceRule::Path`] to
+    /// skip workspace members
+    #[serde(default)]
+    pub exclude_sources: Vec<SourceRule>,
+}
+
+/// How to treat findings in packages which don't end up in production
+/// artifacts, i.e. which are only pulled into the build by dev-dependencies
+/// (see [`Settings::dev_dependencies`]) or only used at build time, by build
+/// scripts or proc macros (see [`Settings::build_dependencies`])
+#[derive(Copy, Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyScopePolicy {
+    /// Report them like any other finding
+    #[default]
+    Report,
+
+    /// Report them, but don't treat vulnerabilities in them as a failure
+    Downgrade,
+
+    /// Leave them out of the report
+    Skip,
+}
+
+impl FromStr for DependencyScopePolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match s {
+            "report" => DependencyScopePolicy::Report,
+            "downgrade" => DependencyScopePolicy::Downgrade,
+            "skip" => DependencyScopePolicy::Skip,
+            other => fail!(
+                ErrorKind::Parse,
+                "invalid dependency scope policy: {} (expected report, downgrade or skip)",
+                other
+            ),
+        })
+    }
 }
 
 impl Settings {
     /// Get a query which corresponds to the configured report settings.
-    /// Note that queries can't filter ignored advisories, so this happens in
-    /// a separate pass
+    /// Note that the query doesn't filter ignored advisories, as they're
+    /// listed in the report, so this happens in a separate pass
     pub fn query(&self) -> Query {
         let mut query = Query::crate_scope()
             .target_arch(self.target_arch.clone())
-            .target_os(self.target_os.clone());
+            .target_os(self.target_os.clone())
+            .cvss_selection(self.cvss_selection.clone());
 
         if let Some(severity) = self.severity {
             query = query.severity(severity);
@@ -91,6 +657,260 @@ impl Settings {
 
         query
     }
+
+    /// Severity threshold for warnings, i.e. [`Settings::warning_severity`]
+    /// if set, or [`Settings::severity`] otherwise
+    pub fn warning_severity_threshold(&self) -> Option<advisory::Severity> {
+        self.warning_severity.or(self.severity)
+    }
+
+    /// Get a query for the informational advisories to generate warnings for,
+    /// which uses the [`Settings::warning_severity_threshold`]
+    pub fn warning_query(&self) -> Query {
+        let mut query = Query::crate_scope()
+            .target_arch(self.target_arch.clone())
+            .target_os(self.target_os.clone())
+            .cvss_selection(self.cvss_selection.clone())
+            .informational(true);
+
+        if let Some(severity) = self.warning_severity_threshold() {
+            query = query.severity(severity);
+        }
+
+        query
+    }
+
+    /// Is the given advisory ignored for the given package, either entirely
+    /// (see [`Settings::ignore`]) or by one of the [`Settings::scoped_ignores`]?
+    pub fn is_ignored(&self, id: &advisory::Id, package: &Package) -> bool {
+        self.ignore.contains(id)
+            || self
+                .scoped_ignores
+                .iter()
+                .any(|ignore| ignore.matches(id, package))
+    }
+
+    /// Get the rule banning the given package, unless it's allowed by one of
+    /// the [`Settings::allowed_packages`]
+    pub fn ban(&self, package: &Package) -> Option<&PackageRule> {
+        if self
+            .allowed_packages
+            .iter()
+            .any(|rule| rule.matches(package))
+        {
+            return None;
+        }
+
+        self.banned_packages
+            .iter()
+            .find(|rule| rule.matches(package))
+    }
+
+    /// Is the given package audited, according to the
+    /// [`Settings::include_sources`] and [`Settings::exclude_sources`]?
+    pub fn is_source_audited(&self, package: &Package) -> bool {
+        (self.include_sources.is_empty()
+            || self
+                .include_sources
+                .iter()
+                .any(|rule| rule.matches(package)))
+            && !self
+                .exclude_sources
+                .iter()
+                .any(|rule| rule.matches(package))
+    }
+
+    /// Is the given informational advisory reported as a vulnerability,
+    /// according to [`Settings::promote`] and
+    /// [`Settings::promote_informational`]?
+    pub fn is_promoted(&self, advisory: &advisory::Metadata) -> bool {
+        !self.is_demoted(&advisory.id)
+            && (self.promote.contains(&advisory.id)
+                || matches!(
+                    &advisory.informational,
+                    Some(informational) if self.promote_informational.contains(informational)
+                ))
+    }
+
+    /// Is the given advisory reported as a warning rather than a
+    /// vulnerability (see [`Settings::demote`])?
+    pub fn is_demoted(&self, id: &advisory::Id) -> bool {
+        self.demote.contains(id)
+    }
+
+    /// Are findings in packages with the given scope (see
+    /// [`Vulnerability::dev_only`] and [`Vulnerability::build_only`])
+    /// downgraded, so that they don't fail an audit?
+    pub fn is_downgraded(&self, dev_only: bool, build_only: bool) -> bool {
+        (dev_only && self.dev_dependencies == DependencyScopePolicy::Downgrade)
+            || (build_only && self.build_dependencies == DependencyScopePolicy::Downgrade)
+    }
+}
+
+/// Advisory to ignore only for a particular package and/or range of its
+/// versions, so the advisory is still reported for other packages and for
+/// versions outside the range (e.g. after a regression)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ScopedIgnore {
+    /// ID of the advisory to ignore
+    pub id: advisory::Id,
+
+    /// Only ignore the advisory for the package with this name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<package::Name>,
+
+    /// Only ignore the advisory while the package's version matches this
+    /// requirement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub versions: Option<VersionReq>,
+
+    /// Reason given for ignoring the advisory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ScopedIgnore {
+    /// Does this ignore apply to the given advisory and package?
+    pub fn matches(&self, id: &advisory::Id, package: &Package) -> bool {
+        &self.id == id
+            && self
+                .package
+                .as_ref()
+                .map_or(true, |name| name == &package.name)
+            && self
+                .versions
+                .as_ref()
+                .map_or(true, |req| req.matches(&package.version))
+    }
+}
+
+/// Package (or range of its versions) which is banned or allowed by the
+/// report settings, see [`Settings::banned_packages`]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PackageRule {
+    /// Name of the package
+    pub name: package::Name,
+
+    /// Only apply to versions of the package which match this requirement
+    /// (default: all versions)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub versions: Option<VersionReq>,
+
+    /// Reason for the rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl PackageRule {
+    /// Does this rule apply to the given package?
+    pub fn matches(&self, package: &Package) -> bool {
+        self.name == package.name
+            && self
+                .versions
+                .as_ref()
+                .map_or(true, |req| req.matches(&package.version))
+    }
+}
+
+/// Source of packages to include in or exclude from the report, see
+/// [`Settings::include_sources`].
+///
+/// Rules are written as `path`, `git`, `crates-io`, or the index URL of a
+/// registry (optionally prefixed with `registry+` or `sparse+`):
+///
+/// ```
+/// use rustsec::report::SourceRule;
+///
+/// let rule: SourceRule = "sparse+https://registry.example.com/index/".parse().unwrap();
+/// assert_eq!(rule, SourceRule::Registry("https://registry.example.com/index".to_owned()));
+/// assert_eq!("path".parse::<SourceRule>().unwrap(), SourceRule::Path);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SourceRule {
+    /// Packages from the filesystem, e.g. workspace members and `path`
+    /// dependencies
+    Path,
+
+    /// Packages from git repositories
+    Git,
+
+    /// Packages from crates.io, using either its git or sparse index
+    CratesIo,
+
+    /// Packages from the registry with the given index URL
+    Registry(String),
+}
+
+impl SourceRule {
+    /// Does this rule apply to the given package?
+    pub fn matches(&self, package: &Package) -> bool {
+        let source = match &package.source {
+            Some(source) => source,
+            None => return *self == SourceRule::Path,
+        };
+
+        match self {
+            SourceRule::Path => source.is_path(),
+            SourceRule::Git => source.is_git(),
+            SourceRule::CratesIo => source.is_default_registry(),
+            SourceRule::Registry(url) => {
+                source.is_registry() && source.url().as_str().trim_end_matches('/') == url
+            }
+        }
+    }
+}
+
+impl fmt::Display for SourceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceRule::Path => f.write_str("path"),
+            SourceRule::Git => f.write_str("git"),
+            SourceRule::CratesIo => f.write_str("crates-io"),
+            SourceRule::Registry(url) => f.write_str(url),
+        }
+    }
+}
+
+impl FromStr for SourceRule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match s {
+            "path" => SourceRule::Path,
+            "git" => SourceRule::Git,
+            "crates-io" => SourceRule::CratesIo,
+            other => {
+                let url = other
+                    .strip_prefix("registry+")
+                    .or_else(|| other.strip_prefix("sparse+"))
+                    .unwrap_or(other);
+
+                if url::Url::parse(url).is_err() {
+                    fail!(
+                        ErrorKind::Parse,
+                        "invalid source (expected `path`, `git`, `crates-io`, or a registry URL): {}",
+                        other
+                    );
+                }
+
+                SourceRule::Registry(url.trim_end_matches('/').to_owned())
+            }
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for SourceRule {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use de::Error;
+        let string = String::deserialize(deserializer)?;
+        string.parse().map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for SourceRule {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
 }
 
 /// Information about the advisory database
@@ -107,8 +927,30 @@ pub struct DatabaseInfo {
     pub last_commit: Option<String>,
 
     /// Date when the advisory database was last committed to
-    #[serde(rename = "last-updated", with = "time::serde::rfc3339::option")]
+    #[serde(
+        rename = "last-updated",
+        default,
+        with = "time::serde::rfc3339::option"
+    )]
     pub last_updated: Option<time::OffsetDateTime>,
+
+    /// Branch, tag, or commit the advisory database was pinned to, if any
+    #[serde(
+        rename = "pinned-revision",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pinned_revision: Option<String>,
+
+    /// Was the database older than [`Settings::max_database_age_days`], or
+    /// loaded from a stale local copy because fetching it failed?
+    #[serde(default)]
+    pub stale: bool,
+
+    /// Was the database loaded from a stale local copy because fetching it
+    /// failed? (see [`Database::is_stale_cache`])
+    #[serde(rename = "stale-cache", default)]
+    pub stale_cache: bool,
 }
 
 #[cfg(feature = "git")]
@@ -119,8 +961,22 @@ impl DatabaseInfo {
             advisory_count: db.iter().count(),
             last_commit: db.latest_commit().map(|c| c.commit_id.to_hex()),
             last_updated: db.latest_commit().map(|c| c.timestamp),
+            pinned_revision: db.revision().map(ToOwned::to_owned),
+            stale: db.is_stale_cache(),
+            stale_cache: db.is_stale_cache(),
         }
     }
+
+    /// Was the database last updated more than the given number of days ago?
+    ///
+    /// Databases with no commit information (e.g. loaded from a plain
+    /// directory) are never considered to be out of date.
+    pub fn is_older_than(&self, days: u64) -> bool {
+        self.last_updated.map_or(false, |last_updated| {
+            let max_age = time::Duration::days(days.try_into().unwrap_or(i64::MAX));
+            time::OffsetDateTime::now_utc() - last_updated > max_age
+        })
+    }
 }
 
 /// Information about `Cargo.lock`
@@ -164,20 +1020,81 @@ impl VulnerabilityInfo {
     }
 }
 
+/// Vulnerabilities and warnings found in a single package
+#[derive(Clone, Debug, Default)]
+pub struct PackageFindings<'a> {
+    /// Vulnerabilities affecting the package
+    pub vulnerabilities: Vec<&'a Vulnerability>,
+
+    /// Warnings about the package, of any [`warning::WarningKind`]
+    pub warnings: Vec<&'a Warning>,
+}
+
 /// Information about warnings
 pub type WarningInfo = Map<warning::WarningKind, Vec<Warning>>;
 
-/// Find warnings from the given advisory [`Database`] and [`Lockfile`]
-pub fn find_warnings(db: &Database, lockfile: &Lockfile, settings: &Settings) -> WarningInfo {
-    let query = settings.query().informational(true);
+/// Advisory which matched a dependency, but was ignored as configured in
+/// [`Settings::ignore`]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct IgnoredAdvisory {
+    /// Advisory which was ignored
+    pub advisory: advisory::Metadata,
+
+    /// Package the advisory matched
+    pub package: Package,
+
+    /// Reason given for ignoring the advisory (if any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl IgnoredAdvisory {
+    /// Record that the given advisory was ignored for the given package
+    fn new(advisory: advisory::Metadata, package: Package, settings: &Settings) -> Self {
+        let reason = settings
+            .scoped_ignores
+            .iter()
+            .find(|ignore| ignore.matches(&advisory.id, &package))
+            .and_then(|ignore| ignore.reason.clone())
+            .or_else(|| settings.ignore_reasons.get(&advisory.id).cloned());
+        Self {
+            advisory,
+            package,
+            reason,
+        }
+    }
+}
+
+/// Find warnings from the given advisory [`Database`] and [`Lockfile`].
+///
+/// Informational advisories which are promoted to vulnerabilities (see
+/// [`Settings::is_promoted`]) aren't included.
+pub fn find_warnings<S>(db: &Database, source: &S, settings: &Settings) -> WarningInfo
+where
+    S: AuditSource + ?Sized,
+{
+    collect_warnings(db, source.packages(), settings, &mut vec![])
+}
+
+/// Find warnings from the given advisory [`Database`] about the given
+/// packages, adding the informational advisories which were ignored to
+/// `ignored`
+fn collect_warnings(
+    db: &Database,
+    packages: &[Package],
+    settings: &Settings,
+    ignored: &mut Vec<IgnoredAdvisory>,
+) -> WarningInfo {
+    let query = settings.warning_query();
 
     let mut warnings = WarningInfo::default();
 
     // TODO(tarcieri): abstract `Cargo.lock` query logic between vulnerabilities/warnings
-    for advisory_vuln in db.query_vulnerabilities(lockfile, &query) {
+    for advisory_vuln in db.query_vulnerabilities(packages, &query) {
         let advisory = &advisory_vuln.advisory;
 
-        if settings.ignore.contains(&advisory.id) {
+        // Promoted advisories are reported as vulnerabilities instead
+        if !settings.is_source_audited(&advisory_vuln.package) || settings.is_promoted(advisory) {
             continue;
         }
 
@@ -186,6 +1103,15 @@ pub fn find_warnings(db: &Database, lockfile: &Lockfile, settings: &Settings) ->
             .iter()
             .any(|info| Some(info) == advisory.informational.as_ref())
         {
+            if settings.is_ignored(&advisory.id, &advisory_vuln.package) {
+                ignored.push(IgnoredAdvisory::new(
+                    advisory.clone(),
+                    advisory_vuln.package.clone(),
+                    settings,
+                ));
+                continue;
+            }
+
             let warning_kind = match advisory
                 .informational
                 .as_ref()
@@ -196,13 +1122,14 @@ pub fn find_warnings(db: &Database, lockfile: &Lockfile, settings: &Settings) ->
                 None => continue,
             };
 
-            let warning = Warning::new(
+            let mut warning = Warning::new(
                 warning_kind,
                 &advisory_vuln.package,
                 Some(advisory.clone()),
                 advisory_vuln.affected.clone(),
                 Some(advisory_vuln.versions.clone()),
             );
+            warning.select_cvss(&settings.cvss_selection);
 
             match warnings.entry(warning.kind) {
                 map::Entry::Occupied(entry) => (*entry.into_mut()).push(warning),
@@ -215,3 +1142,1053 @@ pub fn find_warnings(db: &Database, lockfile: &Lockfile, settings: &Settings) ->
 
     warnings
 }
+
+/// Find warnings from the given advisory [`Database`] about the Rust toolchain
+/// (i.e. `rustc` and the standard library) of the given version
+pub fn find_toolchain_warnings(
+    db: &Database,
+    rustc_version: &Version,
+    settings: &Settings,
+) -> Vec<Warning> {
+    find_tool_warnings(
+        db,
+        &ToolVersion::new(Tool::Rustc, rustc_version.clone()),
+        settings,
+    )
+}
+
+/// Find warnings from the given advisory [`Database`] about the given version
+/// of a tool of the Rust toolchain (e.g. `cargo 1.70.0`)
+pub fn find_tool_warnings(
+    db: &Database,
+    tool_version: &ToolVersion,
+    settings: &Settings,
+) -> Vec<Warning> {
+    let mut query = Query::toolchain_scope()
+        .target_arch(settings.target_arch.clone())
+        .target_os(settings.target_os.clone())
+        .cvss_selection(settings.cvss_selection.clone());
+
+    if let Some(severity) = settings.warning_severity_threshold() {
+        query = query.severity(severity);
+    }
+
+    db.query_toolchain(tool_version, &query)
+        .into_iter()
+        .filter(|vuln| !settings.is_ignored(&vuln.advisory.id, &vuln.package))
+        .map(|vuln| {
+            let mut warning = Warning::new(
+                warning::WarningKind::Toolchain,
+                &vuln.package,
+                Some(vuln.advisory),
+                vuln.affected,
+                Some(vuln.versions),
+            );
+            warning.select_cvss(&settings.cvss_selection);
+            warning
+        })
+        .collect()
+}
+
+/// Compute the length of the shortest chain of dependencies from a root of the
+/// given packages to each of them, where the roots are the local packages
+/// (i.e. workspace members) and any packages nothing depends on
+fn dependency_depths(packages: &[Package]) -> Map<(&package::Name, &Version), usize> {
+    let by_key: Map<_, _> = packages
+        .iter()
+        .map(|package| ((&package.name, &package.version), package))
+        .collect();
+
+    let dependents: BTreeSet<_> = packages
+        .iter()
+        .flat_map(|package| &package.dependencies)
+        .map(|dependency| (&dependency.name, &dependency.version))
+        .collect();
+
+    let mut depths = Map::new();
+    let mut pending: VecDeque<_> = packages
+        .iter()
+        .filter(|package| {
+            package.source.is_none() || !dependents.contains(&(&package.name, &package.version))
+        })
+        .map(|package| ((&package.name, &package.version), 0))
+        .collect();
+
+    // Breadth-first, so each package is first reached by its shortest chain
+    while let Some((key, depth)) = pending.pop_front() {
+        if depths.contains_key(&key) {
+            continue;
+        }
+        depths.insert(key, depth);
+
+        if let Some(package) = by_key.get(&key) {
+            pending.extend(
+                package
+                    .dependencies
+                    .iter()
+                    .map(|dependency| ((&dependency.name, &dependency.version), depth + 1)),
+            );
+        }
+    }
+
+    depths
+}
+
+/// Find packages in the given [`Lockfile`] (or other [`AuditSource`]) from
+/// sources the advisory database doesn't cover: git repositories without a
+/// pinned revision or tag (whose contents may change without the lockfile
+/// noticing), and registries other than crates.io
+pub fn find_source_warnings<S>(source: &S) -> Vec<Warning>
+where
+    S: AuditSource + ?Sized,
+{
+    source
+        .packages()
+        .iter()
+        .filter(|package| {
+            let source = match &package.source {
+                Some(source) => source,
+                None => return false,
+            };
+
+            if source.is_git() {
+                !matches!(
+                    source.git_reference(),
+                    Some(GitReference::Rev(_) | GitReference::Tag(_))
+                )
+            } else {
+                source.is_remote_registry() && !source.is_default_registry()
+            }
+        })
+        .map(|package| Warning::new(warning::WarningKind::Source, package, None, None, None))
+        .collect()
+}
+
+/// Find packages in the given [`Lockfile`] (or other [`AuditSource`]) which
+/// are banned by the given settings (see [`Settings::banned_packages`])
+pub fn find_banned_warnings<S>(source: &S, settings: &Settings) -> Vec<Warning>
+where
+    S: AuditSource + ?Sized,
+{
+    source
+        .packages()
+        .iter()
+        .filter(|package| settings.is_source_audited(package))
+        .filter_map(|package| {
+            let rule = settings.ban(package)?;
+            Some(Warning::banned(package, rule.reason.clone()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const STD_ADVISORY: &str = r#"```toml
+[advisory]
+id = "RUSTSEC-2001-2102"
+package = "std"
+date = "2001-02-03"
+
+[versions]
+patched = [">= 1.52.0"]
+```
+
+# Example standard library vulnerability
+"#;
+
+    const LOCKFILE: &str = r#"
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["deep"]
+
+[[package]]
+name = "deep"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    /// Rewrites the contents of an example advisory
+    type Edit = dyn Fn(String) -> String;
+
+    /// Open a database containing `tests/support/example_advisory_v3.md` under
+    /// each given package and ID, with its contents passed through `edit`
+    fn db_with(advisories: &[(&str, &str, &Edit)]) -> (TempDir, Database) {
+        let db_dir = tempfile::tempdir().unwrap();
+        let example = std::fs::read_to_string("./tests/support/example_advisory_v3.md").unwrap();
+        for (package, id, edit) in advisories {
+            let package_dir = db_dir.path().join("crates").join(package);
+            std::fs::create_dir_all(&package_dir).unwrap();
+            let advisory = example
+                .replace("RUSTSEC-2001-2101", id)
+                .replace("package = \"base\"", &format!("package = \"{}\"", package));
+            std::fs::write(package_dir.join(format!("{}.md", id)), edit(advisory)).unwrap();
+        }
+        let db = Database::open(db_dir.path()).unwrap();
+        (db_dir, db)
+    }
+
+    #[test]
+    fn warnings_by_package() {
+        let (_db_dir, db) = db_with(&[]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let advisory =
+            crate::Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap();
+        let base = &lockfile.packages[0];
+        let deep = &lockfile.packages[1];
+
+        let mut report = Report::generate(&db, &lockfile, &Settings::default());
+        report.vulnerabilities = VulnerabilityInfo::new(vec![Vulnerability::new(&advisory, base)]);
+        report.warnings.insert(
+            warning::WarningKind::Yanked,
+            [base, deep]
+                .iter()
+                .map(|package| {
+                    Warning::new(warning::WarningKind::Yanked, package, None, None, None)
+                })
+                .collect(),
+        );
+
+        let by_package = report.warnings_by_package();
+        assert_eq!(by_package.len(), 2);
+        assert_eq!(by_package[base].vulnerabilities.len(), 1);
+        assert_eq!(by_package[base].warnings.len(), 1);
+        assert!(by_package[deep].vulnerabilities.is_empty());
+        assert!(by_package[deep].warnings[0].is_yanked());
+    }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn database_age() {
+        let database = DatabaseInfo {
+            advisory_count: 0,
+            last_commit: None,
+            last_updated: Some(time::OffsetDateTime::now_utc() - time::Duration::days(100)),
+            pinned_revision: None,
+            stale: false,
+            stale_cache: false,
+        };
+
+        assert!(database.is_older_than(90));
+        assert!(!database.is_older_than(120));
+
+        let unknown = DatabaseInfo {
+            last_updated: None,
+            ..database
+        };
+        assert!(!unknown.is_older_than(0));
+    }
+
+    #[test]
+    fn toolchain_warnings() {
+        let db_dir = tempfile::tempdir().unwrap();
+        let std_dir = db_dir.path().join("rust").join("std");
+        std::fs::create_dir_all(&std_dir).unwrap();
+        std::fs::write(std_dir.join("RUSTSEC-2001-2102.md"), STD_ADVISORY).unwrap();
+        let db = Database::open(db_dir.path()).unwrap();
+        let settings = Settings::default();
+
+        let warnings = find_toolchain_warnings(&db, &"1.51.0".parse().unwrap(), &settings);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].is_toolchain());
+        assert_eq!(warnings[0].package.name.as_str(), "std");
+
+        assert!(find_toolchain_warnings(&db, &"1.52.0".parse().unwrap(), &settings).is_empty());
+
+        // `std` isn't shipped with cargo
+        let cargo = "cargo 1.51.0 (43b129a20 2021-03-16)".parse().unwrap();
+        assert!(find_tool_warnings(&db, &cargo, &settings).is_empty());
+    }
+
+    #[test]
+    fn ignored_advisories() {
+        let (_db_dir, db) = db_with(&[("base", "RUSTSEC-2001-2101", &|advisory| advisory)]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+
+        let id: advisory::Id = "RUSTSEC-2001-2101".parse().unwrap();
+        let mut settings = Settings {
+            ignore: vec![id.clone()],
+            ..Default::default()
+        };
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert!(!report.vulnerabilities.found);
+        assert_eq!(report.ignored.len(), 1);
+        assert_eq!(report.ignored[0].advisory.id, id);
+        assert_eq!(report.ignored[0].package.name.as_str(), "base");
+        assert_eq!(report.ignored[0].reason, None);
+
+        settings
+            .ignore_reasons
+            .insert(id, "not exploitable through our API".to_owned());
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert_eq!(
+            report.ignored[0].reason.as_deref(),
+            Some("not exploitable through our API")
+        );
+    }
+
+    #[test]
+    fn direct_and_transitive_vulnerabilities() {
+        let (_db_dir, db) = db_with(&[
+            ("base", "RUSTSEC-2001-2101", &|advisory| advisory),
+            ("deep", "RUSTSEC-2001-2102", &|advisory| advisory),
+        ]);
+        let lockfile: Lockfile = format!(
+            "[[package]]\nname = \"app\"\nversion = \"0.1.0\"\ndependencies = [\"base\"]\n{}",
+            LOCKFILE
+        )
+        .parse()
+        .unwrap();
+
+        let report = Report::generate(&db, &lockfile, &Settings::default());
+        let depth = |name: &str| {
+            let vulnerability = report
+                .vulnerabilities
+                .list
+                .iter()
+                .find(|vulnerability| vulnerability.package.name.as_str() == name)
+                .unwrap();
+            (vulnerability.direct, vulnerability.depth)
+        };
+        assert_eq!(depth("base"), (true, Some(1)));
+        assert_eq!(depth("deep"), (false, Some(2)));
+    }
+
+    #[test]
+    fn cvss_details() {
+        let (_db_dir, db) = db_with(&[("base", "RUSTSEC-2001-2101", &|advisory| advisory)]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+
+        let mut report = Report::generate(&db, &lockfile, &Settings::default());
+        let json = serde_json::to_value(&report.vulnerabilities.list[0]).unwrap();
+        assert!(json.get("cvss_details").is_none());
+
+        report.add_cvss_details();
+        let json = serde_json::to_value(&report.vulnerabilities.list[0]).unwrap();
+        let details = &json["cvss_details"];
+        assert_eq!(
+            details["vector"],
+            "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H"
+        );
+        assert_eq!(details["metrics"]["AV"], "N");
+        assert_eq!(details["score"], 10.0);
+        assert_eq!(details["severity"], "critical");
+
+        // Structured vectors deserialize back to the same finding
+        let vulnerability: Vulnerability = serde_json::from_value(json).unwrap();
+        assert_eq!(vulnerability, report.vulnerabilities.list[0]);
+    }
+
+    #[test]
+    fn advisory_age_and_patched_release_date() {
+        let (_db_dir, db) = db_with(&[("base", "RUSTSEC-2001-2101", &|advisory| advisory)]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+
+        let mut report = Report::generate(&db, &lockfile, &Settings::default());
+        let vulnerability = &report.vulnerabilities.list[0];
+        assert!(vulnerability.advisory_age_days.is_none());
+        assert!(vulnerability.patched_release_date.is_none());
+
+        // Published on 2001-02-03
+        report.add_advisory_ages(&"2001-03-05".parse().unwrap());
+        assert_eq!(report.vulnerabilities.list[0].advisory_age_days, Some(30));
+        report.add_advisory_ages(&"2001-01-01".parse().unwrap());
+        assert!(report.vulnerabilities.list[0].advisory_age_days.is_none());
+
+        // The earliest release matching `>= 1.2.3` is used, even if it isn't
+        // the first one listed
+        report.add_patched_release_dates(|package| {
+            assert_eq!(package.name.as_str(), "base");
+            [
+                ("1.0.0", "2001-01-01"),
+                ("1.3.0", "2001-04-01"),
+                ("1.2.3", "2001-03-01"),
+            ]
+            .iter()
+            .map(|(version, date)| (version.parse().unwrap(), date.parse().unwrap()))
+            .collect()
+        });
+        assert_eq!(
+            report.vulnerabilities.list[0]
+                .patched_release_date
+                .as_ref()
+                .unwrap()
+                .as_str(),
+            "2001-03-01"
+        );
+    }
+
+    #[test]
+    fn scoped_ignores() {
+        let (_db_dir, db) = db_with(&[("base", "RUSTSEC-2001-2101", &|advisory| advisory)]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+
+        let id: advisory::Id = "RUSTSEC-2001-2101".parse().unwrap();
+        let scoped = |package: &str, versions: &str| Settings {
+            scoped_ignores: vec![ScopedIgnore {
+                id: id.clone(),
+                package: Some(package.parse().unwrap()),
+                versions: Some(versions.parse().unwrap()),
+                reason: Some("scoped".to_owned()),
+            }],
+            ..Default::default()
+        };
+
+        // Ignores scoped to other packages or versions don't apply
+        let report = Report::generate(&db, &lockfile, &scoped("deep", "*"));
+        assert!(report.vulnerabilities.found);
+        let report = Report::generate(&db, &lockfile, &scoped("base", "1.1.*"));
+        assert!(report.vulnerabilities.found);
+
+        let report = Report::generate(&db, &lockfile, &scoped("base", "1.0.*"));
+        assert!(!report.vulnerabilities.found);
+        assert_eq!(report.ignored.len(), 1);
+        assert_eq!(report.ignored[0].reason.as_deref(), Some("scoped"));
+    }
+
+    #[test]
+    fn dev_only_findings() {
+        let (_db_dir, db) = db_with(&[]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let advisory =
+            crate::Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap();
+        let base = &lockfile.packages[0];
+        let deep = &lockfile.packages[1];
+        let is_dev_only = |package: &Package| package.name.as_str() == "deep";
+
+        let report_with = |dev_dependencies| {
+            let settings = Settings {
+                dev_dependencies,
+                ..Default::default()
+            };
+            let mut report = Report::generate(&db, &lockfile, &settings);
+            report.vulnerabilities =
+                VulnerabilityInfo::new(vec![Vulnerability::new(&advisory, deep)]);
+            report.warnings.insert(
+                warning::WarningKind::Yanked,
+                [base, deep]
+                    .iter()
+                    .map(|package| {
+                        Warning::new(warning::WarningKind::Yanked, package, None, None, None)
+                    })
+                    .collect(),
+            );
+            report.mark_dev_only(is_dev_only);
+            report
+        };
+
+        let report = report_with(DependencyScopePolicy::Report);
+        assert!(report.vulnerabilities.found);
+        assert!(report.vulnerabilities.list[0].dev_only);
+        let yanked = &report.warnings[&warning::WarningKind::Yanked];
+        assert!(!yanked[0].dev_only);
+        assert!(yanked[1].dev_only);
+
+        let report = report_with(DependencyScopePolicy::Downgrade);
+        assert!(!report.vulnerabilities.found);
+        assert_eq!(report.vulnerabilities.list.len(), 1);
+
+        let report = report_with(DependencyScopePolicy::Skip);
+        assert!(!report.vulnerabilities.found);
+        assert!(report.vulnerabilities.list.is_empty());
+        assert_eq!(report.warnings[&warning::WarningKind::Yanked].len(), 1);
+    }
+
+    #[test]
+    fn build_only_findings() {
+        let (_db_dir, db) = db_with(&[]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let advisory =
+            crate::Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap();
+        let base = &lockfile.packages[0];
+        let deep = &lockfile.packages[1];
+        let is_build_only = |package: &Package| package.name.as_str() == "deep";
+
+        let report_with = |build_dependencies| {
+            let settings = Settings {
+                build_dependencies,
+                ..Default::default()
+            };
+            let mut report = Report::generate(&db, &lockfile, &settings);
+            report.vulnerabilities =
+                VulnerabilityInfo::new(vec![Vulnerability::new(&advisory, deep)]);
+            report.warnings.insert(
+                warning::WarningKind::Yanked,
+                [base, deep]
+                    .iter()
+                    .map(|package| {
+                        Warning::new(warning::WarningKind::Yanked, package, None, None, None)
+                    })
+                    .collect(),
+            );
+            report.mark_build_only(is_build_only);
+            report.update_summary(&lockfile);
+            report
+        };
+
+        let report = report_with(DependencyScopePolicy::Report);
+        assert!(report.vulnerabilities.found);
+        assert!(report.vulnerabilities.list[0].build_only);
+        assert!(!report.vulnerabilities.list[0].dev_only);
+        assert_eq!(report.summary.build_only, 2);
+        assert_eq!(
+            report.summary.build_only_severity[&advisory::Severity::Critical],
+            1
+        );
+
+        let report = report_with(DependencyScopePolicy::Downgrade);
+        assert!(!report.vulnerabilities.found);
+        assert_eq!(report.vulnerabilities.list.len(), 1);
+
+        let report = report_with(DependencyScopePolicy::Skip);
+        assert!(!report.vulnerabilities.found);
+        assert!(report.vulnerabilities.list.is_empty());
+        assert_eq!(report.warnings[&warning::WarningKind::Yanked].len(), 1);
+        assert_eq!(report.summary.build_only, 0);
+    }
+
+    #[test]
+    fn deprecated_warnings() {
+        let (_db_dir, db) = db_with(&[("base", "RUSTSEC-2001-2101", &|advisory| {
+            advisory.replace(
+                "[advisory]\n",
+                "[advisory]\ninformational = \"deprecated\"\n",
+            )
+        })]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+
+        let mut settings = Settings::default();
+        assert!(find_warnings(&db, &lockfile, &settings).is_empty());
+
+        settings.informational_warnings = vec![advisory::Informational::Deprecated];
+        let warnings = find_warnings(&db, &lockfile, &settings);
+        let deprecated = &warnings[&warning::WarningKind::Deprecated];
+        assert_eq!(deprecated.len(), 1);
+        assert!(deprecated[0].is_deprecated());
+        assert_eq!(deprecated[0].package.name.as_str(), "base");
+    }
+
+    #[test]
+    fn sorted_findings() {
+        let (_db_dir, db) = db_with(&[
+            ("deep", "RUSTSEC-2001-2101", &|advisory| advisory),
+            ("base", "RUSTSEC-2001-2103", &|advisory| advisory),
+            ("base", "RUSTSEC-2001-2102", &|advisory| advisory),
+        ]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+
+        let mut report = Report::generate(&db, &lockfile, &Settings::default());
+        let findings: Vec<_> = report
+            .vulnerabilities
+            .list
+            .iter()
+            .map(|vuln| (vuln.package.name.as_str(), vuln.advisory.id.as_str()))
+            .collect();
+        assert_eq!(
+            findings,
+            [
+                ("base", "RUSTSEC-2001-2102"),
+                ("base", "RUSTSEC-2001-2103"),
+                ("deep", "RUSTSEC-2001-2101"),
+            ]
+        );
+
+        report.warnings.insert(
+            warning::WarningKind::Yanked,
+            lockfile
+                .packages
+                .iter()
+                .rev()
+                .map(|package| {
+                    Warning::new(warning::WarningKind::Yanked, package, None, None, None)
+                })
+                .collect(),
+        );
+        report.sort();
+        let yanked = &report.warnings[&warning::WarningKind::Yanked];
+        assert_eq!(yanked[0].package.name.as_str(), "base");
+        assert_eq!(yanked[1].package.name.as_str(), "deep");
+    }
+
+    #[test]
+    fn warning_severity_threshold() {
+        let (_db_dir, db) = db_with(&[("base", "RUSTSEC-2001-2101", &|advisory| {
+            advisory
+                .replace("[advisory]\n", "[advisory]\ninformational = \"unsound\"\n")
+                .replace(
+                    "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H",
+                    "CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N",
+                )
+        })]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let unsound = |settings: &Settings| {
+            find_warnings(&db, &lockfile, settings)
+                .get(&warning::WarningKind::Unsound)
+                .map_or(0, Vec::len)
+        };
+
+        let mut settings = Settings {
+            informational_warnings: vec![advisory::Informational::Unsound],
+            ..Default::default()
+        };
+        assert_eq!(unsound(&settings), 1);
+
+        settings.severity = Some(advisory::Severity::High);
+        assert_eq!(unsound(&settings), 0);
+
+        settings.warning_severity = Some(advisory::Severity::Low);
+        assert_eq!(unsound(&settings), 1);
+
+        settings.severity = None;
+        settings.warning_severity = Some(advisory::Severity::Medium);
+        assert_eq!(unsound(&settings), 0);
+    }
+
+    #[test]
+    fn promoted_and_demoted_advisories() {
+        let (_db_dir, db) = db_with(&[
+            ("base", "RUSTSEC-2001-2101", &|advisory| {
+                advisory.replace("[advisory]\n", "[advisory]\ninformational = \"unsound\"\n")
+            }),
+            ("deep", "RUSTSEC-2001-2102", &|advisory| advisory),
+        ]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let unsound: advisory::Id = "RUSTSEC-2001-2101".parse().unwrap();
+        let critical: advisory::Id = "RUSTSEC-2001-2102".parse().unwrap();
+
+        let mut settings = Settings {
+            informational_warnings: vec![advisory::Informational::Unsound],
+            ..Default::default()
+        };
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert_eq!(report.vulnerabilities.list.len(), 1);
+        assert_eq!(report.warnings[&warning::WarningKind::Unsound].len(), 1);
+
+        // Unsound advisories are promoted even if they aren't warned for
+        settings.informational_warnings.clear();
+        settings.promote_informational = vec![advisory::Informational::Unsound];
+        settings.demote = vec![critical.clone()];
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert_eq!(report.vulnerabilities.list.len(), 1);
+        let promoted = &report.vulnerabilities.list[0];
+        assert_eq!(promoted.advisory.id, unsound);
+        assert_eq!(
+            promoted.promoted_from,
+            Some(advisory::Informational::Unsound)
+        );
+        let demoted = &report.warnings[&warning::WarningKind::Notice];
+        assert_eq!(demoted.len(), 1);
+        assert_eq!(demoted[0].advisory.as_ref().unwrap().id, critical);
+        assert!(demoted[0].demoted);
+        assert!(!report.warnings.contains_key(&warning::WarningKind::Unsound));
+
+        // Demotions take precedence over promotions by ID or kind
+        settings.promote = vec![unsound.clone()];
+        settings.demote.push(unsound.clone());
+        assert!(!settings.is_promoted(&promoted.advisory));
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert!(report.vulnerabilities.list.is_empty());
+        assert_eq!(report.warnings[&warning::WarningKind::Notice].len(), 1);
+        assert!(!report.warnings.contains_key(&warning::WarningKind::Unsound));
+
+        // Ignored advisories are neither promoted nor demoted
+        settings.demote.clear();
+        settings.ignore = vec![unsound, critical];
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert!(report.vulnerabilities.list.is_empty());
+        assert!(report.warnings.is_empty());
+        assert_eq!(report.ignored.len(), 2);
+    }
+
+    #[test]
+    fn promoted_advisories_are_filtered_like_vulnerabilities() {
+        let (_db_dir, db) = db_with(&[("base", "RUSTSEC-2001-2101", &|advisory| {
+            advisory
+                .replace("[advisory]\n", "[advisory]\ninformational = \"unsound\"\n")
+                .replace(
+                    "cvss = \"CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H\"",
+                    "cvss = \"CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N\"",
+                )
+                .replace("patched = [\">= 1.2.3\"]", "patched = []")
+        })]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+
+        let mut settings = Settings {
+            promote_informational: vec![advisory::Informational::Unsound],
+            ..Default::default()
+        };
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert_eq!(report.vulnerabilities.list.len(), 1);
+
+        // The advisory has no patched versions
+        settings.fixable_only = true;
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert!(report.vulnerabilities.list.is_empty());
+
+        // The advisory's severity is low, which is below the threshold for
+        // vulnerabilities even if warnings have none
+        settings.fixable_only = false;
+        settings.severity = Some(advisory::Severity::High);
+        settings.warning_severity = Some(advisory::Severity::None);
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert!(report.vulnerabilities.list.is_empty());
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn warning_severity_and_cvss() {
+        let (_db_dir, db) = db_with(&[("base", "RUSTSEC-2001-2101", &|advisory| {
+            advisory
+                .replace("[advisory]\n", "[advisory]\ninformational = \"unsound\"\n")
+                .replace(
+                    "[versions]",
+                    "cvss_scores = [\n    { label = \"windows\", \
+                    cvss = \"CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N\" },\n]\n\n[versions]",
+                )
+        })]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let mut settings = Settings {
+            informational_warnings: vec![advisory::Informational::Unsound],
+            ..Default::default()
+        };
+
+        let warnings = find_warnings(&db, &lockfile, &settings);
+        let warning = &warnings[&warning::WarningKind::Unsound][0];
+        assert_eq!(warning.severity, Some(advisory::Severity::Critical));
+
+        let json = serde_json::to_value(warning).unwrap();
+        assert_eq!(json["severity"], "critical");
+        assert_eq!(json["cvss"], "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H");
+
+        // The configured CVSS selection determines the severity of warnings
+        settings.cvss_selection = advisory::CvssSelection::Lowest;
+        let warnings = find_warnings(&db, &lockfile, &settings);
+        let warning = &warnings[&warning::WarningKind::Unsound][0];
+        assert_eq!(warning.severity, Some(advisory::Severity::Low));
+        assert_eq!(
+            warning.cvss.as_ref().unwrap().to_string(),
+            "CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N"
+        );
+
+        // Warnings without an advisory have no score information
+        let yanked = Warning::new(
+            warning::WarningKind::Yanked,
+            &lockfile.packages[0],
+            None,
+            None,
+            None,
+        );
+        let json = serde_json::to_value(&yanked).unwrap();
+        assert!(json.get("severity").is_none() && json.get("cvss").is_none());
+    }
+
+    #[test]
+    fn generate_from_packages() {
+        let (_db_dir, db) = db_with(&[("base", "RUSTSEC-2001-2101", &|advisory| advisory)]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+
+        let from_lockfile = Report::generate(&db, &lockfile, &Settings::default());
+        let from_packages = Report::generate(&db, &lockfile.packages, &Settings::default());
+        assert_eq!(from_lockfile.vulnerabilities.count, 1);
+        assert_eq!(
+            from_packages.vulnerabilities.list,
+            from_lockfile.vulnerabilities.list
+        );
+        assert_eq!(from_packages.lockfile.dependency_count, 2);
+    }
+
+    #[test]
+    fn toml_and_yaml_serialization() {
+        let (_db_dir, db) = db_with(&[]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let advisory =
+            crate::Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap();
+
+        let mut report = Report::generate(&db, &lockfile, &Settings::default());
+        report.vulnerabilities =
+            VulnerabilityInfo::new(vec![Vulnerability::new(&advisory, &lockfile.packages[0])]);
+        report.update_summary(&lockfile);
+
+        let toml = report.to_toml_string().unwrap();
+        let parsed: Report = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.vulnerabilities.list, report.vulnerabilities.list);
+        assert_eq!(parsed.summary, report.summary);
+
+        let yaml = report.to_yaml_string().unwrap();
+        assert!(!yaml.contains("null"), "{}", yaml);
+        let parsed: Report = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.vulnerabilities.list, report.vulnerabilities.list);
+        assert_eq!(parsed.summary, report.summary);
+    }
+
+    #[test]
+    fn banned_warnings() {
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let rule = |name: &str, versions: Option<&str>, reason: Option<&str>| PackageRule {
+            name: name.parse().unwrap(),
+            versions: versions.map(|req| req.parse().unwrap()),
+            reason: reason.map(ToOwned::to_owned),
+        };
+
+        let mut settings = Settings {
+            banned_packages: vec![
+                rule("base", None, Some("use `other` instead")),
+                rule("deep", Some(">= 2.0.0"), None),
+            ],
+            ..Default::default()
+        };
+
+        let warnings = find_banned_warnings(&lockfile, &settings);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].is_banned());
+        assert_eq!(warnings[0].package.name.as_str(), "base");
+        assert_eq!(
+            warnings[0].ban_reason.as_deref(),
+            Some("use `other` instead")
+        );
+
+        let (_db_dir, db) = db_with(&[]);
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert_eq!(report.warnings[&warning::WarningKind::Banned].len(), 1);
+        assert_eq!(report.summary.warnings[&warning::WarningKind::Banned], 1);
+
+        settings.allowed_packages = vec![rule("base", None, None)];
+        assert!(find_banned_warnings(&lockfile, &settings).is_empty());
+    }
+
+    #[test]
+    fn source_warnings() {
+        let lockfile: Lockfile = r#"
+[[package]]
+name = "branch"
+version = "1.0.0"
+source = "git+https://github.com/example/branch?branch=main#0123456789abcdef0123456789abcdef01234567"
+
+[[package]]
+name = "default-branch"
+version = "1.0.0"
+source = "git+https://github.com/example/default-branch#0123456789abcdef0123456789abcdef01234567"
+
+[[package]]
+name = "rev"
+version = "1.0.0"
+source = "git+https://github.com/example/rev?rev=0123456#0123456789abcdef0123456789abcdef01234567"
+
+[[package]]
+name = "tag"
+version = "1.0.0"
+source = "git+https://github.com/example/tag?tag=v1.0.0#0123456789abcdef0123456789abcdef01234567"
+
+[[package]]
+name = "private"
+version = "1.0.0"
+source = "sparse+https://registry.example.com/index/"
+
+[[package]]
+name = "public"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "local"
+version = "1.0.0"
+"#
+        .parse()
+        .unwrap();
+
+        let warnings = find_source_warnings(&lockfile);
+        let names: Vec<_> = warnings
+            .iter()
+            .map(|warning| warning.package.name.as_str())
+            .collect();
+        assert_eq!(names, ["branch", "default-branch", "private"]);
+        assert!(warnings.iter().all(Warning::is_untracked_source));
+
+        let (_db_dir, db) = db_with(&[]);
+        let report = Report::generate(&db, &lockfile, &Settings::default());
+        assert!(report.warnings.is_empty());
+
+        let settings = Settings {
+            source_warnings: true,
+            ..Default::default()
+        };
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert_eq!(report.warnings[&warning::WarningKind::Source].len(), 3);
+    }
+
+    #[test]
+    fn source_rules() {
+        let lockfile: Lockfile = r#"
+[[package]]
+name = "git"
+version = "1.0.0"
+source = "git+https://github.com/example/git#0123456789abcdef0123456789abcdef01234567"
+
+[[package]]
+name = "private"
+version = "1.0.0"
+source = "sparse+https://registry.example.com/index/"
+
+[[package]]
+name = "public"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "local"
+version = "1.0.0"
+"#
+        .parse()
+        .unwrap();
+
+        let matching = |rule: &str| -> Vec<&str> {
+            let rule: SourceRule = rule.parse().unwrap();
+            lockfile
+                .packages
+                .iter()
+                .filter(|package| rule.matches(package))
+                .map(|package| package.name.as_str())
+                .collect()
+        };
+        assert_eq!(matching("path"), ["local"]);
+        assert_eq!(matching("git"), ["git"]);
+        assert_eq!(matching("crates-io"), ["public"]);
+        assert_eq!(matching("https://registry.example.com/index"), ["private"]);
+        assert_eq!(
+            matching("sparse+https://registry.example.com/index/"),
+            ["private"]
+        );
+        assert!("not a source".parse::<SourceRule>().is_err());
+
+        // Rules apply to warnings...
+        let banned = lockfile
+            .packages
+            .iter()
+            .map(|package| PackageRule {
+                name: package.name.clone(),
+                versions: None,
+                reason: None,
+            })
+            .collect();
+        let settings = Settings {
+            banned_packages: banned,
+            include_sources: vec![SourceRule::CratesIo, SourceRule::Git],
+            exclude_sources: vec![SourceRule::Git],
+            ..Default::default()
+        };
+        let warnings = find_banned_warnings(&lockfile, &settings);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].package.name.as_str(), "public");
+
+        // ...as well as vulnerabilities
+        let (_db_dir, db) = db_with(&[("base", "RUSTSEC-2001-2101", &|advisory| advisory)]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+
+        let settings = Settings {
+            exclude_sources: vec![SourceRule::CratesIo],
+            ..Default::default()
+        };
+        let report = Report::generate(&db, &lockfile, &settings);
+        assert!(!report.vulnerabilities.found);
+        assert!(report.ignored.is_empty());
+
+        let settings = Settings {
+            include_sources: vec![SourceRule::CratesIo],
+            ..Default::default()
+        };
+        assert!(
+            Report::generate(&db, &lockfile, &settings)
+                .vulnerabilities
+                .found
+        );
+    }
+
+    #[test]
+    fn enrichment() {
+        let (_db_dir, db) = db_with(&[("base", "RUSTSEC-2001-2101", &|advisory| advisory)]);
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+
+        let mut report = Report::generate(&db, &lockfile, &Settings::default());
+        let advisory = report.vulnerabilities.list[0].advisory.clone();
+        report
+            .vulnerabilities
+            .list
+            .push(report.vulnerabilities.list[0].clone());
+
+        // Advisories are listed once even if they affect several findings
+        let advisories = report.advisories();
+        assert_eq!(advisories.len(), 1);
+        assert_eq!(advisories[0].id, advisory.id);
+
+        let cve: advisory::Id = "CVE-2001-2101".parse().unwrap();
+        report.enrich(|advisory| {
+            Some(Enrichment {
+                aliases: advisory.aliases.clone(),
+                epss: Some(Epss {
+                    cve: cve.clone(),
+                    score: Probability::new(0.5).unwrap(),
+                    percentile: Probability::new(0.9).unwrap(),
+                }),
+                known_exploited: Some(KnownExploited {
+                    cve: cve.clone(),
+                    date_added: None,
+                }),
+                ..Default::default()
+            })
+        });
+        let enrichment = report.vulnerabilities.list[1].enrichment.as_ref().unwrap();
+        assert_eq!(enrichment.aliases, std::slice::from_ref(&cve));
+        assert_eq!(enrichment.epss.as_ref().unwrap().score.value(), 0.5);
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains(r#""known_exploited":{"cve":"CVE-2001-2101""#));
+        let parsed: Report = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed.vulnerabilities.list[0].enrichment,
+            Some(enrichment.clone())
+        );
+
+        // EPSS scores are probabilities
+        assert!(json.contains(r#""score":0.5,"percentile":0.9"#));
+        assert!(serde_json::from_str::<Probability>("1.5").is_err());
+        assert!(Probability::new(f64::NAN).is_none());
+
+        // Reports without enrichments don't include the field at all
+        report.enrich(|_| None);
+        assert!(!serde_json::to_string(&report)
+            .unwrap()
+            .contains("enrichment"));
+    }
+
+    #[test]
+    fn checksum_warning_serialization() {
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let checksum: package::Checksum =
+            "2f9c8c87fdc1d1ecbf5a1a4a48d8a7bea1fb5fb8d6856aed7f86cdcf5ec43ac8"
+                .parse()
+                .unwrap();
+        let warning = Warning::checksum_mismatch(&lockfile.packages[0], checksum.clone());
+
+        let json = serde_json::to_string(&warning).unwrap();
+        assert!(json.contains(r#""kind":"checksum""#));
+        let parsed: Warning = serde_json::from_str(&json).unwrap();
+        assert!(parsed.is_checksum_mismatch());
+        assert_eq!(parsed.registry_checksum, Some(checksum));
+
+        // Other warnings don't include the field at all
+        let yanked = Warning::new(
+            warning::WarningKind::Yanked,
+            &lockfile.packages[0],
+            None,
+            None,
+            None,
+        );
+        assert!(!serde_json::to_string(&yanked)
+            .unwrap()
+            .contains("registry_checksum"));
+    }
+}
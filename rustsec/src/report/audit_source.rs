@@ -0,0 +1,49 @@
+//! Inputs which can be audited
+
+mod metadata;
+mod sbom;
+
+pub use self::{metadata::CargoMetadata, sbom::Sbom};
+
+use crate::{package::Package, Lockfile};
+
+/// Input to an audit, such as a lockfile, the dependency information
+/// recovered from a binary, an SBOM ([`Sbom`]), or the dependency graph
+/// resolved by `cargo metadata` ([`CargoMetadata`]).
+///
+/// Every source is normalized into the set of [`Package`]s it contains, so
+/// that [`Report::generate`](super::Report::generate) applies the same
+/// queries, deduplication and warnings to all of them.
+pub trait AuditSource {
+    /// Get the packages in this source.
+    ///
+    /// The dependencies between them are recorded in their
+    /// [`Package::dependencies`]. Sources without dependency information
+    /// leave those empty, so the findings in them are neither direct nor
+    /// transitive.
+    fn packages(&self) -> &[Package];
+}
+
+impl AuditSource for Lockfile {
+    fn packages(&self) -> &[Package] {
+        &self.packages
+    }
+}
+
+impl AuditSource for [Package] {
+    fn packages(&self) -> &[Package] {
+        self
+    }
+}
+
+impl AuditSource for Vec<Package> {
+    fn packages(&self) -> &[Package] {
+        self
+    }
+}
+
+impl<S: AuditSource + ?Sized> AuditSource for &S {
+    fn packages(&self) -> &[Package] {
+        (**self).packages()
+    }
+}
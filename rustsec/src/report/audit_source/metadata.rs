@@ -0,0 +1,213 @@
+//! Dependency graphs resolved by `cargo metadata`
+
+use super::AuditSource;
+use crate::{
+    error::{Error, ErrorKind},
+    package::{Package, SourceId},
+    Map,
+};
+use cargo_lock::Dependency;
+use serde::Deserialize;
+
+/// Packages in the dependency graph resolved by
+/// `cargo metadata --format-version 1`, deserialized from its JSON output:
+///
+/// ```ignore
+/// let metadata: CargoMetadata = serde_json::from_slice(&output.stdout)?;
+/// let report = Report::generate(&db, &metadata, &Settings::default());
+/// ```
+///
+/// Only the packages in the resolved graph are audited. Output generated
+/// with `--no-deps` has no resolved graph, so only the workspace members it
+/// lists are.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "Metadata")]
+pub struct CargoMetadata {
+    /// Packages in the resolved dependency graph
+    packages: Vec<Package>,
+}
+
+impl AuditSource for CargoMetadata {
+    fn packages(&self) -> &[Package] {
+        &self.packages
+    }
+}
+
+/// Output of `cargo metadata`, as far as it's needed to audit it
+#[derive(Deserialize)]
+struct Metadata {
+    /// Packages in the workspace and its dependencies
+    packages: Vec<MetadataPackage>,
+
+    /// Resolved dependency graph, unless `--no-deps` was passed
+    resolve: Option<Resolve>,
+}
+
+/// Package in the output of `cargo metadata`
+#[derive(Deserialize)]
+struct MetadataPackage {
+    /// Opaque ID of the package, which the resolved graph refers to it by
+    id: String,
+
+    /// Name of the package
+    name: String,
+
+    /// Version of the package
+    version: String,
+
+    /// Source of the package (e.g. a registry), or `None` for path
+    /// dependencies and workspace members
+    source: Option<String>,
+}
+
+/// Resolved dependency graph
+#[derive(Deserialize)]
+struct Resolve {
+    /// Packages in the graph, along with their dependencies
+    nodes: Vec<ResolveNode>,
+}
+
+/// Package in the resolved dependency graph
+#[derive(Deserialize)]
+struct ResolveNode {
+    /// ID of the package
+    id: String,
+
+    /// IDs of the packages it depends on
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+impl TryFrom<Metadata> for CargoMetadata {
+    type Error = Error;
+
+    fn try_from(metadata: Metadata) -> Result<Self, Error> {
+        let mut packages = Map::new();
+        for package in metadata.packages {
+            packages.insert(package.id.clone(), package.to_package()?);
+        }
+
+        let resolve = match metadata.resolve {
+            Some(resolve) => resolve,
+            None => {
+                return Ok(Self {
+                    packages: packages.into_values().collect(),
+                })
+            }
+        };
+
+        let mut resolved = Vec::with_capacity(resolve.nodes.len());
+        for node in &resolve.nodes {
+            let mut package = packages.get(&node.id).cloned().ok_or_else(|| {
+                format_err!(
+                    ErrorKind::Parse,
+                    "unknown package in resolved dependency graph: {}",
+                    node.id
+                )
+            })?;
+
+            package.dependencies = node
+                .dependencies
+                .iter()
+                .filter_map(|id| packages.get(id))
+                .map(Dependency::from)
+                .collect();
+            resolved.push(package);
+        }
+
+        Ok(Self { packages: resolved })
+    }
+}
+
+impl MetadataPackage {
+    /// Convert this package into a lockfile [`Package`]
+    fn to_package(&self) -> Result<Package, Error> {
+        Ok(Package {
+            name: self.name.parse()?,
+            version: self.version.parse().map_err(|e| {
+                format_err!(
+                    ErrorKind::Version,
+                    "invalid version of {}: {}",
+                    self.name,
+                    e
+                )
+            })?,
+            source: self.source.as_deref().map(SourceId::from_url).transpose()?,
+            checksum: None,
+            dependencies: vec![],
+            replace: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuditSource, CargoMetadata};
+
+    /// `cargo metadata` output for a workspace member depending on `base`
+    const METADATA: &str = r#"{
+        "packages": [
+            {
+                "id": "app 0.1.0 (path+file:///app)",
+                "name": "app",
+                "version": "0.1.0",
+                "source": null
+            },
+            {
+                "id": "base 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                "name": "base",
+                "version": "1.0.0",
+                "source": "registry+https://github.com/rust-lang/crates.io-index"
+            }
+        ],
+        "resolve": {
+            "nodes": [
+                {
+                    "id": "app 0.1.0 (path+file:///app)",
+                    "dependencies": [
+                        "base 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)"
+                    ]
+                },
+                {
+                    "id": "base 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                    "dependencies": []
+                }
+            ],
+            "root": "app 0.1.0 (path+file:///app)"
+        },
+        "workspace_members": ["app 0.1.0 (path+file:///app)"]
+    }"#;
+
+    #[test]
+    fn resolved_packages() {
+        let metadata: CargoMetadata = serde_json::from_str(METADATA).unwrap();
+        let packages = metadata.packages();
+        assert_eq!(packages.len(), 2);
+
+        let app = &packages[0];
+        assert_eq!(app.name.as_str(), "app");
+        assert!(app.source.is_none());
+        assert_eq!(app.dependencies.len(), 1);
+        assert!(app.dependencies[0].matches(&packages[1]));
+
+        let base = &packages[1];
+        assert_eq!(base.version.to_string(), "1.0.0");
+        assert!(base.source.as_ref().unwrap().is_default_registry());
+    }
+
+    #[test]
+    fn unresolved_packages() {
+        let mut json: serde_json::Value = serde_json::from_str(METADATA).unwrap();
+        json["resolve"] = serde_json::Value::Null;
+        let metadata: CargoMetadata = serde_json::from_value(json).unwrap();
+        assert_eq!(metadata.packages().len(), 2);
+        assert!(metadata
+            .packages()
+            .iter()
+            .all(|package| package.dependencies.is_empty()));
+
+        assert!(
+            serde_json::from_str::<CargoMetadata>(&METADATA.replace("1.0.0\"", "one\"")).is_err()
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Software bills of materials
+
+use super::AuditSource;
+use crate::{
+    error::{Error, ErrorKind},
+    package::{Package, SourceId},
+    Map,
+};
+use cargo_lock::Dependency;
+use serde::Deserialize;
+
+/// Prefix of the package URLs of Rust packages
+const CARGO_PURL_PREFIX: &str = "pkg:cargo/";
+
+/// Rust packages listed in a [CycloneDX](https://cyclonedx.org/) software
+/// bill of materials (e.g. as generated by `cargo cyclonedx`), deserialized
+/// from its JSON form:
+///
+/// ```ignore
+/// let sbom: Sbom = serde_json::from_str(&fs::read_to_string("app.cdx.json")?)?;
+/// let report = Report::generate(&db, &sbom, &Settings::default());
+/// ```
+///
+/// Components are recognized as Rust packages by their `pkg:cargo/` package
+/// URL. Those without qualifiers are taken to be from crates.io, while the
+/// others (e.g. path or git dependencies) have no source. The dependencies
+/// between them are taken from the SBOM's dependency graph, if it has one.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "Bom")]
+pub struct Sbom {
+    /// Rust packages in the SBOM
+    packages: Vec<Package>,
+}
+
+impl AuditSource for Sbom {
+    fn packages(&self) -> &[Package] {
+        &self.packages
+    }
+}
+
+/// CycloneDX bill of materials, as far as it's needed to audit it
+#[derive(Deserialize)]
+struct Bom {
+    /// Metadata about the bill of materials
+    #[serde(default)]
+    metadata: BomMetadata,
+
+    /// Components the subject of the bill of materials consists of
+    #[serde(default)]
+    components: Vec<Component>,
+
+    /// Dependency graph of the components
+    #[serde(default)]
+    dependencies: Vec<BomDependency>,
+}
+
+/// Metadata about a bill of materials
+#[derive(Default, Deserialize)]
+struct BomMetadata {
+    /// Subject of the bill of materials (e.g. the application)
+    component: Option<Component>,
+}
+
+/// Component listed in a bill of materials
+#[derive(Deserialize)]
+struct Component {
+    /// Reference to the component in the dependency graph
+    #[serde(rename = "bom-ref")]
+    bom_ref: Option<String>,
+
+    /// Package URL of the component (e.g. `pkg:cargo/base@1.0.0`)
+    purl: Option<String>,
+
+    /// Components this component consists of
+    #[serde(default)]
+    components: Vec<Component>,
+}
+
+/// Dependencies of a component
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BomDependency {
+    /// Reference to the dependent component
+    #[serde(rename = "ref")]
+    reference: String,
+
+    /// References to the components it depends on
+    #[serde(default)]
+    depends_on: Vec<String>,
+}
+
+impl TryFrom<Bom> for Sbom {
+    type Error = Error;
+
+    fn try_from(bom: Bom) -> Result<Self, Error> {
+        let mut packages = Map::new();
+        let mut pending: Vec<_> = bom
+            .metadata
+            .component
+            .iter()
+            .chain(&bom.components)
+            .collect();
+
+        while let Some(component) = pending.pop() {
+            pending.extend(&component.components);
+
+            let purl = match &component.purl {
+                Some(purl) if purl.starts_with(CARGO_PURL_PREFIX) => purl,
+                _ => continue,
+            };
+            let reference = component.bom_ref.as_ref().unwrap_or(purl);
+            packages.insert(reference.clone(), parse_purl(purl)?);
+        }
+
+        for dependency in &bom.dependencies {
+            let dependencies: Vec<_> = dependency
+                .depends_on
+                .iter()
+                .filter_map(|reference| packages.get(reference))
+                .map(Dependency::from)
+                .collect();
+
+            if let Some(package) = packages.get_mut(&dependency.reference) {
+                package.dependencies = dependencies;
+            }
+        }
+
+        Ok(Self {
+            packages: packages.into_values().collect(),
+        })
+    }
+}
+
+/// Parse a `pkg:cargo/NAME@VERSION` package URL into a [`Package`]
+fn parse_purl(purl: &str) -> Result<Package, Error> {
+    let invalid = || format_err!(ErrorKind::Parse, "invalid Rust package URL: {}", purl);
+
+    let (path, qualifiers) = match purl[CARGO_PURL_PREFIX.len()..].split_once('?') {
+        Some((path, qualifiers)) => (path, Some(qualifiers)),
+        None => (&purl[CARGO_PURL_PREFIX.len()..], None),
+    };
+    let path = path.split('#').next().unwrap_or_default();
+    let (name, version) = path.split_once('@').ok_or_else(invalid)?;
+
+    Ok(Package {
+        name: name.parse().map_err(|_| invalid())?,
+        // Build metadata is percent-encoded
+        version: version
+            .replace("%2B", "+")
+            .replace("%2b", "+")
+            .parse()
+            .map_err(|_| invalid())?,
+        source: match qualifiers {
+            None => Some(SourceId::default()),
+            Some(_) => None,
+        },
+        checksum: None,
+        dependencies: vec![],
+        replace: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_purl, AuditSource, Sbom};
+
+    /// SBOM of an application depending on `base`, which depends on `deep`
+    const SBOM: &str = r#"{
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "metadata": {
+            "component": {
+                "type": "application",
+                "bom-ref": "app",
+                "name": "app",
+                "purl": "pkg:cargo/app@0.1.0?download_url=file://."
+            }
+        },
+        "components": [
+            {
+                "type": "library",
+                "bom-ref": "base",
+                "name": "base",
+                "version": "1.0.0",
+                "purl": "pkg:cargo/base@1.0.0"
+            },
+            {
+                "type": "library",
+                "bom-ref": "deep",
+                "name": "deep",
+                "version": "2.0.0+build.1",
+                "purl": "pkg:cargo/deep@2.0.0%2Bbuild.1"
+            },
+            {
+                "type": "library",
+                "bom-ref": "openssl",
+                "name": "openssl",
+                "purl": "pkg:generic/openssl@3.0.0"
+            }
+        ],
+        "dependencies": [
+            { "ref": "app", "dependsOn": ["base", "openssl"] },
+            { "ref": "base", "dependsOn": ["deep"] }
+        ]
+    }"#;
+
+    #[test]
+    fn rust_packages() {
+        let sbom: Sbom = serde_json::from_str(SBOM).unwrap();
+        let packages = sbom.packages();
+        assert_eq!(packages.len(), 3);
+
+        let (app, base, deep) = (&packages[0], &packages[1], &packages[2]);
+        assert_eq!(app.name.as_str(), "app");
+        assert!(app.source.is_none());
+        assert_eq!(app.dependencies.len(), 1);
+        assert!(app.dependencies[0].matches(base));
+
+        assert!(base.source.as_ref().unwrap().is_default_registry());
+        assert!(base.dependencies[0].matches(deep));
+        assert_eq!(deep.version.to_string(), "2.0.0+build.1");
+    }
+
+    #[test]
+    fn invalid_purls() {
+        assert!(parse_purl("pkg:cargo/base").is_err());
+        assert!(parse_purl("pkg:cargo/base@one").is_err());
+        assert!(serde_json::from_str::<Sbom>(&SBOM.replace("base@1.0.0", "base@1")).is_err());
+    }
+}
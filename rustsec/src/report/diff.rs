@@ -0,0 +1,189 @@
+//! Comparison of two reports, e.g. before and after a change to a project
+
+use super::Report;
+use crate::{
+    advisory,
+    package::Package,
+    vulnerability::Vulnerability,
+    warning::{Warning, WarningKind},
+    Map,
+};
+use serde::{Deserialize, Serialize};
+
+/// Differences between two [`Report`]s.
+///
+/// Produced by [`Report::diff`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ReportDiff {
+    /// Changes to the detected vulnerabilities
+    pub vulnerabilities: Changes<Vulnerability>,
+
+    /// Changes to the warnings
+    pub warnings: Changes<Warning>,
+}
+
+impl ReportDiff {
+    /// IDs of advisories which are only found in the new report (i.e. which
+    /// were introduced by the change)
+    pub fn added_advisories(&self) -> Vec<&advisory::Id> {
+        advisory_ids(&self.vulnerabilities.added, &self.warnings.added)
+    }
+
+    /// IDs of advisories which are only found in the old report (i.e. which
+    /// were fixed by the change)
+    pub fn removed_advisories(&self) -> Vec<&advisory::Id> {
+        advisory_ids(&self.vulnerabilities.removed, &self.warnings.removed)
+    }
+
+    /// Are the compared reports free of differences?
+    pub fn is_empty(&self) -> bool {
+        self.vulnerabilities.added.is_empty()
+            && self.vulnerabilities.removed.is_empty()
+            && self.warnings.added.is_empty()
+            && self.warnings.removed.is_empty()
+    }
+}
+
+/// Findings which were added, removed, or are present in both reports
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Changes<T> {
+    /// Findings which are only in the new report
+    pub added: Vec<T>,
+
+    /// Findings which are only in the old report
+    pub removed: Vec<T>,
+
+    /// Findings which are in both reports (as they appear in the new report)
+    pub unchanged: Vec<T>,
+}
+
+impl<T> Default for Changes<T> {
+    fn default() -> Self {
+        Self {
+            added: vec![],
+            removed: vec![],
+            unchanged: vec![],
+        }
+    }
+}
+
+impl<T: Clone> Changes<T> {
+    /// Compare the findings from two reports, which are considered to be the
+    /// same if they have the same key
+    fn new<'a, K: Ord>(
+        old: impl IntoIterator<Item = &'a T>,
+        new: impl IntoIterator<Item = &'a T>,
+        key: impl Fn(&'a T) -> K,
+    ) -> Self
+    where
+        T: 'a,
+    {
+        let mut old: Map<K, &T> = old.into_iter().map(|item| (key(item), item)).collect();
+        let mut changes = Self::default();
+
+        for item in new {
+            match old.remove(&key(item)) {
+                Some(_) => changes.unchanged.push(item.clone()),
+                None => changes.added.push(item.clone()),
+            }
+        }
+
+        changes.removed = old.into_values().cloned().collect();
+        changes
+    }
+}
+
+impl Report {
+    /// Compare an `old` report with a `new` one.
+    ///
+    /// Vulnerabilities are matched by advisory ID and package, and warnings by
+    /// their kind, advisory ID (if any), and package.
+    pub fn diff(old: &Report, new: &Report) -> ReportDiff {
+        ReportDiff {
+            vulnerabilities: Changes::new(
+                &old.vulnerabilities.list,
+                &new.vulnerabilities.list,
+                |vuln| (&vuln.advisory.id, &vuln.package),
+            ),
+            warnings: Changes::new(
+                old.warnings.values().flatten(),
+                new.warnings.values().flatten(),
+                warning_key,
+            ),
+        }
+    }
+}
+
+/// Key identifying the same warning in two reports
+fn warning_key(warning: &Warning) -> (WarningKind, Option<&advisory::Id>, &Package) {
+    (
+        warning.kind,
+        warning.advisory.as_ref().map(|advisory| &advisory.id),
+        &warning.package,
+    )
+}
+
+/// Collect the sorted, deduplicated advisory IDs of the given findings
+fn advisory_ids<'a>(
+    vulnerabilities: &'a [Vulnerability],
+    warnings: &'a [Warning],
+) -> Vec<&'a advisory::Id> {
+    let mut ids: Vec<_> = vulnerabilities
+        .iter()
+        .map(|vuln| &vuln.advisory.id)
+        .chain(
+            warnings
+                .iter()
+                .filter_map(|warning| warning.advisory.as_ref())
+                .map(|advisory| &advisory.id),
+        )
+        .collect();
+
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        report::{Settings, VulnerabilityInfo},
+        Advisory, Database, Lockfile,
+    };
+
+    const LOCKFILE: &str = r#"
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    #[test]
+    fn diff_reports() {
+        let db_dir = tempfile::tempdir().unwrap();
+        let db = Database::open(db_dir.path()).unwrap();
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let advisory = Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap();
+        let base = &lockfile.packages[0];
+
+        let mut old = Report::generate(&db, &lockfile, &Settings::default());
+        old.warnings.insert(
+            WarningKind::Yanked,
+            vec![Warning::new(WarningKind::Yanked, base, None, None, None)],
+        );
+        let mut new = old.clone();
+        new.vulnerabilities = VulnerabilityInfo::new(vec![Vulnerability::new(&advisory, base)]);
+
+        let diff = Report::diff(&old, &new);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.vulnerabilities.added.len(), 1);
+        assert!(diff.vulnerabilities.removed.is_empty());
+        assert_eq!(diff.warnings.unchanged.len(), 1);
+        assert_eq!(diff.added_advisories(), [&advisory.metadata.id]);
+
+        let reverse = Report::diff(&new, &old);
+        assert_eq!(reverse.removed_advisories(), [&advisory.metadata.id]);
+        assert!(Report::diff(&old, &old).is_empty());
+    }
+}
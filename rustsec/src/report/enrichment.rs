@@ -0,0 +1,105 @@
+//! Data about advisories fetched from external sources after an audit, e.g.
+//! to prioritize findings which are being exploited
+
+use crate::{
+    advisory::{Date, Id},
+    error::{Error, ErrorKind},
+};
+use serde::{Deserialize, Serialize};
+
+/// Data about the advisory of a finding fetched from external sources, which
+/// is attached to vulnerabilities and warnings by [`Report::enrich`].
+///
+/// Audits only use the advisory database, so none of this is available in a
+/// freshly generated report.
+///
+/// [`Report::enrich`]: super::Report::enrich
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Enrichment {
+    /// IDs of the advisory in other databases (e.g. GHSA IDs) according to
+    /// OSV, which may include ones the advisory doesn't list
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<Id>,
+
+    /// Exploit Prediction Scoring System (EPSS) score of the advisory's CVE
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epss: Option<Epss>,
+
+    /// Entry of the advisory's CVE in CISA's catalog of Known Exploited
+    /// Vulnerabilities (KEV), if it's listed there
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_exploited: Option<KnownExploited>,
+
+    /// Severity of the advisory's GitHub Security Advisory, i.e. `low`,
+    /// `moderate`, `high`, or `critical`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ghsa_severity: Option<String>,
+}
+
+impl Enrichment {
+    /// Is there no data at all?
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Exploit Prediction Scoring System (EPSS) score of a CVE, see
+/// <https://www.first.org/epss/>
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Epss {
+    /// CVE the score is for
+    pub cve: Id,
+
+    /// Probability of exploitation in the next 30 days
+    pub score: Probability,
+
+    /// Percentile of the score among all scored CVEs
+    pub percentile: Probability,
+}
+
+/// Number from 0 to 1, such as an EPSS score or percentile.
+///
+/// Unlike a bare `f64` it can't be NaN, so it implements [`Eq`].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, PartialOrd, Serialize)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct Probability(f64);
+
+impl Probability {
+    /// Create a probability, if the given value is from 0 to 1
+    pub fn new(value: f64) -> Option<Self> {
+        (0.0..=1.0).contains(&value).then_some(Self(value))
+    }
+
+    /// Get the probability as a floating point number
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+impl Eq for Probability {}
+
+impl TryFrom<f64> for Probability {
+    type Error = Error;
+
+    fn try_from(value: f64) -> Result<Self, Error> {
+        Self::new(value)
+            .ok_or_else(|| format_err!(ErrorKind::Parse, "probability out of range: {}", value))
+    }
+}
+
+impl From<Probability> for f64 {
+    fn from(probability: Probability) -> f64 {
+        probability.0
+    }
+}
+
+/// Entry of a CVE in CISA's catalog of Known Exploited Vulnerabilities, see
+/// <https://www.cisa.gov/known-exploited-vulnerabilities-catalog>
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct KnownExploited {
+    /// CVE which is known to be exploited
+    pub cve: Id,
+
+    /// Date the CVE was added to the catalog
+    pub date_added: Option<Date>,
+}
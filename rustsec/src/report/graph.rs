@@ -0,0 +1,526 @@
+//! Graph of the dependencies through which a workspace is affected by the
+//! advisories of a report, e.g. to render attack surface maps
+
+use super::Report;
+use crate::{
+    advisory::{self, Informational, Severity},
+    package::{self, Package, SourceId},
+    Map, Set, Version,
+};
+use cargo_lock::dependency::{Dependency, Tree};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Graph linking the packages of a dependency tree to the advisories
+/// affecting them.
+///
+/// Produced by [`Report::graph`]. Only the packages which are affected by an
+/// advisory, and the ones through which they're depended on, are included.
+/// Nodes are referred to by their index in [`Graph::nodes`].
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Graph {
+    /// Workspace members, dependencies and advisories
+    pub nodes: Vec<Node>,
+
+    /// Dependencies between packages, and advisories affecting packages
+    pub edges: Vec<Edge>,
+}
+
+/// Node of a [`Graph`]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Node {
+    /// Workspace member, i.e. a package with no source in the lockfile
+    Root {
+        /// Name of the package
+        name: package::Name,
+
+        /// Version of the package
+        version: Version,
+    },
+
+    /// Package depended on by a workspace member
+    Dependency {
+        /// Name of the package
+        name: package::Name,
+
+        /// Version of the package
+        version: Version,
+
+        /// Source of the package
+        source: SourceId,
+    },
+
+    /// Advisory affecting a package
+    Advisory {
+        /// ID of the advisory
+        id: advisory::Id,
+
+        /// Title of the advisory
+        title: String,
+
+        /// Severity of the advisory according to its CVSS score, if any
+        severity: Option<Severity>,
+
+        /// Informational kind of the advisory (e.g. `unmaintained`), if any
+        informational: Option<Informational>,
+    },
+}
+
+impl Node {
+    /// Name of the kind of node, i.e. `root`, `dependency` or `advisory`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Node::Root { .. } => "root",
+            Node::Dependency { .. } => "dependency",
+            Node::Advisory { .. } => "advisory",
+        }
+    }
+
+    /// Short human-readable description of the node
+    pub fn label(&self) -> String {
+        match self {
+            Node::Root { name, version } | Node::Dependency { name, version, .. } => {
+                format!("{} {}", name, version)
+            }
+            Node::Advisory { id, .. } => id.to_string(),
+        }
+    }
+}
+
+/// Edge of a [`Graph`], between the nodes at the given indices
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Edge {
+    /// Index of the depending or affected package
+    pub from: usize,
+
+    /// Index of the dependency or advisory
+    pub to: usize,
+
+    /// Kind of relationship between the nodes
+    pub kind: EdgeKind,
+}
+
+/// Relationship represented by an [`Edge`]
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EdgeKind {
+    /// The package depends on the other one
+    DependsOn,
+
+    /// The package is affected by the advisory
+    AffectedBy,
+}
+
+impl EdgeKind {
+    /// Name of the kind of edge, i.e. `depends_on` or `affected_by`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EdgeKind::DependsOn => "depends_on",
+            EdgeKind::AffectedBy => "affected_by",
+        }
+    }
+}
+
+impl Report {
+    /// Build the graph of the dependencies through which the packages of the
+    /// given dependency [`Tree`] (of the lockfile this report was generated
+    /// for) are affected by the advisories of its vulnerabilities and
+    /// warnings.
+    ///
+    /// Warnings which don't come from an advisory (e.g. about yanked
+    /// packages) aren't part of the graph. The severity of advisories is
+    /// determined by the report's [`Settings::cvss_selection`].
+    ///
+    /// [`Settings::cvss_selection`]: super::Settings::cvss_selection
+    #[cfg_attr(docsrs, doc(cfg(feature = "dependency-tree")))]
+    pub fn graph(&self, tree: &Tree) -> Graph {
+        let findings = self
+            .vulnerabilities
+            .list
+            .iter()
+            .map(|vulnerability| (&vulnerability.package, &vulnerability.advisory))
+            .chain(self.warnings.values().flatten().filter_map(|warning| {
+                warning
+                    .advisory
+                    .as_ref()
+                    .map(|advisory| (&warning.package, advisory))
+            }));
+
+        let mut affected: Map<Dependency, Set<&advisory::Id>> = Map::new();
+        let mut advisories = Map::new();
+        for (package, advisory) in findings {
+            affected
+                .entry(Dependency::from(package))
+                .or_default()
+                .insert(&advisory.id);
+            advisories.entry(&advisory.id).or_insert(advisory);
+        }
+
+        // Affected packages, along with the ones depending on them
+        let mut included = Set::new();
+        for dependency in affected.keys() {
+            if let Some(&index) = tree.nodes().get(dependency) {
+                let package = &tree.graph()[index];
+                included.insert(Dependency::from(package));
+                included.extend(
+                    tree.all_dependents(package)
+                        .into_iter()
+                        .map(Dependency::from),
+                );
+            }
+        }
+
+        // Nodes are in the order of the packages in the lockfile
+        let mut graph = Graph::default();
+        let mut indices = Map::new();
+        let packages: Vec<&Package> = tree.graph().node_weights().collect();
+        for package in &packages {
+            let key = Dependency::from(*package);
+            if included.contains(&key) {
+                indices.insert(key, graph.nodes.len());
+                graph.nodes.push(package_node(package));
+            }
+        }
+
+        let mut advisory_indices = Map::new();
+        for (id, advisory) in advisories {
+            advisory_indices.insert(id, graph.nodes.len());
+            graph.nodes.push(Node::Advisory {
+                id: id.clone(),
+                title: advisory.title.clone(),
+                severity: advisory
+                    .selected_cvss(&self.settings.cvss_selection)
+                    .map(|(_, cvss)| cvss.severity()),
+                informational: advisory.informational.clone(),
+            });
+        }
+
+        for package in packages {
+            let key = Dependency::from(package);
+            let Some(&from) = indices.get(&key) else {
+                continue;
+            };
+
+            for dependency in &package.dependencies {
+                if let Some(&to) = indices.get(dependency) {
+                    graph.edges.push(Edge {
+                        from,
+                        to,
+                        kind: EdgeKind::DependsOn,
+                    });
+                }
+            }
+
+            for id in affected.get(&key).into_iter().flatten() {
+                graph.edges.push(Edge {
+                    from,
+                    to: advisory_indices[id],
+                    kind: EdgeKind::AffectedBy,
+                });
+            }
+        }
+
+        graph
+    }
+}
+
+impl Graph {
+    /// Render the graph in the DOT language of Graphviz
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph audit {\n");
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            let shape = match node {
+                Node::Root { .. } => "box",
+                Node::Dependency { .. } => "ellipse",
+                Node::Advisory { .. } => "octagon",
+            };
+            write!(
+                out,
+                "    n{} [label={}, kind={}, shape={}",
+                index,
+                dot_str(&node.label()),
+                node.kind(),
+                shape
+            )
+            .unwrap();
+            if let Node::Advisory {
+                severity: Some(severity),
+                ..
+            } = node
+            {
+                write!(out, ", severity={}", severity.as_str()).unwrap();
+            }
+            out.push_str("];\n");
+        }
+
+        for edge in &self.edges {
+            write!(
+                out,
+                "    n{} -> n{} [kind={}",
+                edge.from,
+                edge.to,
+                edge.kind.as_str()
+            )
+            .unwrap();
+            if edge.kind == EdgeKind::AffectedBy {
+                out.push_str(", style=dashed");
+            }
+            out.push_str("];\n");
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render the graph as a GraphML document
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"kind\" for=\"all\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <key id=\"severity\" for=\"node\" attr.name=\"severity\" attr.type=\"string\"/>\n",
+            "  <graph id=\"audit\" edgedefault=\"directed\">\n",
+        ));
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            write!(
+                out,
+                "    <node id=\"n{}\"><data key=\"kind\">{}</data><data key=\"label\">{}</data>",
+                index,
+                node.kind(),
+                xml_str(&node.label())
+            )
+            .unwrap();
+            if let Node::Advisory {
+                severity: Some(severity),
+                ..
+            } = node
+            {
+                write!(out, "<data key=\"severity\">{}</data>", severity.as_str()).unwrap();
+            }
+            out.push_str("</node>\n");
+        }
+
+        for edge in &self.edges {
+            writeln!(
+                out,
+                "    <edge source=\"n{}\" target=\"n{}\"><data key=\"kind\">{}</data></edge>",
+                edge.from,
+                edge.to,
+                edge.kind.as_str()
+            )
+            .unwrap();
+        }
+
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+}
+
+/// Node of the given package, which is a root if it has no source
+fn package_node(package: &Package) -> Node {
+    match &package.source {
+        None => Node::Root {
+            name: package.name.clone(),
+            version: package.version.clone(),
+        },
+        Some(source) => Node::Dependency {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            source: source.clone(),
+        },
+    }
+}
+
+/// Quote a string for use as a DOT identifier
+fn dot_str(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Escape a string for use as XML character data
+fn xml_str(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        advisory::CvssSelection,
+        report::{Settings, VulnerabilityInfo},
+        Advisory, Database, Lockfile, Vulnerability,
+    };
+
+    const LOCKFILE: &str = r#"
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["base", "other"]
+
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["deep"]
+
+[[package]]
+name = "deep"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "other"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    #[test]
+    fn graph_of_affected_dependencies() {
+        let db_dir = tempfile::tempdir().unwrap();
+        let db = Database::open(db_dir.path()).unwrap();
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let advisory = Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap();
+        let deep = &lockfile.packages[2];
+
+        let mut report = Report::generate(&db, &lockfile, &Settings::default());
+        report.vulnerabilities = VulnerabilityInfo::new(vec![Vulnerability::new(&advisory, deep)]);
+
+        // `other` isn't on the way to an affected package
+        let graph = report.graph(&Tree::new(&lockfile).unwrap());
+        let labels: Vec<_> = graph.nodes.iter().map(Node::label).collect();
+        assert_eq!(
+            labels,
+            ["app 0.1.0", "base 1.0.0", "deep 1.0.0", "RUSTSEC-2001-2101"]
+        );
+        assert_eq!(graph.nodes[0].kind(), "root");
+        assert_eq!(graph.nodes[2].kind(), "dependency");
+        assert!(matches!(
+            graph.nodes[3],
+            Node::Advisory {
+                severity: Some(Severity::Critical),
+                ..
+            }
+        ));
+        assert_eq!(
+            graph.edges,
+            [
+                Edge {
+                    from: 0,
+                    to: 1,
+                    kind: EdgeKind::DependsOn
+                },
+                Edge {
+                    from: 1,
+                    to: 2,
+                    kind: EdgeKind::DependsOn
+                },
+                Edge {
+                    from: 2,
+                    to: 3,
+                    kind: EdgeKind::AffectedBy
+                },
+            ]
+        );
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("n3 [label=\"RUSTSEC-2001-2101\", kind=advisory, shape=octagon"));
+        assert!(dot.contains("n2 -> n3 [kind=affected_by, style=dashed];"));
+
+        let graphml = graph.to_graphml();
+        assert!(graphml.contains("<edge source=\"n0\" target=\"n1\">"));
+        assert!(graphml.contains("<data key=\"severity\">critical</data>"));
+    }
+
+    #[test]
+    fn graph_of_clean_report_is_empty() {
+        let db_dir = tempfile::tempdir().unwrap();
+        let db = Database::open(db_dir.path()).unwrap();
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+
+        let report = Report::generate(&db, &lockfile, &Settings::default());
+        assert_eq!(
+            report.graph(&Tree::new(&lockfile).unwrap()),
+            Graph::default()
+        );
+    }
+
+    #[test]
+    fn packages_are_told_apart_by_source() {
+        let db_dir = tempfile::tempdir().unwrap();
+        let db = Database::open(db_dir.path()).unwrap();
+        let lockfile: Lockfile = format!(
+            "{}{}",
+            LOCKFILE.replace(
+                "dependencies = [\"base\", \"other\"]",
+                "dependencies = [\n \"base\",\n \"deep 1.0.0 (registry+https://github.com/rust-lang/crates.io-index)\",\n \"deep 1.0.0 (git+https://github.com/example/deep#0123456789abcdef0123456789abcdef01234567)\",\n \"other\",\n]",
+            ),
+            r#"
+[[package]]
+name = "deep"
+version = "1.0.0"
+source = "git+https://github.com/example/deep#0123456789abcdef0123456789abcdef01234567"
+"#
+        )
+        .parse()
+        .unwrap();
+        let advisory = Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap();
+        let forked = lockfile
+            .packages
+            .iter()
+            .find(|package| package.source.as_ref().is_some_and(SourceId::is_git))
+            .unwrap();
+
+        let mut report = Report::generate(&db, &lockfile, &Settings::default());
+        report.vulnerabilities =
+            VulnerabilityInfo::new(vec![Vulnerability::new(&advisory, forked)]);
+
+        // The crates.io release of `deep` and `base`, which depends on it,
+        // aren't affected
+        let graph = report.graph(&Tree::new(&lockfile).unwrap());
+        assert_eq!(graph.nodes.len(), 3);
+        assert!(matches!(
+            &graph.nodes[1],
+            Node::Dependency { source, .. } if source.is_git()
+        ));
+        assert_eq!(graph.edges.len(), 2);
+    }
+
+    #[test]
+    fn severity_follows_cvss_selection() {
+        let db_dir = tempfile::tempdir().unwrap();
+        let db = Database::open(db_dir.path()).unwrap();
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let advisory: Advisory = std::fs::read_to_string("./tests/support/example_advisory_v3.md")
+            .unwrap()
+            .replace(
+                "[versions]",
+                "cvss_scores = [\n    { label = \"windows\", \
+                cvss = \"CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N\" },\n]\n\n[versions]",
+            )
+            .parse()
+            .unwrap();
+
+        let settings = Settings {
+            cvss_selection: CvssSelection::Lowest,
+            ..Default::default()
+        };
+        let mut report = Report::generate(&db, &lockfile, &settings);
+        report.vulnerabilities =
+            VulnerabilityInfo::new(vec![Vulnerability::new(&advisory, &lockfile.packages[2])]);
+
+        let graph = report.graph(&Tree::new(&lockfile).unwrap());
+        assert!(matches!(
+            graph.nodes[3],
+            Node::Advisory {
+                severity: Some(Severity::Low),
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,166 @@
+//! Aggregation of several reports into one
+
+use super::Report;
+use crate::{
+    advisory, map,
+    package::Package,
+    vulnerability::Vulnerability,
+    warning::{Warning, WarningKind},
+    Map,
+};
+use serde::{Deserialize, Serialize};
+
+/// Aggregate of several [`Report`]s (e.g. for every lockfile in a monorepo
+/// or every binary in a fleet), with each finding listed only once.
+///
+/// Produced by [`Report::merge`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MergedReport {
+    /// Number of reports which were merged
+    #[serde(rename = "report-count")]
+    pub report_count: usize,
+
+    /// Vulnerabilities detected in any of the reports
+    pub vulnerabilities: Vec<Sourced<Vulnerability>>,
+
+    /// Warnings found in any of the reports
+    pub warnings: Map<WarningKind, Vec<Sourced<Warning>>>,
+}
+
+impl MergedReport {
+    /// IDs of all advisories referenced by the merged vulnerabilities and
+    /// warnings, without duplicates
+    pub fn advisories(&self) -> Vec<&advisory::Id> {
+        let mut ids: Vec<_> = self
+            .vulnerabilities
+            .iter()
+            .map(|vuln| &vuln.finding.advisory.id)
+            .chain(
+                self.warnings
+                    .values()
+                    .flatten()
+                    .filter_map(|warning| warning.finding.advisory.as_ref())
+                    .map(|advisory| &advisory.id),
+            )
+            .collect();
+
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+}
+
+/// A finding along with the reports it was found in
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Sourced<T> {
+    /// The vulnerability or warning
+    #[serde(flatten)]
+    pub finding: T,
+
+    /// Indexes of the reports containing this finding, in the order they
+    /// were passed to [`Report::merge`]
+    pub sources: Vec<usize>,
+}
+
+impl Report {
+    /// Merge the given reports into one aggregate report.
+    ///
+    /// Vulnerabilities and warnings about the same advisory and package which
+    /// occur in several reports are deduplicated, recording the index of each
+    /// report they were found in.
+    pub fn merge(reports: &[Report]) -> MergedReport {
+        let mut vulnerabilities: Map<(&advisory::Id, &Package), Sourced<Vulnerability>> =
+            Map::new();
+        let mut warnings: Map<WarningKind, Map<WarningKey<'_>, Sourced<Warning>>> = Map::new();
+
+        for (index, report) in reports.iter().enumerate() {
+            for vuln in &report.vulnerabilities.list {
+                add_source(
+                    vulnerabilities.entry((&vuln.advisory.id, &vuln.package)),
+                    vuln,
+                    index,
+                );
+            }
+
+            for (kind, kind_warnings) in &report.warnings {
+                let merged = warnings.entry(*kind).or_default();
+
+                for warning in kind_warnings {
+                    let advisory_id = warning.advisory.as_ref().map(|advisory| &advisory.id);
+                    add_source(
+                        merged.entry((advisory_id, &warning.package)),
+                        warning,
+                        index,
+                    );
+                }
+            }
+        }
+
+        MergedReport {
+            report_count: reports.len(),
+            vulnerabilities: vulnerabilities.into_values().collect(),
+            warnings: warnings
+                .into_iter()
+                .map(|(kind, merged)| (kind, merged.into_values().collect()))
+                .collect(),
+        }
+    }
+}
+
+/// Key identifying the same warning across reports: warnings which don't come
+/// from an advisory (e.g. yanked crates) are identified by their package alone
+type WarningKey<'a> = (Option<&'a advisory::Id>, &'a Package);
+
+/// Record that the given finding occurs in the report with the given index
+fn add_source<K: Ord, T: Clone>(entry: map::Entry<'_, K, Sourced<T>>, finding: &T, index: usize) {
+    let sourced = entry.or_insert_with(|| Sourced {
+        finding: finding.clone(),
+        sources: vec![],
+    });
+
+    if sourced.sources.last() != Some(&index) {
+        sourced.sources.push(index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        report::{Settings, VulnerabilityInfo},
+        Advisory, Database, Lockfile,
+    };
+
+    const LOCKFILE: &str = r#"
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    #[test]
+    fn merge_deduplicates() {
+        let db_dir = tempfile::tempdir().unwrap();
+        let db = Database::open(db_dir.path()).unwrap();
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let advisory = Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap();
+        let base = &lockfile.packages[0];
+
+        let clean = Report::generate(&db, &lockfile, &Settings::default());
+        let mut vulnerable = clean.clone();
+        vulnerable.vulnerabilities =
+            VulnerabilityInfo::new(vec![Vulnerability::new(&advisory, base)]);
+        vulnerable.warnings.insert(
+            WarningKind::Yanked,
+            vec![Warning::new(WarningKind::Yanked, base, None, None, None)],
+        );
+
+        let merged = Report::merge(&[vulnerable.clone(), clean, vulnerable]);
+        assert_eq!(merged.report_count, 3);
+        assert_eq!(merged.vulnerabilities.len(), 1);
+        assert_eq!(merged.vulnerabilities[0].sources, [0, 2]);
+        assert_eq!(merged.warnings[&WarningKind::Yanked].len(), 1);
+        assert_eq!(merged.warnings[&WarningKind::Yanked][0].sources, [0, 2]);
+        assert_eq!(merged.advisories(), [&advisory.metadata.id]);
+    }
+}
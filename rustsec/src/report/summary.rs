@@ -0,0 +1,188 @@
+//! Aggregate statistics about the findings in a report
+
+use super::{AuditSource, VulnerabilityInfo, WarningInfo};
+use crate::{
+    advisory::{Category, CvssSelection, Severity},
+    package::{self, Package},
+    warning::WarningKind,
+    Map, Set, Version,
+};
+use serde::{Deserialize, Serialize};
+
+/// Summary of the findings in a [`Report`](super::Report), grouped into
+/// buckets so consumers don't have to recompute them from the full lists
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Summary {
+    /// Number of vulnerabilities per CVSS severity rating
+    pub severity: Map<Severity, usize>,
+
+    /// Number of vulnerabilities whose advisory has no CVSS information
+    pub unscored: usize,
+
+    /// Number of vulnerabilities per advisory category.
+    ///
+    /// Advisories with several categories are counted once for each of them.
+    pub categories: Map<Category, usize>,
+
+    /// Number of warnings per kind (e.g. `unmaintained`, `unsound`, `yanked`)
+    pub warnings: Map<WarningKind, usize>,
+
+    /// Number of findings (vulnerabilities and warnings) in packages which
+    /// are direct dependencies of a workspace member
+    pub direct: usize,
+
+    /// Number of findings (vulnerabilities and warnings) in packages which
+    /// are only pulled in transitively
+    pub transitive: usize,
+
+    /// Number of findings (vulnerabilities and warnings) in packages which
+    /// are only used at build time, by build scripts or proc macros (see
+    /// [`Report::mark_build_only`](super::Report::mark_build_only))
+    #[serde(default)]
+    pub build_only: usize,
+
+    /// Number of vulnerabilities in build-only packages per CVSS severity
+    /// rating, which are also counted in [`Summary::severity`]
+    #[serde(default)]
+    pub build_only_severity: Map<Severity, usize>,
+}
+
+impl Summary {
+    /// Compute a summary of the given vulnerabilities and warnings found in
+    /// the given [`Lockfile`](crate::Lockfile) (or other [`AuditSource`]), rating the severity
+    /// of vulnerabilities with the given [`CvssSelection`]
+    pub fn new<S>(
+        vulnerabilities: &VulnerabilityInfo,
+        warnings: &WarningInfo,
+        source: &S,
+        cvss_selection: &CvssSelection,
+    ) -> Self
+    where
+        S: AuditSource + ?Sized,
+    {
+        let direct_deps = direct_dependencies(source.packages());
+        let mut summary = Self::default();
+
+        for vuln in &vulnerabilities.list {
+            match vuln.advisory.selected_cvss(cvss_selection) {
+                Some((_, cvss)) => {
+                    *summary.severity.entry(cvss.severity()).or_default() += 1;
+                    if vuln.build_only {
+                        *summary
+                            .build_only_severity
+                            .entry(cvss.severity())
+                            .or_default() += 1;
+                    }
+                }
+                None => summary.unscored += 1,
+            }
+
+            if vuln.build_only {
+                summary.build_only += 1;
+            }
+
+            for category in &vuln.advisory.categories {
+                *summary.categories.entry(category.clone()).or_default() += 1;
+            }
+
+            summary.count_dependency(&vuln.package, &direct_deps);
+        }
+
+        for (kind, kind_warnings) in warnings {
+            *summary.warnings.entry(*kind).or_default() += kind_warnings.len();
+
+            for warning in kind_warnings {
+                summary.count_dependency(&warning.package, &direct_deps);
+
+                if warning.build_only {
+                    summary.build_only += 1;
+                }
+            }
+        }
+
+        summary
+    }
+
+    /// Count a finding in the given package as either direct or transitive
+    fn count_dependency(
+        &mut self,
+        package: &Package,
+        direct_deps: &Set<(&package::Name, &Version)>,
+    ) {
+        if package.source.is_none() || direct_deps.contains(&(&package.name, &package.version)) {
+            self.direct += 1;
+        } else {
+            self.transitive += 1;
+        }
+    }
+}
+
+/// Compute the name/version pairs of all packages which are depended on by a
+/// workspace member (i.e. a package with no source)
+fn direct_dependencies(packages: &[Package]) -> Set<(&package::Name, &Version)> {
+    packages
+        .iter()
+        .filter(|package| package.source.is_none())
+        .flat_map(|package| &package.dependencies)
+        .map(|dep| (&dep.name, &dep.version))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Advisory, Lockfile, Vulnerability};
+
+    const LOCKFILE: &str = r#"
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["base"]
+
+[[package]]
+name = "base"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["deep"]
+
+[[package]]
+name = "deep"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    #[test]
+    fn counts_findings() {
+        let lockfile: Lockfile = LOCKFILE.parse().unwrap();
+        let advisory = Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap();
+        let base = &lockfile.packages[1];
+        let deep = &lockfile.packages[2];
+
+        let vulnerabilities = VulnerabilityInfo::new(vec![Vulnerability::new(&advisory, base)]);
+        let mut warnings = WarningInfo::default();
+        warnings.insert(
+            WarningKind::Yanked,
+            vec![crate::Warning::new(
+                WarningKind::Yanked,
+                deep,
+                None,
+                None,
+                None,
+            )],
+        );
+
+        let summary = Summary::new(
+            &vulnerabilities,
+            &warnings,
+            &lockfile,
+            &CvssSelection::default(),
+        );
+        assert_eq!(summary.severity[&Severity::Critical], 1);
+        assert_eq!(summary.unscored, 0);
+        assert_eq!(summary.categories[&Category::CodeExecution], 1);
+        assert_eq!(summary.categories[&Category::PrivilegeEscalation], 1);
+        assert_eq!(summary.warnings[&WarningKind::Yanked], 1);
+        assert_eq!(summary.direct, 1);
+        assert_eq!(summary.transitive, 1);
+    }
+}
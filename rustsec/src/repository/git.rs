@@ -2,13 +2,23 @@
 
 mod commit;
 mod commit_hash;
+mod fetch_options;
 #[cfg(feature = "osv-export")]
 mod gitpath;
 #[cfg(feature = "osv-export")]
 mod modification_time;
+mod progress;
 mod repository;
+mod system_git;
+#[cfg(test)]
+pub(crate) mod testing;
 
-pub use self::{commit::Commit, commit_hash::CommitHash, repository::Repository};
+pub use self::{
+    commit::Commit,
+    commit_hash::CommitHash,
+    fetch_options::{Backend, FetchOptions},
+    repository::Repository,
+};
 use tame_index::external::gix;
 
 #[cfg(feature = "osv-export")]
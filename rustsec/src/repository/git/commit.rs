@@ -4,6 +4,7 @@ use tame_index::external::gix;
 
 use crate::{
     error::{Error, ErrorKind},
+    fs,
     repository::{
         git::{CommitHash, Repository},
         signature::Signature,
@@ -47,6 +48,11 @@ impl Commit {
             .head_commit()
             .map_err(|err| format_err!(ErrorKind::Repo, "unable to locate head commit: {}", err))?;
 
+        Self::from_gix(&commit)
+    }
+
+    /// Get information about the given commit
+    pub(crate) fn from_gix(commit: &gix::Commit<'_>) -> Result<Self, Error> {
         // Since we are pulling multiple pieces from the commit it's better to do this once
         let cref = commit.decode().map_err(|err| {
             format_err!(
@@ -145,6 +151,51 @@ impl Commit {
                     )
                 })?;
 
+        // Remove the files of the previous checkout first, so files which
+        // aren't part of this commit are deleted, and files which are get
+        // rewritten from scratch (checking out over an existing file doesn't
+        // truncate it). Files which were modified since they were checked
+        // out are left alone, and the checkout is refused instead.
+        let previous_index = repo
+            .index_or_empty()
+            .map_err(|err| format_err!(ErrorKind::Repo, "failed to open index: {}", err))?;
+
+        let mut unmodified = Vec::new();
+        for entry in previous_index.entries() {
+            let relative_path = gix::path::from_bstr(entry.path(&previous_index));
+            let path = workdir.join(&relative_path);
+
+            if entry.mode.is_submodule() {
+                continue;
+            }
+
+            // The file may already have been removed from the worktree
+            let contents = match std::fs::symlink_metadata(&path) {
+                Ok(metadata) if metadata.file_type().is_symlink() => {
+                    let target = fs::read_link(&path)?;
+                    gix::path::into_bstr(target).into_owned().into()
+                }
+                Ok(_) => fs::read(&path)?,
+                Err(_) => continue,
+            };
+
+            let id = gix::objs::compute_hash(repo.object_hash(), gix::objs::Kind::Blob, &contents);
+            if id != entry.id {
+                fail!(
+                    ErrorKind::Repo,
+                    "unable to checkout {}: '{}' has local modifications",
+                    self.commit_id,
+                    relative_path.display()
+                );
+            }
+
+            unmodified.push(path);
+        }
+
+        for path in unmodified {
+            fs::remove_file(path)?;
+        }
+
         let mut index = gix::index::File::from_state(index, repo.index_path());
 
         let opts = gix::worktree::state::checkout::Options {
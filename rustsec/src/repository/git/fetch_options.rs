@@ -0,0 +1,142 @@
+//! Options for fetching advisory database repositories
+
+use crate::{progress::Progress, retry::RetryPolicy};
+use std::{fmt, sync::Arc, time::Duration};
+
+/// Default amount of time to wait for the filesystem lock on a repository
+pub(super) const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Options for [`Repository::fetch_with_options`](super::Repository::fetch_with_options)
+#[cfg_attr(docsrs, doc(cfg(feature = "git")))]
+#[derive(Clone)]
+pub struct FetchOptions {
+    /// Fail if the latest commit to the repository is stale
+    pub(super) ensure_fresh: bool,
+
+    /// How long to wait for the filesystem lock on the repository
+    pub(super) lock_timeout: Duration,
+
+    /// Implementation used to perform git operations
+    pub(super) backend: Backend,
+
+    /// Branch, tag, or commit to check out instead of the default branch
+    pub(super) revision: Option<String>,
+
+    /// Observer notified of the progress of the fetch
+    pub(super) progress: Arc<dyn Progress>,
+
+    /// Policy for retrying fetches which fail due to network errors
+    pub(super) retry: RetryPolicy,
+
+    /// Open the existing checkout if fetching fails due to network errors
+    pub(super) offline_fallback: bool,
+}
+
+impl FetchOptions {
+    /// Create the default fetch options: ensure the repository is fresh, wait
+    /// for up to 5 minutes for the filesystem lock, use the [`Backend::Gix`]
+    /// backend, and retry network errors with the default [`RetryPolicy`]
+    pub fn new() -> Self {
+        Self {
+            ensure_fresh: true,
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+            backend: Backend::default(),
+            revision: None,
+            progress: Arc::new(()),
+            retry: RetryPolicy::default(),
+            offline_fallback: false,
+        }
+    }
+
+    /// Fail if the repository's latest commit is stale
+    pub fn ensure_fresh(mut self, ensure_fresh: bool) -> Self {
+        self.ensure_fresh = ensure_fresh;
+        self
+    }
+
+    /// Set how long to wait for the filesystem lock on the repository.
+    ///
+    /// If set to `Duration::from_secs(0)`, fetching fails immediately if the
+    /// lock can't be acquired.
+    pub fn lock_timeout(mut self, lock_timeout: Duration) -> Self {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+
+    /// Set the implementation used to perform git operations
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Pin the repository to the given branch or tag name, or commit hash,
+    /// instead of the tip of the default branch.
+    ///
+    /// See [`Repository::checkout_revision`](super::Repository::checkout_revision).
+    pub fn revision(mut self, revision: impl Into<String>) -> Self {
+        self.revision = Some(revision.into());
+        self
+    }
+
+    /// Notify the given observer of the progress of the fetch.
+    ///
+    /// Progress is only reported by the [`Backend::Gix`] backend.
+    pub fn progress(mut self, progress: Arc<dyn Progress>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Set the policy for retrying fetches which fail due to network errors,
+    /// e.g. [`RetryPolicy::none`] to fail on the first one
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Fall back to the existing checkout of the repository, if there is one,
+    /// when fetching it still fails due to network errors after retrying.
+    ///
+    /// The error is then available from
+    /// [`Repository::fetch_error`](super::Repository::fetch_error), and
+    /// databases loaded from the repository are reported as stale (see
+    /// [`Database::is_stale_cache`](crate::Database::is_stale_cache)).
+    pub fn offline_fallback(mut self, offline_fallback: bool) -> Self {
+        self.offline_fallback = offline_fallback;
+        self
+    }
+}
+
+impl fmt::Debug for FetchOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FetchOptions")
+            .field("ensure_fresh", &self.ensure_fresh)
+            .field("lock_timeout", &self.lock_timeout)
+            .field("backend", &self.backend)
+            .field("revision", &self.revision)
+            .field("retry", &self.retry)
+            .field("offline_fallback", &self.offline_fallback)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Implementations which can be used to clone and fetch repositories
+#[cfg_attr(docsrs, doc(cfg(feature = "git")))]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Backend {
+    /// Use the built-in `gix` implementation
+    #[default]
+    Gix,
+
+    /// Shell out to the `git` binary on `PATH`.
+    ///
+    /// This picks up the system's git configuration, such as credential
+    /// helpers and proxy settings, exactly as `git` itself would.
+    SystemGit,
+}
@@ -0,0 +1,241 @@
+//! Reporting the progress of `gix` fetches to a [`Progress`] observer
+
+use tame_index::external::gix::{
+    self,
+    progress::{
+        prodash::messages::MessageLevel, Count, Id, NestedProgress, Step, StepShared, Unit,
+    },
+};
+
+use crate::progress::Progress;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+/// Progress ID `gix` uses for the bytes of a pack being received
+const READ_PACK_BYTES: Id = *b"BWRB";
+
+/// Progress ID `gix` uses for the objects resolved while indexing a pack
+const RESOLVE_OBJECTS: Id = *b"IWRO";
+
+/// How often the observer is notified of changes
+const INTERVAL: Duration = Duration::from_millis(100);
+
+/// Run `f` with a `gix` progress tree, notifying `observer` of the progress
+/// of the tasks it's interested in while `f` runs.
+///
+/// `gix` updates some counters directly rather than through the progress
+/// tree, so they're polled on a separate thread.
+pub(super) fn observe<T>(observer: &dyn Progress, f: impl FnOnce(&mut GixProgress) -> T) -> T {
+    let mut root = GixProgress::new(Id::default(), Arc::default());
+    let tasks = root.tasks.clone();
+    let done = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let reporter = scope.spawn(|| {
+            let mut last = Snapshot::default();
+            loop {
+                let finished = done.load(Ordering::Acquire);
+                last = Snapshot::take(&tasks).report(&last, observer);
+                if finished {
+                    break;
+                }
+                thread::park_timeout(INTERVAL);
+            }
+        });
+
+        let result = f(&mut root);
+        done.store(true, Ordering::Release);
+        reporter.thread().unpark();
+        result
+    })
+}
+
+/// Task in the `gix` progress tree which is reported to the observer
+struct Task {
+    /// Kind of task
+    id: Id,
+
+    /// Progress of the task so far
+    counter: StepShared,
+
+    /// Expected total progress of the task (if known)
+    max: Mutex<Option<Step>>,
+}
+
+/// Node of a `gix` progress tree keeping track of the tasks to report
+pub(super) struct GixProgress {
+    /// Task this node tracks the progress of
+    task: Arc<Task>,
+
+    /// Reported tasks in the whole tree
+    tasks: Arc<Mutex<Vec<Arc<Task>>>>,
+}
+
+impl GixProgress {
+    /// Create a node tracking a task with the given ID, registering it in
+    /// `tasks` if it's reported
+    fn new(id: Id, tasks: Arc<Mutex<Vec<Arc<Task>>>>) -> Self {
+        let task = Arc::new(Task {
+            id,
+            counter: Arc::new(AtomicUsize::new(0)),
+            max: Mutex::new(None),
+        });
+
+        if id == READ_PACK_BYTES || id == RESOLVE_OBJECTS {
+            tasks.lock().unwrap().push(task.clone());
+        }
+
+        Self { task, tasks }
+    }
+}
+
+impl Count for GixProgress {
+    fn set(&self, step: Step) {
+        self.task.counter.store(step, Ordering::Relaxed);
+    }
+
+    fn step(&self) -> Step {
+        self.task.counter.load(Ordering::Relaxed)
+    }
+
+    fn inc_by(&self, step: Step) {
+        self.task.counter.fetch_add(step, Ordering::Relaxed);
+    }
+
+    fn counter(&self) -> StepShared {
+        self.task.counter.clone()
+    }
+}
+
+impl gix::progress::Progress for GixProgress {
+    fn init(&mut self, max: Option<Step>, _unit: Option<Unit>) {
+        self.set_max(max);
+    }
+
+    fn max(&self) -> Option<Step> {
+        *self.task.max.lock().unwrap()
+    }
+
+    fn set_max(&mut self, max: Option<Step>) -> Option<Step> {
+        std::mem::replace(&mut self.task.max.lock().unwrap(), max)
+    }
+
+    fn set_name(&mut self, _name: String) {}
+
+    fn name(&self) -> Option<String> {
+        None
+    }
+
+    fn id(&self) -> Id {
+        self.task.id
+    }
+
+    fn message(&self, _level: MessageLevel, _message: String) {}
+}
+
+impl NestedProgress for GixProgress {
+    type SubProgress = Self;
+
+    fn add_child(&mut self, _name: impl Into<String>) -> Self {
+        Self::new(Id::default(), self.tasks.clone())
+    }
+
+    fn add_child_with_id(&mut self, _name: impl Into<String>, id: Id) -> Self {
+        Self::new(id, self.tasks.clone())
+    }
+}
+
+/// Progress of the reported tasks at a point in time
+#[derive(Default, PartialEq)]
+struct Snapshot {
+    /// Bytes received
+    bytes: usize,
+
+    /// Objects resolved, out of the total if known
+    objects: (usize, Option<usize>),
+}
+
+impl Snapshot {
+    /// Take a snapshot of the progress of the given tasks
+    fn take(tasks: &Mutex<Vec<Arc<Task>>>) -> Self {
+        let mut snapshot = Self::default();
+
+        for task in tasks.lock().unwrap().iter() {
+            let step = task.counter.load(Ordering::Relaxed);
+            match task.id {
+                READ_PACK_BYTES => snapshot.bytes += step,
+                RESOLVE_OBJECTS => snapshot.objects = (step, *task.max.lock().unwrap()),
+                _ => (),
+            }
+        }
+
+        snapshot
+    }
+
+    /// Notify the observer of what changed since the `last` snapshot
+    fn report(self, last: &Self, observer: &dyn Progress) -> Self {
+        if self.bytes != last.bytes {
+            observer.bytes_received(self.bytes);
+        }
+
+        if self.objects != last.objects {
+            observer.objects_resolved(self.objects.0, self.objects.1);
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tame_index::external::gix::progress::Progress as _;
+
+    /// Records the progress it's notified of
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl Progress for Recorder {
+        fn bytes_received(&self, bytes: usize) {
+            self.0.lock().unwrap().push(format!("{} bytes", bytes));
+        }
+
+        fn objects_resolved(&self, resolved: usize, total: Option<usize>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{}/{:?} objects", resolved, total));
+        }
+    }
+
+    #[test]
+    fn observe_reports_tracked_tasks() {
+        let recorder = Recorder::default();
+
+        observe(&recorder, |root| {
+            let read = root.add_child_with_id("read pack", READ_PACK_BYTES);
+            read.inc_by(1024);
+            read.inc_by(1024);
+
+            let mut index = root.add_child("index");
+            let mut resolve = index.add_child_with_id("resolving", RESOLVE_OBJECTS);
+            resolve.init(Some(3), None);
+            // `gix` updates some counters directly
+            resolve.counter().fetch_add(3, Ordering::Relaxed);
+
+            let other = root.add_child_with_id("checkout", *b"CHCK");
+            other.inc_by(42);
+        });
+
+        let events = recorder.0.into_inner().unwrap();
+        assert_eq!(events.last().map(String::as_str), Some("3/Some(3) objects"));
+        assert!(events.contains(&"2048 bytes".to_owned()), "{:?}", events);
+        assert!(events.iter().all(|event| !event.contains("42")));
+    }
+}
@@ -1,12 +1,21 @@
 //! Git repositories
-use tame_index::{external::gix, utils::flock::LockOptions};
+use tame_index::{
+    external::gix,
+    utils::flock::{FileLock, LockOptions},
+};
 
-use super::{Commit, DEFAULT_URL};
+use super::{
+    fetch_options::DEFAULT_LOCK_TIMEOUT,
+    progress::{self, GixProgress},
+    system_git, Backend, Commit, FetchOptions, DEFAULT_URL,
+};
 use crate::{
+    advisory::Date,
     error::{Error, ErrorKind},
     fs,
 };
 use std::{
+    io,
     path::{Path, PathBuf},
     time::Duration,
 };
@@ -15,18 +24,30 @@ use std::{
 const ADVISORY_DB_DIRECTORY: &str = "advisory-db";
 
 /// Refspec used to fetch updates from remote advisory databases
-const REF_SPEC: &str = "+HEAD:refs/remotes/origin/HEAD";
+pub(super) const REF_SPEC: &str = "+HEAD:refs/remotes/origin/HEAD";
+
+/// Additional refspecs used to fetch all branches and tags when the
+/// repository is pinned to a revision
+pub(super) const PINNED_REF_SPECS: &[&str] = &[
+    "+refs/heads/*:refs/remotes/origin/*",
+    "+refs/tags/*:refs/tags/*",
+];
 
 /// The direction of the remote
 const DIR: gix::remote::Direction = gix::remote::Direction::Fetch;
 
-const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(5 * 60);
-
 /// Git repository for a Rust advisory DB.
 #[cfg_attr(docsrs, doc(cfg(feature = "git")))]
 pub struct Repository {
     /// Repository object
     pub(super) repo: gix::Repository,
+
+    /// Revision the repository was pinned to, if any
+    revision: Option<String>,
+
+    /// Error fetching the repository failed with, if its existing checkout
+    /// was opened instead
+    fetch_error: Option<Error>,
 }
 
 impl Repository {
@@ -39,6 +60,49 @@ impl Repository {
             .join(ADVISORY_DB_DIRECTORY)
     }
 
+    /// Location of the repository for the advisory database at the given
+    /// URL, so that databases from different sources don't share a checkout.
+    ///
+    /// This is [`Repository::default_path`] for [`DEFAULT_URL`], and a
+    /// sibling directory named after the URL otherwise, e.g.
+    /// `~/.cargo/advisory-db-example.com-org-db` for
+    /// `https://example.com/org/db.git`.
+    pub fn default_path_for(url: &str) -> PathBuf {
+        let default_path = Self::default_path();
+        let url = normalize_url(url);
+        if url == normalize_url(DEFAULT_URL) {
+            return default_path;
+        }
+
+        let source = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let slug: String = source
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '_' | '-' => c,
+                _ => '-',
+            })
+            .collect();
+
+        default_path.with_file_name(format!("{}-{}", ADVISORY_DB_DIRECTORY, slug))
+    }
+
+    /// Move an existing checkout of the advisory database at the given URL
+    /// from [`Repository::default_path`], where the databases from all URLs
+    /// used to be kept, to [`Repository::default_path_for`] the URL, and
+    /// return that path.
+    ///
+    /// Nothing is moved if there already is a checkout at the new path, or if
+    /// the one at the old path is of another database (e.g. the default one).
+    ///
+    /// ## Locking
+    /// This function will wait for up to 5 minutes for the filesystem lock on
+    /// the old checkout, like [`Repository::fetch_default_repo`].
+    pub fn migrate_default_path(url: &str) -> Result<PathBuf, Error> {
+        let path = Self::default_path_for(url);
+        move_checkout(url, &Self::default_path(), &path)?;
+        Ok(path)
+    }
+
     /// Fetch the default repository.
     ///
     /// ## Locking
@@ -70,10 +134,27 @@ impl Repository {
         ensure_fresh: bool,
         lock_timeout: Duration,
     ) -> Result<Self, Error> {
-        if !url.starts_with("https://") {
+        let options = FetchOptions::new()
+            .ensure_fresh(ensure_fresh)
+            .lock_timeout(lock_timeout);
+
+        Self::fetch_with_options(url, into_path, &options)
+    }
+
+    /// Create a new [`Repository`] with the given URL and path, and fetch its
+    /// contents using the given [`FetchOptions`].
+    ///
+    /// See [`Repository::fetch`] for details about locking.
+    pub fn fetch_with_options<P: Into<PathBuf>>(
+        url: &str,
+        into_path: P,
+        options: &FetchOptions,
+    ) -> Result<Self, Error> {
+        // Local repositories can't be tampered with in transit
+        if !url.starts_with("https://") && !url.starts_with("file://") {
             fail!(
                 ErrorKind::BadParam,
-                "expected {} to start with https://",
+                "expected {} to start with https:// or file://",
                 url
             );
         }
@@ -96,26 +177,78 @@ impl Repository {
             fs::remove_dir(&path)?;
         }
 
-        // Lock the directory to avoid several checkouts running at the same time trampling on each other.
-        // We do not use Git locks because they have undesirable properties - they leave stale locks on SIGKILL or power loss
-        // with no way to recover. They don't even write the PID to the lockfile.
-        let lock_path = tame_index::Path::from_path(&path)
-            .ok_or_else(|| {
-                format_err!(
-                    ErrorKind::BadParam,
-                    "Path to the advisory DB directory is not valid UTF-8!"
-                )
-            })?
-            .with_extension(".lock");
-        let lock_opts = LockOptions::new(&lock_path).exclusive(false);
-        let _lock = if lock_timeout == Duration::from_secs(0) {
-            lock_opts.try_lock()
-        } else {
-            lock_opts.lock(|_| Some(lock_timeout))
+        let _lock = lock(&path, options.lock_timeout)?;
+
+        let mut refspecs = vec![REF_SPEC];
+        if options.revision.is_some() {
+            refspecs.extend_from_slice(PINNED_REF_SPECS);
         }
-        .map_err(Error::from_tame)?;
 
-        let open_or_clone_repo = || -> Result<_, Error> {
+        let fetched = options.retry.run(|| match options.backend {
+            Backend::Gix => progress::observe(&*options.progress, |progress| {
+                Self::fetch_with_gix(url, &path, &refspecs, progress)
+            }),
+            Backend::SystemGit => {
+                system_git::fetch(url, &path, options.revision.is_some())?;
+                gix::open(&path).map_err(|err| {
+                    format_err!(
+                        ErrorKind::Repo,
+                        "failed to open repository at '{}': {}",
+                        path.display(),
+                        err
+                    )
+                })
+            }
+        });
+
+        // Fall back to the existing checkout, if any, when the network is unavailable
+        let (mut repo, fetch_error) = match fetched {
+            Ok(repo) => (repo, None),
+            Err(err) if options.offline_fallback && err.kind() == ErrorKind::Network => {
+                match gix::open(&path) {
+                    Ok(repo) => (repo, Some(err)),
+                    Err(_) => return Err(err),
+                }
+            }
+            Err(err) => return Err(err),
+        };
+
+        repo.object_cache_size_if_unset(4 * 1024 * 1024);
+        let mut repo = Self {
+            repo,
+            revision: None,
+            fetch_error,
+        };
+
+        let latest_commit = match &options.revision {
+            Some(revision) => repo.checkout_revision(revision)?,
+            None => {
+                let latest_commit = Commit::from_repo_head(&repo)?;
+                latest_commit.reset(&repo)?;
+                latest_commit
+            }
+        };
+
+        // Ensure that the upstream repository hasn't gone stale
+        if options.ensure_fresh && !latest_commit.is_fresh() {
+            fail!(
+                ErrorKind::Repo,
+                "repository is stale (last commit: {:?})",
+                latest_commit.timestamp
+            );
+        }
+
+        Ok(repo)
+    }
+
+    /// Clone or fetch the repository using `gix`
+    fn fetch_with_gix(
+        url: &str,
+        path: &Path,
+        refspecs: &[&str],
+        fetch_progress: &mut GixProgress,
+    ) -> Result<gix::Repository, Error> {
+        let mut open_or_clone_repo = || -> Result<_, Error> {
             let mut mapping = gix::sec::trust::Mapping::default();
             let open_with_complete_config =
                 gix::open::Options::default().permissions(gix::open::Permissions {
@@ -134,7 +267,7 @@ impl Repository {
             // Attempt to open the repository, if it fails for any reason,
             // attempt to perform a fresh clone instead
             let repo = gix::ThreadSafeRepository::discover_opts(
-                &path,
+                path,
                 gix::discover::upwards::Options::default().apply_environment(),
                 mapping,
             )
@@ -147,7 +280,7 @@ impl Repository {
                         .map_or(false, |remote_url| remote_url.to_bstring() == url)
                 })
             })
-            .or_else(|| gix::open_opts(&path, open_with_complete_config).ok());
+            .or_else(|| gix::open_opts(path, open_with_complete_config).ok());
 
             let res = if let Some(repo) = repo {
                 (repo, None)
@@ -161,9 +294,21 @@ impl Repository {
                     })?
                     .with_remote_name("origin")
                     .map_err(|err| format_err!(ErrorKind::Repo, "invalid remote name: {}", err))?
-                    .configure_remote(|remote| Ok(remote.with_refspecs([REF_SPEC], DIR)?))
-                    .fetch_then_checkout(&mut progress, should_interrupt)
-                    .map_err(|err| format_err!(ErrorKind::Repo, "failed to fetch repo: {}", err))?;
+                    .configure_remote({
+                        let refspecs: Vec<String> =
+                            refspecs.iter().map(|spec| spec.to_string()).collect();
+                        move |remote| {
+                            Ok(remote.with_refspecs(refspecs.iter().map(String::as_str), DIR)?)
+                        }
+                    })
+                    .fetch_then_checkout(&mut *fetch_progress, should_interrupt)
+                    .map_err(|err| {
+                        Error::with_source(
+                            ErrorKind::Network,
+                            "failed to fetch repo".to_owned(),
+                            err,
+                        )
+                    })?;
 
                 let repo = prep_checkout
                     .main_worktree(&mut progress, should_interrupt)
@@ -191,25 +336,31 @@ impl Repository {
             // If we didn't open a fresh repo we need to peform a fetch ourselves, and
             // do the work of updating the HEAD to point at the latest remote HEAD, which
             // gix doesn't currently do.
-            Self::perform_fetch(&mut repo)?;
+            Self::perform_fetch(&mut repo, refspecs, fetch_progress)?;
         }
 
-        repo.object_cache_size_if_unset(4 * 1024 * 1024);
-        let repo = Self { repo };
-
-        let latest_commit = Commit::from_repo_head(&repo)?;
-        latest_commit.reset(&repo)?;
+        Ok(repo)
+    }
 
-        // Ensure that the upstream repository hasn't gone stale
-        if ensure_fresh && !latest_commit.is_fresh() {
-            fail!(
-                ErrorKind::Repo,
-                "repository is stale (last commit: {:?})",
-                latest_commit.timestamp
-            );
-        }
+    /// Async variant of [`Repository::fetch_with_options`], which performs
+    /// the fetch on tokio's blocking thread pool.
+    ///
+    /// Must be called from within a tokio runtime.
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub async fn fetch_with_options_async<P: Into<PathBuf>>(
+        url: &str,
+        into_path: P,
+        options: &FetchOptions,
+    ) -> Result<Self, Error> {
+        let url = url.to_owned();
+        let path = into_path.into();
+        let options = options.clone();
 
-        Ok(repo)
+        crate::task::spawn_blocking(ErrorKind::Repo, move || {
+            Self::fetch_with_options(&url, path, &options)
+        })
+        .await
     }
 
     /// Open a repository at the given path
@@ -226,7 +377,251 @@ impl Repository {
 
         // TODO: Figure out how to detect if the worktree has modifications
         // as gix currently doesn't have a status/state summary like git2 has
-        Ok(Self { repo })
+        Ok(Self {
+            repo,
+            revision: None,
+            fetch_error: None,
+        })
+    }
+
+    /// Check out the given revision, which may be a branch or tag name, or a
+    /// commit hash, and return information about the commit it points to.
+    ///
+    /// `HEAD` is detached at that commit, so [`Repository::latest_commit`]
+    /// refers to it afterwards. Branches are resolved against the `origin`
+    /// remote, whose branches and tags must have been fetched beforehand
+    /// (see [`FetchOptions::revision`]).
+    pub fn checkout_revision(&mut self, revision: &str) -> Result<Commit, Error> {
+        let commit_id = self.resolve_revision(revision)?;
+
+        use gix::refs::{transaction as tx, Target};
+
+        let repo = set_committer(&mut self.repo)?;
+        repo.edit_reference(tx::RefEdit {
+            change: tx::Change::Update {
+                log: tx::LogChange {
+                    mode: tx::RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: format!("checkout: {}", revision).into(),
+                },
+                expected: tx::PreviousValue::Any,
+                new: Target::Peeled(commit_id),
+            },
+            name: "HEAD".try_into().unwrap(),
+            deref: false,
+        })
+        .map_err(|err| format_err!(ErrorKind::Repo, "failed to detach HEAD: {}", err))?;
+        drop(repo);
+
+        let commit = Commit::from_repo_head(self)?;
+        commit.reset(self)?;
+        self.revision = Some(revision.to_owned());
+        Ok(commit)
+    }
+
+    /// Read the files at the given paths (relative to the root of the
+    /// repository) as they were at the given revision, which is resolved like
+    /// in [`Repository::checkout_revision`].
+    ///
+    /// Files which didn't exist at that revision are returned as `None`.
+    pub fn read_files_at_revision(
+        &self,
+        revision: &str,
+        paths: &[PathBuf],
+    ) -> Result<Vec<Option<Vec<u8>>>, Error> {
+        let tree = self
+            .repo
+            .find_object(self.resolve_revision(revision)?)
+            .map_err(|err| format_err!(ErrorKind::Repo, "unable to locate commit: {}", err))?
+            .peel_to_tree()
+            .map_err(|err| format_err!(ErrorKind::Repo, "unable to peel to tree: {}", err))?;
+
+        let mut buf = Vec::new();
+        paths
+            .iter()
+            .map(|path| {
+                let entry = tree.lookup_entry_by_path(path, &mut buf).map_err(|err| {
+                    format_err!(
+                        ErrorKind::Repo,
+                        "unable to look up '{}': {}",
+                        path.display(),
+                        err
+                    )
+                })?;
+
+                entry
+                    .map(|entry| {
+                        entry
+                            .object()
+                            .map(|blob| blob.detach().data)
+                            .map_err(|err| {
+                                format_err!(
+                                    ErrorKind::Repo,
+                                    "unable to read '{}': {}",
+                                    path.display(),
+                                    err
+                                )
+                            })
+                    })
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// Read every file under the given directory (relative to the root of the
+    /// repository) as it was at the given revision, which is resolved like in
+    /// [`Repository::checkout_revision`], without touching the working tree.
+    ///
+    /// Returns the paths of the files relative to the root of the repository
+    /// along with their contents, which is empty if the directory didn't
+    /// exist at that revision.
+    pub fn read_dir_at_revision(
+        &self,
+        revision: &str,
+        dir: &Path,
+    ) -> Result<Vec<(PathBuf, Vec<u8>)>, Error> {
+        let tree = self
+            .repo
+            .find_object(self.resolve_revision(revision)?)
+            .map_err(|err| format_err!(ErrorKind::Repo, "unable to locate commit: {}", err))?
+            .peel_to_tree()
+            .map_err(|err| format_err!(ErrorKind::Repo, "unable to peel to tree: {}", err))?;
+
+        let mut buf = Vec::new();
+        let entry = match tree.lookup_entry_by_path(dir, &mut buf).map_err(|err| {
+            format_err!(
+                ErrorKind::Repo,
+                "unable to look up '{}': {}",
+                dir.display(),
+                err
+            )
+        })? {
+            Some(entry) if entry.mode().is_tree() => entry,
+            _ => return Ok(Vec::new()),
+        };
+
+        let subtree = entry
+            .object()
+            .map_err(|err| format_err!(ErrorKind::Repo, "unable to read tree: {}", err))?
+            .into_tree();
+
+        let mut recorder = gix::traverse::tree::Recorder::default();
+        subtree
+            .traverse()
+            .breadthfirst(&mut recorder)
+            .map_err(|err| format_err!(ErrorKind::Repo, "unable to traverse tree: {}", err))?;
+
+        recorder
+            .records
+            .into_iter()
+            .filter(|entry| entry.mode.is_blob())
+            .map(|entry| {
+                let path = dir.join(gix::path::from_bstr(entry.filepath.as_ref()));
+                let data = self
+                    .repo
+                    .find_object(entry.oid)
+                    .map_err(|err| {
+                        format_err!(
+                            ErrorKind::Repo,
+                            "unable to read '{}': {}",
+                            path.display(),
+                            err
+                        )
+                    })?
+                    .detach()
+                    .data;
+                Ok((path, data))
+            })
+            .collect()
+    }
+
+    /// Get the latest commit made on or before the given date (in UTC) to
+    /// the history of `HEAD`, i.e. the commit `HEAD` pointed to at the end of
+    /// that day
+    pub fn commit_at_date(&self, date: &Date) -> Result<Commit, Error> {
+        let end_of_day = (date.days_since_epoch() + 1) * 86_400;
+
+        let head_id = self
+            .repo
+            .head_id()
+            .map_err(|err| format_err!(ErrorKind::Repo, "unable to find head id: {}", err))?;
+        let walk = self
+            .repo
+            .rev_walk(Some(head_id))
+            .sorting(gix::traverse::commit::simple::Sorting::ByCommitTimeNewestFirst)
+            .all()
+            .map_err(|err| format_err!(ErrorKind::Repo, "unable to walk commits: {}", err))?;
+
+        for info in walk {
+            let info = info.map_err(|err| {
+                format_err!(ErrorKind::Repo, "failed to retrieve commit info: {}", err)
+            })?;
+            let commit = info
+                .object()
+                .map_err(|err| format_err!(ErrorKind::Repo, "unable to find commit: {}", err))?;
+            let time = commit
+                .time()
+                .map_err(|err| format_err!(ErrorKind::Repo, "unable to decode commit: {}", err))?;
+
+            if time.seconds < end_of_day {
+                return Commit::from_gix(&commit);
+            }
+        }
+
+        fail!(ErrorKind::Repo, "no commits on or before {}", date)
+    }
+
+    /// Get information about the commit the given revision points to, which
+    /// is resolved like in [`Repository::checkout_revision`]
+    pub fn commit_at_revision(&self, revision: &str) -> Result<Commit, Error> {
+        let commit = self
+            .repo
+            .find_object(self.resolve_revision(revision)?)
+            .map_err(|err| format_err!(ErrorKind::Repo, "unable to locate commit: {}", err))?
+            .into_commit();
+
+        Commit::from_gix(&commit)
+    }
+
+    /// Resolve a branch or tag name, or a commit hash, to the ID of the commit
+    /// it points to
+    fn resolve_revision(&self, revision: &str) -> Result<gix::ObjectId, Error> {
+        let candidates = [
+            revision.to_owned(),
+            format!("refs/remotes/origin/{}", revision),
+            format!("refs/tags/{}", revision),
+        ];
+
+        let commit_id = candidates
+            .iter()
+            .find_map(|spec| self.repo.rev_parse_single(spec.as_str()).ok())
+            .ok_or_else(|| format_err!(ErrorKind::Repo, "unable to find revision '{}'", revision))?
+            .object()
+            .map_err(|err| format_err!(ErrorKind::Repo, "unable to locate revision: {}", err))?
+            .peel_to_kind(gix::object::Kind::Commit)
+            .map_err(|err| {
+                format_err!(
+                    ErrorKind::Repo,
+                    "revision '{}' is not a commit: {}",
+                    revision,
+                    err
+                )
+            })?
+            .id;
+
+        Ok(commit_id)
+    }
+
+    /// Revision this repository was pinned to with
+    /// [`Repository::checkout_revision`], if any
+    pub fn revision(&self) -> Option<&str> {
+        self.revision.as_deref()
+    }
+
+    /// Error fetching this repository failed with, if its existing checkout
+    /// was opened instead (see [`FetchOptions::offline_fallback`])
+    pub fn fetch_error(&self) -> Option<&Error> {
+        self.fetch_error.as_ref()
     }
 
     /// Get information about the latest commit to the repo
@@ -240,6 +635,46 @@ impl Repository {
         self.repo.work_dir().unwrap()
     }
 
+    /// Total size in bytes of the files of the repository on disk, including
+    /// its git objects
+    pub fn cache_size(&self) -> Result<u64, Error> {
+        dir_size(self.path()).map_err(|err| {
+            Error::with_source(
+                ErrorKind::Io,
+                format!("couldn't measure {}", self.path().display()),
+                err,
+            )
+        })
+    }
+
+    /// Compact the repository on disk by packing its objects and pruning the
+    /// ones which are no longer reachable, e.g. after many fetches.
+    ///
+    /// This uses the `git` binary on `PATH`, as `gix` can't do this yet.
+    ///
+    /// ## Locking
+    /// This function will wait for up to 5 minutes for the filesystem lock on
+    /// the repository, like [`Repository::fetch_default_repo`].
+    pub fn gc(&self) -> Result<(), Error> {
+        let _lock = lock(self.path(), DEFAULT_LOCK_TIMEOUT)?;
+        system_git::gc(self.path())
+    }
+
+    /// Delete the repository from disk, e.g. to free up space or recover
+    /// from a corrupted checkout. It's cloned again by the next fetch.
+    ///
+    /// ## Locking
+    /// This function will wait for up to 5 minutes for the filesystem lock on
+    /// the repository, like [`Repository::fetch_default_repo`].
+    pub fn purge(self) -> Result<(), Error> {
+        let path = self.path().to_owned();
+        let _lock = lock(&path, DEFAULT_LOCK_TIMEOUT)?;
+        drop(self);
+
+        fs::remove_dir_all(&path)?;
+        Ok(())
+    }
+
     /// Determines if the tree pointed to by `HEAD` contains the specified path
     pub fn has_relative_path(&self, path: &Path) -> bool {
         let lookup = || {
@@ -256,41 +691,43 @@ impl Repository {
         lookup().unwrap_or_default()
     }
 
-    fn perform_fetch(repo: &mut gix::Repository) -> Result<(), Error> {
-        let mut config = repo.config_snapshot_mut();
-        config
-            .set_raw_value("committer", None, "name", "rustsec")
-            .map_err(|err| {
-                format_err!(ErrorKind::Repo, "failed to set `committer.name`: {}", err)
-            })?;
-        // Note we _have_ to set the email as well, but luckily gix does not actually
-        // validate if it's a proper email or not :)
-        config
-            .set_raw_value("committer", None, "email", "")
-            .map_err(|err| {
-                format_err!(ErrorKind::Repo, "failed to set `committer.email`: {}", err)
-            })?;
-
-        let repo = config
-            .commit_auto_rollback()
-            .map_err(|err| format_err!(ErrorKind::Repo, "failed to set `committer`: {}", err))?;
+    fn perform_fetch(
+        repo: &mut gix::Repository,
+        refspecs: &[&str],
+        progress: &mut GixProgress,
+    ) -> Result<(), Error> {
+        let repo = set_committer(repo)?;
 
         let mut remote = repo.find_remote("origin").map_err(|err| {
             format_err!(ErrorKind::Repo, "failed to find `origin` remote: {}", err)
         })?;
 
         remote
-            .replace_refspecs(Some(REF_SPEC), DIR)
+            .replace_refspecs(refspecs.iter().copied(), DIR)
             .expect("valid statically known refspec");
 
         // Perform the actual fetch
         let outcome = remote
             .connect(DIR)
-            .map_err(|err| format_err!(ErrorKind::Repo, "failed to connect to remote: {}", err))?
+            .map_err(|err| {
+                Error::with_source(
+                    ErrorKind::Network,
+                    "failed to connect to remote".to_owned(),
+                    err,
+                )
+            })?
             .prepare_fetch(&mut gix::progress::Discard, Default::default())
-            .map_err(|err| format_err!(ErrorKind::Repo, "failed to prepare fetch: {}", err))?
-            .receive(&mut gix::progress::Discard, &gix::interrupt::IS_INTERRUPTED)
-            .map_err(|err| format_err!(ErrorKind::Repo, "failed to fetch: {}", err))?;
+            .map_err(|err| {
+                Error::with_source(
+                    ErrorKind::Network,
+                    "failed to prepare fetch".to_owned(),
+                    err,
+                )
+            })?
+            .receive(progress, &gix::interrupt::IS_INTERRUPTED)
+            .map_err(|err| {
+                Error::with_source(ErrorKind::Network, "failed to fetch".to_owned(), err)
+            })?;
 
         let remote_head_id = tame_index::utils::git::write_fetch_head(&repo, &outcome, &remote)
             .map_err(Error::from_tame)?;
@@ -350,3 +787,321 @@ impl Repository {
         Ok(())
     }
 }
+
+/// Strip the trailing slash and `.git` suffix from a repository URL, so that
+/// different spellings of it compare equal
+fn normalize_url(url: &str) -> &str {
+    url.trim_end_matches('/').trim_end_matches(".git")
+}
+
+/// Move the checkout at `from` to `to` if it's a checkout of the repository
+/// at the given URL and there's nothing at `to` yet, returning whether it
+/// was moved
+fn move_checkout(url: &str, from: &Path, to: &Path) -> Result<bool, Error> {
+    if from == to || to.exists() || !from.exists() {
+        return Ok(false);
+    }
+
+    let _lock = lock(from, DEFAULT_LOCK_TIMEOUT)?;
+    let origin = match gix::open(from) {
+        Ok(repo) => repo
+            .config_snapshot()
+            .string("remote.origin.url")
+            .map(|origin| origin.to_string()),
+        // Not a checkout, so not ours to move
+        Err(_) => None,
+    };
+    if origin.as_deref().map(normalize_url) != Some(normalize_url(url)) {
+        return Ok(false);
+    }
+
+    fs::rename(from, to)?;
+    Ok(true)
+}
+
+/// Lock the repository at the given path, to avoid several checkouts running
+/// at the same time trampling on each other.
+///
+/// We do not use Git locks because they have undesirable properties - they
+/// leave stale locks on SIGKILL or power loss with no way to recover. They
+/// don't even write the PID to the lockfile.
+fn lock(path: &Path, lock_timeout: Duration) -> Result<FileLock, Error> {
+    let lock_path = tame_index::Path::from_path(path)
+        .ok_or_else(|| {
+            format_err!(
+                ErrorKind::BadParam,
+                "Path to the advisory DB directory is not valid UTF-8!"
+            )
+        })?
+        .with_extension(".lock");
+    let lock_opts = LockOptions::new(&lock_path).exclusive(false);
+    if lock_timeout == Duration::from_secs(0) {
+        lock_opts.try_lock()
+    } else {
+        lock_opts.lock(|_| Some(lock_timeout))
+    }
+    .map_err(Error::from_tame)
+}
+
+/// Total size in bytes of the files in a directory and its subdirectories
+fn dir_size(path: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        size += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+    Ok(size)
+}
+
+/// Temporarily set the committer used for the reflog when editing references
+fn set_committer(repo: &mut gix::Repository) -> Result<gix::config::CommitAutoRollback<'_>, Error> {
+    let mut config = repo.config_snapshot_mut();
+    config
+        .set_raw_value("committer", None, "name", "rustsec")
+        .map_err(|err| format_err!(ErrorKind::Repo, "failed to set `committer.name`: {}", err))?;
+    // Note we _have_ to set the email as well, but luckily gix does not actually
+    // validate if it's a proper email or not :)
+    config
+        .set_raw_value("committer", None, "email", "")
+        .map_err(|err| format_err!(ErrorKind::Repo, "failed to set `committer.email`: {}", err))?;
+
+    config
+        .commit_auto_rollback()
+        .map_err(|err| format_err!(ErrorKind::Repo, "failed to set `committer`: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::git::testing::git;
+    use crate::retry::RetryPolicy;
+
+    #[test]
+    fn checkout_revision() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path();
+        git(path, &["init", "--quiet"]);
+        fs::write(path.join("README.md"), "first").unwrap();
+        git(path, &["add", "README.md"]);
+        git(path, &["commit", "--quiet", "-m", "first"]);
+        git(path, &["tag", "v1"]);
+        fs::write(path.join("README.md"), "second").unwrap();
+        git(path, &["commit", "--quiet", "-am", "second"]);
+        git(path, &["tag", "v2"]);
+
+        let mut repo = Repository::open(path).unwrap();
+        assert_eq!(repo.revision(), None);
+
+        let commit = repo.checkout_revision("v1").unwrap();
+        assert_eq!(commit.summary, "first");
+        assert_eq!(repo.revision(), Some("v1"));
+        assert_eq!(repo.latest_commit().unwrap().commit_id, commit.commit_id);
+        assert_eq!(fs::read_to_string(path.join("README.md")).unwrap(), "first");
+
+        assert!(repo.checkout_revision("no-such-revision").is_err());
+
+        // Untracked files are kept, and local modifications aren't overwritten
+        fs::write(path.join("NOTES.md"), "untracked").unwrap();
+        repo.checkout_revision("v2").unwrap();
+        assert_eq!(
+            fs::read_to_string(path.join("README.md")).unwrap(),
+            "second"
+        );
+        assert_eq!(
+            fs::read_to_string(path.join("NOTES.md")).unwrap(),
+            "untracked"
+        );
+
+        fs::write(path.join("README.md"), "modified").unwrap();
+        assert!(repo.checkout_revision("v1").is_err());
+        assert_eq!(
+            fs::read_to_string(path.join("README.md")).unwrap(),
+            "modified"
+        );
+    }
+
+    #[test]
+    fn cache_management() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("advisory-db");
+        fs::create_dir(&path).unwrap();
+        git(&path, &["init", "--quiet"]);
+        fs::write(path.join("README.md"), "advisories").unwrap();
+        git(&path, &["add", "README.md"]);
+        git(&path, &["commit", "--quiet", "-m", "first"]);
+
+        let repo = Repository::open(&path).unwrap();
+        let size = repo.cache_size().unwrap();
+        assert!(size > "advisories".len() as u64);
+
+        repo.gc().unwrap();
+        assert_eq!(repo.latest_commit().unwrap().summary, "first");
+
+        repo.purge().unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn offline_fallback() {
+        // Nothing listens on the discard port, so fetching fails right away
+        let url = "https://127.0.0.1:9/advisory-db.git";
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("advisory-db");
+        fs::create_dir(&path).unwrap();
+        git(&path, &["init", "--quiet"]);
+        git(&path, &["remote", "add", "origin", url]);
+        fs::write(path.join("README.md"), "advisories").unwrap();
+        git(&path, &["add", "README.md"]);
+        git(&path, &["commit", "--quiet", "-m", "first"]);
+
+        let options = FetchOptions::new()
+            .ensure_fresh(false)
+            .retry(RetryPolicy::none());
+        match Repository::fetch_with_options(url, &path, &options) {
+            Err(err) => assert_eq!(err.kind(), ErrorKind::Network),
+            Ok(_) => panic!("expected fetching from an unreachable remote to fail"),
+        }
+
+        let repo =
+            Repository::fetch_with_options(url, &path, &options.offline_fallback(true)).unwrap();
+        assert_eq!(repo.fetch_error().unwrap().kind(), ErrorKind::Network);
+        assert_eq!(repo.latest_commit().unwrap().summary, "first");
+
+        let missing = dir.path().join("missing");
+        let options = FetchOptions::new()
+            .retry(RetryPolicy::none())
+            .offline_fallback(true);
+        assert!(Repository::fetch_with_options(url, missing, &options).is_err());
+    }
+
+    #[test]
+    fn fetch_with_system_git() {
+        let dir = tempfile::tempdir().unwrap();
+        let upstream = dir.path().join("upstream");
+        fs::create_dir(&upstream).unwrap();
+        git(&upstream, &["init", "--quiet"]);
+        fs::write(upstream.join("README.md"), "first").unwrap();
+        git(&upstream, &["add", "README.md"]);
+        git(&upstream, &["commit", "--quiet", "-m", "first"]);
+
+        let url = format!("file://{}", upstream.display());
+        let path = dir.path().join("advisory-db");
+        let options = FetchOptions::new().backend(Backend::SystemGit);
+
+        // Clone
+        let repo = Repository::fetch_with_options(&url, &path, &options).unwrap();
+        assert_eq!(repo.latest_commit().unwrap().summary, "first");
+
+        // Fetch into the existing clone
+        fs::write(upstream.join("README.md"), "second").unwrap();
+        git(&upstream, &["commit", "--quiet", "-am", "second"]);
+        let repo = Repository::fetch_with_options(&url, &path, &options).unwrap();
+        assert_eq!(repo.latest_commit().unwrap().summary, "second");
+        assert_eq!(
+            fs::read_to_string(path.join("README.md")).unwrap(),
+            "second"
+        );
+
+        // Local modifications aren't overwritten
+        fs::write(path.join("README.md"), "local").unwrap();
+        fs::write(upstream.join("README.md"), "third").unwrap();
+        git(&upstream, &["commit", "--quiet", "-am", "third"]);
+        match Repository::fetch_with_options(&url, &path, &options) {
+            Err(err) => {
+                assert_eq!(err.kind(), ErrorKind::Repo);
+                assert!(err
+                    .to_string()
+                    .contains("'README.md' has local modifications"));
+            }
+            Ok(_) => panic!("expected updating over local modifications to fail"),
+        }
+        assert_eq!(fs::read_to_string(path.join("README.md")).unwrap(), "local");
+    }
+
+    #[test]
+    fn move_checkout() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("advisory-db");
+        let to = dir.path().join("advisory-db-example.com-org-db");
+        fs::create_dir(&from).unwrap();
+        git(&from, &["init", "--quiet"]);
+        git(
+            &from,
+            &["remote", "add", "origin", "https://example.com/org/db.git"],
+        );
+
+        // Checkouts of other databases stay where they are
+        assert!(!super::move_checkout(DEFAULT_URL, &from, &to).unwrap());
+        assert!(from.exists());
+
+        assert!(super::move_checkout("https://example.com/org/db/", &from, &to).unwrap());
+        assert!(!from.exists());
+        assert!(Repository::open(&to).is_ok());
+
+        // Nothing left to move
+        assert!(!super::move_checkout("https://example.com/org/db", &from, &to).unwrap());
+    }
+
+    #[test]
+    fn default_path_for() {
+        assert_eq!(
+            Repository::default_path_for(DEFAULT_URL),
+            Repository::default_path()
+        );
+        assert_eq!(
+            Repository::default_path_for("https://example.com/org/db.git"),
+            Repository::default_path().with_file_name("advisory-db-example.com-org-db")
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn fetch_with_options_async() {
+        let dir = tempfile::tempdir().unwrap();
+        let upstream = dir.path().join("upstream");
+        fs::create_dir(&upstream).unwrap();
+        git(&upstream, &["init", "--quiet"]);
+        fs::write(upstream.join("README.md"), "advisories").unwrap();
+        git(&upstream, &["add", "README.md"]);
+        git(&upstream, &["commit", "--quiet", "-m", "first"]);
+
+        let url = format!("file://{}", upstream.display());
+        let path = dir.path().join("advisory-db");
+        let options = FetchOptions::new().lock_timeout(Duration::from_secs(10));
+
+        let fetch = Repository::fetch_with_options_async(&url, &path, &options);
+        fn assert_send<T: Send>(_: &T) {}
+        assert_send(&fetch);
+        drop(fetch);
+
+        // Hold the lock on the repository, so the fetch has to wait for it
+        let lock_path = tame_index::Path::from_path(&path)
+            .unwrap()
+            .with_extension(".lock");
+        let guard = LockOptions::new(&lock_path)
+            .exclusive(true)
+            .try_lock()
+            .unwrap();
+
+        // The fetch waits on the blocking thread pool, so the single runtime
+        // thread is still free to run the task which releases the lock
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let repo = runtime.block_on(async move {
+            let fetch = tokio::spawn(async move {
+                Repository::fetch_with_options_async(&url, path, &options).await
+            });
+            tokio::task::yield_now().await;
+            drop(guard);
+            fetch.await.unwrap()
+        });
+
+        assert_eq!(repo.unwrap().latest_commit().unwrap().summary, "first");
+    }
+}
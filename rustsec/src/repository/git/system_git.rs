@@ -0,0 +1,117 @@
+//! Fetch backend which shells out to the system `git` binary
+
+use super::repository::{PINNED_REF_SPECS, REF_SPEC};
+use crate::error::{Error, ErrorKind};
+use std::{path::Path, process::Command};
+
+/// Clone the repository at `url` into `path`, or update an existing checkout
+/// at `path` to the latest commit of the remote's default branch.
+///
+/// If `pinned` is set, all branches and tags are fetched as well so a
+/// revision other than the default branch can be checked out.
+///
+/// Failing clones and fetches are reported as [`ErrorKind::Network`] errors,
+/// as `git` doesn't tell them apart from other failures. Like the built-in
+/// backend, updating a checkout with local modifications is refused.
+pub(super) fn fetch(url: &str, path: &Path, pinned: bool) -> Result<(), Error> {
+    if path.join(".git").exists() {
+        network(
+            Command::new("git")
+                .arg("-C")
+                .arg(path)
+                .args(["fetch", "--quiet", url, REF_SPEC]),
+        )?;
+
+        let modified = output(Command::new("git").arg("-C").arg(path).args([
+            "status",
+            "--porcelain",
+            "--untracked-files=no",
+        ]))?;
+        if let Some(line) = modified.lines().next() {
+            let commit = output(
+                Command::new("git")
+                    .arg("-C")
+                    .arg(path)
+                    .args(["rev-parse", "refs/remotes/origin/HEAD"]),
+            )?;
+            fail!(
+                ErrorKind::Repo,
+                "unable to checkout {}: '{}' has local modifications",
+                commit.trim(),
+                line.get(3..).unwrap_or(line)
+            );
+        }
+
+        run(Command::new("git").arg("-C").arg(path).args([
+            "reset",
+            "--quiet",
+            "--hard",
+            "refs/remotes/origin/HEAD",
+        ]))?;
+    } else {
+        network(
+            Command::new("git")
+                .args(["clone", "--quiet", "--origin", "origin", url])
+                .arg(path),
+        )?;
+    }
+
+    if pinned {
+        network(
+            Command::new("git")
+                .arg("-C")
+                .arg(path)
+                .args(["fetch", "--quiet", url])
+                .args(PINNED_REF_SPECS),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Pack the objects of the repository at `path`, pruning unreachable ones
+pub(super) fn gc(path: &Path) -> Result<(), Error> {
+    run(Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(["gc", "--quiet", "--prune=now"]))
+}
+
+/// Run the given `git` command, failing if it exits unsuccessfully
+fn run(command: &mut Command) -> Result<(), Error> {
+    run_with_kind(command, ErrorKind::Repo).map(drop)
+}
+
+/// Run the given `git` command, returning its standard output
+fn output(command: &mut Command) -> Result<String, Error> {
+    run_with_kind(command, ErrorKind::Repo)
+}
+
+/// Run the given `git` command which talks to a remote, failing with a
+/// network error if it exits unsuccessfully
+fn network(command: &mut Command) -> Result<(), Error> {
+    run_with_kind(command, ErrorKind::Network).map(drop)
+}
+
+/// Run the given `git` command, failing with the given kind of error if it
+/// exits unsuccessfully, and returning its standard output otherwise
+fn run_with_kind(command: &mut Command, kind: ErrorKind) -> Result<String, Error> {
+    let output = command.output().map_err(|err| {
+        format_err!(
+            ErrorKind::Repo,
+            "failed to run `git` (is it installed?): {}",
+            err
+        )
+    })?;
+
+    if !output.status.success() {
+        fail!(
+            kind,
+            "`git` exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
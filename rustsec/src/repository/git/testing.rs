@@ -0,0 +1,38 @@
+//! Helpers for tests creating git repositories
+
+use std::{path::Path, process::Command};
+
+/// Run `git` with a fixed identity in the given directory
+pub(crate) fn git(dir: &Path, args: &[&str]) {
+    run(command(dir, args));
+}
+
+/// Run `git` with a fixed identity in the given directory, dating the commits
+/// it makes at the given time (e.g. `2001-03-15T12:00:00Z`)
+pub(crate) fn git_at(dir: &Path, date: &str, args: &[&str]) {
+    let mut command = command(dir, args);
+    command
+        .env("GIT_AUTHOR_DATE", date)
+        .env("GIT_COMMITTER_DATE", date);
+    run(command);
+}
+
+fn command(dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new("git");
+    command
+        .args([
+            "-c",
+            "user.name=rustsec",
+            "-c",
+            "user.email=rustsec@example.com",
+        ])
+        .arg("-C")
+        .arg(dir)
+        .args(args);
+    command
+}
+
+fn run(mut command: Command) {
+    let status = command.status().unwrap();
+    assert!(status.success(), "{:?} failed", command);
+}
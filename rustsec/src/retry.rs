@@ -0,0 +1,159 @@
+//! Retrying network operations which failed due to transient errors, e.g. a
+//! dropped connection while fetching the advisory database
+
+use crate::error::Error;
+use std::{thread, time::Duration};
+
+/// Policy for retrying operations which fail with transient errors (see
+/// [`Error::is_transient`]), waiting for an exponentially increasing amount
+/// of time between attempts.
+///
+/// Other errors, including
+/// [`ErrorKind::LockTimeout`](crate::ErrorKind::LockTimeout), are returned
+/// immediately: waiting for a filesystem lock is controlled by the lock
+/// timeout of the operation instead.
+///
+/// ```
+/// use rustsec::retry::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .retries(2)
+///     .initial_backoff(Duration::from_millis(500));
+///
+/// assert_eq!(policy.backoff(0), Duration::from_millis(500));
+/// assert_eq!(policy.backoff(1), Duration::from_secs(1));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// Number of times to retry a failed operation
+    retries: u32,
+
+    /// Time to wait before the first retry
+    initial_backoff: Duration,
+
+    /// Maximum time to wait between two attempts
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Create the default retry policy: retry up to 3 times, waiting 1 second
+    /// before the first retry and twice as long before each of the next ones
+    /// (up to 30 seconds)
+    pub fn new() -> Self {
+        Self {
+            retries: 3,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+
+    /// Policy which never retries, failing on the first error
+    pub fn none() -> Self {
+        Self::new().retries(0)
+    }
+
+    /// Set the number of times to retry a failed operation
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Set the time to wait before the first retry, which is doubled before
+    /// each of the next ones
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Set the maximum time to wait between two attempts
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Number of times a failed operation is retried
+    pub fn max_retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Time to wait before the given retry, counting from 0
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.checked_pow(retry).unwrap_or(u32::MAX);
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+
+    /// Run the given operation, retrying it according to this policy for as
+    /// long as it fails with transient errors.
+    ///
+    /// The error of the last attempt is returned if all of them fail.
+    pub fn run<T>(&self, mut operation: impl FnMut() -> Result<T, Error>) -> Result<T, Error> {
+        let mut retry = 0;
+        loop {
+            match operation() {
+                Err(err) if err.is_transient() && retry < self.retries => {
+                    thread::sleep(self.backoff(retry));
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+
+    #[test]
+    fn backoff_schedule() {
+        let policy = RetryPolicy::new();
+        let backoffs: Vec<_> = (0..7)
+            .map(|retry| policy.backoff(retry).as_secs())
+            .collect();
+        assert_eq!(backoffs, [1, 2, 4, 8, 16, 30, 30]);
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn retries_network_errors_only() {
+        let policy = RetryPolicy::new()
+            .retries(2)
+            .initial_backoff(Duration::ZERO);
+
+        let mut attempts = 0;
+        let result = policy.run(|| {
+            attempts += 1;
+            match attempts {
+                1 | 2 => Err(format_err!(ErrorKind::Network, "connection reset")),
+                _ => Ok(attempts),
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        let mut attempts = 0;
+        let result: Result<(), _> = policy.run(|| {
+            attempts += 1;
+            Err(format_err!(ErrorKind::Network, "connection reset"))
+        });
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::Network);
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let result: Result<(), _> = policy.run(|| {
+            attempts += 1;
+            Err(format_err!(ErrorKind::LockTimeout, "lock is held"))
+        });
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::LockTimeout);
+        assert_eq!(attempts, 1);
+    }
+}
@@ -0,0 +1,14 @@
+//! Running blocking operations from async code
+
+use crate::error::{Error, ErrorKind};
+
+/// Run the given blocking operation on tokio's blocking thread pool
+pub(crate) async fn spawn_blocking<T, F>(kind: ErrorKind, f: F) -> Result<T, Error>
+where
+    F: FnOnce() -> Result<T, Error> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|err| format_err!(kind, "background task failed: {}", err))?
+}
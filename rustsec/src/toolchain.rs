@@ -0,0 +1,179 @@
+//! Tools of the Rust toolchain, whose advisories are filed in the
+//! [`Collection::Rust`](crate::Collection::Rust) collection rather than
+//! against crates
+
+use crate::{
+    error::{Error, ErrorKind},
+    package::{self, Package},
+};
+use semver::Version;
+use std::{fmt, str::FromStr};
+
+/// Tool of the Rust toolchain which advisories can be filed against
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Tool {
+    /// The compiler, which links the standard library into every binary
+    Rustc,
+
+    /// The package manager
+    Cargo,
+
+    /// The documentation generator
+    Rustdoc,
+}
+
+impl Tool {
+    /// Get all tools as a slice
+    pub fn all() -> &'static [Self] {
+        &[Tool::Rustc, Tool::Cargo, Tool::Rustdoc]
+    }
+
+    /// Get the name of the tool
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Tool::Rustc => "rustc",
+            Tool::Cargo => "cargo",
+            Tool::Rustdoc => "rustdoc",
+        }
+    }
+
+    /// Names of the packages of the [`Collection::Rust`](crate::Collection::Rust)
+    /// collection shipped with a given version of the tool, e.g. `rustc` along
+    /// with the standard library crates it links into every binary
+    pub fn package_names(&self) -> &'static [&'static str] {
+        match self {
+            Tool::Rustc => &["rustc", "std", "core", "alloc"],
+            Tool::Cargo => &["cargo"],
+            Tool::Rustdoc => &["rustdoc"],
+        }
+    }
+}
+
+impl fmt::Display for Tool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Tool {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match s {
+            "rustc" => Tool::Rustc,
+            "cargo" => Tool::Cargo,
+            "rustdoc" => Tool::Rustdoc,
+            other => fail!(ErrorKind::Parse, "unknown toolchain tool: {}", other),
+        })
+    }
+}
+
+/// Version of a tool of the Rust toolchain, parsed from the output of e.g.
+/// `rustc --version` or `cargo --version`:
+///
+/// ```
+/// use rustsec::toolchain::{Tool, ToolVersion};
+///
+/// let cargo: ToolVersion = "cargo 1.70.0 (ec8a8a0ca 2023-04-25)".parse().unwrap();
+/// assert_eq!(cargo.tool, Tool::Cargo);
+/// assert_eq!(cargo.version, "1.70.0".parse().unwrap());
+/// ```
+///
+/// Pre-release identifiers (e.g. `-nightly`) are dropped, so pre-release
+/// toolchains are audited like the release they precede.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ToolVersion {
+    /// Tool of the toolchain
+    pub tool: Tool,
+
+    /// Version of the tool
+    pub version: Version,
+}
+
+impl ToolVersion {
+    /// Create a new tool version
+    pub fn new(tool: Tool, mut version: Version) -> Self {
+        version.pre = semver::Prerelease::EMPTY;
+        Self { tool, version }
+    }
+
+    /// Packages of the [`Collection::Rust`](crate::Collection::Rust)
+    /// collection shipped with this version of the tool, to match advisories
+    /// against
+    pub fn packages(&self) -> Vec<Package> {
+        self.tool
+            .package_names()
+            .iter()
+            .map(|name| Package {
+                name: name.parse::<package::Name>().expect("valid package name"),
+                version: self.version.clone(),
+                source: None,
+                checksum: None,
+                dependencies: vec![],
+                replace: None,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for ToolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.tool, self.version)
+    }
+}
+
+impl FromStr for ToolVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let mut words = s.split_whitespace();
+        let (tool, version) = match (words.next(), words.next()) {
+            (Some(tool), Some(version)) => (tool, version),
+            _ => fail!(
+                ErrorKind::Parse,
+                "invalid toolchain version (expected e.g. `rustc 1.70.0`): {}",
+                s
+            ),
+        };
+
+        let version = version.parse().map_err(|e| {
+            format_err!(
+                ErrorKind::Version,
+                "invalid version of {}: {}: {}",
+                tool,
+                version,
+                e
+            )
+        })?;
+
+        Ok(Self::new(tool.parse()?, version))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Tool, ToolVersion};
+
+    #[test]
+    fn parse_version_output() {
+        let rustc: ToolVersion = "rustc 1.70.0 (90c541806 2023-05-31)".parse().unwrap();
+        assert_eq!(rustc.tool, Tool::Rustc);
+        assert_eq!(rustc.version, "1.70.0".parse().unwrap());
+        assert_eq!(rustc.to_string(), "rustc 1.70.0");
+
+        let packages = rustc.packages();
+        assert_eq!(packages.len(), 4);
+        assert_eq!(packages[1].name.as_str(), "std");
+
+        let nightly: ToolVersion = "cargo 1.72.0-nightly (0c14026aa 2023-06-14)"
+            .parse()
+            .unwrap();
+        assert_eq!(nightly.tool, Tool::Cargo);
+        assert_eq!(nightly.version, "1.72.0".parse().unwrap());
+
+        assert!("rustc".parse::<ToolVersion>().is_err());
+        assert!("rustup 1.26.0".parse::<ToolVersion>().is_err());
+        assert!("rustc one".parse::<ToolVersion>().is_err());
+    }
+}
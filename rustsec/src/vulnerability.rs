@@ -4,11 +4,15 @@
 use crate::{
     advisory::{self, affected::FunctionPath, Advisory},
     package::Package,
+    report::Enrichment,
 };
-use serde::{Deserialize, Serialize};
+use semver::{BuildMetadata, Version};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// A vulnerable package and the associated advisory
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+// The derived impls are wrapped to serialize the computed fingerprint
+#[serde(remote = "Self")]
 pub struct Vulnerability {
     /// Security advisory for which the package is vulnerable
     pub advisory: advisory::Metadata,
@@ -21,6 +25,68 @@ pub struct Vulnerability {
 
     /// Vulnerable package
     pub package: Package,
+
+    /// Links to the commit and pull request which fixed the vulnerability
+    /// (if available)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed_by: Option<advisory::FixedBy>,
+
+    /// Have patched versions been released, i.e. can the vulnerability be
+    /// fixed by upgrading?
+    #[serde(default)]
+    pub is_fixable: bool,
+
+    /// Is the vulnerable package only pulled into the build by
+    /// dev-dependencies (see [`crate::Report::mark_dev_only`])?
+    #[serde(default)]
+    pub dev_only: bool,
+
+    /// Is the vulnerable package only used at build time, by build scripts
+    /// or proc macros (see [`crate::Report::mark_build_only`])?
+    #[serde(default)]
+    pub build_only: bool,
+
+    /// Is the vulnerable package a direct dependency of one of the root
+    /// packages of the lockfile (e.g. workspace members)?
+    #[serde(default)]
+    pub direct: bool,
+
+    /// Length of the shortest chain of dependencies from a root package of
+    /// the lockfile to the vulnerable package, where `1` means it's a direct
+    /// dependency (if known)
+    #[serde(default)]
+    pub depth: Option<usize>,
+
+    /// Number of days since the advisory was published, if recorded (see
+    /// [`crate::Report::add_advisory_ages`])
+    #[serde(default)]
+    pub advisory_age_days: Option<u64>,
+
+    /// Date the first patched version of the package was released, if known
+    /// (see [`crate::Report::add_patched_release_dates`])
+    #[serde(default)]
+    pub patched_release_date: Option<advisory::Date>,
+
+    /// Data about the advisory fetched from external sources, if any (see
+    /// [`crate::Report::enrich`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enrichment: Option<Enrichment>,
+
+    /// Informational kind of the advisory, if it would have been reported as
+    /// a warning but was promoted to a vulnerability (see
+    /// [`crate::report::Settings::promote_informational`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub promoted_from: Option<advisory::Informational>,
+
+    /// CVSS vector which determines the advisory's severity, serialized with
+    /// its metrics, score, and severity rather than as a vector string, if
+    /// requested (see [`crate::Report::add_cvss_details`])
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "cvss::v3::structured::option"
+    )]
+    pub cvss_details: Option<cvss::v3::Base>,
 }
 
 impl Vulnerability {
@@ -31,9 +97,38 @@ impl Vulnerability {
             versions: advisory.versions.clone(),
             affected: advisory.affected.clone(),
             package: package.clone(),
+            fixed_by: advisory.fixed_by.clone(),
+            is_fixable: !advisory.versions.patched().is_empty(),
+            dev_only: false,
+            build_only: false,
+            direct: false,
+            depth: None,
+            advisory_age_days: None,
+            patched_release_date: None,
+            enrichment: None,
+            promoted_from: None,
+            cvss_details: None,
         }
     }
 
+    /// Stable fingerprint identifying this vulnerability across audits and
+    /// tools, e.g. `RUSTSEC-2001-2101:base@1.0.0`.
+    ///
+    /// It's made of the advisory ID and the name and version of the package,
+    /// without any build metadata in the version.
+    pub fn fingerprint(&self) -> String {
+        fingerprint(self.advisory.id.as_str(), &self.package)
+    }
+
+    /// Does the given version of the vulnerable package contain the fix for
+    /// this vulnerability?
+    pub fn is_patched_version(&self, version: &Version) -> bool {
+        self.versions
+            .patched()
+            .iter()
+            .any(|req| req.matches(version))
+    }
+
     /// Get the set of functions affected by this vulnerability (if available)
     pub fn affected_functions(&self) -> Option<Vec<FunctionPath>> {
         self.affected.as_ref().and_then(|affected| {
@@ -54,3 +149,86 @@ impl Vulnerability {
         })
     }
 }
+
+impl Serialize for Vulnerability {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// Vulnerability along with its [`Vulnerability::fingerprint`], which is
+        /// computed rather than stored so it can't go stale
+        #[derive(Serialize)]
+        struct WithFingerprint<'a> {
+            #[serde(flatten, with = "Vulnerability")]
+            vulnerability: &'a Vulnerability,
+            fingerprint: String,
+        }
+
+        WithFingerprint {
+            vulnerability: self,
+            fingerprint: self.fingerprint(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Vulnerability {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // The serialized fingerprint is ignored, as it's computed instead
+        Vulnerability::deserialize(deserializer)
+    }
+}
+
+/// Fingerprint of a finding from the given source (e.g. an advisory ID) about
+/// the given package
+pub(crate) fn fingerprint(source: &str, package: &Package) -> String {
+    // Build metadata doesn't distinguish versions (e.g. `1.0.0+abc` and
+    // `1.0.0+def` are the same version), so it's left out
+    let mut version = package.version.clone();
+    version.build = BuildMetadata::EMPTY;
+    format!("{}:{}@{}", source, package.name, version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Warning, WarningKind};
+
+    #[test]
+    fn fingerprints() {
+        let advisory = Advisory::load_file("./tests/support/example_advisory_v3.md").unwrap();
+        let package = Package {
+            name: "base".parse().unwrap(),
+            version: "1.0.0+build.5".parse().unwrap(),
+            source: None,
+            checksum: None,
+            dependencies: vec![],
+            replace: None,
+        };
+
+        let vulnerability = Vulnerability::new(&advisory, &package);
+        assert_eq!(vulnerability.fingerprint(), "RUSTSEC-2001-2101:base@1.0.0");
+
+        let json = serde_json::to_value(&vulnerability).unwrap();
+        assert_eq!(json["fingerprint"], "RUSTSEC-2001-2101:base@1.0.0");
+        let deserialized: Vulnerability = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, vulnerability);
+
+        // The fingerprint follows changes to the vulnerability
+        let mut upgraded = vulnerability.clone();
+        upgraded.package.version = "1.0.1".parse().unwrap();
+        let json = serde_json::to_value(&upgraded).unwrap();
+        assert_eq!(json["fingerprint"], "RUSTSEC-2001-2101:base@1.0.1");
+
+        let yanked = Warning::new(WarningKind::Yanked, &package, None, None, None);
+        assert_eq!(yanked.fingerprint(), "yanked:base@1.0.0");
+
+        let unmaintained = Warning::new(
+            WarningKind::Unmaintained,
+            &package,
+            Some(advisory.metadata.clone()),
+            None,
+            None,
+        );
+        assert_eq!(unmaintained.fingerprint(), vulnerability.fingerprint());
+        let json = serde_json::to_value(&unmaintained).unwrap();
+        assert_eq!(json["fingerprint"], "RUSTSEC-2001-2101:base@1.0.0");
+    }
+}
@@ -1,12 +1,19 @@
 //! Warnings sourced from the Advisory DB
 
 use crate::error::{Error, ErrorKind};
-use crate::{advisory, package::Package};
-use serde::{Deserialize, Serialize};
+use crate::{
+    advisory::{self, CvssSelection},
+    package::{self, Checksum, Package},
+    report::Enrichment,
+    vulnerability::fingerprint,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{fmt, str::FromStr};
 
 /// Warnings sourced from the Advisory DB
 #[derive(Clone, Debug, Deserialize, Serialize)]
+// The derived impls are wrapped to serialize the computed fingerprint
+#[serde(remote = "Self")]
 pub struct Warning {
     /// Kind of warning
     pub kind: WarningKind,
@@ -22,6 +29,56 @@ pub struct Warning {
 
     /// Versions impacted by this warning
     pub versions: Option<advisory::Versions>,
+
+    /// Severity of the source advisory according to its CVSS score, if it
+    /// has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<advisory::Severity>,
+
+    /// CVSS v3.1 vector of the source advisory which determines its
+    /// [`Warning::severity`] (the highest scoring one if it has several)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvss: Option<cvss::v3::Base>,
+
+    /// Checksum published in the registry index, for
+    /// [`WarningKind::Checksum`] warnings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_checksum: Option<Checksum>,
+
+    /// Reason the package is banned, for [`WarningKind::Banned`] warnings
+    /// (see [`crate::report::Settings::banned_packages`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ban_reason: Option<String>,
+
+    /// Is the package only pulled into the build by dev-dependencies
+    /// (see [`crate::Report::mark_dev_only`])?
+    #[serde(default)]
+    pub dev_only: bool,
+
+    /// Is the package only used at build time, by build scripts or proc
+    /// macros (see [`crate::Report::mark_build_only`])?
+    #[serde(default)]
+    pub build_only: bool,
+
+    /// Data about the source advisory fetched from external sources, if any
+    /// (see [`crate::Report::enrich`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enrichment: Option<Enrichment>,
+
+    /// Was the warning reported as a vulnerability before its advisory was
+    /// demoted (see [`crate::report::Settings::demote`])?
+    #[serde(default)]
+    pub demoted: bool,
+
+    /// [`Warning::cvss`] serialized with its metrics, score, and severity
+    /// rather than as a vector string, if requested (see
+    /// [`crate::Report::add_cvss_details`])
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "cvss::v3::structured::option"
+    )]
+    pub cvss_details: Option<cvss::v3::Base>,
 }
 
 impl Warning {
@@ -33,12 +90,67 @@ impl Warning {
         affected: Option<advisory::Affected>,
         versions: Option<advisory::Versions>,
     ) -> Self {
-        Self {
+        let mut warning = Self {
             kind,
             package: package.clone(),
             advisory,
             affected,
             versions,
+            severity: None,
+            cvss: None,
+            registry_checksum: None,
+            ban_reason: None,
+            dev_only: false,
+            build_only: false,
+            enrichment: None,
+            demoted: false,
+            cvss_details: None,
+        };
+        warning.select_cvss(&CvssSelection::default());
+        warning
+    }
+
+    /// Set the [`Warning::cvss`] vector and [`Warning::severity`] from the
+    /// source advisory's CVSS score chosen by the given [`CvssSelection`]
+    pub fn select_cvss(&mut self, selection: &CvssSelection) {
+        self.cvss = self
+            .advisory
+            .as_ref()
+            .and_then(|advisory| advisory.selected_cvss(selection))
+            .map(|(_, cvss)| cvss.clone());
+        self.severity = self.cvss.as_ref().map(cvss::v3::Base::severity);
+    }
+
+    /// Stable fingerprint identifying this warning across audits and tools,
+    /// made of the advisory ID for warnings with an advisory and the kind of
+    /// warning otherwise, along with the name and version of the package,
+    /// e.g. `yanked:base@1.0.0`.
+    ///
+    /// See [`Vulnerability::fingerprint`](crate::Vulnerability::fingerprint).
+    pub fn fingerprint(&self) -> String {
+        let source = match &self.advisory {
+            Some(advisory) => advisory.id.as_str(),
+            None => self.kind.as_str(),
+        };
+
+        fingerprint(source, &self.package)
+    }
+
+    /// Create a [`WarningKind::Checksum`] warning about a package whose
+    /// checksum differs from the one published in the registry index
+    pub fn checksum_mismatch(package: &Package, registry_checksum: Checksum) -> Self {
+        Self {
+            registry_checksum: Some(registry_checksum),
+            ..Self::new(WarningKind::Checksum, package, None, None, None)
+        }
+    }
+
+    /// Create a [`WarningKind::Banned`] warning about a package which must
+    /// not be depended on, for the given reason (if any)
+    pub fn banned(package: &Package, reason: Option<String>) -> Self {
+        Self {
+            ban_reason: reason,
+            ..Self::new(WarningKind::Banned, package, None, None, None)
         }
     }
 
@@ -57,10 +169,81 @@ impl Warning {
         self.kind == WarningKind::Unsound
     }
 
+    /// Is this a warning about a `deprecated` crate?
+    pub fn is_deprecated(&self) -> bool {
+        self.kind == WarningKind::Deprecated
+    }
+
     /// Is this a warning about a yanked crate?
     pub fn is_yanked(&self) -> bool {
         self.kind == WarningKind::Yanked
     }
+
+    /// Is this a warning about the Rust toolchain a binary was built with?
+    pub fn is_toolchain(&self) -> bool {
+        self.kind == WarningKind::Toolchain
+    }
+
+    /// Is this a warning about a package whose checksum doesn't match the
+    /// registry index?
+    pub fn is_checksum_mismatch(&self) -> bool {
+        self.kind == WarningKind::Checksum
+    }
+
+    /// Is this a warning about a package from a source the advisory database
+    /// doesn't cover?
+    pub fn is_untracked_source(&self) -> bool {
+        self.kind == WarningKind::Source
+    }
+
+    /// Is this a warning about a package which is banned by the report
+    /// settings?
+    pub fn is_banned(&self) -> bool {
+        self.kind == WarningKind::Banned
+    }
+
+    /// Crates which the source advisory recommends using instead of the
+    /// affected package: the crate superseding it (if any) comes first,
+    /// followed by the listed alternatives
+    pub fn alternatives(&self) -> Vec<&package::Name> {
+        let mut alternatives = vec![];
+
+        if let Some(advisory) = &self.advisory {
+            for name in advisory.superseded_by.iter().chain(&advisory.alternatives) {
+                if !alternatives.contains(&name) {
+                    alternatives.push(name);
+                }
+            }
+        }
+
+        alternatives
+    }
+}
+
+impl Serialize for Warning {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        /// Warning along with its [`Warning::fingerprint`], which is
+        /// computed rather than stored so it can't go stale
+        #[derive(Serialize)]
+        struct WithFingerprint<'a> {
+            #[serde(flatten, with = "Warning")]
+            warning: &'a Warning,
+            fingerprint: String,
+        }
+
+        WithFingerprint {
+            warning: self,
+            fingerprint: self.fingerprint(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Warning {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // The serialized fingerprint is ignored, as it's computed instead
+        Warning::deserialize(deserializer)
+    }
 }
 
 /// Kinds of warnings
@@ -79,9 +262,34 @@ pub enum WarningKind {
     #[serde(rename = "unsound")]
     Unsound,
 
+    /// Packages deprecated by their authors
+    #[serde(rename = "deprecated")]
+    Deprecated,
+
     /// Yanked packages
     #[serde(rename = "yanked")]
     Yanked,
+
+    /// Binaries built with a Rust toolchain (`rustc` or the standard library)
+    /// which is affected by an advisory
+    #[serde(rename = "toolchain")]
+    Toolchain,
+
+    /// Packages whose checksum in `Cargo.lock` doesn't match the one published
+    /// in the registry index, which may indicate a tampered lockfile
+    #[serde(rename = "checksum")]
+    Checksum,
+
+    /// Packages from sources the advisory database doesn't cover: git
+    /// repositories without a pinned revision or tag, and registries other
+    /// than crates.io
+    #[serde(rename = "source")]
+    Source,
+
+    /// Packages which must not be depended on according to the report
+    /// settings, e.g. due to an organization's policy
+    #[serde(rename = "banned")]
+    Banned,
 }
 
 impl WarningKind {
@@ -91,7 +299,12 @@ impl WarningKind {
             Self::Notice => "notice",
             Self::Unmaintained => "unmaintained",
             Self::Unsound => "unsound",
+            Self::Deprecated => "deprecated",
             Self::Yanked => "yanked",
+            Self::Toolchain => "toolchain",
+            Self::Checksum => "checksum",
+            Self::Source => "source",
+            Self::Banned => "banned",
         }
     }
 }
@@ -104,7 +317,12 @@ impl FromStr for WarningKind {
             "notice" => WarningKind::Notice,
             "unmaintained" => WarningKind::Unmaintained,
             "unsound" => WarningKind::Unsound,
+            "deprecated" => WarningKind::Deprecated,
             "yanked" => WarningKind::Yanked,
+            "toolchain" => WarningKind::Toolchain,
+            "checksum" => WarningKind::Checksum,
+            "source" => WarningKind::Source,
+            "banned" => WarningKind::Banned,
             other => fail!(ErrorKind::Parse, "invalid warning type: {}", other),
         })
     }
@@ -66,6 +66,22 @@ fn parse_affected() {
     assert!(!req.matches(&"1.2.3".parse().unwrap()));
 }
 
+/// Parsing of links to the fix
+#[test]
+fn parse_fixed_by() {
+    let fixed_by = load_advisory("v3").fixed_by.unwrap();
+    assert_eq!(
+        fixed_by.commit.unwrap().as_str(),
+        "https://github.com/example/base/commit/8f6d2a1"
+    );
+    assert_eq!(
+        fixed_by.pr.unwrap().as_str(),
+        "https://github.com/example/base/pull/42"
+    );
+
+    assert!(load_advisory("v4").fixed_by.is_none());
+}
+
 /// Parsing of other aliased advisory IDs
 #[test]
 fn parse_aliases() {
@@ -95,6 +111,127 @@ fn parse_cvss_vector_string() {
     assert_eq!(cvss.score().value(), 10.0);
 }
 
+/// Parsing of CVSS vectors labeled with the configuration they apply to
+#[test]
+fn parse_labeled_cvss_scores() {
+    use rustsec::advisory::{CvssSelection, Severity};
+
+    let advisory: rustsec::Advisory = r#"```toml
+[advisory]
+id = "RUSTSEC-2001-2102"
+package = "base"
+date = "2001-02-03"
+cvss = "CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N"
+cvss_scores = [
+    { label = "windows", cvss = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H" },
+    { label = "linux", cvss = "CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:L/I:L/A:N" },
+]
+
+[versions]
+patched = []
+```
+
+# base has a platform-dependent vulnerability
+"#
+    .parse()
+    .unwrap();
+
+    let labels: Vec<_> = advisory
+        .metadata
+        .all_cvss()
+        .map(|(label, _)| label)
+        .collect();
+    assert_eq!(labels, [None, Some("windows"), Some("linux")]);
+
+    assert_eq!(advisory.severity().unwrap(), Severity::Critical);
+    assert_eq!(
+        advisory.severity_with(&CvssSelection::Lowest).unwrap(),
+        Severity::Low
+    );
+    assert_eq!(
+        advisory
+            .severity_with(&CvssSelection::Label("linux".to_owned()))
+            .unwrap(),
+        Severity::Medium
+    );
+    assert_eq!(
+        advisory
+            .severity_with(&CvssSelection::Label("macos".to_owned()))
+            .unwrap(),
+        Severity::Critical
+    );
+
+    let query = rustsec::database::Query::new().severity(Severity::High);
+    assert!(query.matches(&advisory));
+    assert!(!query
+        .cvss_selection(CvssSelection::Lowest)
+        .matches(&advisory));
+}
+
+/// Parsing of typed references alongside plain reference URLs
+#[test]
+fn parse_references() {
+    use rustsec::advisory::ReferenceKind;
+
+    let advisory: rustsec::Advisory = r#"```toml
+[advisory]
+id = "RUSTSEC-2001-2102"
+package = "base"
+date = "2001-02-03"
+references = [
+    { type = "FIX", url = "https://example.com/base/fix.patch" },
+    "https://github.com/example/base/issues/1",
+    "https://github.com/example/base/pull/2",
+]
+
+[versions]
+patched = [">= 1.2.3"]
+```
+
+# base has a vulnerability
+"#
+    .parse()
+    .unwrap();
+
+    let kinds: Vec<_> = advisory
+        .metadata
+        .references
+        .iter()
+        .map(|reference| reference.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            ReferenceKind::Fix,
+            ReferenceKind::Report,
+            ReferenceKind::Fix
+        ]
+    );
+
+    let fixes: Vec<_> = advisory
+        .metadata
+        .references_of_kind(ReferenceKind::Fix)
+        .map(|url| url.as_str())
+        .collect();
+    assert_eq!(
+        fixes,
+        [
+            "https://example.com/base/fix.patch",
+            "https://github.com/example/base/pull/2"
+        ]
+    );
+
+    // References are always serialized with their kind
+    let json = serde_json::to_value(&advisory.metadata).unwrap();
+    assert_eq!(
+        json["references"][1],
+        serde_json::json!({
+            "type": "REPORT",
+            "url": "https://github.com/example/base/issues/1"
+        })
+    );
+}
+
 /// Parsing of patched version reqs
 #[test]
 fn parse_patched_version_reqs() {
@@ -104,3 +241,51 @@ fn parse_patched_version_reqs() {
     assert!(req.matches(&"1.2.3".parse().unwrap()));
     assert!(req.matches(&"1.2.4".parse().unwrap()));
 }
+
+/// Parsing of recommended replacements for an affected crate
+#[test]
+fn parse_alternatives() {
+    let advisory: rustsec::Advisory = r#"```toml
+[advisory]
+id = "RUSTSEC-2001-2102"
+package = "base"
+date = "2001-02-03"
+informational = "unmaintained"
+superseded_by = "base2"
+alternatives = ["other-base", "base2"]
+
+[versions]
+patched = []
+```
+
+# base is unmaintained
+
+Use base2 instead.
+"#
+    .parse()
+    .unwrap();
+
+    assert_eq!(
+        advisory.metadata.superseded_by.as_ref().unwrap().as_str(),
+        "base2"
+    );
+    assert_eq!(advisory.metadata.alternatives.len(), 2);
+
+    let package = rustsec::package::Package {
+        name: "base".parse().unwrap(),
+        version: "1.0.0".parse().unwrap(),
+        source: None,
+        checksum: None,
+        dependencies: vec![],
+        replace: None,
+    };
+    let warning = rustsec::Warning::new(
+        rustsec::WarningKind::Unmaintained,
+        &package,
+        Some(advisory.metadata),
+        None,
+        None,
+    );
+    let alternatives: Vec<_> = warning.alternatives().iter().map(|n| n.as_str()).collect();
+    assert_eq!(alternatives, ["base2", "other-base"]);
+}
@@ -3,7 +3,11 @@
 #![warn(rust_2018_idioms, unused_qualifications)]
 
 use platforms::target::{Arch, OS};
-use rustsec::{advisory::Severity, database::Query, package};
+use rustsec::{
+    advisory::{Category, Severity},
+    database::Query,
+    package,
+};
 
 /// Load example advisory from the filesystem
 fn load_advisory() -> rustsec::Advisory {
@@ -63,3 +67,36 @@ fn matches_target_arch() {
     let query_normal = Query::new().target_arch(vec![Arch::Mips, Arch::Mips64]);
     assert!(!query_normal.matches(&advisory));
 }
+
+#[test]
+fn matches_category() {
+    let advisory = load_advisory();
+
+    let query_matches = Query::new().category(Category::CodeExecution);
+    assert!(query_matches.matches(&advisory));
+
+    let query_any = Query::new()
+        .category(Category::MemoryCorruption)
+        .category(Category::PrivilegeEscalation);
+    assert!(query_any.matches(&advisory));
+
+    let query_nomatch = Query::new().categories(vec![Category::CryptoFailure]);
+    assert!(!query_nomatch.matches(&advisory));
+}
+
+#[test]
+fn matches_patched_versions_only() {
+    let advisory = load_advisory();
+    assert!(Query::new().with_patched_versions_only().matches(&advisory));
+
+    let unpatched: rustsec::Advisory =
+        std::fs::read_to_string("./tests/support/example_advisory_v3.md")
+            .unwrap()
+            .replace(r#"patched = [">= 1.2.3"]"#, "patched = []")
+            .parse()
+            .unwrap();
+    assert!(Query::new().matches(&unpatched));
+    assert!(!Query::new()
+        .with_patched_versions_only()
+        .matches(&unpatched));
+}